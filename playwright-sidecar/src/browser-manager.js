@@ -192,6 +192,24 @@ async function loadCookies(accountId) {
   return [];
 }
 
+/**
+ * Restore backed-up cookies and fingerprint into a profile, so the next
+ * launch with that profile starts logged in
+ */
+export async function restoreProfile(profileId, { cookies, fingerprint } = {}) {
+  await ensureProfilesDir();
+  const profileDir = getProfilePath(profileId);
+  await fs.ensureDir(profileDir);
+
+  if (Array.isArray(cookies)) {
+    await saveCookies(profileId, cookies);
+  }
+  if (fingerprint && Object.keys(fingerprint).length > 0) {
+    await fs.writeJson(path.join(profileDir, 'fingerprint.json'), fingerprint, { spaces: 2 });
+  }
+  return { success: true, profileId };
+}
+
 /**
  * Launch a browser instance for an account
  * 
@@ -518,6 +536,23 @@ export async function takeScreenshot(accountId) {
   }
 }
 
+/**
+ * Capture the current viewport as PNG bytes
+ * Returns null if the account has no active browser
+ */
+export async function captureScreenshot(accountId) {
+  const session = activeBrowsers.get(accountId);
+  if (!session) {
+    return null;
+  }
+
+  let buffer;
+  await callOnPageNoTrace(session.page, async (page) => {
+    buffer = await page.screenshot({ fullPage: false });
+  });
+  return buffer;
+}
+
 /**
  * Execute JavaScript in the page
  * 🔥 Use internal API to avoid tracing
@@ -587,6 +622,45 @@ export async function checkLoginStatus(accountId) {
   }
 }
 
+/**
+ * Check whether a profile's saved session is still logged in
+ *
+ * Launches a headless browser on the profile, loads its saved cookies, opens
+ * the platform home page and runs the platform's login check, then closes
+ * the browser without saving. A profile whose browser is already open is
+ * refused, since the live session may differ from the saved one.
+ */
+export async function verifyProfile(profileId, platformId) {
+  if (activeBrowsers.has(profileId)) {
+    return { success: false, error: 'Browser in use for this profile' };
+  }
+  const adapter = getAdapter(platformId);
+  if (!adapter) {
+    return { success: false, error: `Unknown platform: ${platformId}` };
+  }
+
+  const launched = await launchBrowser({ accountId: profileId, platformId, headless: true });
+  if (!launched.success) {
+    return launched;
+  }
+
+  try {
+    const session = activeBrowsers.get(profileId);
+    const cookies = await loadCookies(profileId);
+    if (cookies.length > 0) {
+      await session.browserContext.addCookies(cookies);
+    }
+    await adapter.goToHome(session.page);
+
+    const status = await checkLoginStatus(profileId);
+    return status.success ? { ...status, profileId } : status;
+  } catch (error) {
+    return { success: false, error: error.message };
+  } finally {
+    await closeBrowser(profileId, false);
+  }
+}
+
 /**
  * Start watching for login status changes
  * Automatically saves session when login is detected
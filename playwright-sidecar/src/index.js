@@ -127,6 +127,20 @@ app.post('/browser/:accountId/screenshot', async (req, res) => {
   }
 });
 
+// Screenshot as PNG bytes
+app.get('/browser/:accountId/screenshot', async (req, res) => {
+  try {
+    const { accountId } = req.params;
+    const png = await browserManager.captureScreenshot(accountId);
+    if (!png) {
+      return res.status(404).json({ success: false, error: 'No active browser for this account' });
+    }
+    res.type('png').send(png);
+  } catch (error) {
+    res.status(500).json({ success: false, error: error.message });
+  }
+});
+
 // Execute script
 app.post('/browser/:accountId/execute', async (req, res) => {
   try {
@@ -317,6 +331,33 @@ app.post('/platforms/:platformId/restore', async (req, res) => {
   }
 });
 
+// Restore auth backup into a specific account's browser profile
+app.post('/profiles/:profileId/restore', async (req, res) => {
+  try {
+    const { profileId } = req.params;
+    const { cookies, fingerprint } = req.body;
+    const result = await browserManager.restoreProfile(profileId, { cookies, fingerprint });
+    res.json(result);
+  } catch (error) {
+    res.status(500).json({ success: false, error: error.message });
+  }
+});
+
+// Check a profile's saved session in a throwaway headless browser
+app.post('/profiles/:profileId/verify', async (req, res) => {
+  try {
+    const { profileId } = req.params;
+    const { platformId } = req.body;
+    if (!platformId) {
+      return res.status(400).json({ success: false, error: 'platformId is required' });
+    }
+    const result = await browserManager.verifyProfile(profileId, platformId);
+    res.json(result);
+  } catch (error) {
+    res.status(500).json({ success: false, error: error.message });
+  }
+});
+
 // ============ Content Publishing APIs ============
 
 // Navigate to publish page
@@ -385,18 +426,21 @@ app.post('/browser/:accountId/ai-new', async (req, res) => {
 });
 
 // Graceful shutdown
-process.on('SIGINT', async () => {
+async function shutdown() {
   console.log('\n[Sidecar] Shutting down...');
   await browserManager.closeAllBrowsers();
   process.exit(0);
-});
+}
 
-process.on('SIGTERM', async () => {
-  console.log('\n[Sidecar] Shutting down...');
-  await browserManager.closeAllBrowsers();
-  process.exit(0);
+// Windows: the app has no console to deliver signals through, so it asks over HTTP
+app.post('/shutdown', (req, res) => {
+  res.json({ success: true });
+  setImmediate(shutdown);
 });
 
+process.on('SIGINT', shutdown);
+process.on('SIGTERM', shutdown);
+
 // Start server
 app.listen(PORT, () => {
   console.log(`🚀 PubCast Playwright Sidecar running at http://localhost:${PORT}`);
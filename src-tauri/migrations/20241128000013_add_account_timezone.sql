@@ -0,0 +1,3 @@
+-- Add per-account timezone for local-time scheduling
+-- IANA timezone name (e.g. 'Asia/Shanghai'); NULL means UTC
+ALTER TABLE accounts ADD COLUMN timezone TEXT;
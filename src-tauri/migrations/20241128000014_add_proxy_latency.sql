@@ -0,0 +1,2 @@
+-- Track latency of the most recent successful health check for proxy selection
+ALTER TABLE proxies ADD COLUMN last_latency_ms INTEGER;
@@ -0,0 +1,14 @@
+-- Create proxy_health_history table for charting proxy performance over time
+CREATE TABLE IF NOT EXISTS proxy_health_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    proxy_id TEXT NOT NULL,
+    checked_at INTEGER NOT NULL,
+    is_healthy INTEGER NOT NULL,                 -- 0 or 1
+    exit_ip TEXT,
+    latency_ms INTEGER,
+    error TEXT,
+    FOREIGN KEY (proxy_id) REFERENCES proxies(id) ON DELETE CASCADE
+);
+
+-- Index for per-proxy history queries (newest first)
+CREATE INDEX IF NOT EXISTS idx_proxy_health_history_proxy ON proxy_health_history(proxy_id, checked_at DESC);
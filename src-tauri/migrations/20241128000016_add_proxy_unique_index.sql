@@ -0,0 +1,33 @@
+-- Point accounts bound to a duplicate proxy at the row that is kept; deleting
+-- the duplicate would otherwise unbind them (ON DELETE SET NULL)
+UPDATE account_proxy
+SET proxy_id = (
+    SELECT kept.id
+    FROM proxies AS dup
+    JOIN proxies AS kept
+      ON kept.protocol = dup.protocol
+     AND kept.host = dup.host
+     AND kept.port = dup.port
+     AND COALESCE(kept.username, '') = COALESCE(dup.username, '')
+    WHERE dup.id = account_proxy.proxy_id
+    ORDER BY kept.rowid
+    LIMIT 1
+)
+WHERE proxy_id IN (
+    SELECT id FROM proxies
+    WHERE rowid NOT IN (
+        SELECT MIN(rowid) FROM proxies
+        GROUP BY protocol, host, port, COALESCE(username, '')
+    )
+);
+
+-- Remove duplicate proxies (keeping the oldest row) before enforcing uniqueness
+DELETE FROM proxies
+WHERE rowid NOT IN (
+    SELECT MIN(rowid) FROM proxies
+    GROUP BY protocol, host, port, COALESCE(username, '')
+);
+
+-- One proxy per (protocol, host, port, username); NULL username treated as ''
+CREATE UNIQUE INDEX IF NOT EXISTS idx_proxies_identity
+    ON proxies(protocol, host, port, COALESCE(username, ''));
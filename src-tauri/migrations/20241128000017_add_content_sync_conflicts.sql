@@ -0,0 +1,9 @@
+-- Track local edits against the last sync so remote updates don't clobber them
+ALTER TABLE contents ADD COLUMN last_synced_at INTEGER;      -- When remote values were last applied
+ALTER TABLE contents ADD COLUMN sync_conflict INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE contents ADD COLUMN conflict_remote TEXT;        -- JSON snapshot of the conflicting remote version
+
+-- Rows synced before this migration count as unmodified since their last sync
+UPDATE contents SET last_synced_at = local_updated_at WHERE remote_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_contents_sync_conflict ON contents(sync_conflict) WHERE sync_conflict = 1;
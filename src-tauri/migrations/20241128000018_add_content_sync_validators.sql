@@ -0,0 +1,3 @@
+-- HTTP cache validators from the last full CMS sync, for conditional fetches
+ALTER TABLE content_sync_status ADD COLUMN etag TEXT;
+ALTER TABLE content_sync_status ADD COLUMN last_modified TEXT;
@@ -0,0 +1,4 @@
+-- Classify publish job failures for statistics (publish_logs already has this column)
+ALTER TABLE publish_jobs ADD COLUMN error_category TEXT;     -- 'auth', 'rate_limit', 'content', 'network', 'unknown'
+
+CREATE INDEX IF NOT EXISTS idx_publish_jobs_error_category ON publish_jobs(error_category) WHERE status = 'failed';
@@ -0,0 +1,9 @@
+-- Store AI platform credentials encrypted (AES-256-GCM), like account credentials
+ALTER TABLE ai_configs ADD COLUMN auth_encrypted BLOB;
+ALTER TABLE ai_configs ADD COLUMN auth_nonce BLOB;
+
+-- auth_data was plaintext and never held real credentials
+ALTER TABLE ai_configs DROP COLUMN auth_data;
+
+-- A platform is only active once credentials have been stored
+UPDATE ai_configs SET status = 'inactive' WHERE status = 'active';
@@ -0,0 +1,6 @@
+-- Recurring (cron) distribution tasks
+ALTER TABLE distribution_tasks ADD COLUMN cron_expression TEXT;  -- Standard 5-field cron, evaluated in local time
+ALTER TABLE distribution_tasks ADD COLUMN next_run_at INTEGER;   -- Next fire time (NULL for one-shot tasks)
+
+CREATE INDEX IF NOT EXISTS idx_distribution_tasks_next_run ON distribution_tasks(next_run_at)
+    WHERE schedule_type = 'recurring';
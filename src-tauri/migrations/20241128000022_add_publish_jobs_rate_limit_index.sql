@@ -0,0 +1,2 @@
+-- Speeds up the per-platform trailing-hour count used for rate limiting
+CREATE INDEX IF NOT EXISTS idx_publish_jobs_platform_completed ON publish_jobs(platform, completed_at);
@@ -0,0 +1,4 @@
+-- Soft-delete accounts into a recoverable trash
+ALTER TABLE accounts ADD COLUMN deleted_at INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_accounts_deleted_at ON accounts(deleted_at);
@@ -0,0 +1,4 @@
+-- Dry-run tasks prepare content per platform without publishing
+-- Dry-run jobs are excluded from statistics and rate limits
+ALTER TABLE distribution_tasks ADD COLUMN dry_run INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE publish_jobs ADD COLUMN dry_run INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,8 @@
+-- CMS API connection settings (single row); the API key is AES encrypted
+CREATE TABLE IF NOT EXISTS content_api_config (
+    id INTEGER PRIMARY KEY NOT NULL CHECK (id = 1),
+    base_url TEXT NOT NULL,
+    api_key_encrypted BLOB,
+    api_key_nonce BLOB,
+    updated_at INTEGER NOT NULL DEFAULT (unixepoch())
+);
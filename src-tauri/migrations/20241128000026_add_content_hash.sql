@@ -0,0 +1,3 @@
+-- Hash of normalized title + body, used to flag likely duplicate contents
+ALTER TABLE contents ADD COLUMN content_hash TEXT;
+CREATE INDEX IF NOT EXISTS idx_contents_content_hash ON contents(content_hash);
@@ -0,0 +1,13 @@
+-- Track when proxies are handed out and which accounts used them
+ALTER TABLE proxies ADD COLUMN last_used_at INTEGER;
+
+CREATE TABLE IF NOT EXISTS proxy_usage (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    proxy_id TEXT NOT NULL,
+    account_id TEXT NOT NULL,
+    used_at INTEGER NOT NULL,
+    FOREIGN KEY (proxy_id) REFERENCES proxies(id) ON DELETE CASCADE
+);
+
+-- Index for per-proxy usage queries (newest first)
+CREATE INDEX IF NOT EXISTS idx_proxy_usage_proxy ON proxy_usage(proxy_id, used_at DESC);
@@ -0,0 +1,3 @@
+-- Platform-specific publish options chosen for a task, as a JSON object keyed
+-- by platform ID. Each job gets its platform's slice in its metadata.
+ALTER TABLE distribution_tasks ADD COLUMN platform_options TEXT;
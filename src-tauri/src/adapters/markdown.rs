@@ -0,0 +1,315 @@
+//! Markdown to HTML conversion
+//!
+//! Shared by adapters whose editors accept HTML. Each platform decides how
+//! elements are styled; the structure of the output is the same for all.
+
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use crate::models::Content;
+
+use super::traits::PreparedContent;
+
+/// An HTML element emitted by [`markdown_to_html`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Element {
+    Paragraph,
+    Heading(HeadingLevel),
+    BlockQuote,
+    /// The `<pre>` wrapping a code block
+    CodeBlock,
+    /// The `<code>` inside a code block
+    CodeBlockCode,
+    InlineCode,
+    OrderedList,
+    UnorderedList,
+    ListItem,
+    Emphasis,
+    Strong,
+    Strikethrough,
+    Link,
+    Image,
+    Rule,
+}
+
+/// Convert Markdown to HTML.
+///
+/// `style` returns the inline style for an element, or `None` to leave it
+/// unstyled. Raw HTML in the source is escaped and shown literally rather
+/// than passed through to the platform's editor.
+pub fn markdown_to_html(markdown: &str, style: impl Fn(Element) -> Option<String>) -> String {
+    let attr = |element: Element| {
+        style(element)
+            .map(|style| format!(r#" style="{}""#, style))
+            .unwrap_or_default()
+    };
+
+    let mut html = String::new();
+    let mut in_image = false;
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            // Only plain text belongs in an image's alt attribute
+            Event::Start(_) if in_image => {}
+            Event::End(tag) if in_image && tag != TagEnd::Image => {}
+            Event::Start(tag) => match tag {
+                Tag::Paragraph => html.push_str(&format!("<p{}>", attr(Element::Paragraph))),
+                Tag::Heading { level, .. } => {
+                    html.push_str(&format!("<{}{}>", level, attr(Element::Heading(level))))
+                }
+                Tag::BlockQuote(_) => {
+                    html.push_str(&format!("<blockquote{}>", attr(Element::BlockQuote)))
+                }
+                Tag::CodeBlock(kind) => {
+                    html.push_str(&format!("<pre{}>", attr(Element::CodeBlock)));
+                    let class = match kind {
+                        CodeBlockKind::Fenced(lang) if !lang.is_empty() => {
+                            format!(r#" class="language-{}""#, escape_html(&lang))
+                        }
+                        _ => String::new(),
+                    };
+                    html.push_str(&format!("<code{}{}>", class, attr(Element::CodeBlockCode)));
+                }
+                Tag::List(Some(1)) => html.push_str(&format!("<ol{}>", attr(Element::OrderedList))),
+                Tag::List(Some(start)) => html.push_str(&format!(
+                    r#"<ol start="{}"{}>"#,
+                    start,
+                    attr(Element::OrderedList)
+                )),
+                Tag::List(None) => html.push_str(&format!("<ul{}>", attr(Element::UnorderedList))),
+                Tag::Item => html.push_str(&format!("<li{}>", attr(Element::ListItem))),
+                Tag::Emphasis => html.push_str(&format!("<em{}>", attr(Element::Emphasis))),
+                Tag::Strong => html.push_str(&format!("<strong{}>", attr(Element::Strong))),
+                Tag::Strikethrough => {
+                    html.push_str(&format!("<del{}>", attr(Element::Strikethrough)))
+                }
+                Tag::Link { dest_url, .. } => html.push_str(&format!(
+                    r#"<a href="{}"{}>"#,
+                    escape_html(&dest_url),
+                    attr(Element::Link)
+                )),
+                Tag::Image { dest_url, .. } => {
+                    in_image = true;
+                    html.push_str(&format!(r#"<img src="{}" alt=""#, escape_html(&dest_url)));
+                }
+                _ => {}
+            },
+            Event::End(tag) => match tag {
+                TagEnd::Paragraph => html.push_str("</p>"),
+                TagEnd::Heading(level) => html.push_str(&format!("</{}>", level)),
+                TagEnd::BlockQuote(_) => html.push_str("</blockquote>"),
+                TagEnd::CodeBlock => html.push_str("</code></pre>"),
+                TagEnd::List(true) => html.push_str("</ol>"),
+                TagEnd::List(false) => html.push_str("</ul>"),
+                TagEnd::Item => html.push_str("</li>"),
+                TagEnd::Emphasis => html.push_str("</em>"),
+                TagEnd::Strong => html.push_str("</strong>"),
+                TagEnd::Strikethrough => html.push_str("</del>"),
+                TagEnd::Link => html.push_str("</a>"),
+                TagEnd::Image => {
+                    in_image = false;
+                    html.push_str(&format!(r#""{}/>"#, attr(Element::Image)));
+                }
+                _ => {}
+            },
+            Event::Text(text) => html.push_str(&escape_html(&text)),
+            Event::Code(code) if in_image => html.push_str(&escape_html(&code)),
+            Event::Code(code) => html.push_str(&format!(
+                "<code{}>{}</code>",
+                attr(Element::InlineCode),
+                escape_html(&code)
+            )),
+            Event::Html(raw) | Event::InlineHtml(raw) => html.push_str(&escape_html(&raw)),
+            Event::SoftBreak if in_image => html.push(' '),
+            Event::SoftBreak => html.push('\n'),
+            Event::HardBreak if in_image => html.push(' '),
+            Event::HardBreak => html.push_str("<br/>"),
+            Event::Rule => html.push_str(&format!("<hr{}/>", attr(Element::Rule))),
+            _ => {}
+        }
+    }
+
+    html
+}
+
+/// Prepare `content` for an article editor that applies its own typography.
+///
+/// The title is cut to `max_title` characters and the body converted to
+/// unstyled HTML of at most `max_body` characters.
+pub fn prepare_unstyled_article(
+    content: &Content,
+    max_title: usize,
+    max_body: usize,
+) -> PreparedContent {
+    let body = content
+        .body
+        .as_deref()
+        .map(|markdown| markdown_to_html(markdown, |_| None))
+        .unwrap_or_default();
+
+    PreparedContent {
+        title: truncate_chars(&content.title, max_title),
+        body: truncate_html(&body, max_body),
+        cover_image: content
+            .cover_image_local
+            .clone()
+            .or(content.cover_image_url.clone()),
+        tags: content.tags.clone().unwrap_or_default(),
+        extra: None,
+        options: None,
+    }
+}
+
+/// Truncate plain text to at most `max_chars` characters, ending with "..."
+pub fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() > max_chars {
+        text.chars()
+            .take(max_chars.saturating_sub(3))
+            .collect::<String>()
+            + "..."
+    } else {
+        text.to_string()
+    }
+}
+
+/// Truncate HTML produced by [`markdown_to_html`] to at most `max_chars`
+/// characters.
+///
+/// The cut falls between tags or characters of text, never inside a tag or
+/// an entity; "..." marks it and every element still open there is closed,
+/// so the result stays well-formed.
+pub fn truncate_html(html: &str, max_chars: usize) -> String {
+    const ELLIPSIS: &str = "...";
+
+    if html.chars().count() <= max_chars {
+        return html.to_string();
+    }
+
+    let mut open: Vec<&str> = Vec::new();
+    // Characters needed to close every element in `open`
+    let mut closing_len = 0;
+    let mut cut = (0, Vec::new());
+    let mut pos = 0;
+    let mut len = 0;
+
+    while pos < html.len() && len + ELLIPSIS.len() <= max_chars {
+        if len + ELLIPSIS.len() + closing_len <= max_chars {
+            cut = (pos, open.clone());
+        }
+
+        let rest = &html[pos..];
+        let unit = match rest.chars().next() {
+            Some('<') => rest.find('>').map_or(rest.len(), |end| end + 1),
+            Some('&') => rest.find(';').map_or(1, |end| end + 1),
+            Some(c) => c.len_utf8(),
+            None => break,
+        };
+        let token = &rest[..unit];
+
+        if token.starts_with("</") {
+            if let Some(name) = open.pop() {
+                closing_len -= name.len() + 3;
+            }
+        } else if token.starts_with('<') && !token.ends_with("/>") {
+            let name = token[1..]
+                .split(|c: char| c.is_ascii_whitespace() || c == '>')
+                .next()
+                .unwrap_or_default();
+            open.push(name);
+            closing_len += name.len() + 3;
+        }
+
+        pos += unit;
+        len += token.chars().count();
+    }
+
+    let (end, open) = cut;
+    let mut truncated = format!("{}{}", &html[..end], ELLIPSIS);
+    for name in open.iter().rev() {
+        truncated.push_str(&format!("</{}>", name));
+    }
+    truncated
+}
+
+/// Escape text for use in HTML content and double-quoted attributes
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_html_unstyled() {
+        let html = markdown_to_html(
+            "## Hi\n\n1. *a* <b>\n\n```sh\nls\n```\n\n---\n\n![x](p.png)",
+            |_| None,
+        );
+
+        assert_eq!(
+            html,
+            concat!(
+                "<h2>Hi</h2><ol><li><em>a</em> &lt;b&gt;</li></ol>",
+                r#"<pre><code class="language-sh">ls"#,
+                "\n</code></pre><hr/>",
+                r#"<p><img src="p.png" alt="x"/></p>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_html_styles_selected_elements() {
+        let html = markdown_to_html("**bold** `code`", |element| {
+            (element == Element::Strong).then(|| "font-weight: bold;".to_string())
+        });
+
+        assert_eq!(
+            html,
+            r#"<p><strong style="font-weight: bold;">bold</strong> <code>code</code></p>"#
+        );
+    }
+
+    #[test]
+    fn test_truncate_html_closes_open_elements() {
+        let html = markdown_to_html("*one* **two & three**\n\n- four", |_| None);
+        assert_eq!(
+            html,
+            "<p><em>one</em> <strong>two &amp; three</strong></p><ul><li>four</li></ul>"
+        );
+
+        // The limit falls inside `<strong>`, whose text holds an entity
+        assert_eq!(
+            truncate_html(&html, 42),
+            "<p><em>one</em> <strong>tw...</strong></p>"
+        );
+        assert_eq!(
+            truncate_html(&html, 48),
+            "<p><em>one</em> <strong>two ...</strong></p>"
+        );
+        assert_eq!(
+            truncate_html(&html, 49),
+            "<p><em>one</em> <strong>two &amp;...</strong></p>"
+        );
+        assert_eq!(truncate_html(&html, html.chars().count()), html);
+        for max in 3..html.chars().count() {
+            let truncated = truncate_html(&html, max);
+            assert!(truncated.chars().count() <= max, "{}: {}", max, truncated);
+            assert!(truncated.ends_with("...") || truncated.ends_with('>'));
+            assert_eq!(
+                truncated.matches('<').count(),
+                truncated.matches('>').count()
+            );
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Image header parsing
+//!
+//! Reads the format and pixel size of PNG, JPEG, GIF and WebP images from
+//! their headers, which is all that is needed to check a cover against a
+//! platform's [`MediaConstraints`](super::traits::MediaConstraints) without
+//! decoding it.
+
+/// Format and size of an image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageInfo {
+    /// Lowercase format name: "png", "jpeg", "gif" or "webp"
+    pub format: &'static str,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Read an image's format and size from its bytes, `None` if the format is
+/// not recognized or the header is cut short
+pub fn image_info(data: &[u8]) -> Option<ImageInfo> {
+    let info = |format, width, height| {
+        Some(ImageInfo {
+            format,
+            width,
+            height,
+        })
+    };
+
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        // IHDR is always the first chunk
+        return info("png", be_u32(data.get(16..20)?), be_u32(data.get(20..24)?));
+    }
+    if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        let width = le_u16(data.get(6..8)?) as u32;
+        let height = le_u16(data.get(8..10)?) as u32;
+        return info("gif", width, height);
+    }
+    if data.starts_with(&[0xFF, 0xD8]) {
+        let (width, height) = jpeg_size(data)?;
+        return info("jpeg", width, height);
+    }
+    if data.get(0..4)? == b"RIFF" && data.get(8..12)? == b"WEBP" {
+        let (width, height) = webp_size(data)?;
+        return info("webp", width, height);
+    }
+    None
+}
+
+/// Size from the first start-of-frame segment
+fn jpeg_size(data: &[u8]) -> Option<(u32, u32)> {
+    let mut pos = 2;
+    loop {
+        // Markers may be padded with extra 0xFF bytes
+        while data.get(pos) == Some(&0xFF) && data.get(pos + 1) == Some(&0xFF) {
+            pos += 1;
+        }
+        if *data.get(pos)? != 0xFF {
+            return None;
+        }
+        let marker = *data.get(pos + 1)?;
+        pos += 2;
+
+        // Standalone markers carry no segment
+        if marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            continue;
+        }
+        let length = be_u16(data.get(pos..pos + 2)?) as usize;
+        let start_of_frame =
+            (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+        if start_of_frame {
+            // Segment: length, precision, height, width
+            let height = be_u16(data.get(pos + 3..pos + 5)?) as u32;
+            let width = be_u16(data.get(pos + 5..pos + 7)?) as u32;
+            return Some((width, height));
+        }
+        if length < 2 {
+            return None;
+        }
+        pos += length;
+    }
+}
+
+/// Size from the first chunk of a lossy, lossless or extended WebP
+fn webp_size(data: &[u8]) -> Option<(u32, u32)> {
+    match data.get(12..16)? {
+        b"VP8 " => {
+            let width = le_u16(data.get(26..28)?) & 0x3FFF;
+            let height = le_u16(data.get(28..30)?) & 0x3FFF;
+            Some((width as u32, height as u32))
+        }
+        b"VP8L" => {
+            let bits = u32::from_le_bytes(data.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        b"VP8X" => {
+            let le_u24 = |b: &[u8]| u32::from_le_bytes([b[0], b[1], b[2], 0]);
+            Some((le_u24(data.get(24..27)?) + 1, le_u24(data.get(27..30)?) + 1))
+        }
+        _ => None,
+    }
+}
+
+fn be_u16(b: &[u8]) -> u16 {
+    u16::from_be_bytes([b[0], b[1]])
+}
+
+fn le_u16(b: &[u8]) -> u16 {
+    u16::from_le_bytes([b[0], b[1]])
+}
+
+fn be_u32(b: &[u8]) -> u32 {
+    u32::from_be_bytes([b[0], b[1], b[2], b[3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal PNG header of the given size
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&[8, 6, 0, 0, 0]);
+        data
+    }
+
+    /// Minimal JPEG with an APP0 segment before the frame header
+    fn jpeg(width: u16, height: u16) -> Vec<u8> {
+        let mut data = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+        data.extend_from_slice(b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0");
+        data.extend_from_slice(&[0xFF, 0xFF, 0xC0, 0x00, 0x11, 0x08]);
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&width.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn test_image_info_reads_each_format() {
+        assert_eq!(
+            image_info(&png(1080, 1440)),
+            Some(ImageInfo {
+                format: "png",
+                width: 1080,
+                height: 1440
+            })
+        );
+        assert_eq!(
+            image_info(&jpeg(900, 383)),
+            Some(ImageInfo {
+                format: "jpeg",
+                width: 900,
+                height: 383
+            })
+        );
+
+        let gif = [
+            b"GIF89a".as_slice(),
+            &640u16.to_le_bytes(),
+            &480u16.to_le_bytes(),
+        ]
+        .concat();
+        assert_eq!(image_info(&gif).unwrap().width, 640);
+
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0".to_vec();
+        webp.extend_from_slice(&[0x7F, 0x07, 0x00, 0x37, 0x04, 0x00]);
+        assert_eq!(
+            image_info(&webp),
+            Some(ImageInfo {
+                format: "webp",
+                width: 1920,
+                height: 1080
+            })
+        );
+
+        assert_eq!(image_info(b"not an image"), None);
+        assert_eq!(image_info(&png(10, 10)[..18]), None);
+    }
+}
@@ -4,8 +4,13 @@
 
 pub mod traits;
 pub mod registry;
+pub mod markdown;
+pub mod media;
+pub mod toutiao;
 pub mod wechat;
+pub mod weibo;
 pub mod xiaohongshu;
+pub mod zhihu;
 
 pub use traits::*;
 pub use registry::*;
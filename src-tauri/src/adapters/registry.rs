@@ -2,34 +2,107 @@
 //!
 //! Manages registration and lookup of platform adapters.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::error::{PubCastError, Result};
+use crate::services::SettingsService;
 
-use super::traits::PlatformAdapter;
+use super::toutiao::ToutiaoAdapter;
+use super::traits::{PlatformAdapter, SessionProbe};
 use super::wechat::WechatAdapter;
+use super::weibo::WeiboAdapter;
 use super::xiaohongshu::XiaohongshuAdapter;
+use super::zhihu::ZhihuAdapter;
+
+/// Setting holding the IDs of platforms the user has disabled
+pub const DISABLED_PLATFORMS_SETTING: &str = "adapters.disabled_platforms";
+
+/// Platforms the user has disabled
+pub async fn disabled_platforms(settings: &SettingsService) -> Result<HashSet<String>> {
+    settings.get_or(DISABLED_PLATFORMS_SETTING, HashSet::new()).await
+}
+
+/// The built-in adapters, checking auth against the account's browser
+/// session through `session` when given
+fn default_adapters(session: Option<Arc<dyn SessionProbe>>) -> Vec<Arc<dyn PlatformAdapter>> {
+    fn probed<A: PlatformAdapter + 'static>(
+        adapter: A,
+        session: &Option<Arc<dyn SessionProbe>>,
+        with_session_probe: fn(A, Arc<dyn SessionProbe>) -> A,
+    ) -> Arc<dyn PlatformAdapter> {
+        match session {
+            Some(session) => Arc::new(with_session_probe(adapter, session.clone())),
+            None => Arc::new(adapter),
+        }
+    }
+
+    vec![
+        probed(
+            WechatAdapter::new(),
+            &session,
+            WechatAdapter::with_session_probe,
+        ),
+        probed(
+            XiaohongshuAdapter::new(),
+            &session,
+            XiaohongshuAdapter::with_session_probe,
+        ),
+        probed(
+            WeiboAdapter::new(),
+            &session,
+            WeiboAdapter::with_session_probe,
+        ),
+        probed(
+            ZhihuAdapter::new(),
+            &session,
+            ZhihuAdapter::with_session_probe,
+        ),
+        probed(
+            ToutiaoAdapter::new(),
+            &session,
+            ToutiaoAdapter::with_session_probe,
+        ),
+    ]
+}
 
 /// Registry for platform adapters
+///
+/// Disabled platforms are hidden from [`AdapterRegistry::list_platforms`] but
+/// still resolve through [`AdapterRegistry::get`], so existing accounts and
+/// jobs keep working.
 pub struct AdapterRegistry {
     adapters: HashMap<String, Arc<dyn PlatformAdapter>>,
+    disabled: HashSet<String>,
+    settings: Option<SettingsService>,
 }
 
 impl AdapterRegistry {
-    /// Create a new adapter registry with default adapters
+    /// Create a new adapter registry with default adapters, all enabled
     pub fn new() -> Self {
         let mut registry = Self {
             adapters: HashMap::new(),
+            disabled: HashSet::new(),
+            settings: None,
         };
 
         // Register default adapters
-        registry.register(Arc::new(WechatAdapter::new()));
-        registry.register(Arc::new(XiaohongshuAdapter::new()));
+        for adapter in default_adapters(None) {
+            registry.register(adapter);
+        }
 
         registry
     }
 
+    /// Re-register the default adapters so their auth checks consult the
+    /// account's browser session through `session`
+    pub fn with_session_probe(mut self, session: Arc<dyn SessionProbe>) -> Self {
+        for adapter in default_adapters(Some(session)) {
+            self.register(adapter);
+        }
+        self
+    }
+
     /// Register an adapter
     pub fn register(&mut self, adapter: Arc<dyn PlatformAdapter>) {
         let id = adapter.platform_id().to_string();
@@ -44,11 +117,60 @@ impl AdapterRegistry {
             .ok_or_else(|| PubCastError::NotFound(format!("Adapter not found: {}", platform_id)))
     }
 
-    /// List all registered platform IDs
+    /// Create a registry with the enabled state persisted in `settings`
+    pub async fn load(settings: SettingsService) -> Result<Self> {
+        Ok(Self {
+            disabled: disabled_platforms(&settings).await?,
+            settings: Some(settings),
+            ..Self::new()
+        })
+    }
+
+    /// List enabled platform IDs, sorted
     pub fn list_platforms(&self) -> Vec<&str> {
+        let mut platforms: Vec<&str> = self
+            .all_platforms()
+            .into_iter()
+            .filter(|platform| self.is_platform_enabled(platform))
+            .collect();
+        platforms.sort_unstable();
+        platforms
+    }
+
+    /// List all registered platform IDs, enabled or not
+    pub fn all_platforms(&self) -> Vec<&str> {
         self.adapters.keys().map(|s| s.as_str()).collect()
     }
 
+    /// Whether a platform is registered and not disabled
+    pub fn is_platform_enabled(&self, platform_id: &str) -> bool {
+        self.has_platform(platform_id) && !self.disabled.contains(platform_id)
+    }
+
+    /// Enable or disable a platform, persisting the change when loaded from settings.
+    ///
+    /// Only hides the platform from listings; its accounts and history are kept.
+    pub async fn set_platform_enabled(&mut self, platform_id: &str, enabled: bool) -> Result<()> {
+        if !self.has_platform(platform_id) {
+            return Err(PubCastError::NotFound(format!("Adapter not found: {}", platform_id)));
+        }
+
+        let mut disabled = self.disabled.clone();
+        if enabled {
+            disabled.remove(platform_id);
+        } else {
+            disabled.insert(platform_id.to_string());
+        }
+        if let Some(settings) = &self.settings {
+            let mut sorted: Vec<&String> = disabled.iter().collect();
+            sorted.sort_unstable();
+            settings.set(DISABLED_PLATFORMS_SETTING, &sorted).await?;
+        }
+
+        self.disabled = disabled;
+        Ok(())
+    }
+
     /// Check if a platform is registered
     pub fn has_platform(&self, platform_id: &str) -> bool {
         self.adapters.contains_key(platform_id)
@@ -60,3 +182,38 @@ impl Default for AdapterRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+
+    #[tokio::test]
+    async fn test_disabled_platform_is_hidden_but_resolvable() {
+        let (_dir, pool) = init_test_database().await;
+        let mut registry = AdapterRegistry::load(SettingsService::new(pool.clone()))
+            .await
+            .unwrap();
+        assert!(registry.list_platforms().contains(&"weibo"));
+
+        registry.set_platform_enabled("weibo", false).await.unwrap();
+        assert!(!registry.is_platform_enabled("weibo"));
+        assert!(!registry.list_platforms().contains(&"weibo"));
+        assert!(registry.all_platforms().contains(&"weibo"));
+        assert_eq!(registry.get("weibo").unwrap().platform_id(), "weibo");
+
+        // The state survives a reload
+        let mut registry = AdapterRegistry::load(SettingsService::new(pool.clone()))
+            .await
+            .unwrap();
+        assert!(!registry.is_platform_enabled("weibo"));
+        assert!(registry.is_platform_enabled("wechat"));
+
+        registry.set_platform_enabled("weibo", true).await.unwrap();
+        assert!(registry.list_platforms().contains(&"weibo"));
+        assert!(matches!(
+            registry.set_platform_enabled("myspace", false).await,
+            Err(PubCastError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+//! Toutiao (今日头条) adapter
+//!
+//! Implements the PlatformAdapter trait for Toutiao articles (头条号).
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::error::{PubCastError, Result};
+use crate::models::{Account, Content, PublishResult};
+
+use super::markdown::prepare_unstyled_article;
+use super::traits::{
+    check_browser_session, PlatformAdapter, PlatformCapabilities, PreparedContent, SessionProbe,
+};
+
+/// Maximum article title length in characters
+const MAX_TITLE_LENGTH: usize = 30;
+/// Maximum article length in characters
+const MAX_CONTENT_LENGTH: usize = 100_000;
+
+/// Toutiao adapter
+pub struct ToutiaoAdapter {
+    session: Option<Arc<dyn SessionProbe>>,
+}
+
+impl ToutiaoAdapter {
+    pub fn new() -> Self {
+        Self { session: None }
+    }
+
+    /// Check auth against the account's browser session through `session`
+    pub fn with_session_probe(mut self, session: Arc<dyn SessionProbe>) -> Self {
+        self.session = Some(session);
+        self
+    }
+}
+
+impl Default for ToutiaoAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PlatformAdapter for ToutiaoAdapter {
+    fn platform_id(&self) -> &'static str {
+        "toutiao"
+    }
+
+    fn platform_name(&self) -> &'static str {
+        "今日头条"
+    }
+
+    fn capabilities(&self) -> PlatformCapabilities {
+        PlatformCapabilities {
+            max_title_length: Some(MAX_TITLE_LENGTH),
+            max_content_length: Some(MAX_CONTENT_LENGTH),
+            supported_media: vec!["image".to_string(), "video".to_string()],
+            supports_scheduling: true,
+            supports_draft: true,
+            supports_html: true,
+            rate_limit_per_hour: Some(5),
+            publish_timeout_secs: None,
+            idempotent_publish: false,
+        }
+    }
+
+    async fn check_auth(&self, account: &Account) -> Result<bool> {
+        check_browser_session(self.session.as_ref(), account).await
+    }
+
+    async fn prepare_content(&self, content: &Content) -> Result<PreparedContent> {
+        Ok(prepare_unstyled_article(
+            content,
+            MAX_TITLE_LENGTH,
+            MAX_CONTENT_LENGTH,
+        ))
+    }
+
+    async fn publish(&self, _account: &Account, _content: &PreparedContent) -> Result<PublishResult> {
+        // TODO: Implement actual publishing via browser automation
+        Err(PubCastError::PlatformAdapter(
+            "Toutiao publishing not implemented - requires browser automation".to_string(),
+        ))
+    }
+
+    fn login_url(&self) -> Option<String> {
+        Some("https://mp.toutiao.com/".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ContentStatus;
+
+    fn content(title: &str, body: &str) -> Content {
+        Content {
+            id: "c1".to_string(),
+            remote_id: None,
+            title: title.to_string(),
+            body: Some(body.to_string()),
+            cover_image_url: Some("https://example.com/cover.png".to_string()),
+            cover_image_local: None,
+            tags: Some(vec!["rust".to_string()]),
+            category: None,
+            author: None,
+            source_url: None,
+            status: ContentStatus::Draft,
+            remote_status: None,
+            remote_updated_at: None,
+            local_updated_at: 0,
+            metadata: None,
+            sync_conflict: false,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let caps = ToutiaoAdapter::new().capabilities();
+        assert!(caps.supports_html);
+        assert!(caps.supports_draft);
+        assert!(caps.supports_scheduling);
+        assert_eq!(caps.max_title_length, Some(30));
+        assert_eq!(caps.max_content_length, Some(100_000));
+        assert_eq!(caps.supported_media, ["image", "video"]);
+        assert_eq!(caps.rate_limit_per_hour, Some(5));
+        assert_eq!(
+            ToutiaoAdapter::new().login_url().as_deref(),
+            Some("https://mp.toutiao.com/")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prepare_content_converts_markdown_to_html() {
+        let markdown = "## 背景\n\n用 **Rust** 写的 [PubCast](https://example.com/?a=1&b=2)：\n\n- 一\n- 二\n\n![封面](https://example.com/c.png)";
+        let prepared = ToutiaoAdapter::new()
+            .prepare_content(&content("发布指南", markdown))
+            .await
+            .unwrap();
+
+        assert_eq!(prepared.title, "发布指南");
+        assert_eq!(
+            prepared.body,
+            concat!(
+                "<h2>背景</h2>",
+                r#"<p>用 <strong>Rust</strong> 写的 <a href="https://example.com/?a=1&amp;b=2">PubCast</a>：</p>"#,
+                "<ul><li>一</li><li>二</li></ul>",
+                r#"<p><img src="https://example.com/c.png" alt="封面"/></p>"#,
+            )
+        );
+        assert_eq!(prepared.cover_image.as_deref(), Some("https://example.com/cover.png"));
+        assert_eq!(prepared.tags, ["rust"]);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_content_truncates_on_char_boundaries() {
+        let prepared = ToutiaoAdapter::new()
+            .prepare_content(&content(&"头".repeat(40), &"条".repeat(MAX_CONTENT_LENGTH)))
+            .await
+            .unwrap();
+
+        assert_eq!(prepared.title.chars().count(), MAX_TITLE_LENGTH);
+        assert!(prepared.title.starts_with("头头头"));
+        assert!(prepared.title.ends_with("..."));
+        // The paragraph wrapper pushes the body over the limit
+        assert_eq!(prepared.body.chars().count(), MAX_CONTENT_LENGTH);
+        assert!(prepared.body.starts_with("<p>条"));
+        assert!(prepared.body.ends_with("条...</p>"));
+    }
+}
@@ -2,11 +2,16 @@
 //!
 //! Defines the core interfaces for platform adapters.
 
+use std::path::Path;
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
-use crate::error::Result;
-use crate::models::{Account, Content, PublishResult};
+use crate::error::{PubCastError, Result};
+use crate::models::{Account, AccountStatus, Content, ContentIssue, IssueSeverity, PublishResult};
+
+use super::media::image_info;
 
 /// Content prepared for publishing to a specific platform
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +26,18 @@ pub struct PreparedContent {
     pub tags: Vec<String>,
     /// Additional platform-specific fields
     pub extra: Option<serde_json::Value>,
+    /// Publish options the user chose for this platform, see
+    /// [`PublishJob::platform_options`](crate::models::PublishJob::platform_options)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<serde_json::Value>,
+}
+
+impl PreparedContent {
+    /// Attach the user's publish options for the platform
+    pub fn with_options(mut self, options: Option<serde_json::Value>) -> Self {
+        self.options = options;
+        self
+    }
 }
 
 /// Login credentials for a platform
@@ -45,6 +62,16 @@ pub enum LoginCredentials {
     QRCode,
 }
 
+impl LoginCredentials {
+    /// Whether these are OAuth tokens expiring within `window_secs` of `now`
+    pub fn oauth_expires_within(&self, now: i64, window_secs: i64) -> bool {
+        matches!(
+            self,
+            Self::OAuth { expires_at: Some(expires_at), .. } if *expires_at - now <= window_secs
+        )
+    }
+}
+
 /// Platform capabilities
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlatformCapabilities {
@@ -62,6 +89,34 @@ pub struct PlatformCapabilities {
     pub supports_html: bool,
     /// Rate limits (publishes per hour)
     pub rate_limit_per_hour: Option<u32>,
+    /// How long a single publish may take before it is abandoned, in seconds
+    pub publish_timeout_secs: Option<u64>,
+    /// Whether re-running an interrupted publish is safe because the platform
+    /// never posts the same content twice
+    #[serde(default)]
+    pub idempotent_publish: bool,
+}
+
+/// Limits a platform puts on uploaded images such as covers.
+/// `None` and empty fields are unconstrained.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MediaConstraints {
+    /// Minimum width in pixels
+    pub min_width: Option<u32>,
+    /// Minimum height in pixels
+    pub min_height: Option<u32>,
+    /// Maximum width in pixels
+    pub max_width: Option<u32>,
+    /// Maximum height in pixels
+    pub max_height: Option<u32>,
+    /// Smallest width / height ratio
+    pub min_aspect_ratio: Option<f64>,
+    /// Largest width / height ratio
+    pub max_aspect_ratio: Option<f64>,
+    /// Maximum file size in bytes
+    pub max_file_size: Option<u64>,
+    /// Accepted formats ("png", "jpeg", "gif", "webp"); empty accepts all of them
+    pub allowed_formats: Vec<String>,
 }
 
 /// Platform adapter trait
@@ -81,14 +136,58 @@ pub trait PlatformAdapter: Send + Sync {
     /// Check if an account is authenticated and valid
     async fn check_auth(&self, account: &Account) -> Result<bool>;
 
-    /// Refresh authentication if needed
-    async fn refresh_auth(&self, account: &Account) -> Result<LoginCredentials>;
+    /// Exchange an account's `credentials` for fresh ones, e.g. an OAuth
+    /// refresh token for a new access token.
+    ///
+    /// Platforms without a refresh flow (cookie or browser sessions) keep the
+    /// default, which reports that refreshing is not supported.
+    async fn refresh_auth(
+        &self,
+        _account: &Account,
+        _credentials: &LoginCredentials,
+    ) -> Result<LoginCredentials> {
+        Err(PubCastError::PlatformAdapter(format!(
+            "{} does not support auth refresh",
+            self.platform_name()
+        )))
+    }
 
     /// Prepare content for this platform
     ///
     /// Adapts the generic content to platform-specific format.
     async fn prepare_content(&self, content: &Content) -> Result<PreparedContent>;
 
+    /// Get the platform's limits on uploaded images
+    fn media_constraints(&self) -> MediaConstraints {
+        MediaConstraints::default()
+    }
+
+    /// Check a local image against [`Self::media_constraints`]
+    fn validate_media(&self, path: &Path) -> Result<()> {
+        check_media(&self.media_constraints(), path)
+    }
+
+    /// Check content against this platform's capabilities without preparing
+    /// it, including a downloaded cover against its media constraints
+    fn validate_content(&self, content: &Content) -> Vec<ContentIssue> {
+        let mut issues = check_capabilities(self.platform_id(), &self.capabilities(), content);
+        let cover_flagged = issues.iter().any(|issue| issue.field == "cover_image");
+        if let (Some(cover), false) = (content.cover_image_local.as_deref(), cover_flagged) {
+            if let Err(e) = self.validate_media(Path::new(cover)) {
+                issues.push(ContentIssue {
+                    platform: self.platform_id().to_string(),
+                    severity: IssueSeverity::Error,
+                    field: "cover_image".to_string(),
+                    message: match e {
+                        PubCastError::Validation(message) => message,
+                        e => e.to_string(),
+                    },
+                });
+            }
+        }
+        issues
+    }
+
     /// Publish content to the platform
     async fn publish(&self, account: &Account, content: &PreparedContent) -> Result<PublishResult>;
 
@@ -98,6 +197,163 @@ pub trait PlatformAdapter: Send + Sync {
     }
 }
 
+/// Report content that exceeds `caps` length limits (truncated on publish)
+/// or uses media the platform does not support
+pub fn check_capabilities(
+    platform: &str,
+    caps: &PlatformCapabilities,
+    content: &Content,
+) -> Vec<ContentIssue> {
+    let issue = |severity, field: &str, message: String| ContentIssue {
+        platform: platform.to_string(),
+        severity,
+        field: field.to_string(),
+        message,
+    };
+    let mut issues = Vec::new();
+
+    let title_len = content.title.chars().count();
+    if let Some(max) = caps.max_title_length.filter(|max| title_len > *max) {
+        issues.push(issue(
+            IssueSeverity::Warning,
+            "title",
+            format!("Title is {} characters and will be truncated to {}", title_len, max),
+        ));
+    }
+
+    let body = content.body.as_deref().unwrap_or_default();
+    let body_len = body.chars().count();
+    if let Some(max) = caps.max_content_length.filter(|max| body_len > *max) {
+        issues.push(issue(
+            IssueSeverity::Warning,
+            "body",
+            format!("Body is {} characters and will be truncated to {}", body_len, max),
+        ));
+    }
+
+    let supports = |media: &str| caps.supported_media.iter().any(|m| m == media);
+    let has_cover = content.cover_image_url.is_some() || content.cover_image_local.is_some();
+    if has_cover && !supports("image") {
+        issues.push(issue(
+            IssueSeverity::Error,
+            "cover_image",
+            "Platform does not support images".to_string(),
+        ));
+    }
+    for media in body_media(body) {
+        if !supports(media) {
+            issues.push(issue(
+                IssueSeverity::Error,
+                "body",
+                format!("Body embeds {} media, which the platform does not support", media),
+            ));
+        }
+    }
+
+    issues
+}
+
+/// How much of an image [`check_media`] reads to find its format and size.
+/// JPEG metadata segments come before the frame header, so leave room for them.
+const IMAGE_HEADER_LIMIT: u64 = 512 * 1024;
+
+/// Check the image at `path` against `constraints`, failing with a
+/// Validation error describing the first constraint it breaks.
+///
+/// Only the file's metadata and header are read, not the whole image.
+pub fn check_media(constraints: &MediaConstraints, path: &Path) -> Result<()> {
+    use std::io::Read;
+
+    let invalid = |message: String| Err(PubCastError::Validation(message));
+    let unreadable = |e: std::io::Error| {
+        PubCastError::Validation(format!("Cannot read image {}: {}", path.display(), e))
+    };
+    let file = std::fs::File::open(path).map_err(unreadable)?;
+    let size = file.metadata().map_err(unreadable)?.len();
+
+    if let Some(max) = constraints.max_file_size.filter(|max| size > *max) {
+        return invalid(format!(
+            "Image is {} bytes, over the limit of {} bytes",
+            size, max
+        ));
+    }
+    let mut header = Vec::new();
+    file.take(IMAGE_HEADER_LIMIT)
+        .read_to_end(&mut header)
+        .map_err(unreadable)?;
+    let Some(info) = image_info(&header) else {
+        return invalid("Image is not a PNG, JPEG, GIF or WebP file".to_string());
+    };
+    if !constraints.allowed_formats.is_empty()
+        && !constraints.allowed_formats.iter().any(|format| format == info.format)
+    {
+        return invalid(format!(
+            "{} images are not accepted, use {}",
+            info.format.to_uppercase(),
+            constraints.allowed_formats.join(", ")
+        ));
+    }
+
+    let (width, height) = (info.width, info.height);
+    if let Some(min) = constraints.min_width.filter(|min| width < *min) {
+        return invalid(format!("Image is {}px wide, below the minimum of {}px", width, min));
+    }
+    if let Some(min) = constraints.min_height.filter(|min| height < *min) {
+        return invalid(format!("Image is {}px tall, below the minimum of {}px", height, min));
+    }
+    if let Some(max) = constraints.max_width.filter(|max| width > *max) {
+        return invalid(format!("Image is {}px wide, above the maximum of {}px", width, max));
+    }
+    if let Some(max) = constraints.max_height.filter(|max| height > *max) {
+        return invalid(format!("Image is {}px tall, above the maximum of {}px", height, max));
+    }
+
+    let ratio = width as f64 / height.max(1) as f64;
+    let out_of_range = constraints.min_aspect_ratio.is_some_and(|min| ratio < min)
+        || constraints.max_aspect_ratio.is_some_and(|max| ratio > max);
+    if out_of_range {
+        return invalid(format!(
+            "Image is {}x{}, outside the accepted aspect ratios of {:.2} to {:.2}",
+            width,
+            height,
+            constraints.min_aspect_ratio.unwrap_or(0.0),
+            constraints.max_aspect_ratio.unwrap_or(f64::INFINITY)
+        ));
+    }
+    Ok(())
+}
+
+/// Media types embedded in a Markdown/HTML body
+fn body_media(body: &str) -> Vec<&'static str> {
+    let lower = body.to_lowercase();
+    let mut media = Vec::new();
+    if lower.contains("![") || lower.contains("<img") {
+        media.push("image");
+    }
+    if lower.contains("<video") || [".mp4", ".mov", ".webm"].iter().any(|ext| lower.contains(ext)) {
+        media.push("video");
+    }
+    media
+}
+
+/// Tells whether an account's restored browser session is still logged in
+#[async_trait]
+pub trait SessionProbe: Send + Sync {
+    async fn is_logged_in(&self, account_id: &str) -> Result<bool>;
+}
+
+/// Auth check for browser-automation platforms: ask `session` about the
+/// account's restored session, or trust the stored status without one
+pub async fn check_browser_session(
+    session: Option<&Arc<dyn SessionProbe>>,
+    account: &Account,
+) -> Result<bool> {
+    match session {
+        Some(session) => session.is_logged_in(&account.id).await,
+        None => Ok(account.status == AccountStatus::Active),
+    }
+}
+
 /// Login strategy for platforms that require browser automation
 #[async_trait]
 pub trait LoginStrategy: Send + Sync {
@@ -110,3 +366,161 @@ pub trait LoginStrategy: Send + Sync {
     /// Check if login is still valid
     async fn check_login(&self, credentials: &LoginCredentials) -> Result<bool>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ContentStatus;
+
+    fn content(body: &str, cover: Option<&str>) -> Content {
+        Content {
+            id: "c1".to_string(),
+            remote_id: None,
+            title: "Hello".to_string(),
+            body: Some(body.to_string()),
+            cover_image_url: cover.map(str::to_string),
+            cover_image_local: None,
+            tags: None,
+            category: None,
+            author: None,
+            source_url: None,
+            status: ContentStatus::Draft,
+            remote_status: None,
+            remote_updated_at: None,
+            local_updated_at: 0,
+            metadata: None,
+            sync_conflict: false,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_check_capabilities_flags_unsupported_media() {
+        let caps = PlatformCapabilities {
+            max_title_length: None,
+            max_content_length: None,
+            supported_media: vec!["image".to_string()],
+            supports_scheduling: false,
+            supports_draft: false,
+            supports_html: false,
+            rate_limit_per_hour: None,
+            publish_timeout_secs: None,
+            idempotent_publish: false,
+        };
+
+        let ok = content("![chart](a.png)", Some("https://example.com/cover.png"));
+        assert!(check_capabilities("text", &caps, &ok).is_empty());
+
+        let video = content("<video src=\"demo.mp4\"></video>", None);
+        let issues = check_capabilities("text", &caps, &video);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+
+        let text_only = PlatformCapabilities { supported_media: Vec::new(), ..caps };
+        let issues = check_capabilities("text", &text_only, &ok);
+        let fields: Vec<_> = issues.iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(fields, ["cover_image", "body"]);
+    }
+
+    /// Minimal PNG header of the given size, padded to `len` bytes
+    fn png(width: u32, height: u32, len: usize) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data.resize(len.max(data.len()), 0);
+        data
+    }
+
+    #[test]
+    fn test_check_media_enforces_each_constraint() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, data: &[u8]| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, data).unwrap();
+            path
+        };
+        let constraints = MediaConstraints {
+            min_width: Some(600),
+            min_height: Some(400),
+            max_width: Some(2000),
+            max_height: Some(2000),
+            min_aspect_ratio: Some(0.75),
+            max_aspect_ratio: Some(2.0),
+            max_file_size: Some(1024),
+            allowed_formats: vec!["png".to_string()],
+        };
+
+        let ok = write("ok.png", &png(800, 600, 100));
+        check_media(&constraints, &ok).unwrap();
+        check_media(&MediaConstraints::default(), &ok).unwrap();
+
+        let failing = [
+            ("large.png", png(800, 600, 2048), "bytes"),
+            ("cover.gif", b"GIF89a\x20\x03\x58\x02".to_vec(), "GIF images"),
+            ("notes.txt", b"not an image".to_vec(), "not a PNG"),
+            ("narrow.png", png(500, 600, 100), "wide, below"),
+            ("short.png", png(600, 300, 100), "tall, below"),
+            ("wide.png", png(2400, 1600, 100), "wide, above"),
+            ("tall.png", png(1500, 2100, 100), "tall, above"),
+            ("banner.png", png(1800, 600, 100), "aspect ratio"),
+            ("portrait.png", png(600, 1000, 100), "aspect ratio"),
+        ];
+        for (name, data, expected) in failing {
+            let path = write(name, &data);
+            match check_media(&constraints, &path) {
+                Err(PubCastError::Validation(message)) => {
+                    assert!(message.contains(expected), "{}: {}", name, message)
+                }
+                other => panic!("{} should fail, got {:?}", name, other),
+            }
+        }
+        assert!(matches!(
+            check_media(&constraints, &dir.path().join("missing.png")),
+            Err(PubCastError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_media_reads_only_the_header_of_large_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("large.png");
+        let len = (IMAGE_HEADER_LIMIT * 4) as usize;
+        std::fs::write(&path, png(800, 600, len)).unwrap();
+
+        check_media(&MediaConstraints::default(), &path).unwrap();
+        let constraints = MediaConstraints {
+            max_file_size: Some(IMAGE_HEADER_LIMIT),
+            ..Default::default()
+        };
+        match check_media(&constraints, &path) {
+            Err(PubCastError::Validation(message)) => {
+                assert!(message.contains(&len.to_string()), "{}", message)
+            }
+            other => panic!("large.png should fail, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_content_checks_local_cover() {
+        use crate::adapters::xiaohongshu::XiaohongshuAdapter;
+
+        let dir = tempfile::tempdir().unwrap();
+        let adapter = XiaohongshuAdapter::new();
+        let with_cover = |name: &str, width, height| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, png(width, height, 100)).unwrap();
+            let mut content = content("Hello", None);
+            content.cover_image_local = Some(path.to_string_lossy().into_owned());
+            content
+        };
+
+        assert!(adapter.validate_content(&with_cover("ok.png", 1080, 1440)).is_empty());
+
+        let issues = adapter.validate_content(&with_cover("small.png", 400, 400));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "cover_image");
+        assert_eq!(issues[0].severity, IssueSeverity::Error);
+        assert!(issues[0].message.contains("below the minimum of 720px"));
+    }
+}
@@ -2,30 +2,120 @@
 //!
 //! Implements the PlatformAdapter trait for WeChat MP (公众号).
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use pulldown_cmark::HeadingLevel;
 
 use crate::error::{PubCastError, Result};
 use crate::models::{Account, Content, PublishResult};
 
-use super::traits::{LoginCredentials, PlatformAdapter, PlatformCapabilities, PreparedContent};
+use super::markdown::{markdown_to_html, truncate_html, Element};
+use super::traits::{
+    check_browser_session, MediaConstraints, PlatformAdapter, PlatformCapabilities,
+    PreparedContent, SessionProbe,
+};
+
+/// Base typography for generated article HTML
+#[derive(Debug, Clone, PartialEq)]
+pub struct WechatStyle {
+    pub font_size_px: u32,
+    pub line_height: f32,
+}
+
+impl Default for WechatStyle {
+    fn default() -> Self {
+        Self {
+            font_size_px: 16,
+            line_height: 1.8,
+        }
+    }
+}
 
 /// WeChat Official Account adapter
-pub struct WechatAdapter;
+pub struct WechatAdapter {
+    style: WechatStyle,
+    session: Option<Arc<dyn SessionProbe>>,
+}
 
 impl WechatAdapter {
     pub fn new() -> Self {
-        Self
+        Self {
+            style: WechatStyle::default(),
+            session: None,
+        }
+    }
+
+    /// Use custom base typography for generated HTML
+    pub fn with_style(mut self, style: WechatStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Check auth against the account's browser session through `session`
+    pub fn with_session_probe(mut self, session: Arc<dyn SessionProbe>) -> Self {
+        self.session = Some(session);
+        self
     }
 
-    /// Convert Markdown to WeChat-compatible HTML
-    fn markdown_to_wechat_html(markdown: &str) -> String {
-        // TODO: Implement proper Markdown to WeChat HTML conversion
-        // For now, just wrap in basic HTML
+    /// Convert Markdown to WeChat-compatible HTML.
+    ///
+    /// WeChat's editor strips `<style>` blocks and external CSS, so every
+    /// element carries its own inline style.
+    fn markdown_to_wechat_html(&self, markdown: &str) -> String {
+        let base = self.style.font_size_px as f32;
+        let heading_size = |level: HeadingLevel| -> f32 {
+            match level {
+                HeadingLevel::H1 => base * 1.5,
+                HeadingLevel::H2 => base * 1.35,
+                HeadingLevel::H3 => base * 1.2,
+                _ => base,
+            }
+        };
+
+        let body = markdown_to_html(markdown, |element| {
+            let style = match element {
+                Element::Paragraph => "margin: 0 0 1em;".to_string(),
+                Element::Heading(level) => format!(
+                    "font-size: {}px; font-weight: bold; margin: 1.2em 0 0.6em;",
+                    heading_size(level)
+                ),
+                Element::BlockQuote => {
+                    "margin: 0 0 1em; padding: 0.5em 1em; border-left: 4px solid #dbdbdb; color: #666;"
+                        .to_string()
+                }
+                Element::CodeBlock => {
+                    "margin: 0 0 1em; padding: 1em; background: #f6f8fa; border-radius: 4px; overflow-x: auto; line-height: 1.5;"
+                        .to_string()
+                }
+                Element::CodeBlockCode => {
+                    "font-family: Menlo, Consolas, monospace; font-size: 0.875em; color: #333; white-space: pre;"
+                        .to_string()
+                }
+                Element::InlineCode => {
+                    "padding: 2px 4px; background: #f6f8fa; border-radius: 3px; font-family: Menlo, Consolas, monospace; font-size: 0.9em; color: #c7254e;"
+                        .to_string()
+                }
+                Element::OrderedList => {
+                    "margin: 0 0 1em; padding-left: 2em; list-style-type: decimal;".to_string()
+                }
+                Element::UnorderedList => {
+                    "margin: 0 0 1em; padding-left: 2em; list-style-type: disc;".to_string()
+                }
+                Element::ListItem => "margin: 0.2em 0;".to_string(),
+                Element::Emphasis => "font-style: italic;".to_string(),
+                Element::Strong => "font-weight: bold;".to_string(),
+                Element::Strikethrough => "text-decoration: line-through;".to_string(),
+                Element::Link => "color: #576b95; text-decoration: none;".to_string(),
+                Element::Image => "max-width: 100%; display: block; margin: 0 auto 1em;".to_string(),
+                Element::Rule => "margin: 1.5em 0; border: none; border-top: 1px solid #e5e5e5;".to_string(),
+            };
+            Some(style)
+        });
+
         format!(
-            r#"<section style="font-size: 16px; line-height: 1.8;">{}</section>"#,
-            markdown
-                .replace("\n\n", "</p><p>")
-                .replace("\n", "<br/>")
+            r#"<section style="font-size: {}px; line-height: {}; color: #333;">{}</section>"#,
+            self.style.font_size_px, self.style.line_height, body
         )
     }
 }
@@ -55,28 +145,29 @@ impl PlatformAdapter for WechatAdapter {
             supports_draft: true,
             supports_html: true,
             rate_limit_per_hour: Some(10),
+            publish_timeout_secs: None,
+            idempotent_publish: false,
         }
     }
 
-    async fn check_auth(&self, account: &Account) -> Result<bool> {
-        // TODO: Implement actual auth check via browser automation
-        // For now, just check if account status is active
-        Ok(account.status == crate::models::AccountStatus::Active)
+    fn media_constraints(&self) -> MediaConstraints {
+        MediaConstraints {
+            max_file_size: Some(10 * 1024 * 1024),
+            allowed_formats: vec!["jpeg".to_string(), "png".to_string(), "gif".to_string()],
+            ..MediaConstraints::default()
+        }
     }
 
-    async fn refresh_auth(&self, _account: &Account) -> Result<LoginCredentials> {
-        // TODO: Implement auth refresh
-        Err(PubCastError::PlatformAdapter(
-            "WeChat auth refresh not implemented".to_string(),
-        ))
+    async fn check_auth(&self, account: &Account) -> Result<bool> {
+        check_browser_session(self.session.as_ref(), account).await
     }
 
     async fn prepare_content(&self, content: &Content) -> Result<PreparedContent> {
         let title = content.title.clone();
 
         // Truncate title if too long
-        let title = if title.len() > 64 {
-            format!("{}...", &title[..61])
+        let title = if title.chars().count() > 64 {
+            title.chars().take(61).collect::<String>() + "..."
         } else {
             title
         };
@@ -85,15 +176,11 @@ impl PlatformAdapter for WechatAdapter {
         let body = content
             .body
             .as_ref()
-            .map(|b| Self::markdown_to_wechat_html(b))
+            .map(|b| self.markdown_to_wechat_html(b))
             .unwrap_or_default();
 
-        // Truncate if too long
-        let body = if body.len() > 20000 {
-            format!("{}...", &body[..19997])
-        } else {
-            body
-        };
+        // Truncate if too long, closing the elements cut off
+        let body = truncate_html(&body, 20000);
 
         Ok(PreparedContent {
             title,
@@ -101,6 +188,7 @@ impl PlatformAdapter for WechatAdapter {
             cover_image: content.cover_image_local.clone().or(content.cover_image_url.clone()),
             tags: content.tags.clone().unwrap_or_default(),
             extra: None,
+            options: None,
         })
     }
 
@@ -115,3 +203,109 @@ impl PlatformAdapter for WechatAdapter {
         Some("https://mp.weixin.qq.com/".to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ContentStatus;
+
+    #[test]
+    fn test_markdown_to_wechat_html_snapshot() {
+        let markdown = "\
+# Title
+
+Some **bold**, *italic* and `code` with a [link](https://example.com/?a=1&b=2).
+
+## Lists
+
+- one
+- two
+
+3. three
+4. four
+
+> Quoted <b>text</b>
+
+```rust
+fn main() {}
+```
+
+![Cover *image*](https://example.com/c.png)
+";
+        let expected = concat!(
+            r#"<section style="font-size: 16px; line-height: 1.8; color: #333;">"#,
+            r#"<h1 style="font-size: 24px; font-weight: bold; margin: 1.2em 0 0.6em;">Title</h1>"#,
+            r#"<p style="margin: 0 0 1em;">Some <strong style="font-weight: bold;">bold</strong>, "#,
+            r#"<em style="font-style: italic;">italic</em> and "#,
+            r#"<code style="padding: 2px 4px; background: #f6f8fa; border-radius: 3px; font-family: Menlo, Consolas, monospace; font-size: 0.9em; color: #c7254e;">code</code> "#,
+            r#"with a <a href="https://example.com/?a=1&amp;b=2" style="color: #576b95; text-decoration: none;">link</a>.</p>"#,
+            r#"<h2 style="font-size: 21.6px; font-weight: bold; margin: 1.2em 0 0.6em;">Lists</h2>"#,
+            r#"<ul style="margin: 0 0 1em; padding-left: 2em; list-style-type: disc;">"#,
+            r#"<li style="margin: 0.2em 0;">one</li><li style="margin: 0.2em 0;">two</li></ul>"#,
+            r#"<ol start="3" style="margin: 0 0 1em; padding-left: 2em; list-style-type: decimal;">"#,
+            r#"<li style="margin: 0.2em 0;">three</li><li style="margin: 0.2em 0;">four</li></ol>"#,
+            r#"<blockquote style="margin: 0 0 1em; padding: 0.5em 1em; border-left: 4px solid #dbdbdb; color: #666;">"#,
+            r#"<p style="margin: 0 0 1em;">Quoted &lt;b&gt;text&lt;/b&gt;</p></blockquote>"#,
+            r#"<pre style="margin: 0 0 1em; padding: 1em; background: #f6f8fa; border-radius: 4px; overflow-x: auto; line-height: 1.5;">"#,
+            r#"<code class="language-rust" style="font-family: Menlo, Consolas, monospace; font-size: 0.875em; color: #333; white-space: pre;">"#,
+            "fn main() {}\n</code></pre>",
+            r#"<p style="margin: 0 0 1em;"><img src="https://example.com/c.png" alt="Cover image" "#,
+            r#"style="max-width: 100%; display: block; margin: 0 auto 1em;"/></p>"#,
+            "</section>",
+        );
+
+        assert_eq!(
+            WechatAdapter::new().markdown_to_wechat_html(markdown),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_wechat_html_custom_style() {
+        let adapter = WechatAdapter::new().with_style(WechatStyle {
+            font_size_px: 15,
+            line_height: 1.75,
+        });
+        let html = adapter.markdown_to_wechat_html("Line one  \nline two");
+
+        assert_eq!(
+            html,
+            concat!(
+                r#"<section style="font-size: 15px; line-height: 1.75; color: #333;">"#,
+                r#"<p style="margin: 0 0 1em;">Line one<br/>line two</p></section>"#,
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prepare_content_truncates_long_body_between_tags() {
+        let content = Content {
+            id: "c1".to_string(),
+            remote_id: None,
+            title: "Title".to_string(),
+            body: Some(format!("{}**bold**", "word ".repeat(4000))),
+            cover_image_url: None,
+            cover_image_local: None,
+            tags: None,
+            category: None,
+            author: None,
+            source_url: None,
+            status: ContentStatus::Draft,
+            remote_status: None,
+            remote_updated_at: None,
+            local_updated_at: 0,
+            metadata: None,
+            sync_conflict: false,
+            created_at: 0,
+            updated_at: 0,
+        };
+        let prepared = WechatAdapter::new()
+            .prepare_content(&content)
+            .await
+            .unwrap();
+
+        assert!(prepared.body.chars().count() <= 20000);
+        assert!(!prepared.body.contains("<strong"));
+        assert!(prepared.body.ends_with("...</p></section>"));
+    }
+}
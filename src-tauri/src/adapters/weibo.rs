@@ -0,0 +1,185 @@
+//! Weibo (微博) adapter
+//!
+//! Implements the PlatformAdapter trait for Weibo.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::error::{PubCastError, Result};
+use crate::models::{Account, Content, PublishResult};
+
+use super::traits::{
+    check_browser_session, PlatformAdapter, PlatformCapabilities, PreparedContent, SessionProbe,
+};
+
+/// Maximum post length in characters, hashtags included
+const MAX_CONTENT_LENGTH: usize = 2000;
+
+/// Weibo adapter
+pub struct WeiboAdapter {
+    session: Option<Arc<dyn SessionProbe>>,
+}
+
+impl WeiboAdapter {
+    pub fn new() -> Self {
+        Self { session: None }
+    }
+
+    /// Check auth against the account's browser session through `session`
+    pub fn with_session_probe(mut self, session: Arc<dyn SessionProbe>) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    /// Wrap a tag as a Weibo topic (`#tag#`), `None` for blank tags
+    fn format_hashtag(tag: &str) -> Option<String> {
+        let tag = tag.trim().trim_matches('#').trim();
+        (!tag.is_empty()).then(|| format!("#{}#", tag))
+    }
+
+    /// Topics to append, skipping duplicates and ones already in the body
+    fn hashtags(tags: &[String], body: &str) -> Vec<String> {
+        let mut hashtags: Vec<String> = Vec::new();
+        for hashtag in tags.iter().filter_map(|tag| Self::format_hashtag(tag)) {
+            if !body.contains(&hashtag) && !hashtags.contains(&hashtag) {
+                hashtags.push(hashtag);
+            }
+        }
+        hashtags
+    }
+}
+
+impl Default for WeiboAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PlatformAdapter for WeiboAdapter {
+    fn platform_id(&self) -> &'static str {
+        "weibo"
+    }
+
+    fn platform_name(&self) -> &'static str {
+        "微博"
+    }
+
+    fn capabilities(&self) -> PlatformCapabilities {
+        PlatformCapabilities {
+            max_title_length: None,
+            max_content_length: Some(MAX_CONTENT_LENGTH),
+            supported_media: vec!["image".to_string(), "video".to_string()],
+            supports_scheduling: false,
+            supports_draft: false,
+            supports_html: false,
+            rate_limit_per_hour: Some(10),
+            publish_timeout_secs: None,
+            idempotent_publish: false,
+        }
+    }
+
+    async fn check_auth(&self, account: &Account) -> Result<bool> {
+        check_browser_session(self.session.as_ref(), account).await
+    }
+
+    async fn prepare_content(&self, content: &Content) -> Result<PreparedContent> {
+        let body = content.body.clone().unwrap_or_default();
+        let tags = content.tags.clone().unwrap_or_default();
+
+        // Topics go at the end, so the body gives way to keep them within the limit
+        let hashtags = Self::hashtags(&tags, &body);
+        let suffix = if hashtags.is_empty() {
+            String::new()
+        } else {
+            format!("\n{}", hashtags.join(" "))
+        };
+        let budget = MAX_CONTENT_LENGTH.saturating_sub(suffix.chars().count());
+        let body = if body.chars().count() > budget {
+            body.chars().take(budget.saturating_sub(3)).collect::<String>() + "..."
+        } else {
+            body
+        };
+
+        Ok(PreparedContent {
+            title: content.title.clone(),
+            body: body + &suffix,
+            cover_image: content.cover_image_local.clone().or(content.cover_image_url.clone()),
+            tags,
+            extra: None,
+            options: None,
+        })
+    }
+
+    async fn publish(&self, _account: &Account, _content: &PreparedContent) -> Result<PublishResult> {
+        // TODO: Implement actual publishing via browser automation
+        Err(PubCastError::PlatformAdapter(
+            "Weibo publishing not implemented - requires browser automation".to_string(),
+        ))
+    }
+
+    fn login_url(&self) -> Option<String> {
+        Some("https://weibo.com/".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ContentStatus;
+
+    fn content(body: &str, tags: &[&str]) -> Content {
+        Content {
+            id: "c1".to_string(),
+            remote_id: None,
+            title: "发布指南".to_string(),
+            body: Some(body.to_string()),
+            cover_image_url: None,
+            cover_image_local: None,
+            tags: Some(tags.iter().map(|tag| tag.to_string()).collect()),
+            category: None,
+            author: None,
+            source_url: None,
+            status: ContentStatus::Draft,
+            remote_status: None,
+            remote_updated_at: None,
+            local_updated_at: 0,
+            metadata: None,
+            sync_conflict: false,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_format_hashtag_wraps_both_sides() {
+        assert_eq!(WeiboAdapter::format_hashtag("Rust"), Some("#Rust#".to_string()));
+        assert_eq!(WeiboAdapter::format_hashtag("#科技#"), Some("#科技#".to_string()));
+        assert_eq!(WeiboAdapter::format_hashtag(" #tauri "), Some("#tauri#".to_string()));
+        assert_eq!(WeiboAdapter::format_hashtag(" # "), None);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_content_appends_weibo_hashtags() {
+        let prepared = WeiboAdapter::new()
+            .prepare_content(&content("今天发布了新版本 #科技#", &["Rust", "#tauri", "科技", "Rust", ""]))
+            .await
+            .unwrap();
+
+        // Topics already in the body and duplicates are not repeated
+        assert_eq!(prepared.body, "今天发布了新版本 #科技#\n#Rust# #tauri#");
+    }
+
+    #[tokio::test]
+    async fn test_prepare_content_truncates_body_to_keep_hashtags() {
+        let body = "微".repeat(MAX_CONTENT_LENGTH);
+        let prepared = WeiboAdapter::new()
+            .prepare_content(&content(&body, &["开源"]))
+            .await
+            .unwrap();
+
+        assert_eq!(prepared.body.chars().count(), MAX_CONTENT_LENGTH);
+        assert!(prepared.body.ends_with("...\n#开源#"));
+    }
+}
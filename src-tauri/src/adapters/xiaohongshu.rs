@@ -2,19 +2,32 @@
 //!
 //! Implements the PlatformAdapter trait for Xiaohongshu.
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 
 use crate::error::{PubCastError, Result};
 use crate::models::{Account, Content, PublishResult};
 
-use super::traits::{LoginCredentials, PlatformAdapter, PlatformCapabilities, PreparedContent};
+use super::traits::{
+    check_browser_session, MediaConstraints, PlatformAdapter, PlatformCapabilities,
+    PreparedContent, SessionProbe,
+};
 
 /// Xiaohongshu adapter
-pub struct XiaohongshuAdapter;
+pub struct XiaohongshuAdapter {
+    session: Option<Arc<dyn SessionProbe>>,
+}
 
 impl XiaohongshuAdapter {
     pub fn new() -> Self {
-        Self
+        Self { session: None }
+    }
+
+    /// Check auth against the account's browser session through `session`
+    pub fn with_session_probe(mut self, session: Arc<dyn SessionProbe>) -> Self {
+        self.session = Some(session);
+        self
     }
 
     /// Extract hashtags from content
@@ -53,18 +66,27 @@ impl PlatformAdapter for XiaohongshuAdapter {
             supports_draft: true,
             supports_html: false,
             rate_limit_per_hour: Some(5),
+            // Video uploads can take a while
+            publish_timeout_secs: Some(600),
+            idempotent_publish: false,
         }
     }
 
-    async fn check_auth(&self, account: &Account) -> Result<bool> {
-        // TODO: Implement actual auth check
-        Ok(account.status == crate::models::AccountStatus::Active)
+    fn media_constraints(&self) -> MediaConstraints {
+        // Covers are shown between 3:4 portrait and 4:3 landscape
+        MediaConstraints {
+            min_width: Some(720),
+            min_height: Some(720),
+            min_aspect_ratio: Some(3.0 / 4.0),
+            max_aspect_ratio: Some(4.0 / 3.0),
+            max_file_size: Some(20 * 1024 * 1024),
+            allowed_formats: vec!["jpeg".to_string(), "png".to_string(), "webp".to_string()],
+            ..MediaConstraints::default()
+        }
     }
 
-    async fn refresh_auth(&self, _account: &Account) -> Result<LoginCredentials> {
-        Err(PubCastError::PlatformAdapter(
-            "Xiaohongshu auth refresh not implemented".to_string(),
-        ))
+    async fn check_auth(&self, account: &Account) -> Result<bool> {
+        check_browser_session(self.session.as_ref(), account).await
     }
 
     async fn prepare_content(&self, content: &Content) -> Result<PreparedContent> {
@@ -100,6 +122,7 @@ impl PlatformAdapter for XiaohongshuAdapter {
             cover_image: content.cover_image_local.clone().or(content.cover_image_url.clone()),
             tags,
             extra: None,
+            options: None,
         })
     }
 
@@ -0,0 +1,185 @@
+//! Zhihu (知乎) adapter
+//!
+//! Implements the PlatformAdapter trait for Zhihu articles (知乎专栏).
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::error::{PubCastError, Result};
+use crate::models::{Account, Content, PublishResult};
+
+use super::markdown::prepare_unstyled_article;
+use super::traits::{
+    check_browser_session, PlatformAdapter, PlatformCapabilities, PreparedContent, SessionProbe,
+};
+
+/// Maximum article title length in characters
+const MAX_TITLE_LENGTH: usize = 100;
+/// Maximum article length in characters
+const MAX_CONTENT_LENGTH: usize = 100_000;
+
+/// Zhihu adapter
+pub struct ZhihuAdapter {
+    session: Option<Arc<dyn SessionProbe>>,
+}
+
+impl ZhihuAdapter {
+    pub fn new() -> Self {
+        Self { session: None }
+    }
+
+    /// Check auth against the account's browser session through `session`
+    pub fn with_session_probe(mut self, session: Arc<dyn SessionProbe>) -> Self {
+        self.session = Some(session);
+        self
+    }
+}
+
+impl Default for ZhihuAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl PlatformAdapter for ZhihuAdapter {
+    fn platform_id(&self) -> &'static str {
+        "zhihu"
+    }
+
+    fn platform_name(&self) -> &'static str {
+        "知乎"
+    }
+
+    fn capabilities(&self) -> PlatformCapabilities {
+        PlatformCapabilities {
+            max_title_length: Some(MAX_TITLE_LENGTH),
+            max_content_length: Some(MAX_CONTENT_LENGTH),
+            supported_media: vec!["image".to_string(), "video".to_string()],
+            supports_scheduling: false,
+            supports_draft: true,
+            supports_html: true,
+            rate_limit_per_hour: Some(10),
+            publish_timeout_secs: None,
+            idempotent_publish: false,
+        }
+    }
+
+    async fn check_auth(&self, account: &Account) -> Result<bool> {
+        check_browser_session(self.session.as_ref(), account).await
+    }
+
+    async fn prepare_content(&self, content: &Content) -> Result<PreparedContent> {
+        Ok(prepare_unstyled_article(
+            content,
+            MAX_TITLE_LENGTH,
+            MAX_CONTENT_LENGTH,
+        ))
+    }
+
+    async fn publish(&self, _account: &Account, _content: &PreparedContent) -> Result<PublishResult> {
+        // TODO: Implement actual publishing via browser automation
+        Err(PubCastError::PlatformAdapter(
+            "Zhihu publishing not implemented - requires browser automation".to_string(),
+        ))
+    }
+
+    fn login_url(&self) -> Option<String> {
+        Some("https://www.zhihu.com/signin".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ContentStatus;
+
+    fn content(title: &str, body: &str) -> Content {
+        Content {
+            id: "c1".to_string(),
+            remote_id: None,
+            title: title.to_string(),
+            body: Some(body.to_string()),
+            cover_image_url: Some("https://example.com/cover.png".to_string()),
+            cover_image_local: None,
+            tags: Some(vec!["rust".to_string()]),
+            category: None,
+            author: None,
+            source_url: None,
+            status: ContentStatus::Draft,
+            remote_status: None,
+            remote_updated_at: None,
+            local_updated_at: 0,
+            metadata: None,
+            sync_conflict: false,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let caps = ZhihuAdapter::new().capabilities();
+        assert!(caps.supports_html);
+        assert!(caps.supports_draft);
+        assert_eq!(caps.max_title_length, Some(100));
+        assert_eq!(caps.max_content_length, Some(100_000));
+        assert_eq!(caps.supported_media, ["image", "video"]);
+        assert_eq!(
+            ZhihuAdapter::new().login_url().as_deref(),
+            Some("https://www.zhihu.com/signin")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prepare_content_converts_markdown_to_html() {
+        let markdown = "## 背景\n\n用 **Rust** 写的 [PubCast](https://example.com/?a=1&b=2)：\n\n- 一\n- 二\n\n```rust\nfn main() {}\n```\n\n<script>x</script>";
+        let prepared = ZhihuAdapter::new()
+            .prepare_content(&content("发布指南", markdown))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            prepared.body,
+            concat!(
+                "<h2>背景</h2>",
+                r#"<p>用 <strong>Rust</strong> 写的 <a href="https://example.com/?a=1&amp;b=2">PubCast</a>：</p>"#,
+                "<ul><li>一</li><li>二</li></ul>",
+                r#"<pre><code class="language-rust">fn main() {}"#,
+                "\n</code></pre>",
+                "&lt;script&gt;x&lt;/script&gt;",
+            )
+        );
+        assert_eq!(prepared.cover_image.as_deref(), Some("https://example.com/cover.png"));
+        assert_eq!(prepared.tags, ["rust"]);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_content_truncates_long_title() {
+        let prepared = ZhihuAdapter::new()
+            .prepare_content(&content(&"知".repeat(120), ""))
+            .await
+            .unwrap();
+
+        assert_eq!(prepared.title.chars().count(), MAX_TITLE_LENGTH);
+        assert!(prepared.title.ends_with("..."));
+        assert_eq!(prepared.body, "");
+    }
+
+    #[tokio::test]
+    async fn test_prepare_content_truncates_long_body_between_tags() {
+        // The limit falls inside the `<strong>` element at the end
+        let markdown = format!("{}**粗体**", "知".repeat(MAX_CONTENT_LENGTH - 10));
+        let prepared = ZhihuAdapter::new()
+            .prepare_content(&content("发布指南", &markdown))
+            .await
+            .unwrap();
+
+        assert_eq!(prepared.body.chars().count(), MAX_CONTENT_LENGTH);
+        assert_eq!(
+            prepared.body,
+            format!("<p>{}...</p>", "知".repeat(MAX_CONTENT_LENGTH - 10))
+        );
+    }
+}
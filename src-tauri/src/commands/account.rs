@@ -1,8 +1,14 @@
 //! Account management Tauri commands
 
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 
-use crate::models::{Account, AccountStatus, CreateAccountRequest, UpdateAccountRequest};
+use crate::models::{
+    Account, AccountExport, AccountStatus, CreateAccountRequest, ImportSummary,
+    UpdateAccountRequest,
+};
+use crate::services::account::{
+    AccountRefreshSummary, ACCOUNT_REFRESH_EVENT, DEFAULT_REFRESH_CONCURRENCY,
+};
 use crate::AppState;
 
 /// List all accounts
@@ -39,19 +45,21 @@ pub async fn add_account(
     platform: String,
     name: String,
     username: Option<String>,
+    timezone: Option<String>,
 ) -> Result<Account, String> {
     let req = CreateAccountRequest {
         platform,
         name,
         username,
         credentials: None,
+        timezone,
     };
 
     let service = state.account_service.read().await;
     service.create_account(req).await.map_err(|e| e.to_string())
 }
 
-/// Update an account
+/// Update an account; an empty `timezone` clears it
 #[tauri::command]
 pub async fn update_account(
     state: State<'_, AppState>,
@@ -59,14 +67,18 @@ pub async fn update_account(
     name: Option<String>,
     username: Option<String>,
     status: Option<String>,
+    timezone: Option<String>,
 ) -> Result<Account, String> {
     let status = status.and_then(|s| s.parse::<AccountStatus>().ok());
 
+    let timezone = timezone.map(|tz| Some(tz).filter(|tz| !tz.is_empty()));
+
     let req = UpdateAccountRequest {
         name,
         username,
         credentials: None,
         status,
+        timezone,
     };
 
     let service = state.account_service.read().await;
@@ -76,11 +88,46 @@ pub async fn update_account(
         .map_err(|e| e.to_string())
 }
 
-/// Delete an account
+/// Delete an account (moved to trash unless `hard` is set)
+#[tauri::command]
+pub async fn delete_account(
+    state: State<'_, AppState>,
+    id: String,
+    hard: Option<bool>,
+) -> Result<(), String> {
+    let service = state.account_service.read().await;
+    service
+        .delete_account(&id, hard.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List trashed accounts
+#[tauri::command]
+pub async fn list_trashed_accounts(state: State<'_, AppState>) -> Result<Vec<Account>, String> {
+    let service = state.account_service.read().await;
+    service.list_trashed_accounts().await.map_err(|e| e.to_string())
+}
+
+/// Move an account to trash
 #[tauri::command]
-pub async fn delete_account(state: State<'_, AppState>, id: String) -> Result<(), String> {
+pub async fn trash_account(state: State<'_, AppState>, id: String) -> Result<(), String> {
     let service = state.account_service.read().await;
-    service.delete_account(&id).await.map_err(|e| e.to_string())
+    service.trash_account(&id).await.map_err(|e| e.to_string())
+}
+
+/// Restore a trashed account
+#[tauri::command]
+pub async fn restore_account(state: State<'_, AppState>, id: String) -> Result<Account, String> {
+    let service = state.account_service.read().await;
+    service.restore_account(&id).await.map_err(|e| e.to_string())
+}
+
+/// Permanently delete an account
+#[tauri::command]
+pub async fn purge_account(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    let service = state.account_service.read().await;
+    service.purge_account(&id).await.map_err(|e| e.to_string())
 }
 
 /// Update account status
@@ -101,3 +148,78 @@ pub async fn update_account_status(
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Verify an account's login through its platform adapter, marking it active
+/// or expired. Returns whether the login is still valid.
+#[tauri::command]
+pub async fn check_account_auth(state: State<'_, AppState>, account_id: String) -> Result<bool, String> {
+    let registry = state.adapter_registry.read().await;
+    let service = state.account_service.read().await;
+    service
+        .check_auth(&registry, &account_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create accounts in bulk from records parsed by the frontend, skipping
+/// duplicates
+#[tauri::command]
+pub async fn import_accounts(
+    state: State<'_, AppState>,
+    records: Vec<CreateAccountRequest>,
+) -> Result<ImportSummary, String> {
+    let registry = state.adapter_registry.read().await;
+    let service = state.account_service.read().await;
+    service
+        .import_accounts(&registry, records)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Export the account list without credentials or cookies as JSON
+#[tauri::command]
+pub async fn export_accounts(state: State<'_, AppState>) -> Result<String, String> {
+    let service = state.account_service.read().await;
+    let exports = service.export_metadata().await.map_err(|e| e.to_string())?;
+    serde_json::to_string_pretty(&exports).map_err(|e| e.to_string())
+}
+
+/// Recreate accounts from `export_accounts` JSON; they need to log in again
+#[tauri::command]
+pub async fn import_accounts_metadata(
+    state: State<'_, AppState>,
+    json: String,
+) -> Result<ImportSummary, String> {
+    let exports: Vec<AccountExport> =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid account export: {}", e))?;
+    let registry = state.adapter_registry.read().await;
+    let service = state.account_service.read().await;
+    service
+        .import_metadata(&registry, exports)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Re-check every account's login and record the results, emitting
+/// `accounts://refresh-progress` after each account
+#[tauri::command]
+pub async fn refresh_all_accounts(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    concurrency: Option<usize>,
+) -> Result<AccountRefreshSummary, String> {
+    let registry = state.adapter_registry.read().await;
+    let service = state.account_service.read().await;
+    service
+        .refresh_all_statuses(
+            &registry,
+            concurrency.unwrap_or(DEFAULT_REFRESH_CONCURRENCY),
+            |progress| {
+                if let Err(e) = app.emit(ACCOUNT_REFRESH_EVENT, progress) {
+                    tracing::warn!("Failed to emit account refresh progress: {}", e);
+                }
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -2,7 +2,7 @@
 
 use tauri::State;
 use crate::AppState;
-use crate::models::ai::{AIConfig, AICheckLog};
+use crate::models::ai::{AIConfig, AICheckLogList, AICheckLogQuery};
 
 /// List all AI configurations
 #[tauri::command]
@@ -18,6 +18,24 @@ pub async fn toggle_ai_auth(state: State<'_, AppState>, platform: String) -> Res
     service.toggle_auth(&platform).await.map_err(|e| e.to_string())
 }
 
+/// Store encrypted credentials for an AI platform
+#[tauri::command]
+pub async fn set_ai_credentials(
+    state: State<'_, AppState>,
+    platform: String,
+    credentials: serde_json::Value,
+) -> Result<AIConfig, String> {
+    let service = state.ai_service.read().await;
+    service.set_credentials(&platform, credentials).await.map_err(|e| e.to_string())
+}
+
+/// Remove stored credentials for an AI platform
+#[tauri::command]
+pub async fn clear_ai_credentials(state: State<'_, AppState>, platform: String) -> Result<AIConfig, String> {
+    let service = state.ai_service.read().await;
+    service.clear_credentials(&platform).await.map_err(|e| e.to_string())
+}
+
 /// Run AI check task
 #[tauri::command]
 pub async fn run_ai_check(state: State<'_, AppState>) -> Result<(), String> {
@@ -27,10 +45,21 @@ pub async fn run_ai_check(state: State<'_, AppState>) -> Result<(), String> {
 
 /// List AI check logs
 #[tauri::command]
-pub async fn list_ai_logs(state: State<'_, AppState>, limit: Option<i32>) -> Result<Vec<AICheckLog>, String> {
-    let limit = limit.unwrap_or(100);
+pub async fn list_ai_logs(
+    state: State<'_, AppState>,
+    platform: Option<String>,
+    status: Option<String>,
+    limit: Option<i32>,
+    offset: Option<i32>,
+) -> Result<AICheckLogList, String> {
     let service = state.ai_service.read().await;
-    service.list_logs(limit).await.map_err(|e| e.to_string())
+    let query = AICheckLogQuery {
+        platform,
+        status,
+        limit,
+        offset,
+    };
+    service.list_logs(&query).await.map_err(|e| e.to_string())
 }
 
 /// Clear AI check logs
@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 use tauri::State;
 use crate::AppState;
-use crate::models::account::{AuthBackup, AuthStatus};
+use crate::models::account::{AuthBackup, AuthStatus, BackupVerification};
 
 /// Response for auth operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,40 +36,10 @@ pub async fn sync_auth_from_browser(
     // Save session to file first
     browser_service.save_session(&account_id).await.map_err(|e| e.to_string())?;
 
-    // Now fetch the saved cookies and fingerprint from sidecar
-    let client = reqwest::Client::new();
-    let base_url = "http://localhost:8857";
-    
-    // Get cookies from account profile
-    let cookies_resp = client
-        .get(format!("{}/platforms/{}/cookies", base_url, account_id))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-    
-    let cookies: serde_json::Value = if cookies_resp.status().is_success() {
-        cookies_resp.json().await.map_err(|e| e.to_string())?
-    } else {
-        serde_json::json!([])
-    };
-
-    // Get fingerprint from account profile
-    let fp_resp = client
-        .get(format!("{}/platforms/{}/fingerprint", base_url, account_id))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-    
-    let fingerprint: serde_json::Value = if fp_resp.status().is_success() {
-        fp_resp.json().await.map_err(|e| e.to_string())?
-    } else {
-        serde_json::json!({})
-    };
-
-    // Save to database using account_id
+    // Now back up the saved cookies and fingerprint from the account profile
     let auth_service = state.auth_service.read().await;
     auth_service
-        .backup_auth(&account_id, &cookies, &fingerprint)
+        .backup_from_browser(&browser_service, &account_id)
         .await
         .map_err(|e| e.to_string())?;
 
@@ -100,15 +70,15 @@ pub async fn update_auth_status(
     })
 }
 
-/// Get auth status for a platform
+/// Get auth status for an account
 #[tauri::command]
 pub async fn get_auth_status(
     state: State<'_, AppState>,
-    platform: String,
+    account_id: String,
 ) -> Result<String, String> {
     let auth_service = state.auth_service.read().await;
     let status = auth_service
-        .get_auth_status(&platform)
+        .get_auth_status(&account_id)
         .await
         .map_err(|e| e.to_string())?;
     
@@ -127,6 +97,18 @@ pub async fn export_auth_backups(
         .map_err(|e| e.to_string())
 }
 
+/// Check every stored auth backup still decrypts, without returning its contents
+#[tauri::command]
+pub async fn verify_auth_backups(
+    state: State<'_, AppState>,
+) -> Result<Vec<BackupVerification>, String> {
+    let auth_service = state.auth_service.read().await;
+    auth_service
+        .verify_backups()
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Import auth backup from another device
 #[tauri::command]
 pub async fn import_auth_backup(
@@ -145,6 +127,38 @@ pub async fn import_auth_backup(
     })
 }
 
+/// Export all auth backups to a passphrase-encrypted file
+#[tauri::command]
+pub async fn export_auth_file(
+    state: State<'_, AppState>,
+    path: String,
+    passphrase: String,
+) -> Result<(), String> {
+    let auth_service = state.auth_service.read().await;
+    let bytes = auth_service
+        .export_encrypted(&passphrase)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    std::fs::write(&path, bytes).map_err(|e| e.to_string())
+}
+
+/// Import auth backups from a passphrase-encrypted file, returning how many were restored
+#[tauri::command]
+pub async fn import_auth_file(
+    state: State<'_, AppState>,
+    path: String,
+    passphrase: String,
+) -> Result<usize, String> {
+    let bytes = std::fs::read(&path).map_err(|e| e.to_string())?;
+
+    let auth_service = state.auth_service.read().await;
+    auth_service
+        .import_encrypted(&bytes, &passphrase)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Clear auth for a platform
 #[tauri::command]
 pub async fn clear_auth(
@@ -163,50 +177,42 @@ pub async fn clear_auth(
     })
 }
 
-/// Restore auth backup to browser (restore cookies/fingerprint to sidecar)
+/// Check whether an account's stored session is still logged in
+#[tauri::command]
+pub async fn verify_auth(
+    state: State<'_, AppState>,
+    account_id: String,
+) -> Result<String, String> {
+    let auth_service = state.auth_service.read().await;
+    let status = auth_service
+        .verify_auth(&account_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(status.to_string())
+}
+
+/// Restore an account's auth backup into its own browser profile in the sidecar
 #[tauri::command]
 pub async fn restore_auth_to_browser(
     state: State<'_, AppState>,
-    platform: String,
+    account_id: String,
 ) -> Result<AuthResponse, String> {
-    // Get backup from database
     let auth_service = state.auth_service.read().await;
-    let backup = auth_service
-        .restore_auth(&platform)
+    let restored = auth_service
+        .restore_to_browser(&account_id)
         .await
         .map_err(|e| e.to_string())?;
 
-    match backup {
-        Some(b) => {
-            // Send to sidecar to restore
-            let client = reqwest::Client::new();
-            let base_url = "http://localhost:8857";
-            
-            let resp = client
-                .post(format!("{}/platforms/{}/restore", base_url, platform))
-                .json(&serde_json::json!({
-                    "cookies": b.cookies,
-                    "fingerprint": b.fingerprint,
-                }))
-                .send()
-                .await
-                .map_err(|e| e.to_string())?;
-
-            if resp.status().is_success() {
-                Ok(AuthResponse {
-                    success: true,
-                    error: None,
-                })
-            } else {
-                Ok(AuthResponse {
-                    success: false,
-                    error: Some("Failed to restore to browser".to_string()),
-                })
-            }
-        }
-        None => Ok(AuthResponse {
+    if restored {
+        Ok(AuthResponse {
+            success: true,
+            error: None,
+        })
+    } else {
+        Ok(AuthResponse {
             success: false,
-            error: Some("No backup found for this platform".to_string()),
-        }),
+            error: Some("No backup found for this account".to_string()),
+        })
     }
 }
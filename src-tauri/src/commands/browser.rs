@@ -1,16 +1,21 @@
 //! Browser automation commands
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use tauri::State;
 use crate::AppState;
-use crate::services::browser::{BrowserResponse, PageInfoResponse, SessionInfo};
+use crate::infrastructure::http::{HttpClientFactory, DEFAULT_TIMEOUT};
+use crate::services::browser::{BrowserResponse, LoginStateResponse, PageInfoResponse, SessionDetail, SessionInfo};
 
-const SIDECAR_URL: &str = "http://localhost:8857";
+use crate::services::sidecar_manager::{configured_port, sidecar_base_url};
 
 /// Check if sidecar is running (independent of AppState)
 #[tauri::command]
 pub async fn browser_health_check() -> Result<bool, String> {
-    let client = reqwest::Client::new();
-    match client.get(format!("{}/health", SIDECAR_URL)).send().await {
+    let base_url = sidecar_base_url(configured_port().map_err(|e| e.to_string())?);
+    let client = HttpClientFactory::new()
+        .direct_client(DEFAULT_TIMEOUT)
+        .map_err(|e| e.to_string())?;
+    match client.get(format!("{}/health", base_url)).send().await {
         Ok(resp) => Ok(resp.status().is_success()),
         Err(_) => Ok(false),
     }
@@ -34,10 +39,19 @@ pub async fn launch_browser(
     };
 
     let browser_service = state.browser_service.read().await;
-    browser_service
+    let response = browser_service
         .launch_browser(&account_id, &platform_id, proxy.as_ref(), headless)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    if let Some(proxy) = proxy.filter(|_| response.success) {
+        let proxy_service = state.proxy_service.read().await;
+        if let Err(e) = proxy_service.record_usage(&proxy.id, &account_id).await {
+            tracing::warn!("Failed to record usage of proxy {}: {}", proxy.id, e);
+        }
+    }
+
+    Ok(response)
 }
 
 /// Navigate to URL
@@ -81,6 +95,17 @@ pub async fn browser_close(
     service.close_browser(&account_id).await.map_err(|e| e.to_string())
 }
 
+/// Capture a screenshot of the account's browser as base64-encoded PNG
+#[tauri::command]
+pub async fn browser_screenshot(
+    state: State<'_, AppState>,
+    account_id: String,
+) -> Result<String, String> {
+    let service = state.browser_service.read().await;
+    let png = service.screenshot(&account_id).await.map_err(|e| e.to_string())?;
+    Ok(STANDARD.encode(png))
+}
+
 /// Get all active browser sessions
 #[tauri::command]
 pub async fn browser_get_sessions(
@@ -90,6 +115,22 @@ pub async fn browser_get_sessions(
     service.get_sessions().await.map_err(|e| e.to_string())
 }
 
+/// Get all active browser sessions with their account's platform and name
+#[tauri::command]
+pub async fn browser_list_session_details(
+    state: State<'_, AppState>,
+) -> Result<Vec<SessionDetail>, String> {
+    let sessions = state
+        .browser_service
+        .read()
+        .await
+        .get_sessions()
+        .await
+        .map_err(|e| e.to_string())?;
+    let account_service = state.account_service.read().await;
+    account_service.session_details(sessions).await.map_err(|e| e.to_string())
+}
+
 /// Close all browsers
 #[tauri::command]
 pub async fn browser_close_all(
@@ -101,44 +142,42 @@ pub async fn browser_close_all(
 
 /// Get login state from sidecar (cached from login watcher)
 #[tauri::command]
-pub async fn browser_get_login_state(account_id: String) -> Result<LoginStateResponse, String> {
-    let client = reqwest::Client::new();
-    let url = format!("{}/browser/{}/login-state", SIDECAR_URL, account_id);
-    
-    match client.get(&url).send().await {
-        Ok(resp) => {
-            if resp.status().is_success() {
-                resp.json().await.map_err(|e| e.to_string())
-            } else {
-                Ok(LoginStateResponse {
-                    success: false,
-                    account_id: Some(account_id),
-                    is_logged_in: false,
-                    login_detected_at: None,
-                    error: Some("Failed to get login state".to_string()),
-                })
-            }
-        }
+pub async fn browser_get_login_state(
+    state: State<'_, AppState>,
+    account_id: String,
+) -> Result<LoginStateResponse, String> {
+    let service = state.browser_service.read().await;
+    match service.get_login_state(&account_id).await {
+        Ok(login_state) => Ok(login_state),
         Err(e) => Ok(LoginStateResponse {
             success: false,
             account_id: Some(account_id),
             is_logged_in: false,
             login_detected_at: None,
             error: Some(e.to_string()),
+            timed_out: false,
         }),
     }
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct LoginStateResponse {
-    pub success: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub account_id: Option<String>,
-    #[serde(default)]
-    pub is_logged_in: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub login_detected_at: Option<i64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub error: Option<String>,
+/// Watch an account's QR login, emitting `browser://login-state` on changes and
+/// backing up its auth once logged in
+#[tauri::command]
+pub async fn start_login_watch(
+    state: State<'_, AppState>,
+    account_id: String,
+) -> Result<(), String> {
+    let service = state.browser_service.read().await;
+    service.start_login_watch(state.auth_service.clone(), &account_id);
+    Ok(())
+}
+
+/// Stop watching an account's login, returning whether a watch was running
+#[tauri::command]
+pub async fn stop_login_watch(
+    state: State<'_, AppState>,
+    account_id: String,
+) -> Result<bool, String> {
+    let service = state.browser_service.read().await;
+    Ok(service.stop_login_watch(&account_id))
 }
@@ -2,14 +2,31 @@
 
 use tauri::State;
 
-use crate::models::Content;
+use crate::adapters::PreparedContent;
+use crate::models::{
+    ConflictResolution, Content, ContentListQuery, ContentListResponse, ContentStatus, CreateContentRequest,
+    DuplicateContentGroup, UpdateContentRequest,
+};
+use crate::services::{apply_cms_proxy, ConnectionInfo, ContentApiSettings};
 use crate::AppState;
 
-/// List all local contents
+/// List local contents with optional paging and filters
 #[tauri::command]
-pub async fn list_contents(state: State<'_, AppState>) -> Result<Vec<Content>, String> {
+pub async fn list_contents(
+    state: State<'_, AppState>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+    status: Option<ContentStatus>,
+    search: Option<String>,
+) -> Result<ContentListResponse, String> {
     let service = state.content_service.read().await;
-    service.list_contents().await.map_err(|e| e.to_string())
+    let query = ContentListQuery {
+        page,
+        per_page,
+        status,
+        search,
+    };
+    service.list_contents(&query).await.map_err(|e| e.to_string())
 }
 
 /// Get a single content by ID
@@ -19,21 +36,196 @@ pub async fn get_content(state: State<'_, AppState>, id: String) -> Result<Conte
     service.get_content(&id).await.map_err(|e| e.to_string())
 }
 
-/// Sync all contents from remote API
+/// Preview a content as it would be published to a platform
+#[tauri::command]
+pub async fn preview_content(
+    state: State<'_, AppState>,
+    content_id: String,
+    platform: String,
+) -> Result<PreparedContent, String> {
+    let registry = state.adapter_registry.read().await;
+    let service = state.content_service.read().await;
+    service
+        .preview_content(&registry, &content_id, &platform)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create a local content draft
+#[tauri::command]
+pub async fn create_content(
+    state: State<'_, AppState>,
+    req: CreateContentRequest,
+) -> Result<Content, String> {
+    let service = state.content_service.read().await;
+    service.create_content(req).await.map_err(|e| e.to_string())
+}
+
+/// Update a content item
+#[tauri::command]
+pub async fn update_content(
+    state: State<'_, AppState>,
+    id: String,
+    req: UpdateContentRequest,
+) -> Result<Content, String> {
+    let service = state.content_service.read().await;
+    service
+        .update_content(&id, req)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a content item (soft delete unless `hard` is set)
+#[tauri::command]
+pub async fn delete_content(
+    state: State<'_, AppState>,
+    id: String,
+    hard: Option<bool>,
+) -> Result<(), String> {
+    let service = state.content_service.read().await;
+    service
+        .delete_content(&id, hard.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List every tag in use with how many contents use it
+#[tauri::command]
+pub async fn list_content_tags(state: State<'_, AppState>) -> Result<Vec<(String, i64)>, String> {
+    let service = state.content_service.read().await;
+    service.list_all_tags().await.map_err(|e| e.to_string())
+}
+
+/// Rename a tag across all contents, returning how many changed
+#[tauri::command]
+pub async fn rename_content_tag(
+    state: State<'_, AppState>,
+    from: String,
+    to: String,
+) -> Result<u64, String> {
+    let service = state.content_service.read().await;
+    service.rename_tag(&from, &to).await.map_err(|e| e.to_string())
+}
+
+/// Merge several tags into one across all contents, returning how many changed
+#[tauri::command]
+pub async fn merge_content_tags(
+    state: State<'_, AppState>,
+    from: Vec<String>,
+    into: String,
+) -> Result<u64, String> {
+    let service = state.content_service.read().await;
+    service.merge_tags(&from, &into).await.map_err(|e| e.to_string())
+}
+
+/// Get the CMS API URL and whether a key is stored
+#[tauri::command]
+pub async fn get_content_api_config(state: State<'_, AppState>) -> Result<ContentApiSettings, String> {
+    let service = state.content_service.read().await;
+    Ok(service.api_settings())
+}
+
+/// Set the CMS API URL and key (`None` keeps the stored key, empty removes it)
+#[tauri::command]
+pub async fn set_content_api_config(
+    state: State<'_, AppState>,
+    base_url: String,
+    api_key: Option<String>,
+) -> Result<ContentApiSettings, String> {
+    let encryption = state.encryption.read().await;
+    let mut service = state.content_service.write().await;
+    service
+        .configure_api(&encryption, &base_url, api_key.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Test the CMS API address and key
+#[tauri::command]
+pub async fn test_cms_connection(state: State<'_, AppState>) -> Result<ConnectionInfo, String> {
+    let service = state.content_service.read().await;
+    service.test_connection().await.map_err(|e| e.to_string())
+}
+
+/// Sync all contents from remote API, through the CMS proxy if one is configured
 #[tauri::command]
 pub async fn sync_contents(state: State<'_, AppState>) -> Result<SyncResultDto, String> {
+    apply_cms_proxy(
+        &state.settings_service,
+        &state.proxy_service,
+        &state.content_service,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
     let service = state.content_service.read().await;
     let result = service.sync_all().await.map_err(|e| e.to_string())?;
 
     Ok(SyncResultDto {
         synced: result.synced,
         failed: result.failed,
+        conflicts: result.conflicts,
+        duplicates: result.duplicates,
+        proxy_id: result.proxy_id,
+        fell_back_to_direct: result.fell_back_to_direct,
     })
 }
 
+/// Sync a single content from the remote API by its remote ID
+#[tauri::command]
+pub async fn sync_content(state: State<'_, AppState>, remote_id: String) -> Result<Content, String> {
+    apply_cms_proxy(
+        &state.settings_service,
+        &state.proxy_service,
+        &state.content_service,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let service = state.content_service.read().await;
+    service.sync_one(&remote_id).await.map_err(|e| e.to_string())
+}
+
+/// List contents with unresolved sync conflicts
+#[tauri::command]
+pub async fn get_content_conflicts(state: State<'_, AppState>) -> Result<Vec<Content>, String> {
+    let service = state.content_service.read().await;
+    service.get_conflicts().await.map_err(|e| e.to_string())
+}
+
+/// Resolve a sync conflict by keeping the local or remote version
+#[tauri::command]
+pub async fn resolve_content_conflict(
+    state: State<'_, AppState>,
+    id: String,
+    keep: ConflictResolution,
+) -> Result<Content, String> {
+    let service = state.content_service.read().await;
+    service
+        .resolve_conflict(&id, keep)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List groups of contents with the same normalized title and body
+#[tauri::command]
+pub async fn find_duplicate_contents(
+    state: State<'_, AppState>,
+) -> Result<Vec<DuplicateContentGroup>, String> {
+    let service = state.content_service.read().await;
+    service
+        .find_duplicate_contents()
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// DTO for sync result
 #[derive(serde::Serialize)]
 pub struct SyncResultDto {
     pub synced: i32,
     pub failed: i32,
+    pub conflicts: i32,
+    pub duplicates: i32,
+    pub proxy_id: Option<String>,
+    pub fell_back_to_direct: bool,
 }
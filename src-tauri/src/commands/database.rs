@@ -0,0 +1,46 @@
+//! Database maintenance Tauri commands
+
+use std::path::PathBuf;
+
+use tauri::{AppHandle, State};
+
+use crate::infrastructure::database;
+use crate::AppState;
+
+/// Back up the database to `dest`
+#[tauri::command]
+pub async fn backup_database(state: State<'_, AppState>, dest: String) -> Result<(), String> {
+    database::backup_database(&state.db, &PathBuf::from(dest))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Restore the database from a backup and restart the app to load it.
+///
+/// Encrypted secrets in the backup can only be read with the master key of
+/// the machine that created it.
+#[tauri::command]
+pub async fn restore_database(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    src: String,
+) -> Result<(), String> {
+    database::restore_database(&PathBuf::from(src), &state.db_config)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    state.db.close().await;
+    app.restart()
+}
+
+/// Applied migration version, whether migrations are pending and the app version
+#[tauri::command]
+pub async fn get_db_version(state: State<'_, AppState>) -> Result<database::DbVersion, String> {
+    database::db_version(&state.db).await.map_err(|e| e.to_string())
+}
+
+/// Reclaim unused space in the database file
+#[tauri::command]
+pub async fn vacuum_database(state: State<'_, AppState>) -> Result<database::VacuumReport, String> {
+    database::vacuum(&state.db).await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,25 @@
+//! System health Tauri commands
+
+use tauri::State;
+
+use crate::services::health::{check_system_health, HEALTH_CHECK_TIMEOUT};
+use crate::services::SystemHealth;
+use crate::AppState;
+
+/// Check every subsystem at once: sidecar, browser service, database, CMS API,
+/// proxies and accounts
+#[tauri::command]
+pub async fn system_health(state: State<'_, AppState>) -> Result<SystemHealth, String> {
+    let sidecar = state.sidecar_manager.read().await.get_status_info().await;
+    let browser = state.browser_service.read().await;
+    let content = state.content_service.read().await;
+
+    Ok(check_system_health(
+        &state.db,
+        &sidecar,
+        browser.health_check(),
+        content.ping_api(),
+        HEALTH_CHECK_TIMEOUT,
+    )
+    .await)
+}
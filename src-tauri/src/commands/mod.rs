@@ -7,8 +7,14 @@ pub mod ai;
 pub mod auth;
 pub mod browser;
 pub mod content;
+pub mod database;
+pub mod health;
+pub mod notification;
+pub mod platform;
 pub mod proxy;
 pub mod scheduler;
+pub mod security;
+pub mod settings;
 pub mod stats;
 
 pub use account::*;
@@ -16,8 +22,14 @@ pub use ai::*;
 pub use auth::*;
 pub use browser::*;
 pub use content::*;
+pub use database::*;
+pub use health::*;
+pub use notification::*;
+pub use platform::*;
 pub use proxy::*;
 pub use scheduler::*;
+pub use security::*;
+pub use settings::*;
 pub use stats::*;
 
 /// Greet command for testing
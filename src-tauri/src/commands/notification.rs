@@ -0,0 +1,32 @@
+//! Notification Tauri commands
+
+use tauri::State;
+
+use crate::AppState;
+
+/// Get the webhook URLs notified when a task finishes
+#[tauri::command]
+pub async fn get_notification_webhooks(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let service = state.notification_service.read().await;
+    service.webhooks().await.map_err(|e| e.to_string())
+}
+
+/// Replace the webhook URLs notified when a task finishes
+#[tauri::command]
+pub async fn set_notification_webhook(
+    state: State<'_, AppState>,
+    urls: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let service = state.notification_service.read().await;
+    service.set_webhooks(&urls).await.map_err(|e| e.to_string())
+}
+
+/// Send a sample notification to a webhook
+#[tauri::command]
+pub async fn test_notification_webhook(
+    state: State<'_, AppState>,
+    url: String,
+) -> Result<(), String> {
+    let service = state.notification_service.read().await;
+    service.test_webhook(&url).await.map_err(|e| e.to_string())
+}
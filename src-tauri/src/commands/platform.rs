@@ -0,0 +1,33 @@
+//! Platform enable/disable commands
+
+use tauri::State;
+
+use crate::AppState;
+
+/// List enabled platform IDs
+#[tauri::command]
+pub async fn list_platforms(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let registry = state.adapter_registry.read().await;
+    Ok(registry.list_platforms().into_iter().map(str::to_string).collect())
+}
+
+/// Check whether a platform is enabled
+#[tauri::command]
+pub async fn is_platform_enabled(state: State<'_, AppState>, platform: String) -> Result<bool, String> {
+    let registry = state.adapter_registry.read().await;
+    Ok(registry.is_platform_enabled(&platform))
+}
+
+/// Enable or disable a platform; its accounts and data are kept
+#[tauri::command]
+pub async fn set_platform_enabled(
+    state: State<'_, AppState>,
+    platform: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut registry = state.adapter_registry.write().await;
+    registry
+        .set_platform_enabled(&platform, enabled)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -3,7 +3,12 @@
 use tauri::State;
 
 use crate::error::PubCastError;
-use crate::models::{CreateProxyRequest, Proxy, ProxyHealthResult};
+use crate::models::{
+    CreateProxyRequest, ImportResult, Proxy, ProxyHealthRecord, ProxyHealthResult,
+    ProxyUsageRecord, UpdateProxyRequest,
+};
+use crate::services::proxy::DEFAULT_HEALTH_CHECK_CONCURRENCY;
+use crate::services::proxy_monitor::ProxyHealthCheckStatus;
 use crate::AppState;
 
 /// List all proxies
@@ -46,6 +51,37 @@ pub async fn add_proxy(
     service.create_proxy(req).await.map_err(|e| e.to_string())
 }
 
+/// Update an existing proxy; an empty `username` clears it
+#[tauri::command]
+pub async fn update_proxy(
+    state: State<'_, AppState>,
+    id: String,
+    protocol: Option<String>,
+    host: Option<String>,
+    port: Option<u16>,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<Proxy, String> {
+    let protocol = protocol
+        .map(|p| p.parse())
+        .transpose()
+        .map_err(|e: String| PubCastError::Validation(e).to_string())?;
+
+    let req = UpdateProxyRequest {
+        protocol,
+        host,
+        port,
+        username: username.map(|u| Some(u).filter(|u| !u.is_empty())),
+        password,
+    };
+
+    let service = state.proxy_service.read().await;
+    service
+        .update_proxy(&id, req)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Delete a proxy
 #[tauri::command]
 pub async fn delete_proxy(state: State<'_, AppState>, id: String) -> Result<(), String> {
@@ -66,12 +102,84 @@ pub async fn check_proxy(
         .map_err(|e| e.to_string())
 }
 
+/// Check all proxies concurrently
+#[tauri::command]
+pub async fn check_all_proxies(
+    state: State<'_, AppState>,
+    concurrency: Option<usize>,
+) -> Result<Vec<ProxyHealthResult>, String> {
+    let service = state.proxy_service.read().await;
+    service
+        .check_all_proxies(concurrency.unwrap_or(DEFAULT_HEALTH_CHECK_CONCURRENCY))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Check every proxy periodically in the background, optionally changing the
+/// interval. Stays on across restarts until stopped.
+#[tauri::command]
+pub async fn start_proxy_health_checks(
+    state: State<'_, AppState>,
+    interval_minutes: Option<u64>,
+) -> Result<ProxyHealthCheckStatus, String> {
+    let mut monitor = state.proxy_health_monitor.write().await;
+    monitor.enable(interval_minutes).await.map_err(|e| e.to_string())?;
+    monitor.status().await.map_err(|e| e.to_string())
+}
+
+/// Stop the periodic proxy health checks
+#[tauri::command]
+pub async fn stop_proxy_health_checks(
+    state: State<'_, AppState>,
+) -> Result<ProxyHealthCheckStatus, String> {
+    let mut monitor = state.proxy_health_monitor.write().await;
+    monitor.disable().await.map_err(|e| e.to_string())?;
+    monitor.status().await.map_err(|e| e.to_string())
+}
+
+/// Whether periodic proxy health checks run, and when all proxies were last checked
+#[tauri::command]
+pub async fn get_proxy_health_check_status(
+    state: State<'_, AppState>,
+) -> Result<ProxyHealthCheckStatus, String> {
+    let monitor = state.proxy_health_monitor.read().await;
+    monitor.status().await.map_err(|e| e.to_string())
+}
+
+/// Get health check history for a proxy
+#[tauri::command]
+pub async fn get_proxy_history(
+    state: State<'_, AppState>,
+    id: String,
+    limit: Option<i64>,
+) -> Result<Vec<ProxyHealthRecord>, String> {
+    let service = state.proxy_service.read().await;
+    service
+        .get_proxy_history(&id, limit.unwrap_or(100))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the accounts recently routed through a proxy
+#[tauri::command]
+pub async fn get_proxy_usage(
+    state: State<'_, AppState>,
+    id: String,
+    limit: Option<i64>,
+) -> Result<Vec<ProxyUsageRecord>, String> {
+    let service = state.proxy_service.read().await;
+    service
+        .get_proxy_usage(&id, limit.unwrap_or(100))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Import proxies from text
 #[tauri::command]
 pub async fn import_proxies(
     state: State<'_, AppState>,
     text: String,
-) -> Result<Vec<Proxy>, String> {
+) -> Result<ImportResult, String> {
     let service = state.proxy_service.read().await;
     service
         .import_proxies(&text)
@@ -1,33 +1,22 @@
 //! Scheduler Tauri commands
 
+use std::path::Path;
+
 use tauri::State;
 
-use crate::models::{CreateDistributionTaskRequest, DistributionTask, ScheduleType};
+use crate::models::{
+    CreateDistributionTaskRequest, CreateDistributionTaskResponse, DistributionTask,
+    DistributionTaskListQuery, DistributionTaskListResponse, DistributionTaskStatus, PublishJob,
+};
+use crate::services::scheduler::TaskPurge;
 use crate::AppState;
 
 /// Create a distribution task
 #[tauri::command]
 pub async fn create_distribution_task(
     state: State<'_, AppState>,
-    content_id: String,
-    name: Option<String>,
-    target_account_ids: Vec<String>,
-    schedule_type: String,
-    scheduled_at: Option<i64>,
-) -> Result<DistributionTask, String> {
-    let schedule_type = match schedule_type.as_str() {
-        "scheduled" => ScheduleType::Scheduled,
-        _ => ScheduleType::Immediate,
-    };
-
-    let req = CreateDistributionTaskRequest {
-        content_id,
-        name,
-        target_account_ids,
-        schedule_type,
-        scheduled_at,
-    };
-
+    req: CreateDistributionTaskRequest,
+) -> Result<CreateDistributionTaskResponse, String> {
     let service = state.scheduler_service.read().await;
     service
         .create_distribution_task(req)
@@ -48,14 +37,35 @@ pub async fn get_distribution_task(
         .map_err(|e| e.to_string())
 }
 
-/// List distribution tasks
+/// List distribution tasks a page at a time, optionally by status
 #[tauri::command]
 pub async fn list_distribution_tasks(
     state: State<'_, AppState>,
-) -> Result<Vec<DistributionTask>, String> {
+    page: Option<i32>,
+    per_page: Option<i32>,
+    status: Option<DistributionTaskStatus>,
+) -> Result<DistributionTaskListResponse, String> {
+    let service = state.scheduler_service.read().await;
+    let query = DistributionTaskListQuery {
+        page,
+        per_page,
+        status,
+    };
+    service
+        .list_distribution_tasks(&query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List the publish jobs of a distribution task
+#[tauri::command]
+pub async fn list_task_jobs(
+    state: State<'_, AppState>,
+    task_id: String,
+) -> Result<Vec<PublishJob>, String> {
     let service = state.scheduler_service.read().await;
     service
-        .list_distribution_tasks()
+        .list_jobs_for_task(&task_id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -72,3 +82,71 @@ pub async fn cancel_distribution_task(
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Pause a distribution task without losing its progress
+#[tauri::command]
+pub async fn pause_distribution_task(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    let service = state.scheduler_service.read().await;
+    service
+        .pause_distribution_task(&id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Resume a paused distribution task
+#[tauri::command]
+pub async fn resume_distribution_task(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    let service = state.scheduler_service.read().await;
+    service
+        .resume_distribution_task(&id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the maximum number of concurrent publish jobs
+#[tauri::command]
+pub async fn get_publish_concurrency(state: State<'_, AppState>) -> Result<usize, String> {
+    let service = state.scheduler_service.read().await;
+    Ok(service.concurrency())
+}
+
+/// Set the maximum number of concurrent publish jobs
+#[tauri::command]
+pub async fn set_publish_concurrency(
+    state: State<'_, AppState>,
+    concurrency: usize,
+) -> Result<(), String> {
+    let service = state.scheduler_service.read().await;
+    service
+        .set_concurrency(concurrency)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Cancel a single publish job within a distribution task
+#[tauri::command]
+pub async fn cancel_publish_job(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    let service = state.scheduler_service.read().await;
+    service.cancel_job(&id).await.map_err(|e| e.to_string())
+}
+
+/// Delete finished distribution tasks older than `older_than_days`, with
+/// their jobs, optionally archiving them to `archive_path` as JSON first
+#[tauri::command]
+pub async fn purge_old_tasks(
+    state: State<'_, AppState>,
+    older_than_days: i64,
+    archive_path: Option<String>,
+) -> Result<TaskPurge, String> {
+    let service = state.scheduler_service.read().await;
+    service
+        .purge_old_tasks(older_than_days, archive_path.as_deref().map(Path::new))
+        .await
+        .map_err(|e| e.to_string())
+}
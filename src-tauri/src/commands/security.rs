@@ -0,0 +1,32 @@
+//! Security Tauri commands
+
+use tauri::State;
+
+use crate::infrastructure::encryption;
+use crate::AppState;
+
+/// Rotate the master key and re-encrypt all stored secrets.
+///
+/// Returns the rows that could not be decrypted and were left unchanged.
+#[tauri::command]
+pub async fn rotate_master_key(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    // Hold every secret-handling service for the duration so nothing is
+    // written under the old key while rows are being re-encrypted
+    let mut encryption = state.encryption.write().await;
+    let mut account_service = state.account_service.write().await;
+    let mut proxy_service = state.proxy_service.write().await;
+    let mut auth_service = state.auth_service.write().await;
+    let mut ai_service = state.ai_service.write().await;
+
+    let rotated = encryption::rotate_master_key(&state.keychain, &state.db, &encryption)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    account_service.set_encryption(rotated.service.clone());
+    proxy_service.set_encryption(rotated.service.clone());
+    auth_service.set_encryption(rotated.service.clone());
+    ai_service.set_encryption(rotated.service.clone());
+    *encryption = rotated.service;
+
+    Ok(rotated.skipped)
+}
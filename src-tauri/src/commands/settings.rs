@@ -0,0 +1,67 @@
+//! Application settings Tauri commands
+
+use tauri::State;
+
+use crate::infrastructure::http_log::{self, HTTP_LOGGING_SETTING_KEY};
+use crate::services::apply_cms_proxy;
+use crate::services::content::CMS_PROXY_KEYS;
+use crate::AppState;
+
+/// Get a setting's JSON value, `null` when unset
+#[tauri::command]
+pub async fn get_setting(
+    state: State<'_, AppState>,
+    key: String,
+) -> Result<Option<serde_json::Value>, String> {
+    let service = state.settings_service.read().await;
+    service.get_value(&key).await.map_err(|e| e.to_string())
+}
+
+/// Store a setting's JSON value
+#[tauri::command]
+pub async fn set_setting(
+    state: State<'_, AppState>,
+    key: String,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    state
+        .settings_service
+        .read()
+        .await
+        .set(&key, &value)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Later CMS requests, not just syncs, go through the new proxy
+    if CMS_PROXY_KEYS.contains(&key.as_str()) {
+        apply_cms_proxy(
+            &state.settings_service,
+            &state.proxy_service,
+            &state.content_service,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Whether outbound HTTP requests are being logged
+#[tauri::command]
+pub async fn get_http_logging() -> Result<bool, String> {
+    Ok(http_log::is_enabled())
+}
+
+/// Turn logging of outbound HTTP requests on or off, now and on later launches
+#[tauri::command]
+pub async fn set_http_logging(
+    state: State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    let service = state.settings_service.read().await;
+    service
+        .set(HTTP_LOGGING_SETTING_KEY, &enabled)
+        .await
+        .map_err(|e| e.to_string())?;
+    http_log::set_enabled(enabled);
+    Ok(())
+}
@@ -2,21 +2,31 @@
 
 use tauri::State;
 
-use crate::services::stats::{DailyStats, PlatformStats, PublishStats};
+use crate::services::stats::{
+    DailyStats, ErrorBreakdown, PlatformStats, PublishStats, StatsKind, StatsReset,
+};
 use crate::AppState;
 
-/// Get overall publish statistics
+/// Get overall publish statistics, optionally within a `from`/`to` Unix-timestamp range
 #[tauri::command]
-pub async fn get_overall_stats(state: State<'_, AppState>) -> Result<PublishStats, String> {
+pub async fn get_overall_stats(
+    state: State<'_, AppState>,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<PublishStats, String> {
     let service = state.stats_service.read().await;
-    service.get_overall_stats().await.map_err(|e| e.to_string())
+    service.get_overall_stats(from, to).await.map_err(|e| e.to_string())
 }
 
-/// Get statistics by platform
+/// Get statistics by platform, optionally within a `from`/`to` Unix-timestamp range
 #[tauri::command]
-pub async fn get_platform_stats(state: State<'_, AppState>) -> Result<Vec<PlatformStats>, String> {
+pub async fn get_platform_stats(
+    state: State<'_, AppState>,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<Vec<PlatformStats>, String> {
     let service = state.stats_service.read().await;
-    service.get_platform_stats().await.map_err(|e| e.to_string())
+    service.get_platform_stats(from, to).await.map_err(|e| e.to_string())
 }
 
 /// Get daily statistics for the last N days
@@ -29,3 +39,42 @@ pub async fn get_daily_stats(
     let service = state.stats_service.read().await;
     service.get_daily_stats(days).await.map_err(|e| e.to_string())
 }
+
+/// Get failed publish counts per error category
+#[tauri::command]
+pub async fn get_error_breakdown(
+    state: State<'_, AppState>,
+) -> Result<Vec<ErrorBreakdown>, String> {
+    let service = state.stats_service.read().await;
+    service.get_error_breakdown().await.map_err(|e| e.to_string())
+}
+
+/// Export statistics as CSV text; the frontend writes it to disk
+#[tauri::command]
+pub async fn export_stats_csv(
+    state: State<'_, AppState>,
+    kind: StatsKind,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<String, String> {
+    let service = state.stats_service.read().await;
+    service.export_csv(kind, from, to).await.map_err(|e| e.to_string())
+}
+
+/// Clear publish logs and daily statistics, plus finished publish jobs when
+/// `include_jobs` is set. `confirm` must be true, guarding against stray clicks.
+#[tauri::command]
+pub async fn reset_stats(
+    state: State<'_, AppState>,
+    confirm: bool,
+    include_jobs: Option<bool>,
+) -> Result<StatsReset, String> {
+    if !confirm {
+        return Err("Resetting statistics must be confirmed".to_string());
+    }
+    let service = state.stats_service.read().await;
+    service
+        .reset_stats(include_jobs.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -22,6 +22,9 @@ pub enum PubCastError {
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
     #[error("Platform adapter error: {0}")]
     PlatformAdapter(String),
 
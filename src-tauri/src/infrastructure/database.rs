@@ -1,16 +1,65 @@
 //! Database infrastructure
 //!
-//! Handles SQLite connection pool initialization and migrations.
+//! Handles SQLite connection pool initialization and migrations. Built with
+//! the `sqlcipher` feature, the whole database file can also be encrypted at
+//! rest with a key from the keychain (see [`DatabaseConfig::with_encryption_key`]).
+//! Encryption is opt-in through `PUBCAST_DB_ENCRYPT`.
 
+use std::future::Future;
+
+use serde::Serialize;
 use sqlx::{
     sqlite::{SqliteConnectOptions, SqlitePoolOptions},
-    SqlitePool,
+    ConnectOptions, Connection, SqlitePool,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 
 use crate::error::{PubCastError, Result};
 
+/// Environment variable overriding the connection pool size
+pub const MAX_CONNECTIONS_ENV: &str = "PUBCAST_DB_MAX_CONNECTIONS";
+/// Environment variable that turns on database file encryption in SQLCipher builds
+#[cfg(feature = "sqlcipher")]
+pub const ENCRYPT_ENV: &str = "PUBCAST_DB_ENCRYPT";
+/// Default connection pool size
+const DEFAULT_MAX_CONNECTIONS: u32 = 5;
+/// How long a connection waits on a locked database before failing
+const DEFAULT_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Retries after the first attempt of a write that found the database locked
+const LOCK_RETRIES: u32 = 3;
+/// Delay before the first retry of a locked write, doubled for each one after
+const LOCK_RETRY_BACKOFF: Duration = Duration::from_millis(50);
+/// Header every unencrypted SQLite database file starts with
+#[cfg(feature = "sqlcipher")]
+const PLAINTEXT_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Raw 256-bit SQLCipher key for the database file
+#[cfg(feature = "sqlcipher")]
+#[derive(Clone)]
+pub struct DatabaseKey(zeroize::Zeroizing<[u8; 32]>);
+
+#[cfg(feature = "sqlcipher")]
+impl DatabaseKey {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self(zeroize::Zeroizing::new(key))
+    }
+
+    /// Key in SQLCipher's raw key syntax, `x'<64 hex digits>'`
+    fn raw_key(&self) -> zeroize::Zeroizing<String> {
+        let hex: String = self.0.iter().map(|b| format!("{:02x}", b)).collect();
+        zeroize::Zeroizing::new(format!("x'{}'", hex))
+    }
+}
+
+#[cfg(feature = "sqlcipher")]
+impl std::fmt::Debug for DatabaseKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DatabaseKey(..)")
+    }
+}
+
 /// Database configuration
 #[derive(Debug, Clone)]
 pub struct DatabaseConfig {
@@ -18,30 +67,126 @@ pub struct DatabaseConfig {
     pub database_path: PathBuf,
     /// Maximum number of connections in the pool
     pub max_connections: u32,
+    /// How long a write waits for another connection's lock before failing
+    pub busy_timeout: Duration,
+    /// Encrypt the database file with this key; an existing plaintext
+    /// database is encrypted on the next [`init_database`]
+    #[cfg(feature = "sqlcipher")]
+    pub encryption_key: Option<DatabaseKey>,
 }
 
 impl Default for DatabaseConfig {
     fn default() -> Self {
         Self {
             database_path: PathBuf::from("pubcast.db"),
-            max_connections: 5,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            busy_timeout: DEFAULT_BUSY_TIMEOUT,
+            #[cfg(feature = "sqlcipher")]
+            encryption_key: None,
         }
     }
 }
 
 impl DatabaseConfig {
-    /// Create a new database config with the given path
+    /// Create a new database config with the given path.
+    ///
+    /// The pool size can be overridden with `PUBCAST_DB_MAX_CONNECTIONS`.
     pub fn new(database_path: PathBuf) -> Self {
         Self {
             database_path,
+            max_connections: max_connections_from(std::env::var(MAX_CONNECTIONS_ENV).ok()),
             ..Default::default()
         }
     }
 
+    /// Set how long writes wait on a locked database
+    pub fn with_busy_timeout(mut self, busy_timeout: Duration) -> Self {
+        self.busy_timeout = busy_timeout;
+        self
+    }
+
+    /// Open the database encrypted with `key`
+    #[cfg(feature = "sqlcipher")]
+    pub fn with_encryption_key(mut self, key: DatabaseKey) -> Self {
+        self.encryption_key = Some(key);
+        self
+    }
+
     /// Get the database URL for SQLx
     pub fn database_url(&self) -> String {
         format!("sqlite:{}?mode=rwc", self.database_path.display())
     }
+
+    /// Path a validated backup is staged at until the next startup swaps it in
+    pub fn pending_restore_path(&self) -> PathBuf {
+        sibling_path(&self.database_path, "restore")
+    }
+
+    /// Whether the database file should be opened encrypted: when asked to
+    /// with `PUBCAST_DB_ENCRYPT`, or when the file is already encrypted so
+    /// dropping the variable later never locks the data out
+    #[cfg(feature = "sqlcipher")]
+    pub fn encryption_requested(&self) -> bool {
+        encrypt_from(std::env::var(ENCRYPT_ENV).ok())
+            || (self.database_path.exists() && !is_plaintext(&self.database_path))
+    }
+}
+
+/// Parse the encryption opt-in; unset, empty and falsy values leave it off
+#[cfg(feature = "sqlcipher")]
+fn encrypt_from(value: Option<String>) -> bool {
+    value.is_some_and(|v| {
+        let v = v.trim().to_ascii_lowercase();
+        !v.is_empty() && !matches!(v.as_str(), "0" | "false" | "no" | "off")
+    })
+}
+
+/// Whether `path` starts with the plaintext SQLite header
+#[cfg(feature = "sqlcipher")]
+fn is_plaintext(path: &Path) -> bool {
+    use std::io::Read;
+
+    let mut header = [0u8; 16];
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok_and(|()| &header == PLAINTEXT_HEADER)
+}
+
+/// Send `key` when opening the database, if one is set
+#[cfg(feature = "sqlcipher")]
+fn with_key(options: SqliteConnectOptions, key: Option<&DatabaseKey>) -> SqliteConnectOptions {
+    // SQLx sends `key` before any other pragma, as SQLCipher requires
+    match key {
+        Some(key) => options.pragma("key", format!("\"{}\"", key.raw_key().as_str())),
+        None => options,
+    }
+}
+
+/// Parse a pool size override, falling back to the default when unset or invalid
+fn max_connections_from(value: Option<String>) -> u32 {
+    let Some(value) = value else {
+        return DEFAULT_MAX_CONNECTIONS;
+    };
+    match value.trim().parse::<u32>() {
+        Ok(n) if n >= 1 => n,
+        _ => {
+            tracing::warn!(
+                "Ignoring invalid {}={:?}, using {}",
+                MAX_CONNECTIONS_ENV,
+                value,
+                DEFAULT_MAX_CONNECTIONS
+            );
+            DEFAULT_MAX_CONNECTIONS
+        }
+    }
+}
+
+/// `path` with `suffix` appended to its file name, e.g. `pubcast.db-wal`
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(if suffix.starts_with('-') { "" } else { "." });
+    name.push(suffix);
+    path.with_file_name(name)
 }
 
 /// Initialize the database connection pool
@@ -51,7 +196,10 @@ pub async fn init_pool(config: &DatabaseConfig) -> Result<SqlitePool> {
         .create_if_missing(true)
         .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
         .synchronous(sqlx::sqlite::SqliteSynchronous::Normal)
+        .busy_timeout(config.busy_timeout)
         .foreign_keys(true);
+    #[cfg(feature = "sqlcipher")]
+    let connect_options = with_key(connect_options, config.encryption_key.as_ref());
 
     let pool = SqlitePoolOptions::new()
         .max_connections(config.max_connections)
@@ -75,16 +223,357 @@ pub async fn run_migrations(pool: &SqlitePool) -> Result<()> {
 
 /// Initialize database with migrations
 pub async fn init_database(config: &DatabaseConfig) -> Result<SqlitePool> {
+    apply_pending_restore(config)?;
+    #[cfg(feature = "sqlcipher")]
+    encrypt_plaintext_database(config).await?;
     let pool = init_pool(config).await?;
     run_migrations(&pool).await?;
     Ok(pool)
 }
 
+/// Write a consistent copy of the live database to `dest`.
+///
+/// Uses `VACUUM INTO`, so it is safe while the pool is in use and the copy
+/// is compacted. `dest` must not already exist.
+pub async fn backup_database(pool: &SqlitePool, dest: &Path) -> Result<()> {
+    if dest.exists() {
+        return Err(PubCastError::Validation(format!(
+            "Backup destination already exists: {}",
+            dest.display()
+        )));
+    }
+
+    let dest = dest.to_string_lossy().to_string();
+    sqlx::query("VACUUM INTO ?").bind(&dest).execute(pool).await?;
+
+    tracing::info!("Database backed up to {}", dest);
+    Ok(())
+}
+
+/// Check that `src` is a PubCast database this build can migrate, opening
+/// it with the configured key unless it is plaintext
+#[cfg_attr(not(feature = "sqlcipher"), allow(unused_variables))]
+async fn validate_backup(src: &Path, config: &DatabaseConfig) -> Result<()> {
+    let invalid = |reason: String| {
+        PubCastError::Validation(format!("{} is not a PubCast backup: {}", src.display(), reason))
+    };
+
+    if !src.is_file() {
+        return Err(invalid("file not found".to_string()));
+    }
+
+    let options = SqliteConnectOptions::new().filename(src).read_only(true);
+    #[cfg(feature = "sqlcipher")]
+    let options = if is_plaintext(src) {
+        options
+    } else {
+        with_key(options, config.encryption_key.as_ref())
+    };
+    let mut conn = options
+        .connect()
+        .await
+        .map_err(|e| invalid(e.to_string()))?;
+
+    let tables: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM sqlite_master
+         WHERE type = 'table' AND name IN ('_sqlx_migrations', 'contents', 'publish_jobs')",
+    )
+    .fetch_one(&mut conn)
+    .await
+    .map_err(|e| invalid(e.to_string()))?;
+    if tables < 3 {
+        return Err(invalid("missing PubCast tables".to_string()));
+    }
+
+    // A backup from a newer version would fail to migrate on startup
+    let applied: Vec<i64> = sqlx::query_scalar("SELECT version FROM _sqlx_migrations")
+        .fetch_all(&mut conn)
+        .await?;
+    let migrator = sqlx::migrate!("./migrations");
+    if let Some(version) = applied
+        .iter()
+        .find(|v| !migrator.iter().any(|m| m.version == **v))
+    {
+        return Err(invalid(format!("created by a newer version (migration {})", version)));
+    }
+
+    conn.close().await?;
+    Ok(())
+}
+
+/// Validate the backup at `src` and stage it to replace the database.
+///
+/// The swap happens in [`init_database`] on the next startup, once no
+/// connections are open.
+pub async fn restore_database(src: &Path, config: &DatabaseConfig) -> Result<()> {
+    validate_backup(src, config).await?;
+
+    std::fs::copy(src, config.pending_restore_path()).map_err(|e| {
+        PubCastError::Configuration(format!("Failed to stage database restore: {}", e))
+    })?;
+
+    tracing::info!("Database restore from {} staged for next startup", src.display());
+    Ok(())
+}
+
+/// Swap in a staged restore, keeping the replaced database alongside it
+fn apply_pending_restore(config: &DatabaseConfig) -> Result<()> {
+    let staged = config.pending_restore_path();
+    if !staged.exists() {
+        return Ok(());
+    }
+
+    let db = &config.database_path;
+    let io_err = |e: std::io::Error| {
+        PubCastError::Configuration(format!("Failed to apply database restore: {}", e))
+    };
+
+    if db.exists() {
+        std::fs::rename(db, sibling_path(db, "pre-restore")).map_err(io_err)?;
+    }
+    // Stale WAL files belong to the replaced database
+    for suffix in ["-wal", "-shm"] {
+        let path = sibling_path(db, suffix);
+        if path.exists() {
+            std::fs::remove_file(path).map_err(io_err)?;
+        }
+    }
+    std::fs::rename(&staged, db).map_err(io_err)?;
+
+    tracing::warn!("Restored database from backup");
+    Ok(())
+}
+
+/// Encrypt the database in place if a key is configured and the file is
+/// still plaintext, e.g. from before encryption was turned on.
+///
+/// The data is exported into an encrypted copy with `sqlcipher_export`,
+/// which then replaces the plaintext file.
+#[cfg(feature = "sqlcipher")]
+async fn encrypt_plaintext_database(config: &DatabaseConfig) -> Result<()> {
+    let Some(key) = &config.encryption_key else {
+        return Ok(());
+    };
+    let db = &config.database_path;
+    if !is_plaintext(db) {
+        return Ok(());
+    }
+
+    let io_err = |e: std::io::Error| {
+        PubCastError::Configuration(format!("Failed to encrypt database: {}", e))
+    };
+    let encrypted = sibling_path(db, "encrypting");
+    if encrypted.exists() {
+        std::fs::remove_file(&encrypted).map_err(io_err)?;
+    }
+
+    let mut conn = SqliteConnectOptions::new().filename(db).connect().await?;
+    // The key is hex digits only, so it can be inlined safely
+    sqlx::query(&format!(
+        "ATTACH DATABASE ? AS encrypted KEY \"{}\"",
+        key.raw_key().as_str()
+    ))
+    .bind(encrypted.to_string_lossy().to_string())
+    .execute(&mut conn)
+    .await?;
+    sqlx::query("SELECT sqlcipher_export('encrypted')")
+        .execute(&mut conn)
+        .await?;
+    sqlx::query("DETACH DATABASE encrypted").execute(&mut conn).await?;
+    // Closing the last connection checkpoints and removes the WAL
+    conn.close().await?;
+
+    for suffix in ["-wal", "-shm"] {
+        let path = sibling_path(db, suffix);
+        if path.exists() {
+            std::fs::remove_file(path).map_err(io_err)?;
+        }
+    }
+    std::fs::rename(&encrypted, db).map_err(io_err)?;
+
+    tracing::warn!("Encrypted existing plaintext database");
+    Ok(())
+}
+
+/// On-disk database size around a [`vacuum`]
+#[derive(Debug, Clone, Serialize)]
+pub struct VacuumReport {
+    pub size_before: u64,
+    pub size_after: u64,
+    pub reclaimed_bytes: u64,
+}
+
+/// Rebuild the database file and truncate the WAL to reclaim free space.
+///
+/// Meant for when the app is idle: `VACUUM` needs exclusive access and waits
+/// for other connections' transactions to finish.
+pub async fn vacuum(pool: &SqlitePool) -> Result<VacuumReport> {
+    let path: String =
+        sqlx::query_scalar("SELECT file FROM pragma_database_list WHERE name = 'main'")
+            .fetch_one(pool)
+            .await?;
+    let path = PathBuf::from(path);
+
+    let size_before = on_disk_size(&path);
+    sqlx::query("VACUUM").execute(pool).await?;
+    // VACUUM itself goes through the WAL; checkpoint it back into the main file
+    sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)").execute(pool).await?;
+    let size_after = on_disk_size(&path);
+
+    let report = VacuumReport {
+        size_before,
+        size_after,
+        reclaimed_bytes: size_before.saturating_sub(size_after),
+    };
+    tracing::info!("Database vacuumed, reclaimed {} bytes", report.reclaimed_bytes);
+    Ok(report)
+}
+
+/// Migration state of the database, for diagnosing upgrade issues
+#[derive(Debug, Clone, Serialize)]
+pub struct DbVersion {
+    /// Latest applied migration, 0 if none
+    pub version: i64,
+    /// Latest migration this build ships
+    pub latest_version: i64,
+    /// Whether any migration this build ships has not been applied
+    pub pending: bool,
+    /// Version of the running app
+    pub app_version: String,
+}
+
+/// Version of the latest successfully applied migration, 0 if none
+pub async fn current_migration_version(pool: &SqlitePool) -> Result<i64> {
+    let version: Option<i64> =
+        sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1")
+            .fetch_one(pool)
+            .await?;
+    Ok(version.unwrap_or(0))
+}
+
+/// Applied migration version compared with the migrations in this build
+pub async fn db_version(pool: &SqlitePool) -> Result<DbVersion> {
+    let applied: Vec<i64> =
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success = 1")
+            .fetch_all(pool)
+            .await?;
+    let migrator = sqlx::migrate!("./migrations");
+
+    Ok(DbVersion {
+        version: current_migration_version(pool).await?,
+        latest_version: migrator.iter().map(|m| m.version).max().unwrap_or(0),
+        pending: migrator.iter().any(|m| !applied.contains(&m.version)),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+/// Run `f` again with a short backoff while it fails because the database
+/// is busy or locked, for writes that can lose the race for the lock even
+/// after waiting out `busy_timeout`. Any other error is returned right away.
+pub async fn with_sqlite_retry<T, F, Fut>(mut f: F) -> std::result::Result<T, sqlx::Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::result::Result<T, sqlx::Error>>,
+{
+    let mut backoff = LOCK_RETRY_BACKOFF;
+    let mut retries = 0;
+    loop {
+        match f().await {
+            Err(e) if retries < LOCK_RETRIES && is_lock_error(&e) => {
+                retries += 1;
+                tracing::debug!("Database locked, retry {} in {:?}: {}", retries, backoff, e);
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Whether `error` is SQLITE_BUSY or SQLITE_LOCKED, including their extended codes
+fn is_lock_error(error: &sqlx::Error) -> bool {
+    const SQLITE_BUSY: i32 = 5;
+    const SQLITE_LOCKED: i32 = 6;
+
+    let sqlx::Error::Database(db_error) = error else {
+        return false;
+    };
+    db_error
+        .code()
+        .and_then(|code| code.parse::<i32>().ok())
+        .is_some_and(|code| matches!(code & 0xff, SQLITE_BUSY | SQLITE_LOCKED))
+}
+
+/// Size of the database file plus its WAL
+fn on_disk_size(path: &Path) -> u64 {
+    [path.to_path_buf(), sibling_path(path, "-wal")]
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Create a migrated database in a temporary directory for tests
+#[cfg(test)]
+pub(crate) async fn init_test_database() -> (tempfile::TempDir, SqlitePool) {
+    let dir = tempfile::tempdir().unwrap();
+    let config = DatabaseConfig::new(dir.path().join("test.db"));
+    let pool = init_database(&config).await.unwrap();
+    (dir, pool)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[tokio::test]
+    async fn test_proxy_dedup_migration_keeps_account_bindings() {
+        let dir = tempdir().unwrap();
+        let pool = init_pool(&DatabaseConfig::new(dir.path().join("test.db")))
+            .await
+            .unwrap();
+        let dedup_version = 20241128000016;
+        let migrator = sqlx::migrate!("./migrations");
+        for migration in migrator.iter().filter(|m| m.version < dedup_version) {
+            sqlx::raw_sql(&migration.sql).execute(&pool).await.unwrap();
+        }
+
+        for sql in [
+            "INSERT INTO accounts (id, platform, name) VALUES ('a1', 'zhihu', 'a1'), ('a2', 'zhihu', 'a2')",
+            "INSERT INTO proxies (id, protocol, host, port) VALUES ('kept', 'http', '10.0.0.1', 8080)",
+            "INSERT INTO proxies (id, protocol, host, port) VALUES ('dup', 'http', '10.0.0.1', 8080)",
+            "INSERT INTO proxies (id, protocol, host, port) VALUES ('other', 'http', '10.0.0.2', 8080)",
+            "INSERT INTO account_proxy (account_id, proxy_id) VALUES ('a1', 'dup'), ('a2', 'other')",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+
+        let dedup = migrator
+            .iter()
+            .find(|m| m.version == dedup_version)
+            .unwrap();
+        sqlx::raw_sql(&dedup.sql).execute(&pool).await.unwrap();
+
+        let proxies: Vec<String> = sqlx::query_scalar("SELECT id FROM proxies ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(proxies, ["kept", "other"]);
+        let bindings: Vec<(String, Option<String>)> =
+            sqlx::query_as("SELECT account_id, proxy_id FROM account_proxy ORDER BY account_id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            bindings,
+            [
+                ("a1".to_string(), Some("kept".to_string())),
+                ("a2".to_string(), Some("other".to_string())),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_init_pool() {
         let dir = tempdir().unwrap();
@@ -94,4 +583,304 @@ mod tests {
         let pool = init_pool(&config).await.unwrap();
         assert!(pool.size() > 0 || pool.num_idle() >= 0);
     }
+
+    #[test]
+    fn test_max_connections_override() {
+        assert_eq!(max_connections_from(None), DEFAULT_MAX_CONNECTIONS);
+        assert_eq!(max_connections_from(Some(" 12 ".to_string())), 12);
+        assert_eq!(max_connections_from(Some("0".to_string())), DEFAULT_MAX_CONNECTIONS);
+        assert_eq!(max_connections_from(Some("many".to_string())), DEFAULT_MAX_CONNECTIONS);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_writers_wait_for_the_lock() {
+        let (dir, pool) = init_test_database().await;
+
+        // Hold the write lock for a while on one connection
+        let mut holder = pool.acquire().await.unwrap();
+        sqlx::query("BEGIN IMMEDIATE").execute(&mut *holder).await.unwrap();
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'first')")
+            .execute(&mut *holder)
+            .await
+            .unwrap();
+
+        let writer = sqlx::query("INSERT INTO contents (id, title) VALUES ('c2', 'second')")
+            .execute(&pool);
+        let release = async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            sqlx::query("COMMIT").execute(&mut *holder).await
+        };
+        let (written, released) = tokio::join!(writer, release);
+
+        written.unwrap();
+        released.unwrap();
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM contents")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        // Without enough patience the second writer gives up
+        let impatient = init_pool(
+            &DatabaseConfig::new(dir.path().join("test.db")).with_busy_timeout(Duration::from_millis(10)),
+        )
+        .await
+        .unwrap();
+        sqlx::query("BEGIN IMMEDIATE").execute(&mut *holder).await.unwrap();
+        assert!(sqlx::query("INSERT INTO contents (id, title) VALUES ('c3', 'third')")
+            .execute(&impatient)
+            .await
+            .is_err());
+        sqlx::query("COMMIT").execute(&mut *holder).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_retry_outlasts_a_transient_lock() {
+        let (dir, pool) = init_test_database().await;
+        let impatient = init_pool(
+            &DatabaseConfig::new(dir.path().join("test.db")).with_busy_timeout(Duration::from_millis(10)),
+        )
+        .await
+        .unwrap();
+
+        let mut holder = pool.acquire().await.unwrap();
+        sqlx::query("BEGIN IMMEDIATE").execute(&mut *holder).await.unwrap();
+
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+        let writer = with_sqlite_retry(|| {
+            attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'retried')").execute(&impatient)
+        });
+        let release = async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            sqlx::query("COMMIT").execute(&mut *holder).await
+        };
+        let (written, released) = tokio::join!(writer, release);
+
+        written.unwrap();
+        released.unwrap();
+        assert!(attempts.load(std::sync::atomic::Ordering::SeqCst) > 1);
+
+        // Other errors are not retried
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+        let duplicate = with_sqlite_retry(|| {
+            attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'again')").execute(&impatient)
+        })
+        .await;
+        assert!(duplicate.is_err());
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    async fn row_counts(pool: &SqlitePool) -> Vec<i64> {
+        let mut counts = Vec::new();
+        for table in ["contents", "accounts", "app_settings", "ai_configs"] {
+            let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+                .fetch_one(pool)
+                .await
+                .unwrap();
+            counts.push(count);
+        }
+        counts
+    }
+
+    #[tokio::test]
+    async fn test_backup_and_restore_preserve_rows() {
+        let (dir, pool) = init_test_database().await;
+        crate::services::ensure_schema_seed(&pool).await.unwrap();
+        for i in 0..5 {
+            sqlx::query("INSERT INTO contents (id, title) VALUES (?, 'Hello')")
+                .bind(format!("c{}", i))
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        sqlx::query("INSERT INTO accounts (id, platform, name) VALUES ('a1', 'wechat', 'a1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let backup = dir.path().join("backup.db");
+        backup_database(&pool, &backup).await.unwrap();
+        assert!(matches!(
+            backup_database(&pool, &backup).await,
+            Err(PubCastError::Validation(_))
+        ));
+
+        let restored_dir = tempdir().unwrap();
+        let config = DatabaseConfig::new(restored_dir.path().join("pubcast.db"));
+        restore_database(&backup, &config).await.unwrap();
+        let restored = init_database(&config).await.unwrap();
+
+        assert_eq!(row_counts(&restored).await, row_counts(&pool).await);
+        assert_eq!(row_counts(&restored).await[0], 5);
+        assert!(!config.pending_restore_path().exists());
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_shrinks_file_after_deletes() {
+        let (dir, pool) = init_test_database().await;
+        let body = "x".repeat(4096);
+        for i in 0..500 {
+            sqlx::query("INSERT INTO contents (id, title, body) VALUES (?, 'Hello', ?)")
+                .bind(format!("c{}", i))
+                .bind(&body)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        sqlx::query("DELETE FROM contents").execute(&pool).await.unwrap();
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)").execute(&pool).await.unwrap();
+
+        let path = dir.path().join("test.db");
+        let before = std::fs::metadata(&path).unwrap().len();
+
+        let report = vacuum(&pool).await.unwrap();
+        let after = std::fs::metadata(&path).unwrap().len();
+
+        assert!(after < before);
+        assert!(report.reclaimed_bytes > 0);
+        assert_eq!(report.size_before - report.reclaimed_bytes, report.size_after);
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_non_pubcast_files() {
+        let dir = tempdir().unwrap();
+        let config = DatabaseConfig::new(dir.path().join("pubcast.db"));
+
+        let text = dir.path().join("notes.txt");
+        std::fs::write(&text, "not a database").unwrap();
+        assert!(matches!(
+            restore_database(&text, &config).await,
+            Err(PubCastError::Validation(_))
+        ));
+
+        let other = DatabaseConfig::new(dir.path().join("other.db"));
+        init_pool(&other).await.unwrap().close().await;
+        assert!(matches!(
+            restore_database(&other.database_path, &config).await,
+            Err(PubCastError::Validation(_))
+        ));
+        assert!(!config.pending_restore_path().exists());
+    }
+
+    #[tokio::test]
+    async fn test_db_version_of_a_migrated_database() {
+        let (_dir, pool) = init_test_database().await;
+
+        let latest = sqlx::migrate!("./migrations")
+            .iter()
+            .map(|migration| migration.version)
+            .max()
+            .unwrap();
+        let version = current_migration_version(&pool).await.unwrap();
+        assert_eq!(version, latest);
+        let report = db_version(&pool).await.unwrap();
+        assert_eq!(report.version, version);
+        assert_eq!(report.latest_version, version);
+        assert!(!report.pending);
+        assert_eq!(report.app_version, env!("CARGO_PKG_VERSION"));
+
+        // Forget the last migration, as if the database predates it
+        sqlx::query("DELETE FROM _sqlx_migrations WHERE version = ?")
+            .bind(version)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let report = db_version(&pool).await.unwrap();
+        assert!(report.version < version);
+        assert_eq!(report.latest_version, version);
+        assert!(report.pending);
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[tokio::test]
+    async fn test_encrypted_database_needs_its_key() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.db");
+
+        // Start from a plaintext database with data in it
+        let pool = init_database(&DatabaseConfig::new(path.clone())).await.unwrap();
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'secret title')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool.close().await;
+
+        // Opening with a key encrypts it in place and keeps the data
+        let key = DatabaseKey::new([7; 32]);
+        let config = DatabaseConfig::new(path.clone()).with_encryption_key(key);
+        let pool = init_database(&config).await.unwrap();
+        let title: String = sqlx::query_scalar("SELECT title FROM contents WHERE id = 'c1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(title, "secret title");
+        pool.close().await;
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert_ne!(&bytes[..16], PLAINTEXT_HEADER);
+        assert!(!bytes.windows(12).any(|w| w == b"secret title"));
+
+        // Without the key, or with the wrong one, it can't be read
+        let wrong = DatabaseConfig::new(path.clone()).with_encryption_key(DatabaseKey::new([8; 32]));
+        for config in [DatabaseConfig::new(path.clone()), wrong] {
+            let readable = match init_pool(&config).await {
+                Ok(pool) => sqlx::query("SELECT COUNT(*) FROM contents")
+                    .fetch_one(&pool)
+                    .await
+                    .is_ok(),
+                Err(_) => false,
+            };
+            assert!(!readable);
+        }
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[tokio::test]
+    async fn test_backup_and_restore_an_encrypted_database() {
+        let dir = tempdir().unwrap();
+        let key = DatabaseKey::new([7; 32]);
+        let config =
+            DatabaseConfig::new(dir.path().join("pubcast.db")).with_encryption_key(key.clone());
+        let pool = init_database(&config).await.unwrap();
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'secret title')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let backup = dir.path().join("backup.db");
+        backup_database(&pool, &backup).await.unwrap();
+        assert!(!is_plaintext(&backup));
+
+        // Restoring needs the key the backup was written with
+        let restored_dir = tempdir().unwrap();
+        let wrong = DatabaseConfig::new(restored_dir.path().join("pubcast.db"))
+            .with_encryption_key(DatabaseKey::new([8; 32]));
+        assert!(matches!(
+            restore_database(&backup, &wrong).await,
+            Err(PubCastError::Validation(_))
+        ));
+
+        let config =
+            DatabaseConfig::new(restored_dir.path().join("pubcast.db")).with_encryption_key(key);
+        restore_database(&backup, &config).await.unwrap();
+        let restored = init_database(&config).await.unwrap();
+        let title: String = sqlx::query_scalar("SELECT title FROM contents WHERE id = 'c1'")
+            .fetch_one(&restored)
+            .await
+            .unwrap();
+        assert_eq!(title, "secret title");
+        assert!(config.encryption_requested());
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn test_encryption_is_opt_in() {
+        assert!(!encrypt_from(None));
+        assert!(!encrypt_from(Some(" ".to_string())));
+        assert!(!encrypt_from(Some("off".to_string())));
+        assert!(encrypt_from(Some("1".to_string())));
+        assert!(encrypt_from(Some("true".to_string())));
+    }
 }
@@ -6,20 +6,144 @@ use aes_gcm::{
     aead::{Aead, KeyInit, OsRng},
     Aes256Gcm, Nonce,
 };
-use argon2::{password_hash::SaltString, Argon2, PasswordHasher};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD, Engine};
 use rand::RngCore;
+use std::path::PathBuf;
+use sqlx::{Row, SqlitePool};
+use uuid::Uuid;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 use crate::error::{PubCastError, Result};
 
 /// Nonce size for AES-256-GCM (96 bits = 12 bytes)
-const NONCE_SIZE: usize = 12;
+pub(crate) const NONCE_SIZE: usize = 12;
 /// Salt size for Argon2id (16 bytes recommended)
 const SALT_SIZE: usize = 16;
 /// Key size for AES-256 (256 bits = 32 bytes)
 const KEY_SIZE: usize = 32;
+/// Current version of the key material header
+const KEY_MATERIAL_VERSION: u8 = 1;
+/// Header layout: version (1 byte) + m_cost, t_cost, p_cost (u32 little-endian each)
+const KEY_MATERIAL_HEADER_SIZE: usize = 13;
+/// Row ID of the active key in `encryption_metadata`
+const METADATA_ID: &str = "master";
+/// Keychain account names for the active and in-flight master keys
+const MASTER_KEY_ENTRY: &str = "master_key";
+const PENDING_KEY_ENTRY: &str = "master_key_pending";
+/// Keychain account name for the SQLCipher database key
+#[cfg(feature = "sqlcipher")]
+const DATABASE_KEY_ENTRY: &str = "database_key";
+
+/// Encrypted (data, nonce) column pairs: (table, data column, nonce column)
+const ENCRYPTED_COLUMNS: &[(&str, &str, &str)] = &[
+    ("proxies", "password_encrypted", "password_nonce"),
+    ("accounts", "credentials_encrypted", "credentials_nonce"),
+    ("accounts", "cookies_backup", "cookies_nonce"),
+    ("accounts", "fingerprint_backup", "fingerprint_nonce"),
+    ("browser_sessions", "cookies_encrypted", "cookies_nonce"),
+    ("browser_sessions", "local_storage_encrypted", "local_storage_nonce"),
+    ("ai_configs", "auth_encrypted", "auth_nonce"),
+    ("content_api_config", "api_key_encrypted", "api_key_nonce"),
+];
+
+/// Argon2id cost parameters used for key derivation.
+///
+/// These are pinned explicitly rather than taken from `Argon2::default()` so a
+/// dependency upgrade can never change the derived key for existing data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub m_cost: u32,
+    /// Number of iterations
+    pub t_cost: u32,
+    /// Degree of parallelism
+    pub p_cost: u32,
+}
+
+impl KdfParams {
+    /// Parameters used by version 1 key material (argon2 0.5 defaults)
+    pub const V1: Self = Self {
+        m_cost: 19 * 1024,
+        t_cost: 2,
+        p_cost: 1,
+    };
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self::V1
+    }
+}
+
+/// Salt plus the KDF parameters it was used with, as persisted in the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMaterial {
+    pub params: KdfParams,
+    pub salt: Vec<u8>,
+}
+
+impl KeyMaterial {
+    /// Fresh salt with the default parameters
+    pub fn generate() -> Self {
+        Self {
+            params: KdfParams::default(),
+            salt: EncryptionService::generate_salt(),
+        }
+    }
+
+    /// Serialize as a versioned header followed by the salt
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(KEY_MATERIAL_HEADER_SIZE + self.salt.len());
+        bytes.push(KEY_MATERIAL_VERSION);
+        bytes.extend_from_slice(&self.params.m_cost.to_le_bytes());
+        bytes.extend_from_slice(&self.params.t_cost.to_le_bytes());
+        bytes.extend_from_slice(&self.params.p_cost.to_le_bytes());
+        bytes.extend_from_slice(&self.salt);
+        bytes
+    }
+
+    /// Parse stored key material. A bare salt without a header is treated as
+    /// version 1 material.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() == SALT_SIZE {
+            return Ok(Self {
+                params: KdfParams::V1,
+                salt: bytes.to_vec(),
+            });
+        }
+
+        if bytes.len() <= KEY_MATERIAL_HEADER_SIZE {
+            return Err(PubCastError::Encryption("Key material too short".to_string()));
+        }
+        if bytes[0] != KEY_MATERIAL_VERSION {
+            return Err(PubCastError::Encryption(format!(
+                "Unsupported key material version: {}",
+                bytes[0]
+            )));
+        }
+
+        let read_u32 = |offset: usize| {
+            let mut buf = [0u8; 4];
+            buf.copy_from_slice(&bytes[offset..offset + 4]);
+            u32::from_le_bytes(buf)
+        };
+
+        Ok(Self {
+            params: KdfParams {
+                m_cost: read_u32(1),
+                t_cost: read_u32(5),
+                p_cost: read_u32(9),
+            },
+            salt: bytes[KEY_MATERIAL_HEADER_SIZE..].to_vec(),
+        })
+    }
+}
 
 /// Encryption service for secure credential storage
-#[derive(Clone)]
+///
+/// The derived key is wiped from memory when the service (or any clone) is dropped.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
 pub struct EncryptionService {
     /// Derived encryption key
     key: [u8; KEY_SIZE],
@@ -28,38 +152,30 @@ pub struct EncryptionService {
 impl EncryptionService {
     /// Create a new encryption service with the given master key and salt
     pub fn new(master_key: &[u8], salt: &[u8]) -> Result<Self> {
-        let key = Self::derive_key(master_key, salt)?;
-        Ok(Self { key })
+        Self::with_params(master_key, salt, &KdfParams::default())
     }
 
-    /// Derive a key from the master key using Argon2id
-    fn derive_key(master_key: &[u8], salt: &[u8]) -> Result<[u8; KEY_SIZE]> {
-        // Create salt string from bytes
-        let salt_string = SaltString::encode_b64(salt)
-            .map_err(|e| PubCastError::Encryption(format!("Invalid salt: {}", e)))?;
-
-        // Configure Argon2id
-        let argon2 = Argon2::default();
-
-        // Hash the master key to derive encryption key
-        let hash = argon2
-            .hash_password(master_key, &salt_string)
-            .map_err(|e| PubCastError::Encryption(format!("Key derivation failed: {}", e)))?;
+    /// Create a new encryption service with explicit KDF parameters
+    pub fn with_params(master_key: &[u8], salt: &[u8], params: &KdfParams) -> Result<Self> {
+        let key = Self::derive_key(master_key, salt, params)?;
+        Ok(Self { key })
+    }
 
-        // Extract the hash output
-        let hash_output = hash
-            .hash
-            .ok_or_else(|| PubCastError::Encryption("No hash output".to_string()))?;
+    /// Create a new encryption service from stored key material
+    pub fn from_material(master_key: &[u8], material: &KeyMaterial) -> Result<Self> {
+        Self::with_params(master_key, &material.salt, &material.params)
+    }
 
-        let hash_bytes = hash_output.as_bytes();
-        if hash_bytes.len() < KEY_SIZE {
-            return Err(PubCastError::Encryption(
-                "Hash output too short".to_string(),
-            ));
-        }
+    /// Derive a key from the master key using Argon2id
+    fn derive_key(master_key: &[u8], salt: &[u8], params: &KdfParams) -> Result<[u8; KEY_SIZE]> {
+        let params = Params::new(params.m_cost, params.t_cost, params.p_cost, Some(KEY_SIZE))
+            .map_err(|e| PubCastError::Encryption(format!("Invalid KDF params: {}", e)))?;
+        let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
 
         let mut key = [0u8; KEY_SIZE];
-        key.copy_from_slice(&hash_bytes[..KEY_SIZE]);
+        argon2
+            .hash_password_into(master_key, salt, &mut key)
+            .map_err(|e| PubCastError::Encryption(format!("Key derivation failed: {}", e)))?;
         Ok(key)
     }
 
@@ -111,11 +227,214 @@ impl EncryptionService {
         OsRng.fill_bytes(&mut salt);
         salt
     }
+
+    /// Generate a random master key
+    pub fn generate_master_key() -> Vec<u8> {
+        let mut key = vec![0u8; KEY_SIZE];
+        OsRng.fill_bytes(&mut key);
+        key
+    }
+
+    /// Re-encrypt every stored secret under `new`, recording `new_key_id` and
+    /// `new_material` as the active key metadata.
+    ///
+    /// Runs in a single transaction: either every row moves to the new key or none do.
+    /// Rows the current key cannot decrypt are left untouched and returned as
+    /// `table.column row N` labels instead of failing the rotation.
+    pub async fn reencrypt_all(
+        &self,
+        pool: &SqlitePool,
+        new: &EncryptionService,
+        new_key_id: &str,
+        new_material: &KeyMaterial,
+    ) -> Result<Vec<String>> {
+        let mut tx = pool.begin().await?;
+        let mut skipped = Vec::new();
+
+        for (table, data_col, nonce_col) in ENCRYPTED_COLUMNS {
+            let select = format!(
+                "SELECT rowid, {data_col}, {nonce_col} FROM {table} \
+                 WHERE {data_col} IS NOT NULL AND {nonce_col} IS NOT NULL"
+            );
+            let update = format!("UPDATE {table} SET {data_col} = ?, {nonce_col} = ? WHERE rowid = ?");
+
+            let rows = sqlx::query(&select).fetch_all(&mut *tx).await?;
+            for row in rows {
+                let rowid: i64 = row.get(0);
+                let ciphertext: Vec<u8> = row.get(1);
+                let nonce: Vec<u8> = row.get(2);
+
+                let plaintext = match self.decrypt(&ciphertext, &nonce) {
+                    Ok(plaintext) => plaintext,
+                    Err(e) => {
+                        let label = format!("{}.{} row {}", table, data_col, rowid);
+                        tracing::warn!("Skipping undecryptable {} during rotation: {}", label, e);
+                        skipped.push(label);
+                        continue;
+                    }
+                };
+                let (ciphertext, nonce) = new.encrypt(&plaintext)?;
+
+                sqlx::query(&update)
+                    .bind(ciphertext)
+                    .bind(nonce)
+                    .bind(rowid)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query(
+            r#"
+            INSERT INTO encryption_metadata (id, key_id, salt, created_at, rotated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                key_id = excluded.key_id, salt = excluded.salt,
+                rotated_at = excluded.rotated_at, version = version + 1
+            "#,
+        )
+        .bind(METADATA_ID)
+        .bind(new_key_id)
+        .bind(new_material.encode())
+        .bind(now)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(skipped)
+    }
+}
+
+/// Active key metadata persisted in the database
+#[derive(Debug, Clone)]
+pub struct KeyMetadata {
+    pub key_id: String,
+    pub material: KeyMaterial,
+}
+
+/// Load the active key's material and ID, creating them on first run
+pub async fn load_or_create_key_metadata(pool: &SqlitePool) -> Result<KeyMetadata> {
+    let material = KeyMaterial::generate();
+    let key_id = Uuid::new_v4().to_string();
+
+    sqlx::query("INSERT OR IGNORE INTO encryption_metadata (id, key_id, salt) VALUES (?, ?, ?)")
+        .bind(METADATA_ID)
+        .bind(&key_id)
+        .bind(material.encode())
+        .execute(pool)
+        .await?;
+
+    let row = sqlx::query("SELECT key_id, salt FROM encryption_metadata WHERE id = ?")
+        .bind(METADATA_ID)
+        .fetch_one(pool)
+        .await?;
+    let stored: Vec<u8> = row.get("salt");
+
+    Ok(KeyMetadata {
+        key_id: row.get("key_id"),
+        material: KeyMaterial::decode(&stored)?,
+    })
+}
+
+/// Outcome of a master key rotation
+pub struct KeyRotation {
+    /// Service holding the new key
+    pub service: EncryptionService,
+    /// Rows that could not be decrypted and were left as they were
+    pub skipped: Vec<String>,
+}
+
+/// Rotate the master key and re-encrypt every stored secret.
+///
+/// The new key is parked in the keychain as pending before the database
+/// transaction starts and promoted only after it commits, so a crash at any
+/// point leaves a key that can read the database (see [`recover_pending_rotation`]).
+/// Once the transaction has committed the rotation succeeds even if promoting
+/// the key fails; the pending entry is then promoted on next startup.
+pub async fn rotate_master_key(
+    keychain: &KeychainService,
+    pool: &SqlitePool,
+    current: &EncryptionService,
+) -> Result<KeyRotation> {
+    let new_key = EncryptionService::generate_master_key();
+    let new_material = KeyMaterial::generate();
+    let new_key_id = Uuid::new_v4().to_string();
+    let new_service = EncryptionService::from_material(&new_key, &new_material)?;
+
+    keychain.set_pending_master_key(&new_key_id, &new_key)?;
+
+    let skipped = match current
+        .reencrypt_all(pool, &new_service, &new_key_id, &new_material)
+        .await
+    {
+        Ok(skipped) => skipped,
+        Err(e) => {
+            keychain.clear_pending_master_key()?;
+            return Err(e);
+        }
+    };
+
+    // The database is on the new key from here on, so keychain failures must
+    // not be reported as a failed rotation
+    match keychain.set_master_key(&new_key) {
+        Ok(()) => {
+            if let Err(e) = keychain.clear_pending_master_key() {
+                tracing::warn!("Failed to clear pending master key: {}", e);
+            }
+        }
+        Err(e) => tracing::warn!(
+            "Failed to promote rotated master key, will retry on startup: {}",
+            e
+        ),
+    }
+
+    tracing::info!("Master key rotated ({} rows skipped)", skipped.len());
+    Ok(KeyRotation {
+        service: new_service,
+        skipped,
+    })
+}
+
+/// Finish or discard a rotation interrupted by a crash.
+///
+/// If a pending key exists and the database already records its key ID, the
+/// rotation committed and the pending key is promoted; otherwise it is dropped.
+/// Returns the master key that matches the database.
+pub async fn recover_pending_rotation(
+    keychain: &KeychainService,
+    pool: &SqlitePool,
+    master_key: Vec<u8>,
+) -> Result<Vec<u8>> {
+    let Some((pending_id, pending_key)) = keychain.get_pending_master_key()? else {
+        return Ok(master_key);
+    };
+
+    let active_id: Option<String> =
+        sqlx::query_scalar("SELECT key_id FROM encryption_metadata WHERE id = ?")
+            .bind(METADATA_ID)
+            .fetch_optional(pool)
+            .await?;
+
+    let key = if active_id.as_deref() == Some(pending_id.as_str()) {
+        tracing::warn!("Completing interrupted master key rotation");
+        keychain.set_master_key(&pending_key)?;
+        pending_key
+    } else {
+        tracing::warn!("Discarding master key from an incomplete rotation");
+        master_key
+    };
+
+    keychain.clear_pending_master_key()?;
+    Ok(key)
 }
 
 /// Keychain service for master key management
 pub struct KeychainService {
     service_name: String,
+    /// Passphrase-protected key files used when the system keychain is unavailable
+    file_fallback: Option<FileKeyStore>,
 }
 
 impl KeychainService {
@@ -123,46 +442,257 @@ impl KeychainService {
     pub fn new(service_name: &str) -> Self {
         Self {
             service_name: service_name.to_string(),
+            file_fallback: None,
         }
     }
 
+    /// Fall back to encrypted key files in `data_dir` when the system keychain
+    /// is unavailable (e.g. no Secret Service on headless Linux).
+    ///
+    /// Only platform-unavailable errors trigger the fallback, and every use is logged.
+    pub fn with_file_fallback(
+        mut self,
+        data_dir: impl Into<PathBuf>,
+        passphrase: impl Into<String>,
+    ) -> Self {
+        self.file_fallback = Some(FileKeyStore {
+            dir: data_dir.into(),
+            passphrase: Zeroizing::new(passphrase.into()),
+        });
+        self
+    }
+
     /// Get or create the master key from the system keychain
     pub fn get_or_create_master_key(&self) -> Result<Vec<u8>> {
-        let entry = keyring::Entry::new(&self.service_name, "master_key")?;
-
         // Try to get existing key
-        match entry.get_password() {
-            Ok(key_b64) => {
+        match self.read_secret(MASTER_KEY_ENTRY)? {
+            Some(key_b64) => {
                 // Decode base64 key
-                use base64::{engine::general_purpose::STANDARD, Engine};
                 STANDARD
                     .decode(&key_b64)
                     .map_err(|e| PubCastError::Encryption(format!("Invalid key format: {}", e)))
             }
-            Err(keyring::Error::NoEntry) => {
+            None => {
                 // Generate new key
-                let mut key = vec![0u8; KEY_SIZE];
-                OsRng.fill_bytes(&mut key);
+                let key = EncryptionService::generate_master_key();
 
                 // Store in keychain (base64 encoded)
-                use base64::{engine::general_purpose::STANDARD, Engine};
-                let key_b64 = STANDARD.encode(&key);
-                entry.set_password(&key_b64)?;
+                self.write_secret(MASTER_KEY_ENTRY, &STANDARD.encode(&key))?;
 
                 tracing::info!("Generated new master key and stored in keychain");
                 Ok(key)
             }
-            Err(e) => Err(e.into()),
         }
     }
 
+    /// Replace the master key in the keychain
+    pub fn set_master_key(&self, key: &[u8]) -> Result<()> {
+        self.write_secret(MASTER_KEY_ENTRY, &STANDARD.encode(key))
+    }
+
+    /// Park a new master key (tagged with its key ID) while a rotation is in flight
+    pub fn set_pending_master_key(&self, key_id: &str, key: &[u8]) -> Result<()> {
+        self.write_secret(
+            PENDING_KEY_ENTRY,
+            &format!("{}:{}", key_id, STANDARD.encode(key)),
+        )
+    }
+
+    /// Get the pending master key and its key ID, if a rotation was interrupted
+    pub fn get_pending_master_key(&self) -> Result<Option<(String, Vec<u8>)>> {
+        let Some(value) = self.read_secret(PENDING_KEY_ENTRY)? else {
+            return Ok(None);
+        };
+
+        let (key_id, key_b64) = value
+            .split_once(':')
+            .ok_or_else(|| PubCastError::Encryption("Invalid pending key format".to_string()))?;
+        let key = STANDARD
+            .decode(key_b64)
+            .map_err(|e| PubCastError::Encryption(format!("Invalid key format: {}", e)))?;
+        Ok(Some((key_id.to_string(), key)))
+    }
+
+    /// Remove the pending master key
+    pub fn clear_pending_master_key(&self) -> Result<()> {
+        self.delete_secret(PENDING_KEY_ENTRY)
+    }
+
+    /// Get the SQLCipher database key, deriving it from the master key the
+    /// first time.
+    ///
+    /// The derived key is kept in its own entry, so rotating the master key
+    /// later doesn't require re-keying the database file.
+    #[cfg(feature = "sqlcipher")]
+    pub fn get_or_create_database_key(
+        &self,
+    ) -> Result<crate::infrastructure::database::DatabaseKey> {
+        use sha2::{Digest, Sha256};
+
+        let encoded = match self.read_secret(DATABASE_KEY_ENTRY)? {
+            Some(encoded) => Zeroizing::new(encoded),
+            None => {
+                let master_key = Zeroizing::new(self.get_or_create_master_key()?);
+                let mut hasher = Sha256::new();
+                hasher.update(b"pubcast-database-key-v1");
+                hasher.update(master_key.as_slice());
+                let encoded = Zeroizing::new(STANDARD.encode(hasher.finalize()));
+                self.write_secret(DATABASE_KEY_ENTRY, &encoded)?;
+                tracing::info!("Derived database encryption key and stored in keychain");
+                encoded
+            }
+        };
+
+        let key = Zeroizing::new(
+            STANDARD
+                .decode(encoded.as_str())
+                .map_err(|e| PubCastError::Encryption(format!("Invalid key format: {}", e)))?,
+        );
+        let key: [u8; KEY_SIZE] = key.as_slice().try_into().map_err(|_| {
+            PubCastError::Encryption("Database key has the wrong length".to_string())
+        })?;
+        Ok(crate::infrastructure::database::DatabaseKey::new(key))
+    }
+
     /// Delete the master key from keychain (for testing/reset)
     #[allow(dead_code)]
     pub fn delete_master_key(&self) -> Result<()> {
-        let entry = keyring::Entry::new(&self.service_name, "master_key")?;
-        entry.delete_credential()?;
+        self.delete_secret(MASTER_KEY_ENTRY)
+    }
+
+    fn read_secret(&self, name: &str) -> Result<Option<String>> {
+        let result = keyring::Entry::new(&self.service_name, name).and_then(|entry| {
+            match entry.get_password() {
+                Ok(value) => Ok(Some(value)),
+                Err(keyring::Error::NoEntry) => Ok(None),
+                Err(e) => Err(e),
+            }
+        });
+
+        match result {
+            Ok(value) => Ok(value),
+            Err(e) => self.fallback_for(e)?.read(name),
+        }
+    }
+
+    fn write_secret(&self, name: &str, value: &str) -> Result<()> {
+        let result = keyring::Entry::new(&self.service_name, name)
+            .and_then(|entry| entry.set_password(value));
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(e) => self.fallback_for(e)?.write(name, value),
+        }
+    }
+
+    fn delete_secret(&self, name: &str) -> Result<()> {
+        let result = keyring::Entry::new(&self.service_name, name)
+            .and_then(|entry| entry.delete_credential());
+
+        match result {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => self.fallback_for(e)?.delete(name),
+        }
+    }
+
+    /// Return the file fallback if `error` means the keychain itself is unavailable
+    fn fallback_for(&self, error: keyring::Error) -> Result<&FileKeyStore> {
+        let unavailable = matches!(
+            error,
+            keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_)
+        );
+
+        match &self.file_fallback {
+            Some(store) if unavailable => {
+                tracing::warn!(
+                    "System keychain unavailable ({}); using passphrase-protected key file in {}",
+                    error,
+                    store.dir.display()
+                );
+                Ok(store)
+            }
+            _ => Err(error.into()),
+        }
+    }
+}
+
+/// Key files encrypted with a key derived from a user passphrase.
+///
+/// File layout: material length (1 byte) + key material + nonce + ciphertext.
+struct FileKeyStore {
+    dir: PathBuf,
+    passphrase: Zeroizing<String>,
+}
+
+impl FileKeyStore {
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.key", name))
+    }
+
+    fn read(&self, name: &str) -> Result<Option<String>> {
+        let path = self.path(name);
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(&path, e)),
+        };
+
+        let invalid = || PubCastError::Encryption(format!("Corrupt key file: {}", path.display()));
+        let (&material_len, rest) = bytes.split_first().ok_or_else(invalid)?;
+        let material_len = material_len as usize;
+        if rest.len() < material_len + NONCE_SIZE {
+            return Err(invalid());
+        }
+        let material = KeyMaterial::decode(&rest[..material_len])?;
+        let (nonce, ciphertext) = rest[material_len..].split_at(NONCE_SIZE);
+
+        let service = EncryptionService::from_material(self.passphrase.as_bytes(), &material)?;
+        let plaintext = Zeroizing::new(service.decrypt(ciphertext, nonce)?);
+        let value = String::from_utf8(plaintext.to_vec())
+            .map_err(|e| PubCastError::Encryption(e.to_string()))?;
+        Ok(Some(value))
+    }
+
+    fn write(&self, name: &str, value: &str) -> Result<()> {
+        let material = KeyMaterial::generate();
+        let service = EncryptionService::from_material(self.passphrase.as_bytes(), &material)?;
+        let (ciphertext, nonce) = service.encrypt(value.as_bytes())?;
+
+        let encoded = material.encode();
+        let mut bytes = Vec::with_capacity(1 + encoded.len() + nonce.len() + ciphertext.len());
+        bytes.push(encoded.len() as u8);
+        bytes.extend_from_slice(&encoded);
+        bytes.extend_from_slice(&nonce);
+        bytes.extend_from_slice(&ciphertext);
+
+        std::fs::create_dir_all(&self.dir).map_err(|e| io_error(&self.dir, e))?;
+
+        // Write then rename so a crash never leaves a truncated key file
+        let path = self.path(name);
+        let tmp = path.with_extension("key.tmp");
+        std::fs::write(&tmp, &bytes).map_err(|e| io_error(&tmp, e))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600))
+                .map_err(|e| io_error(&tmp, e))?;
+        }
+        std::fs::rename(&tmp, &path).map_err(|e| io_error(&path, e))?;
         Ok(())
     }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        let path = self.path(name);
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_error(&path, e)),
+        }
+    }
+}
+
+fn io_error(path: &std::path::Path, e: std::io::Error) -> PubCastError {
+    PubCastError::Configuration(format!("Key file error ({}): {}", path.display(), e))
 }
 
 #[cfg(test)]
@@ -203,6 +733,302 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_reencrypt_all_rotates_every_secret() {
+        let (_dir, pool) = crate::infrastructure::database::init_test_database().await;
+        let old_salt = EncryptionService::generate_salt();
+        let old = EncryptionService::new(b"old_master_key", &old_salt).unwrap();
+
+        let (pw, pw_nonce) = old.encrypt(b"proxy-password").unwrap();
+        sqlx::query(
+            "INSERT INTO proxies (id, protocol, host, port, password_encrypted, password_nonce) \
+             VALUES ('p1', 'http', 'h', 1, ?, ?)",
+        )
+        .bind(&pw)
+        .bind(&pw_nonce)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let (creds, creds_nonce) = old.encrypt(b"{\"token\":\"abc\"}").unwrap();
+        let (cookies, cookies_nonce) = old.encrypt(b"[cookies]").unwrap();
+        sqlx::query(
+            "INSERT INTO accounts (id, platform, name, status, credentials_encrypted, \
+             credentials_nonce, cookies_backup, cookies_nonce) \
+             VALUES ('a1', 'wechat', 'n', 'active', ?, ?, ?, ?)",
+        )
+        .bind(&creds)
+        .bind(&creds_nonce)
+        .bind(&cookies)
+        .bind(&cookies_nonce)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Before rotation: old ciphertexts decrypt under the old key
+        assert_eq!(old.decrypt(&pw, &pw_nonce).unwrap(), b"proxy-password");
+
+        let new_material = KeyMaterial::generate();
+        let new = EncryptionService::from_material(b"new_master_key", &new_material).unwrap();
+        old.reencrypt_all(&pool, &new, "key-2", &new_material).await.unwrap();
+
+        // After rotation: stored ciphertexts decrypt to the same plaintext under the new key
+        let row = sqlx::query("SELECT password_encrypted, password_nonce FROM proxies")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let (new_pw, new_pw_nonce): (Vec<u8>, Vec<u8>) = (row.get(0), row.get(1));
+        assert_ne!(new_pw, pw);
+        assert_eq!(new.decrypt(&new_pw, &new_pw_nonce).unwrap(), b"proxy-password");
+        assert!(old.decrypt(&new_pw, &new_pw_nonce).is_err());
+
+        let row = sqlx::query(
+            "SELECT credentials_encrypted, credentials_nonce, cookies_backup, cookies_nonce \
+             FROM accounts",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let c: Vec<u8> = row.get(0);
+        let cn: Vec<u8> = row.get(1);
+        let k: Vec<u8> = row.get(2);
+        let kn: Vec<u8> = row.get(3);
+        assert_eq!(new.decrypt(&c, &cn).unwrap(), b"{\"token\":\"abc\"}");
+        assert_eq!(new.decrypt(&k, &kn).unwrap(), b"[cookies]");
+
+        let meta = load_or_create_key_metadata(&pool).await.unwrap();
+        assert_eq!(meta.key_id, "key-2");
+        assert_eq!(meta.material, new_material);
+    }
+
+    #[tokio::test]
+    async fn test_reencrypt_all_is_atomic() {
+        let (_dir, pool) = crate::infrastructure::database::init_test_database().await;
+        let salt = EncryptionService::generate_salt();
+        let old = EncryptionService::new(b"old_master_key", &salt).unwrap();
+        let before = load_or_create_key_metadata(&pool).await.unwrap();
+
+        let (good, good_nonce) = old.encrypt(b"good").unwrap();
+        sqlx::query(
+            "INSERT INTO proxies (id, protocol, host, port, password_encrypted, password_nonce) \
+             VALUES ('p1', 'http', 'h', 1, ?, ?)",
+        )
+        .bind(&good)
+        .bind(&good_nonce)
+        .execute(&pool)
+        .await
+        .unwrap();
+        // Make a later table fail after the proxies have been rewritten
+        sqlx::query("DROP TABLE content_api_config")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let new_material = KeyMaterial::generate();
+        let new = EncryptionService::from_material(b"new_master_key", &new_material).unwrap();
+        assert!(old.reencrypt_all(&pool, &new, "key-2", &new_material).await.is_err());
+
+        // The readable row was rolled back and still decrypts under the old key
+        let row = sqlx::query("SELECT password_encrypted, password_nonce FROM proxies WHERE id = 'p1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let (data, nonce): (Vec<u8>, Vec<u8>) = (row.get(0), row.get(1));
+        assert_eq!(old.decrypt(&data, &nonce).unwrap(), b"good");
+
+        let after = load_or_create_key_metadata(&pool).await.unwrap();
+        assert_eq!(after.key_id, before.key_id);
+    }
+
+    #[tokio::test]
+    async fn test_reencrypt_all_reports_undecryptable_rows() {
+        let (_dir, pool) = crate::infrastructure::database::init_test_database().await;
+        let salt = EncryptionService::generate_salt();
+        let old = EncryptionService::new(b"old_master_key", &salt).unwrap();
+        let stranger = EncryptionService::new(b"someone_else", &salt).unwrap();
+
+        let (good, good_nonce) = old.encrypt(b"good").unwrap();
+        let (bad, bad_nonce) = stranger.encrypt(b"unreadable").unwrap();
+        for (id, data, nonce) in [("p1", &good, &good_nonce), ("p2", &bad, &bad_nonce)] {
+            sqlx::query(
+                "INSERT INTO proxies (id, protocol, host, port, password_encrypted, password_nonce) \
+                 VALUES (?, 'http', ?, 1, ?, ?)",
+            )
+            .bind(id)
+            .bind(id)
+            .bind(data)
+            .bind(nonce)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        let bad_rowid: i64 = sqlx::query_scalar("SELECT rowid FROM proxies WHERE id = 'p2'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        let new_material = KeyMaterial::generate();
+        let new = EncryptionService::from_material(b"new_master_key", &new_material).unwrap();
+        let skipped = old
+            .reencrypt_all(&pool, &new, "key-2", &new_material)
+            .await
+            .unwrap();
+        assert_eq!(
+            skipped,
+            vec![format!("proxies.password_encrypted row {}", bad_rowid)]
+        );
+
+        // The readable row moved to the new key; the unreadable one was left as is
+        let row = sqlx::query("SELECT password_encrypted, password_nonce FROM proxies WHERE id = 'p1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let (data, nonce): (Vec<u8>, Vec<u8>) = (row.get(0), row.get(1));
+        assert_eq!(new.decrypt(&data, &nonce).unwrap(), b"good");
+        let row = sqlx::query("SELECT password_encrypted FROM proxies WHERE id = 'p2'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(row.get::<Vec<u8>, _>(0), bad);
+
+        let meta = load_or_create_key_metadata(&pool).await.unwrap();
+        assert_eq!(meta.key_id, "key-2");
+    }
+
+    #[test]
+    fn test_derived_key_is_stable() {
+        let params = KdfParams {
+            m_cost: 1024,
+            t_cost: 1,
+            p_cost: 1,
+        };
+        let key = EncryptionService::derive_key(b"master", b"fixed-salt-16byt", &params).unwrap();
+        assert_eq!(hex(&key), "35e0eb7f18d99d48ac75ffe2d7c8efac35379fd8015e385748db35287178a7c3");
+
+        // V1 reproduces keys derived with the argon2 0.5 defaults
+        let v1 = EncryptionService::derive_key(b"master", b"fixed-salt-16byt", &KdfParams::V1)
+            .unwrap();
+        assert_eq!(
+            hex(&v1),
+            "e3b6a193c5c06e2698583864a901281f64131a3888ffaacf833d689888ccca7d"
+        );
+    }
+
+    #[test]
+    fn test_key_material_round_trip() {
+        let material = KeyMaterial {
+            params: KdfParams {
+                m_cost: 65536,
+                t_cost: 3,
+                p_cost: 4,
+            },
+            salt: EncryptionService::generate_salt(),
+        };
+        let encoded = material.encode();
+        assert_eq!(encoded[0], KEY_MATERIAL_VERSION);
+        assert_eq!(KeyMaterial::decode(&encoded).unwrap(), material);
+
+        // Bare salts from before the header existed use V1 params
+        let legacy = KeyMaterial::decode(&material.salt).unwrap();
+        assert_eq!(legacy.params, KdfParams::V1);
+        assert_eq!(legacy.salt, material.salt);
+
+        let mut unknown = encoded.clone();
+        unknown[0] = 99;
+        assert!(KeyMaterial::decode(&unknown).is_err());
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_key_is_zeroized() {
+        fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+        assert_zeroize_on_drop::<EncryptionService>();
+
+        let salt = EncryptionService::generate_salt();
+        let mut service = EncryptionService::new(b"test_master_key", &salt).unwrap();
+        let clone = service.clone();
+        let (ciphertext, nonce) = service.encrypt(b"secret").unwrap();
+
+        // Wiping one instance leaves clones with their own intact copy
+        service.zeroize();
+        assert_eq!(service.key, [0u8; KEY_SIZE]);
+        assert_ne!(clone.key, [0u8; KEY_SIZE]);
+        assert_eq!(clone.decrypt(&ciphertext, &nonce).unwrap(), b"secret");
+
+        let another = clone.clone();
+        drop(clone);
+        assert_eq!(another.decrypt(&ciphertext, &nonce).unwrap(), b"secret");
+    }
+
+    /// Credential builder that behaves like a machine without a keychain
+    struct UnavailableKeychain;
+
+    impl keyring::credential::CredentialBuilderApi for UnavailableKeychain {
+        fn build(
+            &self,
+            _target: Option<&str>,
+            _service: &str,
+            _user: &str,
+        ) -> keyring::Result<Box<keyring::credential::Credential>> {
+            Err(keyring::Error::PlatformFailure("no secret service".into()))
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn simulate_unavailable_keychain() {
+        keyring::set_default_credential_builder(Box::new(UnavailableKeychain));
+    }
+
+    #[test]
+    fn test_unavailable_keychain_without_fallback_fails() {
+        simulate_unavailable_keychain();
+        let keychain = KeychainService::new("com.pubcast.test");
+        assert!(matches!(
+            keychain.get_or_create_master_key(),
+            Err(PubCastError::Keychain(keyring::Error::PlatformFailure(_)))
+        ));
+    }
+
+    #[test]
+    fn test_unavailable_keychain_falls_back_to_key_file() {
+        simulate_unavailable_keychain();
+        let dir = tempfile::tempdir().unwrap();
+
+        let keychain =
+            KeychainService::new("com.pubcast.test").with_file_fallback(dir.path(), "hunter2");
+        let key = keychain.get_or_create_master_key().unwrap();
+        assert_eq!(key.len(), KEY_SIZE);
+
+        // The key file exists and does not contain the key in the clear
+        let file = std::fs::read(dir.path().join("master_key.key")).unwrap();
+        let key_b64 = STANDARD.encode(&key);
+        assert!(!file.windows(key_b64.len()).any(|w| w == key_b64.as_bytes()));
+
+        // A fresh instance with the same passphrase reads the same key back
+        let reopened =
+            KeychainService::new("com.pubcast.test").with_file_fallback(dir.path(), "hunter2");
+        assert_eq!(reopened.get_or_create_master_key().unwrap(), key);
+
+        // A wrong passphrase cannot read it
+        let wrong =
+            KeychainService::new("com.pubcast.test").with_file_fallback(dir.path(), "letmein");
+        assert!(wrong.get_or_create_master_key().is_err());
+
+        // Pending rotation entries use the same fallback
+        reopened.set_pending_master_key("key-2", b"pending").unwrap();
+        assert_eq!(
+            reopened.get_pending_master_key().unwrap(),
+            Some(("key-2".to_string(), b"pending".to_vec()))
+        );
+        reopened.clear_pending_master_key().unwrap();
+        assert_eq!(reopened.get_pending_master_key().unwrap(), None);
+    }
+
     #[test]
     fn test_empty_plaintext() {
         let master_key = b"test_master_key";
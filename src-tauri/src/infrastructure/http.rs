@@ -0,0 +1,209 @@
+//! Shared HTTP client configuration
+//!
+//! Services get their `reqwest::Client` from [`HttpClientFactory`] so the
+//! timeout, user agent and the user's global proxy are applied the same way
+//! everywhere. The global proxy is read from the `http.proxy` setting at
+//! startup; changing it takes effect after a restart.
+
+use std::time::Duration;
+
+use reqwest::{Client, ClientBuilder, Proxy};
+
+use crate::error::{PubCastError, Result};
+use crate::services::SettingsService;
+
+/// Setting holding the global proxy URL, e.g. `"socks5://127.0.0.1:1080"`
+pub const PROXY_SETTING_KEY: &str = "http.proxy";
+/// Default timeout for a whole request
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// User agent sent on every request
+pub const USER_AGENT: &str = concat!("PubCast/", env!("CARGO_PKG_VERSION"));
+
+/// Builds HTTP clients with the app's timeout, user agent and global proxy
+#[derive(Debug, Clone)]
+pub struct HttpClientFactory {
+    timeout: Duration,
+    user_agent: String,
+    proxy: Option<String>,
+}
+
+impl Default for HttpClientFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpClientFactory {
+    /// Factory with the default timeout and no global proxy
+    pub fn new() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            user_agent: USER_AGENT.to_string(),
+            proxy: None,
+        }
+    }
+
+    /// Factory using the global proxy stored in `settings`
+    pub async fn load(settings: &SettingsService) -> Result<Self> {
+        let proxy: Option<String> = settings.get(PROXY_SETTING_KEY).await?;
+        Self::new().with_proxy(proxy)
+    }
+
+    /// Give up on requests after `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Send `user_agent` instead of the default
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Route requests through `proxy_url`; `None` or an empty URL uses the system proxy
+    pub fn with_proxy(mut self, proxy_url: Option<String>) -> Result<Self> {
+        let proxy_url = proxy_url.filter(|url| !url.trim().is_empty());
+        if let Some(url) = &proxy_url {
+            parse_proxy(url)?;
+        }
+        self.proxy = proxy_url;
+        Ok(self)
+    }
+
+    /// Timeout applied to clients from [`Self::client`]
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Global proxy URL, if one is configured
+    pub fn proxy(&self) -> Option<&str> {
+        self.proxy.as_deref()
+    }
+
+    /// Client with the configured timeout and global proxy
+    pub fn client(&self) -> Result<Client> {
+        self.client_with_timeout(self.timeout)
+    }
+
+    /// Client with the global proxy and a call-specific timeout
+    pub fn client_with_timeout(&self, timeout: Duration) -> Result<Client> {
+        let mut builder = self.builder(timeout);
+        if let Some(url) = &self.proxy {
+            builder = builder.proxy(parse_proxy(url)?);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Client routed through `proxy_url` instead of the global proxy
+    pub fn client_with_proxy(&self, proxy_url: &str, timeout: Duration) -> Result<Client> {
+        Ok(self.builder(timeout).proxy(parse_proxy(proxy_url)?).build()?)
+    }
+
+    /// Client that never uses a proxy, for local services such as the sidecar
+    pub fn direct_client(&self, timeout: Duration) -> Result<Client> {
+        Ok(self.builder(timeout).no_proxy().build()?)
+    }
+
+    fn builder(&self, timeout: Duration) -> ClientBuilder {
+        Client::builder().timeout(timeout).user_agent(&self.user_agent)
+    }
+}
+
+fn parse_proxy(url: &str) -> Result<Proxy> {
+    Proxy::all(url).map_err(|e| PubCastError::Configuration(format!("Invalid proxy URL: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// An address nothing listens on
+    fn unused_proxy() -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        format!("http://127.0.0.1:{}", port)
+    }
+
+    #[tokio::test]
+    async fn test_client_applies_timeout_and_user_agent() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(2)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/fast"))
+            .and(header("user-agent", USER_AGENT))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let factory = HttpClientFactory::new().with_timeout(Duration::from_millis(200));
+        let client = factory.client().unwrap();
+        let error = client.get(format!("{}/slow", server.uri())).send().await.unwrap_err();
+        assert!(error.is_timeout());
+        let response = client.get(format!("{}/fast", server.uri())).send().await.unwrap();
+        assert!(response.status().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_global_proxy_and_per_call_override() {
+        // A plain HTTP proxy receives the absolute URL; the mock matches its path
+        let proxy = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/ip"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("via proxy"))
+            .mount(&proxy)
+            .await;
+
+        let factory = HttpClientFactory::new().with_proxy(Some(proxy.uri())).unwrap();
+        assert_eq!(factory.proxy(), Some(proxy.uri().as_str()));
+        let body = factory
+            .client()
+            .unwrap()
+            .get("http://pubcast.invalid/ip")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "via proxy");
+
+        // A per-call proxy replaces a broken global one
+        let broken = HttpClientFactory::new().with_proxy(Some(unused_proxy())).unwrap();
+        let response = broken
+            .client_with_proxy(&proxy.uri(), DEFAULT_TIMEOUT)
+            .unwrap()
+            .get("http://pubcast.invalid/ip")
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert!(broken.client().unwrap().get("http://pubcast.invalid/ip").send().await.is_err());
+
+        // Direct clients skip the global proxy entirely
+        let response = broken
+            .direct_client(DEFAULT_TIMEOUT)
+            .unwrap()
+            .get(format!("{}/ip", proxy.uri()))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+    }
+
+    #[test]
+    fn test_invalid_or_empty_proxy() {
+        assert!(matches!(
+            HttpClientFactory::new().with_proxy(Some("not a url".to_string())),
+            Err(PubCastError::Configuration(_))
+        ));
+        let factory = HttpClientFactory::new().with_proxy(Some("  ".to_string())).unwrap();
+        assert_eq!(factory.proxy(), None);
+    }
+}
@@ -0,0 +1,268 @@
+//! Opt-in logging of outbound HTTP traffic
+//!
+//! When the `debug.http_logging` setting is on, requests sent through
+//! [`send`] log their method, URL, status and the start of each body so a
+//! failing publish or sync can be diagnosed without a rebuild. Secrets such
+//! as API keys, passwords and cookies are redacted before anything is logged.
+//! With the setting off, [`send`] is a plain `RequestBuilder::send`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::{RequestBuilder, Response, ResponseBuilderExt, Url};
+
+use crate::error::Result;
+use crate::services::SettingsService;
+
+/// Setting turning HTTP logging on
+pub const HTTP_LOGGING_SETTING_KEY: &str = "debug.http_logging";
+/// Tracing target of the logged traffic, for filtering
+const LOG_TARGET: &str = "pubcast::http";
+/// Characters of each body that are logged
+const MAX_LOGGED_BODY_CHARS: usize = 2000;
+/// Replacement for redacted values
+const REDACTED: &str = "[REDACTED]";
+/// Key fragments marking a value as secret, matched case-insensitively
+const SENSITIVE_KEYS: &[&str] = &[
+    "api_key",
+    "apikey",
+    "authorization",
+    "cookie",
+    "credential",
+    "passwd",
+    "password",
+    "secret",
+    "token",
+];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether outbound HTTP traffic is being logged
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Turn logging on or off for this run
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Apply the stored setting. Returns whether logging is on.
+pub async fn load(settings: &SettingsService) -> Result<bool> {
+    let enabled = settings.get_or(HTTP_LOGGING_SETTING_KEY, false).await?;
+    set_enabled(enabled);
+    Ok(enabled)
+}
+
+/// Send `request`, logging it and its response when logging is on.
+///
+/// Text responses are buffered to be logged and handed back unchanged;
+/// binary ones (images, media) are streamed as usual and only their status
+/// is logged.
+pub async fn send(request: RequestBuilder) -> reqwest::Result<Response> {
+    if !is_enabled() {
+        return request.send().await;
+    }
+
+    let (client, request) = request.build_split();
+    let request = request?;
+    let method = request.method().clone();
+    let url = redact_url(request.url());
+    match request.body().and_then(|body| body.as_bytes()) {
+        Some(body) => {
+            tracing::info!(target: LOG_TARGET, "--> {} {} {}", method, url, loggable(body))
+        }
+        None => tracing::info!(target: LOG_TARGET, "--> {} {}", method, url),
+    }
+
+    let started = Instant::now();
+    let response = match client.execute(request).await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::info!(target: LOG_TARGET, "<-- {} {} failed: {}", method, url, e);
+            return Err(e);
+        }
+    };
+    let status = response.status();
+    let elapsed = started.elapsed().as_millis();
+
+    if !is_text(response.headers()) {
+        tracing::info!(target: LOG_TARGET, "<-- {} {} {} ({}ms)", status, method, url, elapsed);
+        return Ok(response);
+    }
+
+    let version = response.version();
+    let headers = response.headers().clone();
+    let response_url = response.url().clone();
+    let body = response.bytes().await?;
+    tracing::info!(
+        target: LOG_TARGET,
+        "<-- {} {} {} ({}ms) {}",
+        status,
+        method,
+        url,
+        elapsed,
+        loggable(&body)
+    );
+
+    let mut rebuilt = http::Response::builder()
+        .status(status)
+        .version(version)
+        .url(response_url)
+        .body(body)
+        .expect("parts of a received response are valid");
+    *rebuilt.headers_mut() = headers;
+    Ok(Response::from(rebuilt))
+}
+
+/// `text` with the values of secret-looking fields replaced.
+///
+/// Handles JSON documents and form or query encoded pairs; anything else is
+/// returned as is.
+pub fn redact(text: &str) -> String {
+    if let Ok(mut json) = serde_json::from_str::<serde_json::Value>(text) {
+        redact_json(&mut json);
+        return json.to_string();
+    }
+    if text.contains('=') && !text.contains(char::is_whitespace) {
+        return url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(
+                url::form_urlencoded::parse(text.as_bytes()).map(|(key, value)| {
+                    let value = if is_sensitive(&key) {
+                        REDACTED.into()
+                    } else {
+                        value
+                    };
+                    (key, value)
+                }),
+            )
+            .finish();
+    }
+    text.to_string()
+}
+
+fn redact_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_sensitive(key) {
+                    *value = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// `url` with secret query parameters redacted
+fn redact_url(url: &Url) -> String {
+    let mut url = url.clone();
+    if let Some(query) = url.query().map(redact) {
+        url.set_query(Some(&query));
+    }
+    url.to_string()
+}
+
+fn is_sensitive(key: &str) -> bool {
+    let key = key.to_ascii_lowercase().replace('-', "_");
+    SENSITIVE_KEYS.iter().any(|fragment| key.contains(fragment))
+}
+
+/// Redacted body, cut to [`MAX_LOGGED_BODY_CHARS`]
+fn loggable(body: &[u8]) -> String {
+    let text = redact(&String::from_utf8_lossy(body));
+    if text.chars().count() <= MAX_LOGGED_BODY_CHARS {
+        return text;
+    }
+    let cut: String = text.chars().take(MAX_LOGGED_BODY_CHARS).collect();
+    format!("{}... ({} bytes)", cut, body.len())
+}
+
+/// Whether a response body is text worth logging
+fn is_text(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| {
+            content_type.starts_with("text/")
+                || content_type.contains("json")
+                || content_type.contains("xml")
+                || content_type.contains("x-www-form-urlencoded")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Turns the global flag back off when dropped, even if the test panics
+    struct EnabledGuard;
+
+    impl Drop for EnabledGuard {
+        fn drop(&mut self) {
+            set_enabled(false);
+        }
+    }
+
+    #[test]
+    fn test_redact_removes_sensitive_fields() {
+        let body = serde_json::json!({
+            "title": "Launch post",
+            "api_key": "sk-live-123",
+            "account": { "username": "alice", "password": "hunter2" },
+            "cookies": [{ "name": "sid", "value": "abc" }],
+            "Authorization": "Bearer xyz",
+            "proxies": [{ "host": "127.0.0.1", "proxy_password": "p4ss" }],
+        });
+        let logged = redact(&body.to_string());
+        for secret in ["sk-live-123", "hunter2", "abc", "xyz", "p4ss"] {
+            assert!(!logged.contains(secret), "{} leaked in {}", secret, logged);
+        }
+        for kept in ["Launch post", "alice", "127.0.0.1"] {
+            assert!(logged.contains(kept), "{} missing from {}", kept, logged);
+        }
+
+        let logged = redact("page=1&apiKey=sk-live-123&access_token=t0k");
+        assert!(logged.starts_with("page=1&"));
+        assert!(!logged.contains("sk-live-123") && !logged.contains("t0k"));
+
+        let url =
+            Url::parse("https://cms.example.com/contents?api_key=sk-live-123&page=2").unwrap();
+        let logged = redact_url(&url);
+        assert!(!logged.contains("sk-live-123"));
+        assert!(logged.contains("page=2"));
+
+        assert_eq!(redact("plain text"), "plain text");
+        assert!(loggable("x".repeat(5000).as_bytes()).ends_with("(5000 bytes)"));
+    }
+
+    #[tokio::test]
+    async fn test_logged_response_is_passed_through() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .insert_header("x-request-id", "r1")
+                    .set_body_json(serde_json::json!({ "total": 3 })),
+            )
+            .mount(&server)
+            .await;
+
+        set_enabled(true);
+        let _enabled = EnabledGuard;
+        let url = format!("{}/contents", server.uri());
+        let response = send(reqwest::Client::new().get(&url)).await.unwrap();
+        assert_eq!(response.status(), 201);
+        assert_eq!(response.url().as_str(), url);
+        assert_eq!(response.headers()["x-request-id"], "r1");
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["total"], 3);
+    }
+}
@@ -4,3 +4,5 @@
 
 pub mod database;
 pub mod encryption;
+pub mod http;
+pub mod http_log;
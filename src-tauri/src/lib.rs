@@ -16,25 +16,47 @@ use sqlx::SqlitePool;
 use tauri::Manager;
 use tokio::sync::RwLock;
 
+use adapters::AdapterRegistry;
 use infrastructure::database::{DatabaseConfig, init_database};
-use infrastructure::encryption::{EncryptionService, KeychainService};
-use services::{AccountService, AIService, AuthService, BrowserService, ContentService, ContentApiConfig, ProxyService, SchedulerService, StatsService, SidecarManager};
+use infrastructure::encryption::{
+    load_or_create_key_metadata, recover_pending_rotation, EncryptionService, KeychainService,
+};
+use infrastructure::http::HttpClientFactory;
+use infrastructure::http_log;
+use services::{AccountService, AIService, AuthService, BrowserService, ContentService, ContentApiConfig, NotificationService, ProxyHealthMonitor, ProxyService, SchedulerService, SettingsService, StatsService, SidecarManager};
 
 /// Application state shared across commands
 pub struct AppState {
     pub db: SqlitePool,
-    pub encryption: EncryptionService,
+    pub db_config: DatabaseConfig,
+    pub keychain: KeychainService,
+    pub settings_service: Arc<RwLock<SettingsService>>,
+    pub adapter_registry: Arc<RwLock<AdapterRegistry>>,
+    pub encryption: Arc<RwLock<EncryptionService>>,
     pub proxy_service: Arc<RwLock<ProxyService>>,
+    pub proxy_health_monitor: Arc<RwLock<ProxyHealthMonitor>>,
     pub account_service: Arc<RwLock<AccountService>>,
     pub scheduler_service: Arc<RwLock<SchedulerService>>,
     pub content_service: Arc<RwLock<ContentService>>,
     pub stats_service: Arc<RwLock<StatsService>>,
+    pub notification_service: Arc<RwLock<NotificationService>>,
     pub ai_service: Arc<RwLock<AIService>>,
     pub browser_service: Arc<RwLock<BrowserService>>,
     pub auth_service: Arc<RwLock<AuthService>>,
     pub sidecar_manager: Arc<RwLock<SidecarManager>>,
 }
 
+/// Build the adapter registry shared by commands and the scheduler, with
+/// adapters checking login state through `browser`
+async fn init_adapter_registry(
+    db: &SqlitePool,
+    browser: &BrowserService,
+) -> error::Result<AdapterRegistry> {
+    Ok(AdapterRegistry::load(SettingsService::new(db.clone()))
+        .await?
+        .with_session_probe(Arc::new(browser.clone())))
+}
+
 impl AppState {
     /// Initialize application state with database and services
     pub async fn init(app_handle: &tauri::AppHandle, data_dir: PathBuf) -> error::Result<Self> {
@@ -43,57 +65,140 @@ impl AppState {
             error::PubCastError::Configuration(format!("Failed to create data dir: {}", e))
         })?;
 
+        let mut keychain = KeychainService::new("com.pubcast.app");
+        // Opt-in fallback for machines without a usable system keychain
+        if let Ok(passphrase) = std::env::var("PUBCAST_KEY_PASSPHRASE") {
+            tracing::info!("Key file fallback enabled via PUBCAST_KEY_PASSPHRASE");
+            keychain = keychain.with_file_fallback(&data_dir, passphrase);
+        }
+
         // Initialize database
         let db_path = data_dir.join("pubcast.db");
         let db_config = DatabaseConfig::new(db_path);
+        // SQLCipher builds encrypt the whole file when opted in
+        #[cfg(feature = "sqlcipher")]
+        let db_config = if db_config.encryption_requested() {
+            db_config.with_encryption_key(keychain.get_or_create_database_key()?)
+        } else {
+            db_config
+        };
         let db = init_database(&db_config).await?;
 
+        // Seed default data independent of any command being called
+        services::ensure_schema_seed(&db).await?;
+
         // Initialize encryption
-        let keychain = KeychainService::new("com.pubcast.app");
         let master_key = keychain.get_or_create_master_key()?;
-        let salt = EncryptionService::generate_salt();
-        let encryption = EncryptionService::new(&master_key, &salt)?;
+        let master_key = recover_pending_rotation(&keychain, &db, master_key).await?;
+        let key_metadata = load_or_create_key_metadata(&db).await?;
+        let encryption = EncryptionService::from_material(&master_key, &key_metadata.material)?;
 
         // Initialize services
-        let proxy_service = Arc::new(RwLock::new(ProxyService::new(
-            db.clone(),
-            encryption.clone(),
-        )));
+        let settings_service = Arc::new(RwLock::new(SettingsService::new(db.clone())));
+        let sidecar_port = services::sidecar_manager::configured_port()
+            .map_err(|e| error::PubCastError::Configuration(e.to_string()))?;
+        let (login_events_tx, login_events_rx) = tokio::sync::mpsc::unbounded_channel();
+        services::browser::spawn_login_event_forwarder(app_handle.clone(), login_events_rx);
+        let (session_events_tx, session_events_rx) = tokio::sync::mpsc::unbounded_channel();
+        services::browser::spawn_sessions_reset_forwarder(app_handle.clone(), session_events_rx);
+        let browser = BrowserService::with_port(sidecar_port)
+            .with_login_event_sender(login_events_tx)
+            .with_session_event_sender(session_events_tx);
+        let adapter_registry = Arc::new(RwLock::new(init_adapter_registry(&db, &browser).await?));
+        let browser_service = Arc::new(RwLock::new(browser.clone()));
+
+        let http = match HttpClientFactory::load(&SettingsService::new(db.clone())).await {
+            Ok(http) => http,
+            Err(e) => {
+                tracing::warn!("Ignoring global proxy setting: {}", e);
+                HttpClientFactory::new()
+            }
+        };
+        if let Err(e) = http_log::load(&SettingsService::new(db.clone())).await {
+            tracing::warn!("Ignoring HTTP logging setting: {}", e);
+        }
 
-        let account_service = Arc::new(RwLock::new(AccountService::new(
-            db.clone(),
-            encryption.clone(),
-        )));
+        let proxy_service = Arc::new(RwLock::new(
+            ProxyService::new(db.clone(), encryption.clone()).with_http(http.clone()),
+        ));
+        let mut proxy_health_monitor = ProxyHealthMonitor::new(db.clone(), proxy_service.clone());
+        if let Err(e) = proxy_health_monitor.start_if_enabled().await {
+            tracing::warn!("Failed to start proxy health checks: {}", e);
+        }
+        let proxy_health_monitor = Arc::new(RwLock::new(proxy_health_monitor));
 
-        let scheduler_service = Arc::new(RwLock::new(SchedulerService::new(db.clone())));
+        let account_service = AccountService::new(db.clone(), encryption.clone());
+        match account_service.purge_expired_trash().await {
+            Ok(0) => {}
+            Ok(purged) => tracing::info!("Purged {} expired accounts from trash", purged),
+            Err(e) => tracing::warn!("Failed to purge expired accounts from trash: {}", e),
+        }
+        let account_service = Arc::new(RwLock::new(account_service));
 
-        let content_service = Arc::new(RwLock::new(ContentService::new(
-            db.clone(),
-            ContentApiConfig::default(),
-        )));
+        let (task_events_tx, task_events_rx) = tokio::sync::mpsc::unbounded_channel();
+        services::scheduler::spawn_event_forwarder(app_handle.clone(), task_events_rx);
+        let notification_service = NotificationService::with_http(db.clone(), &http);
+        let scheduler_service = Arc::new(RwLock::new(
+            SchedulerService::load(db.clone())
+                .await?
+                .with_event_sender(task_events_tx)
+                .with_notifications(notification_service.clone())
+                .with_adapters(adapter_registry.clone())
+                .with_pre_publish_hook(Arc::new(services::account::OAuthRefreshHook::new(
+                    account_service.clone(),
+                    adapter_registry.clone(),
+                ))),
+        ));
+        let notification_service = Arc::new(RwLock::new(notification_service));
+
+        let content_config = match ContentApiConfig::load(&db, &encryption).await {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!("Ignoring content API config: {}", e);
+                ContentApiConfig::default()
+            }
+        };
+        let content_service = Arc::new(RwLock::new(
+            ContentService::new(db.clone(), content_config)
+                .with_http(&http)
+                .with_covers_dir(data_dir.join("covers")),
+        ));
+        if let Err(e) =
+            services::apply_cms_proxy(&settings_service, &proxy_service, &content_service).await
+        {
+            tracing::warn!("Ignoring CMS proxy setting: {}", e);
+        }
 
         let stats_service = Arc::new(RwLock::new(StatsService::new(db.clone())));
-        let ai_service = Arc::new(RwLock::new(AIService::new(db.clone())));
-        let browser_service = Arc::new(RwLock::new(BrowserService::new()));
-        let auth_service = Arc::new(RwLock::new(AuthService::new(
-            db.clone(),
-            encryption.clone(),
-        )));
+        let ai_service = Arc::new(RwLock::new(
+            AIService::new(db.clone(), encryption.clone()).with_http(&http),
+        ));
+        let auth_service = Arc::new(RwLock::new(
+            AuthService::new(db.clone(), encryption.clone())
+                .with_sidecar_url(services::sidecar_manager::sidecar_base_url(sidecar_port)),
+        ));
 
         // Initialize SidecarManager
         let sidecar_manager = Arc::new(RwLock::new(
-            SidecarManager::new(app_handle)
+            SidecarManager::new(app_handle, sidecar_port)
                 .map_err(|e| error::PubCastError::Configuration(format!("Failed to create SidecarManager: {}", e)))?
+                .with_restart_hook(Arc::new(browser))
         ));
 
         Ok(Self {
             db,
-            encryption,
+            db_config,
+            keychain,
+            settings_service,
+            adapter_registry,
+            encryption: Arc::new(RwLock::new(encryption)),
             proxy_service,
+            proxy_health_monitor,
             account_service,
             scheduler_service,
             content_service,
             stats_service,
+            notification_service,
             ai_service,
             browser_service,
             auth_service,
@@ -130,6 +235,41 @@ async fn get_sidecar_logs(
         .map_err(|e| e.to_user_message())
 }
 
+/// Get sidecar log entries parsed into timestamp, level and message (Tauri command)
+#[tauri::command]
+async fn get_sidecar_log_entries(
+    state: tauri::State<'_, AppState>,
+    log_type: String,
+    lines: Option<usize>,
+    level: Option<services::LogLevel>,
+    since: Option<i64>,
+) -> Result<Vec<services::LogEntry>, String> {
+    let manager = state.sidecar_manager.read().await;
+    manager
+        .get_log_entries(&log_type, lines.unwrap_or(100), level, since)
+        .map_err(|e| e.to_user_message())
+}
+
+/// Search current and archived sidecar logs, newest file first (Tauri command)
+#[tauri::command]
+async fn search_sidecar_logs(
+    state: tauri::State<'_, AppState>,
+    query: String,
+    log_type: String,
+    max_results: Option<usize>,
+    case_sensitive: Option<bool>,
+) -> Result<Vec<services::LogSearchMatch>, String> {
+    let manager = state.sidecar_manager.read().await;
+    manager
+        .search_logs(
+            &query,
+            &log_type,
+            max_results.unwrap_or(200),
+            case_sensitive.unwrap_or(false),
+        )
+        .map_err(|e| e.to_user_message())
+}
+
 /// List sidecar log files (Tauri command)
 #[tauri::command]
 async fn list_sidecar_log_files(
@@ -184,6 +324,12 @@ pub fn run() {
                             }
                         });
 
+                        // Start the scheduler's background worker
+                        services::scheduler::spawn_worker(state.scheduler_service.clone()).await;
+
+                        // Periodically catch expired sessions before publishing hits them
+                        services::auth::spawn_auth_monitor(state.auth_service.clone());
+
                         handle.manage(state);
                         tracing::info!("PubCast application initialized successfully");
                     }
@@ -198,12 +344,26 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             commands::greet,
             commands::get_app_version,
+            // Security commands
+            commands::rotate_master_key,
+            // Database commands
+            commands::backup_database,
+            commands::restore_database,
+            commands::vacuum_database,
+            commands::get_db_version,
             // Proxy commands
             commands::list_proxies,
             commands::get_proxy,
             commands::add_proxy,
+            commands::update_proxy,
             commands::delete_proxy,
             commands::check_proxy,
+            commands::check_all_proxies,
+            commands::start_proxy_health_checks,
+            commands::stop_proxy_health_checks,
+            commands::get_proxy_health_check_status,
+            commands::get_proxy_history,
+            commands::get_proxy_usage,
             commands::import_proxies,
             // Account commands
             commands::list_accounts,
@@ -212,23 +372,61 @@ pub fn run() {
             commands::add_account,
             commands::update_account,
             commands::delete_account,
+            commands::list_trashed_accounts,
+            commands::trash_account,
+            commands::restore_account,
+            commands::purge_account,
             commands::update_account_status,
+            commands::check_account_auth,
+            commands::refresh_all_accounts,
+            commands::import_accounts,
+            commands::export_accounts,
+            commands::import_accounts_metadata,
             // Content commands
             commands::list_contents,
             commands::get_content,
+            commands::preview_content,
+            commands::create_content,
+            commands::update_content,
+            commands::delete_content,
+            commands::list_content_tags,
+            commands::rename_content_tag,
+            commands::merge_content_tags,
             commands::sync_contents,
+            commands::sync_content,
+            commands::test_cms_connection,
+            commands::get_content_api_config,
+            commands::set_content_api_config,
+            commands::get_content_conflicts,
+            commands::resolve_content_conflict,
+            commands::find_duplicate_contents,
             // Scheduler commands
             commands::create_distribution_task,
             commands::get_distribution_task,
             commands::list_distribution_tasks,
+            commands::list_task_jobs,
+            commands::purge_old_tasks,
             commands::cancel_distribution_task,
+            commands::pause_distribution_task,
+            commands::resume_distribution_task,
+            commands::get_notification_webhooks,
+            commands::set_notification_webhook,
+            commands::test_notification_webhook,
+            commands::cancel_publish_job,
+            commands::get_publish_concurrency,
+            commands::set_publish_concurrency,
             // Stats commands
             commands::get_overall_stats,
             commands::get_platform_stats,
             commands::get_daily_stats,
+            commands::get_error_breakdown,
+            commands::export_stats_csv,
+            commands::reset_stats,
             // AI commands
             commands::list_ai_configs,
             commands::toggle_ai_auth,
+            commands::set_ai_credentials,
+            commands::clear_ai_credentials,
             commands::run_ai_check,
             commands::list_ai_logs,
             commands::clear_ai_logs,
@@ -239,31 +437,56 @@ pub fn run() {
             commands::browser_get_page_info,
             commands::browser_save_session,
             commands::browser_close,
+            commands::browser_screenshot,
             commands::browser_get_sessions,
+            commands::browser_list_session_details,
             commands::browser_close_all,
             commands::browser_get_login_state,
+            commands::start_login_watch,
+            commands::stop_login_watch,
             // Auth commands (for cross-device migration)
             commands::sync_auth_from_browser,
             commands::update_auth_status,
             commands::get_auth_status,
             commands::export_auth_backups,
+            commands::verify_auth_backups,
             commands::import_auth_backup,
+            commands::export_auth_file,
+            commands::import_auth_file,
             commands::clear_auth,
             commands::restore_auth_to_browser,
+            commands::verify_auth,
             // Sidecar commands
             restart_sidecar,
             get_sidecar_status,
             get_sidecar_logs,
+            get_sidecar_log_entries,
+            search_sidecar_logs,
             list_sidecar_log_files,
             clear_sidecar_logs,
+            // Health commands
+            commands::system_health,
+            // Settings commands
+            commands::get_setting,
+            commands::set_setting,
+            commands::get_http_logging,
+            commands::set_http_logging,
+            // Platform commands
+            commands::list_platforms,
+            commands::is_platform_enabled,
+            commands::set_platform_enabled,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|app_handle, event| {
             if let tauri::RunEvent::Exit = event {
-                // Stop sidecar on exit
+                // Stop background work and the sidecar on exit
                 if let Some(state) = app_handle.try_state::<AppState>() {
                     tauri::async_runtime::block_on(async {
+                        // Stop periodic proxy health checks
+                        if let Ok(mut monitor) = state.proxy_health_monitor.try_write() {
+                            monitor.stop().await;
+                        }
                         if let Ok(manager) = state.sidecar_manager.try_read() {
                             if let Err(e) = manager.stop().await {
                                 tracing::error!("Failed to stop sidecar: {}", e);
@@ -274,3 +497,24 @@ pub fn run() {
             }
         });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+
+    #[tokio::test]
+    async fn test_adapter_registry_has_default_adapters() {
+        let (_dir, pool) = init_test_database().await;
+        let registry = init_adapter_registry(&pool, &BrowserService::new())
+            .await
+            .unwrap();
+
+        let mut platforms = registry.all_platforms();
+        platforms.sort();
+        assert_eq!(platforms, ["toutiao", "wechat", "weibo", "xiaohongshu", "zhihu"]);
+        for platform in platforms {
+            assert!(registry.is_platform_enabled(platform));
+        }
+    }
+}
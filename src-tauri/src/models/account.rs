@@ -95,11 +95,19 @@ pub struct Account {
     pub auth_status: AuthStatus,
     pub profile_id: Option<String>,
     pub last_auth_sync_at: Option<i64>,
+    /// IANA timezone name used for local-time scheduling (None = UTC)
+    pub timezone: Option<String>,
+    /// When the account was moved to trash (None = not trashed)
+    #[serde(default)]
+    pub deleted_at: Option<i64>,
 }
 
 /// Auth backup data for export/import
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthBackup {
+    /// Account the backup was exported from (empty in older exports)
+    #[serde(default)]
+    pub account_id: String,
     pub platform: String,
     pub profile_id: String,
     pub cookies: serde_json::Value,      // Decrypted cookies
@@ -107,6 +115,16 @@ pub struct AuthBackup {
     pub exported_at: i64,
 }
 
+/// Whether an account's stored auth backup still decrypts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupVerification {
+    pub account_id: String,
+    pub platform: String,
+    pub ok: bool,
+    /// Why the backup is corrupt (None when ok)
+    pub error: Option<String>,
+}
+
 /// Account creation request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateAccountRequest {
@@ -114,6 +132,57 @@ pub struct CreateAccountRequest {
     pub name: String,
     pub username: Option<String>,
     pub credentials: Option<serde_json::Value>,
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+/// Non-secret account fields for backing up the account list.
+///
+/// Deliberately leaves out credentials, cookies, fingerprints and their
+/// nonces, so an export can be stored anywhere.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountExport {
+    pub platform: String,
+    pub name: String,
+    pub username: Option<String>,
+    pub status: AccountStatus,
+    #[serde(default)]
+    pub timezone: Option<String>,
+    pub created_at: i64,
+}
+
+impl From<Account> for AccountExport {
+    fn from(account: Account) -> Self {
+        Self {
+            platform: account.platform,
+            name: account.name,
+            username: account.username,
+            status: account.status,
+            timezone: account.timezone,
+            created_at: account.created_at,
+        }
+    }
+}
+
+impl From<AccountExport> for CreateAccountRequest {
+    fn from(export: AccountExport) -> Self {
+        Self {
+            platform: export.platform,
+            name: export.name,
+            username: export.username,
+            credentials: None,
+            timezone: export.timezone,
+        }
+    }
+}
+
+/// Result of a bulk account import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSummary {
+    /// Newly created accounts
+    pub imported: Vec<Account>,
+    /// Records matching an existing account or an earlier record
+    pub skipped: usize,
 }
 
 /// Account update request
@@ -123,4 +192,7 @@ pub struct UpdateAccountRequest {
     pub username: Option<String>,
     pub credentials: Option<serde_json::Value>,
     pub status: Option<AccountStatus>,
+    /// `Some(None)` clears the timezone
+    #[serde(default, deserialize_with = "super::deserialize_some")]
+    pub timezone: Option<Option<String>>,
 }
@@ -42,7 +42,8 @@ pub struct AIConfig {
     pub id: String,
     pub platform: String,
     pub status: AIPlatformStatus,
-    pub auth_data: Option<String>,
+    /// Whether encrypted credentials are stored; the credentials themselves never leave the backend
+    pub has_credentials: bool,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -56,3 +57,20 @@ pub struct AICheckLog {
     pub duration_ms: Option<i64>,
     pub created_at: i64,
 }
+
+/// Filters and paging for listing AI check logs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AICheckLogQuery {
+    pub platform: Option<String>,
+    pub status: Option<String>,
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
+}
+
+/// A page of AI check logs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AICheckLogList {
+    pub logs: Vec<AICheckLog>,
+    /// Number of logs matching the filters, ignoring paging
+    pub total: i64,
+}
@@ -61,10 +61,73 @@ pub struct Content {
     pub remote_updated_at: Option<i64>,
     pub local_updated_at: i64,
     pub metadata: Option<serde_json::Value>,
+    /// Local edits conflict with a newer remote version
+    pub sync_conflict: bool,
     pub created_at: i64,
     pub updated_at: i64,
 }
 
+/// Which side wins when resolving a sync conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    KeepLocal,
+    KeepRemote,
+}
+
+/// Contents sharing the same normalized title and body, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateContentGroup {
+    pub content_hash: String,
+    pub contents: Vec<Content>,
+}
+
+/// Create local content request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateContentRequest {
+    pub title: String,
+    pub body: Option<String>,
+    pub cover_image_url: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub category: Option<String>,
+    pub author: Option<String>,
+    pub source_url: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Update content request; `None` fields are left untouched
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateContentRequest {
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub cover_image_url: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub category: Option<String>,
+    pub author: Option<String>,
+    pub source_url: Option<String>,
+    pub status: Option<ContentStatus>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Filters and paging for listing local contents
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContentListQuery {
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+    pub status: Option<ContentStatus>,
+    pub search: Option<String>,
+}
+
+/// A page of local contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentListResponse {
+    pub contents: Vec<Content>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+    pub has_more: bool,
+}
+
 /// Remote content from CMS API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RemoteContent {
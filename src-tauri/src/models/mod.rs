@@ -8,13 +8,36 @@ pub mod content;
 pub mod proxy;
 pub mod publish;
 
-pub use account::{Account, AccountStatus, AuthBackup, AuthStatus, CreateAccountRequest, UpdateAccountRequest};
-pub use ai::{AIConfig, AICheckLog, AIPlatformStatus};
-pub use content::{Content, ContentStatus, RemoteContent, RemoteContentListResponse};
+pub use account::{
+    Account, AccountExport, AccountStatus, AuthBackup, AuthStatus, BackupVerification, CreateAccountRequest,
+    ImportSummary, UpdateAccountRequest,
+};
+pub use ai::{AIConfig, AICheckLog, AICheckLogList, AICheckLogQuery, AIPlatformStatus};
+pub use content::{
+    ConflictResolution, Content, ContentListQuery, ContentListResponse, ContentStatus,
+    CreateContentRequest, DuplicateContentGroup, RemoteContent, RemoteContentListResponse, UpdateContentRequest,
+};
 pub use proxy::{
-    CreateProxyRequest, Proxy, ProxyHealthResult, ProxyProtocol, ProxyStatus, ProxyStrategy,
+    CreateProxyRequest, ImportResult, Proxy, ProxyHealthRecord, ProxyHealthResult, ProxyProtocol, ProxyStatus,
+    ProxyStrategy, ProxyUsageRecord, UpdateProxyRequest,
 };
 pub use publish::{
-    CreateDistributionTaskRequest, DistributionTask, DistributionTaskStatus, PublishJob,
-    PublishJobStatus, PublishResult, ScheduleType,
+    classify_error, ContentIssue, CreateDistributionTaskRequest, CreateDistributionTaskResponse,
+    DistributionTask, DistributionTaskListQuery, DistributionTaskListResponse,
+    DistributionTaskStatus, ErrorCategory, IssueSeverity, PublishJob,
+    PublishJobStatus, PublishResult, ScheduleType, TaskProgress,
 };
+
+use serde::{Deserialize, Deserializer};
+
+/// Deserialize a present field as `Some`, even when it is `null`.
+///
+/// With `#[serde(default)]` this lets `Option<Option<T>>` update fields tell
+/// a missing field (leave unchanged) apart from `null` (clear).
+pub(crate) fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
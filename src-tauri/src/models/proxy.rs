@@ -8,7 +8,10 @@ use serde::{Deserialize, Serialize};
 pub enum ProxyProtocol {
     Http,
     Https,
+    Socks4,
     Socks5,
+    /// SOCKS5 with DNS resolved by the proxy
+    Socks5h,
 }
 
 impl std::fmt::Display for ProxyProtocol {
@@ -16,7 +19,9 @@ impl std::fmt::Display for ProxyProtocol {
         match self {
             Self::Http => write!(f, "http"),
             Self::Https => write!(f, "https"),
+            Self::Socks4 => write!(f, "socks4"),
             Self::Socks5 => write!(f, "socks5"),
+            Self::Socks5h => write!(f, "socks5h"),
         }
     }
 }
@@ -28,7 +33,9 @@ impl std::str::FromStr for ProxyProtocol {
         match s.to_lowercase().as_str() {
             "http" => Ok(Self::Http),
             "https" => Ok(Self::Https),
+            "socks4" => Ok(Self::Socks4),
             "socks5" => Ok(Self::Socks5),
+            "socks5h" => Ok(Self::Socks5h),
             _ => Err(format!("Unknown proxy protocol: {}", s)),
         }
     }
@@ -79,6 +86,10 @@ pub enum ProxyStrategy {
     Fixed,
     RoundRobin,
     Random,
+    /// Prefer the healthy proxy with the lowest last measured latency
+    LowestLatency,
+    /// Prefer the healthy proxy with the fewest failed checks in the last day
+    LeastFailures,
 }
 
 impl Default for ProxyStrategy {
@@ -87,6 +98,33 @@ impl Default for ProxyStrategy {
     }
 }
 
+impl std::fmt::Display for ProxyStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fixed => write!(f, "fixed"),
+            Self::RoundRobin => write!(f, "round_robin"),
+            Self::Random => write!(f, "random"),
+            Self::LowestLatency => write!(f, "lowest_latency"),
+            Self::LeastFailures => write!(f, "least_failures"),
+        }
+    }
+}
+
+impl std::str::FromStr for ProxyStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fixed" => Ok(Self::Fixed),
+            "round_robin" => Ok(Self::RoundRobin),
+            "random" => Ok(Self::Random),
+            "lowest_latency" => Ok(Self::LowestLatency),
+            "least_failures" => Ok(Self::LeastFailures),
+            _ => Err(format!("Unknown proxy strategy: {}", s)),
+        }
+    }
+}
+
 /// Proxy configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Proxy {
@@ -100,6 +138,10 @@ pub struct Proxy {
     pub last_check_ip: Option<String>,
     pub last_check_location: Option<String>,
     pub fail_count: i64,
+    /// Latency of the last successful health check
+    pub last_latency_ms: Option<i64>,
+    /// When the proxy was last handed out by selection or used for a browser launch
+    pub last_used_at: Option<i64>,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -131,6 +173,29 @@ pub struct CreateProxyRequest {
     pub password: Option<String>,
 }
 
+/// Update proxy request; `None` fields are left untouched
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateProxyRequest {
+    pub protocol: Option<ProxyProtocol>,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    /// `Some(None)` clears the username
+    #[serde(default, deserialize_with = "super::deserialize_some")]
+    pub username: Option<Option<String>>,
+    pub password: Option<String>,
+}
+
+/// Result of a batch proxy import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportResult {
+    /// Newly created proxies
+    pub imported: Vec<Proxy>,
+    /// Lines matching an existing proxy
+    pub skipped: usize,
+    /// Lines that could not be parsed or inserted
+    pub invalid: usize,
+}
+
 /// Proxy health check result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProxyHealthResult {
@@ -141,3 +206,26 @@ pub struct ProxyHealthResult {
     pub latency_ms: Option<u64>,
     pub error: Option<String>,
 }
+
+/// A single recorded proxy health check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyHealthRecord {
+    pub id: i64,
+    pub proxy_id: String,
+    pub checked_at: i64,
+    pub is_healthy: bool,
+    pub exit_ip: Option<String>,
+    pub latency_ms: Option<i64>,
+    pub error: Option<String>,
+}
+
+/// An account routed through a proxy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyUsageRecord {
+    pub id: i64,
+    pub proxy_id: String,
+    pub account_id: String,
+    /// Name of the account, if it still exists
+    pub account_name: Option<String>,
+    pub used_at: i64,
+}
@@ -1,5 +1,7 @@
 //! Publish models
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Distribution task status
@@ -8,6 +10,8 @@ use serde::{Deserialize, Serialize};
 pub enum DistributionTaskStatus {
     Pending,
     InProgress,
+    /// Held by the user; pending jobs wait until the task is resumed
+    Paused,
     Completed,
     Failed,
     Cancelled,
@@ -24,6 +28,7 @@ impl std::fmt::Display for DistributionTaskStatus {
         match self {
             Self::Pending => write!(f, "pending"),
             Self::InProgress => write!(f, "in_progress"),
+            Self::Paused => write!(f, "paused"),
             Self::Completed => write!(f, "completed"),
             Self::Failed => write!(f, "failed"),
             Self::Cancelled => write!(f, "cancelled"),
@@ -38,6 +43,7 @@ impl std::str::FromStr for DistributionTaskStatus {
         match s {
             "pending" => Ok(Self::Pending),
             "in_progress" => Ok(Self::InProgress),
+            "paused" => Ok(Self::Paused),
             "completed" => Ok(Self::Completed),
             "failed" => Ok(Self::Failed),
             "cancelled" => Ok(Self::Cancelled),
@@ -96,6 +102,8 @@ impl std::str::FromStr for PublishJobStatus {
 pub enum ScheduleType {
     Immediate,
     Scheduled,
+    /// Repeats on a cron schedule
+    Recurring,
 }
 
 impl Default for ScheduleType {
@@ -104,6 +112,29 @@ impl Default for ScheduleType {
     }
 }
 
+impl std::fmt::Display for ScheduleType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Immediate => write!(f, "immediate"),
+            Self::Scheduled => write!(f, "scheduled"),
+            Self::Recurring => write!(f, "recurring"),
+        }
+    }
+}
+
+impl std::str::FromStr for ScheduleType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "immediate" => Ok(Self::Immediate),
+            "scheduled" => Ok(Self::Scheduled),
+            "recurring" => Ok(Self::Recurring),
+            _ => Err(format!("Unknown schedule type: {}", s)),
+        }
+    }
+}
+
 /// Distribution task
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DistributionTask {
@@ -114,16 +145,40 @@ pub struct DistributionTask {
     pub target_accounts: Vec<String>,
     pub schedule_type: ScheduleType,
     pub scheduled_at: Option<i64>,
+    /// Cron expression for recurring tasks
+    pub cron_expression: Option<String>,
+    /// Next time a recurring task fires
+    pub next_run_at: Option<i64>,
     pub started_at: Option<i64>,
     pub completed_at: Option<i64>,
     pub total_jobs: i64,
     pub completed_jobs: i64,
     pub failed_jobs: i64,
     pub error_message: Option<String>,
+    /// Jobs were prepared but not published
+    pub dry_run: bool,
     pub created_at: i64,
     pub updated_at: i64,
 }
 
+/// Filter and paging for listing distribution tasks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DistributionTaskListQuery {
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+    pub status: Option<DistributionTaskStatus>,
+}
+
+/// A page of distribution tasks, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionTaskListResponse {
+    pub tasks: Vec<DistributionTask>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+    pub has_more: bool,
+}
+
 /// Publish job
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublishJob {
@@ -148,14 +203,94 @@ pub struct PublishJob {
     pub updated_at: i64,
 }
 
+impl PublishJob {
+    /// Publish options chosen for this job's platform when the task was created
+    pub fn platform_options(&self) -> Option<&serde_json::Value> {
+        self.metadata.as_ref()?.get("options")
+    }
+}
+
 /// Create distribution task request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateDistributionTaskRequest {
     pub content_id: String,
     pub name: Option<String>,
+    #[serde(default)]
     pub target_account_ids: Vec<String>,
+    /// Also target every account on these platforms, resolved when the task is created
+    #[serde(default)]
+    pub target_platforms: Option<Vec<String>>,
+    /// Limit `target_platforms` to accounts whose status is active
+    #[serde(default)]
+    pub only_active: bool,
     pub schedule_type: ScheduleType,
     pub scheduled_at: Option<i64>,
+    /// Target wall-clock time (e.g. "2024-12-01T09:00") interpreted in each
+    /// account's timezone. Takes precedence over `scheduled_at` when set.
+    #[serde(default)]
+    pub local_time: Option<String>,
+    /// Standard 5-field cron expression (e.g. "0 9 * * 1-5"), evaluated in
+    /// local time. Required for recurring schedules.
+    #[serde(default)]
+    pub cron_expression: Option<String>,
+    /// Job priority by account ID; higher publishes first, unlisted accounts get 0.
+    /// Not supported for recurring schedules.
+    #[serde(default)]
+    pub account_priorities: HashMap<String, i64>,
+    /// Refuse to create the task if the content has any issue on a target platform
+    #[serde(default)]
+    pub strict: bool,
+    /// Prepare the content for each platform and record it on the jobs
+    /// instead of publishing. Not supported for recurring schedules.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Publish options by platform ID (e.g. WeChat's original content
+    /// declaration), copied into the metadata of that platform's jobs
+    #[serde(default)]
+    pub per_platform_options: HashMap<String, serde_json::Value>,
+    /// Publish immediately on target platforms that can't schedule posts,
+    /// instead of refusing a scheduled task that includes them
+    #[serde(default)]
+    pub downgrade_unschedulable: bool,
+}
+
+/// Severity of a content issue found before publishing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    /// The platform will adapt the content, e.g. by truncating it
+    Warning,
+    /// The platform cannot publish the content as-is
+    Error,
+}
+
+/// A mismatch between content and a platform's capabilities
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentIssue {
+    pub platform: String,
+    pub severity: IssueSeverity,
+    /// Content field the issue is about ("title", "body", "cover_image")
+    pub field: String,
+    pub message: String,
+}
+
+/// Create distribution task response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateDistributionTaskResponse {
+    pub task: DistributionTask,
+    /// Issues found when checking the content against each target platform
+    pub issues: Vec<ContentIssue>,
+}
+
+/// Live progress of a distribution task, sent whenever one of its jobs changes state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskProgress {
+    pub task_id: String,
+    pub completed_jobs: i64,
+    pub failed_jobs: i64,
+    pub total_jobs: i64,
+    /// The job whose transition triggered this update
+    pub last_job: PublishJob,
 }
 
 /// Publish result for a single job
@@ -190,3 +325,109 @@ impl std::fmt::Display for ErrorCategory {
         }
     }
 }
+
+impl std::str::FromStr for ErrorCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auth" => Ok(Self::Auth),
+            "rate_limit" => Ok(Self::RateLimit),
+            "content" => Ok(Self::Content),
+            "network" => Ok(Self::Network),
+            "unknown" => Ok(Self::Unknown),
+            _ => Err(format!("Unknown error category: {}", s)),
+        }
+    }
+}
+
+/// Keywords checked in order; rate limits come first since throttling
+/// responses often also mention the account or token.
+const ERROR_KEYWORDS: &[(ErrorCategory, &[&str])] = &[
+    (
+        ErrorCategory::RateLimit,
+        &["rate_limit", "rate limit", "ratelimit", "too many requests", "429", "quota", "频繁", "限流"],
+    ),
+    (
+        ErrorCategory::Auth,
+        &[
+            "auth", "401", "403", "forbidden", "login", "token", "session expired", "cookie", "登录",
+            "授权",
+        ],
+    ),
+    (
+        ErrorCategory::Network,
+        &[
+            "network", "timeout", "timed out", "connection", "dns", "proxy", "unreachable", "网络",
+            "超时",
+        ],
+    ),
+    (
+        ErrorCategory::Content,
+        &[
+            "content", "title", "invalid", "too long", "sensitive", "validation", "violat", "内容",
+            "标题", "敏感", "违规", "审核",
+        ],
+    ),
+];
+
+fn classify_text(text: &str) -> ErrorCategory {
+    let text = text.to_lowercase();
+    ERROR_KEYWORDS
+        .iter()
+        .find(|(_, keywords)| keywords.iter().any(|k| text.contains(k)))
+        .map(|(category, _)| category.clone())
+        .unwrap_or(ErrorCategory::Unknown)
+}
+
+/// Classify a publish failure from its error code and message.
+///
+/// The code is trusted over the message; the message is only consulted when
+/// the code is missing or unrecognised.
+pub fn classify_error(code: Option<&str>, message: Option<&str>) -> ErrorCategory {
+    [code, message]
+        .into_iter()
+        .flatten()
+        .map(classify_text)
+        .find(|category| *category != ErrorCategory::Unknown)
+        .unwrap_or(ErrorCategory::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_error_messages() {
+        let cases = [
+            (None, Some("HTTP 429 Too Many Requests"), ErrorCategory::RateLimit),
+            (None, Some("操作过于频繁，请稍后再试"), ErrorCategory::RateLimit),
+            (None, Some("Login session expired, please re-authenticate"), ErrorCategory::Auth),
+            (None, Some("登录状态已失效"), ErrorCategory::Auth),
+            (None, Some("Request timed out after 30s"), ErrorCategory::Network),
+            (None, Some("error sending request: connection refused"), ErrorCategory::Network),
+            (None, Some("Title exceeds 64 characters"), ErrorCategory::Content),
+            (None, Some("内容包含敏感词"), ErrorCategory::Content),
+            (None, Some("something odd happened"), ErrorCategory::Unknown),
+            (None, None, ErrorCategory::Unknown),
+        ];
+
+        for (code, message, expected) in cases {
+            assert_eq!(classify_error(code, message), expected, "{:?}", message);
+        }
+    }
+
+    #[test]
+    fn test_classify_error_prefers_code() {
+        assert_eq!(
+            classify_error(Some("AUTH_EXPIRED"), Some("request timed out")),
+            ErrorCategory::Auth
+        );
+        // Unrecognised codes fall back to the message
+        assert_eq!(
+            classify_error(Some("E1001"), Some("rate limit exceeded")),
+            ErrorCategory::RateLimit
+        );
+        assert_eq!("rate_limit".parse::<ErrorCategory>(), Ok(ErrorCategory::RateLimit));
+    }
+}
@@ -1,11 +1,55 @@
 //! Account management service
 
-use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use sqlx::{SqliteConnection, SqlitePool};
+use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use crate::adapters::registry::disabled_platforms;
+use crate::adapters::{AdapterRegistry, LoginCredentials};
 use crate::error::{PubCastError, Result};
 use crate::infrastructure::encryption::EncryptionService;
-use crate::models::{Account, AccountStatus, AuthStatus, CreateAccountRequest, UpdateAccountRequest};
+use crate::models::{
+    Account, AccountExport, AccountStatus, AuthStatus, CreateAccountRequest, ImportSummary, PublishJob,
+    UpdateAccountRequest,
+};
+use crate::services::browser::{SessionDetail, SessionInfo};
+use crate::services::scheduler::PrePublishHook;
+use crate::services::SettingsService;
+
+/// Days a trashed account stays recoverable before it is purged
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+/// OAuth tokens expiring within this many seconds are refreshed before a publish
+pub const OAUTH_REFRESH_WINDOW_SECS: i64 = 300;
+/// Default number of accounts checked at once by a bulk refresh
+pub const DEFAULT_REFRESH_CONCURRENCY: usize = 4;
+/// Event name for bulk refresh progress, payload is [`AccountRefreshProgress`]
+pub const ACCOUNT_REFRESH_EVENT: &str = "accounts://refresh-progress";
+/// Error message recorded on accounts whose login no longer works
+const LOGIN_EXPIRED_MESSAGE: &str = "Login expired, please log in again";
+
+/// Progress of a bulk refresh, sent after each account is checked
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountRefreshProgress {
+    pub account_id: String,
+    pub status: AccountStatus,
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// Number of accounts in each status after a bulk refresh
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AccountRefreshSummary {
+    pub total: usize,
+    pub active: usize,
+    pub expired: usize,
+    pub error: usize,
+}
 
 /// Account management service
 pub struct AccountService {
@@ -19,15 +63,21 @@ impl AccountService {
         Self { pool, encryption }
     }
 
-    /// List all accounts
+    /// Replace the encryption service after a master key rotation
+    pub fn set_encryption(&mut self, encryption: EncryptionService) {
+        self.encryption = encryption;
+    }
+
+    /// List all accounts, excluding trashed ones
     pub async fn list_accounts(&self) -> Result<Vec<Account>> {
         let rows = sqlx::query!(
             r#"
             SELECT id, platform, name, username, status,
                    last_login_at, last_check_at, error_message,
                    metadata, created_at, updated_at,
-                   auth_status, profile_id, last_auth_sync_at
+                   auth_status, profile_id, last_auth_sync_at, timezone, deleted_at
             FROM accounts
+            WHERE deleted_at IS NULL
             ORDER BY created_at DESC
             "#
         )
@@ -51,6 +101,8 @@ impl AccountService {
                 auth_status: row.auth_status.parse().unwrap_or_default(),
                 profile_id: row.profile_id,
                 last_auth_sync_at: row.last_auth_sync_at,
+                timezone: row.timezone,
+                deleted_at: row.deleted_at,
             })
             .collect();
 
@@ -64,9 +116,9 @@ impl AccountService {
             SELECT id, platform, name, username, status,
                    last_login_at, last_check_at, error_message,
                    metadata, created_at, updated_at,
-                   auth_status, profile_id, last_auth_sync_at
+                   auth_status, profile_id, last_auth_sync_at, timezone, deleted_at
             FROM accounts
-            WHERE platform = ?
+            WHERE platform = ? AND deleted_at IS NULL
             ORDER BY created_at DESC
             "#,
             platform
@@ -91,6 +143,8 @@ impl AccountService {
                 auth_status: row.auth_status.parse().unwrap_or_default(),
                 profile_id: row.profile_id,
                 last_auth_sync_at: row.last_auth_sync_at,
+                timezone: row.timezone,
+                deleted_at: row.deleted_at,
             })
             .collect();
 
@@ -104,7 +158,7 @@ impl AccountService {
             SELECT id, platform, name, username, status,
                    last_login_at, last_check_at, error_message,
                    metadata, created_at, updated_at,
-                   auth_status, profile_id, last_auth_sync_at
+                   auth_status, profile_id, last_auth_sync_at, timezone, deleted_at
             FROM accounts WHERE id = ?
             "#,
             id
@@ -128,11 +182,107 @@ impl AccountService {
             auth_status: row.auth_status.parse().unwrap_or_default(),
             profile_id: row.profile_id,
             last_auth_sync_at: row.last_auth_sync_at,
+            timezone: row.timezone,
+            deleted_at: row.deleted_at,
         })
     }
 
     /// Create a new account
     pub async fn create_account(&self, req: CreateAccountRequest) -> Result<Account> {
+        if let Some(tz) = &req.timezone {
+            validate_timezone(tz)?;
+        }
+        let disabled = disabled_platforms(&SettingsService::new(self.pool.clone())).await?;
+        if disabled.contains(&req.platform) {
+            return Err(PubCastError::Validation(format!(
+                "Platform {} is disabled",
+                req.platform
+            )));
+        }
+
+        let id = {
+            let mut conn = self.pool.acquire().await?;
+            self.insert_account(&mut conn, &req).await?
+        };
+
+        self.get_account(&id).await
+    }
+
+    /// Create accounts in bulk within one transaction.
+    ///
+    /// Records matching an existing account or an earlier record on
+    /// `(platform, name)` are skipped. Nothing is imported if any record names
+    /// an unknown or disabled platform or an invalid timezone.
+    pub async fn import_accounts(
+        &self,
+        adapters: &AdapterRegistry,
+        records: Vec<CreateAccountRequest>,
+    ) -> Result<ImportSummary> {
+        let disabled = disabled_platforms(&SettingsService::new(self.pool.clone())).await?;
+        for req in &records {
+            if !adapters.has_platform(&req.platform) {
+                return Err(PubCastError::Validation(format!(
+                    "Unknown platform {} for account {}",
+                    req.platform, req.name
+                )));
+            }
+            if disabled.contains(&req.platform) {
+                return Err(PubCastError::Validation(format!(
+                    "Platform {} is disabled",
+                    req.platform
+                )));
+            }
+            if let Some(tz) = &req.timezone {
+                validate_timezone(tz)?;
+            }
+        }
+
+        let mut seen: HashSet<(String, String)> = self
+            .list_accounts()
+            .await?
+            .into_iter()
+            .map(|account| (account.platform, account.name))
+            .collect();
+        let mut ids = Vec::new();
+        let mut skipped = 0;
+
+        let mut tx = self.pool.begin().await?;
+        for req in &records {
+            if !seen.insert((req.platform.clone(), req.name.clone())) {
+                skipped += 1;
+                continue;
+            }
+            ids.push(self.insert_account(&mut tx, req).await?);
+        }
+        tx.commit().await?;
+
+        let mut imported = Vec::with_capacity(ids.len());
+        for id in &ids {
+            imported.push(self.get_account(id).await?);
+        }
+        Ok(ImportSummary { imported, skipped })
+    }
+
+    /// Non-secret fields of every account, for backing up the account list
+    pub async fn export_metadata(&self) -> Result<Vec<AccountExport>> {
+        let accounts = self.list_accounts().await?;
+        Ok(accounts.into_iter().map(AccountExport::from).collect())
+    }
+
+    /// Recreate accounts from [`export_metadata`](Self::export_metadata)
+    /// output. They start with an unknown status and no credentials, so each
+    /// needs to log in again; existing accounts are skipped.
+    pub async fn import_metadata(
+        &self,
+        adapters: &AdapterRegistry,
+        exports: Vec<AccountExport>,
+    ) -> Result<ImportSummary> {
+        let records = exports.into_iter().map(CreateAccountRequest::from).collect();
+        self.import_accounts(adapters, records).await
+    }
+
+    /// Insert a new account with encrypted credentials, returning its ID
+    async fn insert_account(&self, conn: &mut SqliteConnection, req: &CreateAccountRequest) -> Result<String> {
         let id = Uuid::new_v4().to_string();
         let now = chrono::Utc::now().timestamp();
 
@@ -150,8 +300,8 @@ impl AccountService {
 
         sqlx::query!(
             r#"
-            INSERT INTO accounts (id, platform, name, username, credentials_encrypted, credentials_nonce, status, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO accounts (id, platform, name, username, credentials_encrypted, credentials_nonce, status, timezone, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             id,
             req.platform,
@@ -160,13 +310,14 @@ impl AccountService {
             credentials_encrypted,
             credentials_nonce,
             status,
+            req.timezone,
             now,
             now
         )
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
-        self.get_account(&id).await
+        Ok(id)
     }
 
     /// Update an account
@@ -180,6 +331,11 @@ impl AccountService {
         let username = req.username.or(existing.username);
         let status = req.status.unwrap_or(existing.status).to_string();
 
+        if let Some(Some(tz)) = &req.timezone {
+            validate_timezone(tz)?;
+        }
+        let timezone = req.timezone.unwrap_or(existing.timezone);
+
         // Handle credentials update
         if let Some(credentials) = &req.credentials {
             let json = serde_json::to_string(credentials)?;
@@ -188,7 +344,7 @@ impl AccountService {
             sqlx::query!(
                 r#"
                 UPDATE accounts
-                SET name = ?, username = ?, status = ?, 
+                SET name = ?, username = ?, status = ?, timezone = ?,
                     credentials_encrypted = ?, credentials_nonce = ?,
                     updated_at = ?
                 WHERE id = ?
@@ -196,6 +352,7 @@ impl AccountService {
                 name,
                 username,
                 status,
+                timezone,
                 encrypted,
                 nonce,
                 now,
@@ -207,12 +364,13 @@ impl AccountService {
             sqlx::query!(
                 r#"
                 UPDATE accounts
-                SET name = ?, username = ?, status = ?, updated_at = ?
+                SET name = ?, username = ?, status = ?, timezone = ?, updated_at = ?
                 WHERE id = ?
                 "#,
                 name,
                 username,
                 status,
+                timezone,
                 now,
                 id
             )
@@ -223,8 +381,83 @@ impl AccountService {
         self.get_account(id).await
     }
 
-    /// Delete an account
-    pub async fn delete_account(&self, id: &str) -> Result<()> {
+    /// Delete an account; moves it to trash unless `hard` is set
+    pub async fn delete_account(&self, id: &str, hard: bool) -> Result<()> {
+        if hard {
+            self.purge_account(id).await
+        } else {
+            self.trash_account(id).await
+        }
+    }
+
+    /// Hide an account from listings, keeping its credentials recoverable
+    /// for [`TRASH_RETENTION_DAYS`]
+    pub async fn trash_account(&self, id: &str) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        let result = sqlx::query!(
+            "UPDATE accounts SET deleted_at = ?, updated_at = ? WHERE id = ? AND deleted_at IS NULL",
+            now,
+            now,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(PubCastError::NotFound(format!("Account not found: {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Bring a trashed account back
+    pub async fn restore_account(&self, id: &str) -> Result<Account> {
+        let now = chrono::Utc::now().timestamp();
+        let result = sqlx::query!(
+            "UPDATE accounts SET deleted_at = NULL, updated_at = ? WHERE id = ? AND deleted_at IS NOT NULL",
+            now,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(PubCastError::NotFound(format!("Account not in trash: {}", id)));
+        }
+
+        self.get_account(id).await
+    }
+
+    /// Resolve the platform and name of each session's account.
+    ///
+    /// Sessions whose account was purged or trashed are kept but flagged as orphaned.
+    pub async fn session_details(&self, sessions: Vec<SessionInfo>) -> Result<Vec<SessionDetail>> {
+        let mut details = Vec::with_capacity(sessions.len());
+        for session in sessions {
+            let account = sqlx::query!(
+                "SELECT platform, name, deleted_at FROM accounts WHERE id = ?",
+                session.account_id
+            )
+            .fetch_optional(&self.pool)
+            .await?;
+
+            let orphaned = account.as_ref().is_none_or(|a| a.deleted_at.is_some());
+            let (platform, account_name) = account.map(|a| (a.platform, a.name)).unzip();
+            details.push(SessionDetail {
+                account_id: session.account_id,
+                launched_at: session.launched_at,
+                has_proxy: session.has_proxy,
+                platform,
+                account_name,
+                orphaned,
+            });
+        }
+        Ok(details)
+    }
+
+    /// Permanently delete an account, trashed or not, with its credentials
+    /// and auth backups
+    pub async fn purge_account(&self, id: &str) -> Result<()> {
         let result = sqlx::query!("DELETE FROM accounts WHERE id = ?", id)
             .execute(&self.pool)
             .await?;
@@ -236,6 +469,61 @@ impl AccountService {
         Ok(())
     }
 
+    /// List trashed accounts, most recently trashed first
+    pub async fn list_trashed_accounts(&self) -> Result<Vec<Account>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, platform, name, username, status,
+                   last_login_at, last_check_at, error_message,
+                   metadata, created_at, updated_at,
+                   auth_status, profile_id, last_auth_sync_at, timezone, deleted_at
+            FROM accounts
+            WHERE deleted_at IS NOT NULL
+            ORDER BY deleted_at DESC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let accounts = rows
+            .into_iter()
+            .map(|row| Account {
+                id: row.id,
+                platform: row.platform,
+                name: row.name,
+                username: row.username,
+                status: row.status.parse().unwrap_or(AccountStatus::Unknown),
+                last_login_at: row.last_login_at,
+                last_check_at: row.last_check_at,
+                error_message: row.error_message,
+                metadata: row.metadata.as_ref().and_then(|m| serde_json::from_str(m).ok()),
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                auth_status: row.auth_status.parse().unwrap_or_default(),
+                profile_id: row.profile_id,
+                last_auth_sync_at: row.last_auth_sync_at,
+                timezone: row.timezone,
+                deleted_at: row.deleted_at,
+            })
+            .collect();
+
+        Ok(accounts)
+    }
+
+    /// Permanently delete accounts trashed longer than the retention window,
+    /// returning how many were removed
+    pub async fn purge_expired_trash(&self) -> Result<u64> {
+        let cutoff = chrono::Utc::now().timestamp() - TRASH_RETENTION_DAYS * 24 * 60 * 60;
+        let result = sqlx::query!(
+            "DELETE FROM accounts WHERE deleted_at IS NOT NULL AND deleted_at < ?",
+            cutoff
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Update account status
     pub async fn update_account_status(
         &self,
@@ -264,6 +552,90 @@ impl AccountService {
         Ok(())
     }
 
+    /// Ask the account's adapter whether its login still works and record the
+    /// result as the account's status
+    pub async fn check_auth(&self, adapters: &AdapterRegistry, id: &str) -> Result<bool> {
+        let account = self.get_account(id).await?;
+        let adapter = adapters.get(&account.platform)?;
+        let valid = adapter.check_auth(&account).await?;
+
+        let (status, error_message) = if valid {
+            (AccountStatus::Active, None)
+        } else {
+            (AccountStatus::Expired, Some(LOGIN_EXPIRED_MESSAGE.to_string()))
+        };
+        self.update_account_status(id, status, error_message).await?;
+        Ok(valid)
+    }
+
+    /// Check every account's login with at most `concurrency` checks in flight
+    /// and record each result as the account's status. `on_progress` is called
+    /// after each account.
+    ///
+    /// An account whose check fails, including one without a registered
+    /// adapter, is marked as errored; it never stops the others.
+    pub async fn refresh_all_statuses(
+        &self,
+        adapters: &AdapterRegistry,
+        concurrency: usize,
+        on_progress: impl Fn(&AccountRefreshProgress),
+    ) -> Result<AccountRefreshSummary> {
+        let accounts = self.list_accounts().await?;
+        let mut summary = AccountRefreshSummary {
+            total: accounts.len(),
+            ..Default::default()
+        };
+
+        let mut results = stream::iter(accounts)
+            .map(|account| async move {
+                let status = self.refresh_status(adapters, &account).await;
+                (account.id, status)
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        let mut completed = 0;
+        while let Some((account_id, status)) = results.next().await {
+            completed += 1;
+            match status {
+                AccountStatus::Active => summary.active += 1,
+                AccountStatus::Expired => summary.expired += 1,
+                _ => summary.error += 1,
+            }
+            on_progress(&AccountRefreshProgress {
+                account_id,
+                status,
+                completed,
+                total: summary.total,
+            });
+        }
+
+        Ok(summary)
+    }
+
+    /// Check one account's login for a bulk refresh and record the result
+    async fn refresh_status(&self, adapters: &AdapterRegistry, account: &Account) -> AccountStatus {
+        let checked = match adapters.get(&account.platform) {
+            Ok(adapter) => adapter.check_auth(account).await,
+            Err(e) => Err(e),
+        };
+        let (status, error_message) = match checked {
+            Ok(true) => (AccountStatus::Active, None),
+            Ok(false) => (AccountStatus::Expired, Some(LOGIN_EXPIRED_MESSAGE.to_string())),
+            Err(e) => {
+                tracing::warn!("Auth check for account {} failed: {}", account.id, e);
+                (AccountStatus::Error, Some(e.to_string()))
+            }
+        };
+
+        if let Err(e) = self
+            .update_account_status(&account.id, status.clone(), error_message)
+            .await
+        {
+            tracing::warn!("Failed to record status for account {}: {}", account.id, e);
+        }
+        status
+    }
+
     /// Update last login time
     pub async fn update_last_login(&self, id: &str) -> Result<()> {
         let now = chrono::Utc::now().timestamp();
@@ -284,6 +656,32 @@ impl AccountService {
         Ok(())
     }
 
+    /// Encrypt and store new credentials for an account
+    pub async fn update_credentials(&self, id: &str, credentials: &serde_json::Value) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        let json = serde_json::to_string(credentials)?;
+        let (encrypted, nonce) = self.encryption.encrypt(json.as_bytes())?;
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE accounts
+            SET credentials_encrypted = ?, credentials_nonce = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            encrypted,
+            nonce,
+            now,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(PubCastError::NotFound(format!("Account not found: {}", id)));
+        }
+        Ok(())
+    }
+
     /// Get decrypted credentials for an account
     pub async fn get_credentials(&self, id: &str) -> Result<Option<serde_json::Value>> {
         let row = sqlx::query!(
@@ -304,3 +702,619 @@ impl AccountService {
         }
     }
 }
+
+/// Pre-publish hook refreshing OAuth tokens that are about to expire through
+/// the platform adapter, so a long queue doesn't publish with a dead token
+pub struct OAuthRefreshHook {
+    accounts: Arc<RwLock<AccountService>>,
+    adapters: Arc<RwLock<AdapterRegistry>>,
+    refresh_window_secs: i64,
+}
+
+impl OAuthRefreshHook {
+    /// Create a hook refreshing tokens within [`OAUTH_REFRESH_WINDOW_SECS`] of expiry
+    pub fn new(accounts: Arc<RwLock<AccountService>>, adapters: Arc<RwLock<AdapterRegistry>>) -> Self {
+        Self {
+            accounts,
+            adapters,
+            refresh_window_secs: OAUTH_REFRESH_WINDOW_SECS,
+        }
+    }
+
+    /// Refresh tokens expiring within `secs` instead
+    pub fn with_refresh_window(mut self, secs: i64) -> Self {
+        self.refresh_window_secs = secs;
+        self
+    }
+}
+
+#[async_trait]
+impl PrePublishHook for OAuthRefreshHook {
+    async fn before_publish(&self, job: &PublishJob) -> Result<()> {
+        let accounts = self.accounts.read().await;
+        let Some(stored) = accounts.get_credentials(&job.account_id).await? else {
+            return Ok(());
+        };
+        // Cookie dumps and other free-form credentials have nothing to refresh
+        let Ok(credentials) = serde_json::from_value::<LoginCredentials>(stored) else {
+            return Ok(());
+        };
+        let now = chrono::Utc::now().timestamp();
+        if !credentials.oauth_expires_within(now, self.refresh_window_secs) {
+            return Ok(());
+        }
+
+        let account = accounts.get_account(&job.account_id).await?;
+        let adapter = self.adapters.read().await.get(&job.platform)?;
+        match adapter.refresh_auth(&account, &credentials).await {
+            Ok(refreshed) => {
+                accounts
+                    .update_credentials(&job.account_id, &serde_json::to_value(&refreshed)?)
+                    .await?;
+                tracing::info!("Refreshed OAuth token for account {}", job.account_id);
+            }
+            // The old token still works for now; try again before the next publish
+            Err(e) if !credentials.oauth_expires_within(now, 0) => {
+                tracing::warn!("Failed to refresh OAuth token for account {}: {}", job.account_id, e);
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+}
+
+/// Validate that a timezone is a known IANA name (e.g. "Asia/Shanghai")
+fn validate_timezone(tz: &str) -> Result<()> {
+    tz.parse::<chrono_tz::Tz>()
+        .map(|_| ())
+        .map_err(|_| PubCastError::Validation(format!("Unknown timezone: {}", tz)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+
+    async fn service_with_account(pool: &SqlitePool) -> (AccountService, Account) {
+        let salt = EncryptionService::generate_salt();
+        let service = AccountService::new(pool.clone(), EncryptionService::new(b"test_master_key", &salt).unwrap());
+        let account = service
+            .create_account(CreateAccountRequest {
+                platform: "wechat".to_string(),
+                name: "Main".to_string(),
+                username: None,
+                credentials: Some(serde_json::json!({ "token": "secret" })),
+                timezone: None,
+            })
+            .await
+            .unwrap();
+        (service, account)
+    }
+
+    #[tokio::test]
+    async fn test_create_account_rejects_disabled_platform() {
+        let (_dir, pool) = init_test_database().await;
+        let (service, account) = service_with_account(&pool).await;
+        let mut registry = crate::adapters::AdapterRegistry::load(SettingsService::new(pool.clone()))
+            .await
+            .unwrap();
+        registry.set_platform_enabled("wechat", false).await.unwrap();
+
+        let result = service
+            .create_account(CreateAccountRequest {
+                platform: "wechat".to_string(),
+                name: "Second".to_string(),
+                username: None,
+                credentials: None,
+                timezone: None,
+            })
+            .await;
+        assert!(matches!(result, Err(PubCastError::Validation(_))));
+        // Existing accounts are untouched
+        assert_eq!(service.get_account(&account.id).await.unwrap().platform, "wechat");
+    }
+
+    #[tokio::test]
+    async fn test_update_account_sets_and_clears_timezone() {
+        let (_dir, pool) = init_test_database().await;
+        let (service, account) = service_with_account(&pool).await;
+        let request =
+            |json: serde_json::Value| serde_json::from_value::<UpdateAccountRequest>(json).unwrap();
+
+        let req = request(serde_json::json!({ "timezone": "Asia/Tokyo" }));
+        let updated = service.update_account(&account.id, req).await.unwrap();
+        assert_eq!(updated.timezone.as_deref(), Some("Asia/Tokyo"));
+
+        // A missing field leaves the timezone alone, `null` clears it
+        let req = request(serde_json::json!({ "name": "Renamed" }));
+        let updated = service.update_account(&account.id, req).await.unwrap();
+        assert_eq!(updated.timezone.as_deref(), Some("Asia/Tokyo"));
+        let req = request(serde_json::json!({ "timezone": null }));
+        let updated = service.update_account(&account.id, req).await.unwrap();
+        assert_eq!(updated.timezone, None);
+        assert_eq!(updated.name, "Renamed");
+
+        let req = request(serde_json::json!({ "timezone": "Mars/Base" }));
+        assert!(matches!(
+            service.update_account(&account.id, req).await,
+            Err(PubCastError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_trash_hides_account_until_restored() {
+        let (_dir, pool) = init_test_database().await;
+        let (service, account) = service_with_account(&pool).await;
+
+        service.delete_account(&account.id, false).await.unwrap();
+        assert!(service.list_accounts().await.unwrap().is_empty());
+        assert!(service.list_accounts_by_platform("wechat").await.unwrap().is_empty());
+        let trashed = service.list_trashed_accounts().await.unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert!(trashed[0].deleted_at.is_some());
+        assert!(matches!(
+            service.trash_account(&account.id).await,
+            Err(PubCastError::NotFound(_))
+        ));
+
+        let restored = service.restore_account(&account.id).await.unwrap();
+        assert!(restored.deleted_at.is_none());
+        assert_eq!(service.list_accounts().await.unwrap().len(), 1);
+        assert!(service.list_trashed_accounts().await.unwrap().is_empty());
+        // Credentials survive the round trip through trash
+        assert_eq!(
+            service.get_credentials(&account.id).await.unwrap(),
+            Some(serde_json::json!({ "token": "secret" }))
+        );
+        assert!(matches!(
+            service.restore_account(&account.id).await,
+            Err(PubCastError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_purge_deletes_permanently() {
+        let (_dir, pool) = init_test_database().await;
+        let (service, account) = service_with_account(&pool).await;
+
+        service.delete_account(&account.id, true).await.unwrap();
+        assert!(matches!(
+            service.get_account(&account.id).await,
+            Err(PubCastError::NotFound(_))
+        ));
+        assert!(service.list_trashed_accounts().await.unwrap().is_empty());
+        assert!(matches!(
+            service.purge_account(&account.id).await,
+            Err(PubCastError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired_trash_respects_retention() {
+        let (_dir, pool) = init_test_database().await;
+        let (service, recent) = service_with_account(&pool).await;
+        let (_, old) = service_with_account(&pool).await;
+        service.trash_account(&recent.id).await.unwrap();
+
+        let expired_at = chrono::Utc::now().timestamp() - (TRASH_RETENTION_DAYS + 1) * 24 * 60 * 60;
+        sqlx::query("UPDATE accounts SET deleted_at = ? WHERE id = ?")
+            .bind(expired_at)
+            .bind(&old.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(service.purge_expired_trash().await.unwrap(), 1);
+        let trashed = service.list_trashed_accounts().await.unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].id, recent.id);
+    }
+
+    #[tokio::test]
+    async fn test_session_details_flag_orphaned_sessions() {
+        let (_dir, pool) = init_test_database().await;
+        let (service, account) = service_with_account(&pool).await;
+        let session = |account_id: &str| SessionInfo {
+            account_id: account_id.to_string(),
+            launched_at: 1700000000,
+            has_proxy: false,
+        };
+
+        let details = service
+            .session_details(vec![session(&account.id), session("gone")])
+            .await
+            .unwrap();
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].platform.as_deref(), Some("wechat"));
+        assert_eq!(details[0].account_name.as_deref(), Some("Main"));
+        assert!(!details[0].orphaned);
+        assert_eq!(details[1].account_id, "gone");
+        assert!(details[1].platform.is_none());
+        assert!(details[1].orphaned);
+
+        // A trashed account still resolves, but its session is orphaned
+        service.trash_account(&account.id).await.unwrap();
+        let details = service.session_details(vec![session(&account.id)]).await.unwrap();
+        assert_eq!(details[0].account_name.as_deref(), Some("Main"));
+        assert!(details[0].orphaned);
+    }
+
+    fn account_record(platform: &str, name: &str) -> CreateAccountRequest {
+        CreateAccountRequest {
+            platform: platform.to_string(),
+            name: name.to_string(),
+            username: None,
+            credentials: Some(serde_json::json!({ "token": name })),
+            timezone: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_accounts_skips_duplicates() {
+        let (_dir, pool) = init_test_database().await;
+        let (service, account) = service_with_account(&pool).await;
+
+        let summary = service
+            .import_accounts(
+                &AdapterRegistry::new(),
+                vec![
+                    account_record("wechat", "Main"),
+                    account_record("weibo", "Main"),
+                    account_record("zhihu", "Writer"),
+                    account_record("zhihu", "Writer"),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(summary.skipped, 2);
+        let imported: Vec<_> = summary
+            .imported
+            .iter()
+            .map(|a| (a.platform.as_str(), a.name.as_str()))
+            .collect();
+        assert_eq!(imported, [("weibo", "Main"), ("zhihu", "Writer")]);
+        assert!(summary.imported.iter().all(|a| a.id != account.id));
+
+        let credentials = service.get_credentials(&summary.imported[1].id).await.unwrap();
+        assert_eq!(credentials, Some(serde_json::json!({ "token": "Writer" })));
+        assert_eq!(service.list_accounts().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_import_accounts_rejects_unknown_platform() {
+        let (_dir, pool) = init_test_database().await;
+        let (service, _account) = service_with_account(&pool).await;
+
+        let result = service
+            .import_accounts(
+                &AdapterRegistry::new(),
+                vec![account_record("weibo", "Fan"), account_record("myspace", "Old")],
+            )
+            .await;
+        assert!(matches!(result, Err(PubCastError::Validation(msg)) if msg.contains("myspace")));
+        // Nothing is imported when any record is rejected
+        assert_eq!(service.list_accounts().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_metadata_leaves_out_secrets() {
+        let (_dir, pool) = init_test_database().await;
+        let (service, account) = service_with_account(&pool).await;
+        sqlx::query("UPDATE accounts SET cookies_backup = x'00', cookies_nonce = x'01' WHERE id = ?")
+            .bind(&account.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        service
+            .update_account_status(&account.id, AccountStatus::Active, None)
+            .await
+            .unwrap();
+
+        let exports = service.export_metadata().await.unwrap();
+        let json = serde_json::to_value(&exports).unwrap();
+        let fields: Vec<&str> = json[0].as_object().unwrap().keys().map(String::as_str).collect();
+        for secret in ["credentials", "cookies", "fingerprint", "nonce", "secret"] {
+            assert!(fields.iter().all(|field| !field.contains(secret)), "{} in {:?}", secret, fields);
+        }
+        assert!(!json.to_string().contains("secret"));
+
+        // Importing elsewhere recreates a shell that has to log in again
+        let (_other_dir, other_pool) = init_test_database().await;
+        let other = AccountService::new(
+            other_pool,
+            EncryptionService::new(b"test_master_key", &EncryptionService::generate_salt()).unwrap(),
+        );
+        let summary = other.import_metadata(&AdapterRegistry::new(), exports).await.unwrap();
+        let shell = &summary.imported[0];
+        assert_eq!((shell.platform.as_str(), shell.name.as_str()), ("wechat", "Main"));
+        assert_eq!(shell.status, AccountStatus::Unknown);
+        assert_eq!(other.get_credentials(&shell.id).await.unwrap(), None);
+    }
+
+    /// Session probe reporting a fixed login state for every account
+    struct FixedSessionProbe(bool);
+
+    #[async_trait]
+    impl crate::adapters::SessionProbe for FixedSessionProbe {
+        async fn is_logged_in(&self, _account_id: &str) -> Result<bool> {
+            Ok(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_auth_records_session_state() {
+        let (_dir, pool) = init_test_database().await;
+        let (service, account) = service_with_account(&pool).await;
+
+        let logged_out = AdapterRegistry::new().with_session_probe(Arc::new(FixedSessionProbe(false)));
+        assert!(!service.check_auth(&logged_out, &account.id).await.unwrap());
+        let expired = service.get_account(&account.id).await.unwrap();
+        assert_eq!(expired.status, AccountStatus::Expired);
+        assert!(expired.error_message.is_some());
+        assert!(expired.last_check_at.is_some());
+
+        // Without a probe the stored status is trusted, and it now says expired
+        assert!(!service.check_auth(&AdapterRegistry::new(), &account.id).await.unwrap());
+
+        let logged_in = AdapterRegistry::new().with_session_probe(Arc::new(FixedSessionProbe(true)));
+        assert!(service.check_auth(&logged_in, &account.id).await.unwrap());
+        let active = service.get_account(&account.id).await.unwrap();
+        assert_eq!(active.status, AccountStatus::Active);
+        assert_eq!(active.error_message, None);
+
+        assert!(matches!(
+            service.check_auth(&logged_in, "missing").await,
+            Err(PubCastError::NotFound(_))
+        ));
+    }
+
+    /// Session probe logged in for `active` accounts that fails for `broken` ones
+    struct MixedSessionProbe {
+        active: Vec<String>,
+        broken: Vec<String>,
+    }
+
+    #[async_trait]
+    impl crate::adapters::SessionProbe for MixedSessionProbe {
+        async fn is_logged_in(&self, account_id: &str) -> Result<bool> {
+            if self.broken.iter().any(|id| id == account_id) {
+                return Err(PubCastError::BrowserAutomation("sidecar unavailable".to_string()));
+            }
+            Ok(self.active.iter().any(|id| id == account_id))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refresh_all_statuses_tolerates_failures() {
+        let (_dir, pool) = init_test_database().await;
+        let (service, active) = service_with_account(&pool).await;
+        let mut ids = Vec::new();
+        for name in ["Logged out", "Broken"] {
+            let account = service
+                .create_account(CreateAccountRequest {
+                    platform: "weibo".to_string(),
+                    name: name.to_string(),
+                    username: None,
+                    credentials: None,
+                    timezone: None,
+                })
+                .await
+                .unwrap();
+            ids.push(account.id);
+        }
+        sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES ('retired', 'myspace', 'Old', 'active')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let registry = AdapterRegistry::new().with_session_probe(Arc::new(MixedSessionProbe {
+            active: vec![active.id.clone()],
+            broken: vec![ids[1].clone()],
+        }));
+        let progress = std::sync::Mutex::new(Vec::new());
+        let summary = service
+            .refresh_all_statuses(&registry, 2, |p| progress.lock().unwrap().push(p.clone()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            summary,
+            AccountRefreshSummary { total: 4, active: 1, expired: 1, error: 2 }
+        );
+        let progress = progress.into_inner().unwrap();
+        assert_eq!(progress.len(), 4);
+        assert_eq!(progress.last().unwrap().completed, 4);
+        assert!(progress.iter().all(|p| p.total == 4));
+
+        assert_eq!(service.get_account(&active.id).await.unwrap().status, AccountStatus::Active);
+        assert_eq!(service.get_account(&ids[0]).await.unwrap().status, AccountStatus::Expired);
+        let broken = service.get_account(&ids[1]).await.unwrap();
+        assert_eq!(broken.status, AccountStatus::Error);
+        assert!(broken.error_message.unwrap().contains("sidecar unavailable"));
+        let retired = service.get_account("retired").await.unwrap();
+        assert_eq!(retired.status, AccountStatus::Error);
+        assert!(retired.last_check_at.is_some());
+    }
+
+    /// OAuth adapter handing out a new token on every refresh
+    #[derive(Default)]
+    struct FakeOAuthAdapter {
+        refreshes: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl crate::adapters::PlatformAdapter for FakeOAuthAdapter {
+        fn platform_id(&self) -> &'static str {
+            "fake_oauth"
+        }
+
+        fn platform_name(&self) -> &'static str {
+            "Fake OAuth"
+        }
+
+        fn capabilities(&self) -> crate::adapters::PlatformCapabilities {
+            crate::adapters::PlatformCapabilities {
+                max_title_length: None,
+                max_content_length: None,
+                supported_media: Vec::new(),
+                supports_scheduling: false,
+                supports_draft: false,
+                supports_html: false,
+                rate_limit_per_hour: None,
+                publish_timeout_secs: None,
+                idempotent_publish: false,
+            }
+        }
+
+        async fn check_auth(&self, _account: &Account) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn refresh_auth(
+            &self,
+            _account: &Account,
+            credentials: &LoginCredentials,
+        ) -> Result<LoginCredentials> {
+            let LoginCredentials::OAuth { refresh_token, .. } = credentials else {
+                return Err(PubCastError::Validation("Not OAuth".to_string()));
+            };
+            let n = self.refreshes.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            Ok(LoginCredentials::OAuth {
+                access_token: format!("access-{}", n),
+                refresh_token: refresh_token.clone(),
+                expires_at: Some(chrono::Utc::now().timestamp() + 3600),
+            })
+        }
+
+        async fn prepare_content(
+            &self,
+            content: &crate::models::Content,
+        ) -> Result<crate::adapters::PreparedContent> {
+            Ok(crate::adapters::PreparedContent {
+                title: content.title.clone(),
+                body: content.body.clone().unwrap_or_default(),
+                cover_image: None,
+                tags: Vec::new(),
+                extra: None,
+                options: None,
+            })
+        }
+
+        async fn publish(
+            &self,
+            _account: &Account,
+            _content: &crate::adapters::PreparedContent,
+        ) -> Result<crate::models::PublishResult> {
+            Err(PubCastError::PlatformAdapter("not used".to_string()))
+        }
+    }
+
+    struct SucceedingExecutor;
+
+    #[async_trait]
+    impl crate::services::scheduler::JobExecutor for SucceedingExecutor {
+        async fn execute(&self, _job: &PublishJob) -> crate::models::PublishResult {
+            crate::models::PublishResult {
+                success: true,
+                published_url: None,
+                published_id: None,
+                error_code: None,
+                error_message: None,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expiring_oauth_token_is_refreshed_before_publish() {
+        let (_dir, pool) = init_test_database().await;
+        let salt = EncryptionService::generate_salt();
+        let service = AccountService::new(pool.clone(), EncryptionService::new(b"test_master_key", &salt).unwrap());
+        let now = chrono::Utc::now().timestamp();
+        let oauth = |expires_in: i64| {
+            serde_json::to_value(LoginCredentials::OAuth {
+                access_token: "access-0".to_string(),
+                refresh_token: Some("refresh".to_string()),
+                expires_at: Some(now + expires_in),
+            })
+            .unwrap()
+        };
+
+        let mut account_ids = Vec::new();
+        for (name, credentials) in [
+            ("expiring", Some(oauth(60))),
+            ("fresh", Some(oauth(86_400))),
+            ("cookies", Some(serde_json::json!({ "type": "cookies", "cookies": "sid=1" }))),
+        ] {
+            let account = service
+                .create_account(CreateAccountRequest {
+                    platform: "fake_oauth".to_string(),
+                    name: name.to_string(),
+                    username: None,
+                    credentials,
+                    timezone: None,
+                })
+                .await
+                .unwrap();
+            account_ids.push(account.id);
+        }
+
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'Hello')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO distribution_tasks (id, content_id, target_accounts, total_jobs) VALUES ('t1', 'c1', '[]', 3)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (i, account_id) in account_ids.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO publish_jobs (id, distribution_task_id, content_id, account_id, platform)
+                 VALUES (?, 't1', 'c1', ?, 'fake_oauth')",
+            )
+            .bind(format!("j{}", i))
+            .bind(account_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let adapter = Arc::new(FakeOAuthAdapter::default());
+        let mut registry = AdapterRegistry::new();
+        registry.register(adapter.clone());
+        let accounts = Arc::new(RwLock::new(service));
+        let hook = OAuthRefreshHook::new(accounts.clone(), Arc::new(RwLock::new(registry)));
+        let scheduler = crate::services::SchedulerService::new(pool.clone())
+            .with_pre_publish_hook(Arc::new(hook));
+        assert_eq!(scheduler.dispatch_pending_jobs(&SucceedingExecutor, 10).await.unwrap(), 3);
+
+        // Only the expiring token was refreshed, and the new one is stored encrypted
+        assert_eq!(adapter.refreshes.load(std::sync::atomic::Ordering::SeqCst), 1);
+        let accounts = accounts.read().await;
+        let stored: LoginCredentials =
+            serde_json::from_value(accounts.get_credentials(&account_ids[0]).await.unwrap().unwrap()).unwrap();
+        let LoginCredentials::OAuth { access_token, expires_at, .. } = stored else {
+            panic!("expected OAuth credentials");
+        };
+        assert_eq!(access_token, "access-1");
+        assert!(expires_at.unwrap() > now + 3000);
+        assert_eq!(accounts.get_credentials(&account_ids[1]).await.unwrap(), Some(oauth(86_400)));
+
+        let succeeded: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM publish_jobs WHERE status = 'success'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(succeeded, 3);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_auth_defaults_to_unsupported() {
+        let adapter = crate::adapters::wechat::WechatAdapter::new();
+        let (_dir, pool) = init_test_database().await;
+        let (_service, account) = service_with_account(&pool).await;
+        let credentials = LoginCredentials::Cookies { cookies: "sid=1".to_string() };
+        assert!(matches!(
+            crate::adapters::PlatformAdapter::refresh_auth(&adapter, &account, &credentials).await,
+            Err(PubCastError::PlatformAdapter(_))
+        ));
+    }
+}
@@ -1,43 +1,77 @@
 //! AI Service for authorization and content checking
 
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
 use sqlx::SqlitePool;
 use uuid::Uuid;
-use crate::error::Result;
-use crate::models::ai::{AIConfig, AIPlatformStatus, AICheckLog};
+use crate::error::{PubCastError, Result};
+use crate::infrastructure::encryption::EncryptionService;
+use crate::infrastructure::http::HttpClientFactory;
+use crate::models::ai::{AIConfig, AIPlatformStatus, AICheckLog, AICheckLogList, AICheckLogQuery};
+use crate::services::ai_checker::{
+    default_checkers, AIChecker, CheckOutcome, CheckTarget, UnsupportedChecker,
+};
 
 const DEFAULT_PLATFORMS: &[&str] = &[
     "deepseek", "doubao", "yuanbao", "tongyi", 
     "wenxin", "nanmi", "kimi", "zhipu"
 ];
 
+/// Most recently published articles checked per run
+const MAX_CHECK_TARGETS: i64 = 10;
+/// Default and maximum number of logs returned per page
+const DEFAULT_LOG_LIMIT: i32 = 100;
+const MAX_LOG_LIMIT: i32 = 500;
+
 pub struct AIService {
     pool: SqlitePool,
+    encryption: EncryptionService,
+    checkers: HashMap<String, Arc<dyn AIChecker>>,
 }
 
 impl AIService {
-    pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+    pub fn new(pool: SqlitePool, encryption: EncryptionService) -> Self {
+        let mut service = Self {
+            pool,
+            encryption,
+            checkers: HashMap::new(),
+        };
+        for checker in default_checkers() {
+            service.register_checker(Arc::new(checker));
+        }
+        service
+    }
+
+    /// Rebuild the built-in checkers with clients from `http`
+    pub fn with_http(mut self, http: &HttpClientFactory) -> Self {
+        for checker in default_checkers() {
+            self.register_checker(Arc::new(checker.with_http(http)));
+        }
+        self
+    }
+
+    /// Replace the encryption service after a master key rotation
+    pub fn set_encryption(&mut self, encryption: EncryptionService) {
+        self.encryption = encryption;
+    }
+
+    /// Register (or replace) the checker for a platform
+    pub fn register_checker(&mut self, checker: Arc<dyn AIChecker>) {
+        self.checkers.insert(checker.platform().to_string(), checker);
+    }
+
+    fn checker_for(&self, platform: &str) -> Arc<dyn AIChecker> {
+        self.checkers
+            .get(platform)
+            .cloned()
+            .unwrap_or_else(|| Arc::new(UnsupportedChecker::new(platform)))
     }
 
     /// Ensure default platforms exist in database
     pub async fn ensure_defaults(&self) -> Result<()> {
-        let now = chrono::Utc::now().timestamp();
-        for platform in DEFAULT_PLATFORMS {
-            let id = Uuid::new_v4().to_string();
-            sqlx::query!(
-                r#"
-                INSERT OR IGNORE INTO ai_configs (id, platform, status, created_at, updated_at)
-                VALUES (?, ?, 'inactive', ?, ?)
-                "#,
-                id,
-                platform,
-                now,
-                now
-            )
-            .execute(&self.pool)
-            .await?;
-        }
-        Ok(())
+        ensure_default_platforms(&self.pool).await
     }
 
     /// Get all AI configurations
@@ -46,7 +80,8 @@ impl AIService {
 
         let rows = sqlx::query!(
             r#"
-            SELECT id, platform, status, auth_data, created_at, updated_at
+            SELECT id, platform, status, auth_encrypted IS NOT NULL as "has_credentials!: bool",
+                   created_at, updated_at
             FROM ai_configs
             ORDER BY platform ASC
             "#
@@ -60,7 +95,7 @@ impl AIService {
                 id: row.id.unwrap_or_default(),
                 platform: row.platform,
                 status: row.status.parse().unwrap_or(AIPlatformStatus::Inactive),
-                auth_data: row.auth_data,
+                has_credentials: row.has_credentials,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
             })
@@ -69,60 +104,157 @@ impl AIService {
         Ok(configs)
     }
 
-    /// Toggle authorization status (Simulation)
-    pub async fn toggle_auth(&self, platform: &str) -> Result<AIConfig> {
-        // In a real app, this would handle OAuth or API key validation
-        // For MVP, we just toggle between active and inactive
-        
-        let current = sqlx::query!(
-            "SELECT status FROM ai_configs WHERE platform = ?",
+    /// Get a single AI configuration
+    pub async fn get_config(&self, platform: &str) -> Result<AIConfig> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, platform, status, auth_encrypted IS NOT NULL as "has_credentials!: bool",
+                   created_at, updated_at
+            FROM ai_configs
+            WHERE platform = ?
+            "#,
             platform
         )
         .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| PubCastError::NotFound(format!("AI platform not found: {}", platform)))?;
+
+        Ok(AIConfig {
+            id: row.id.unwrap_or_default(),
+            platform: row.platform,
+            status: row.status.parse().unwrap_or(AIPlatformStatus::Inactive),
+            has_credentials: row.has_credentials,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+
+    /// Encrypt and store a platform's credentials (e.g. `{"api_key": "..."}`) and activate it
+    pub async fn set_credentials(
+        &self,
+        platform: &str,
+        credentials: serde_json::Value,
+    ) -> Result<AIConfig> {
+        validate_credentials(&credentials)?;
+        self.ensure_defaults().await?;
+
+        let json = serde_json::to_string(&credentials)?;
+        let (encrypted, nonce) = self.encryption.encrypt(json.as_bytes())?;
+        let status = AIPlatformStatus::Active.to_string();
+        let now = chrono::Utc::now().timestamp();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE ai_configs
+            SET auth_encrypted = ?, auth_nonce = ?, status = ?, updated_at = ?
+            WHERE platform = ?
+            "#,
+            encrypted,
+            nonce,
+            status,
+            now,
+            platform
+        )
+        .execute(&self.pool)
         .await?;
 
-        let new_status = match current {
-            Some(row) => if row.status == "active" { "inactive" } else { "active" },
-            None => "active", // Should not happen due to ensure_defaults
-        };
+        if result.rows_affected() == 0 {
+            return Err(PubCastError::NotFound(format!("AI platform not found: {}", platform)));
+        }
 
+        self.get_config(platform).await
+    }
+
+    /// Remove a platform's stored credentials and deactivate it
+    pub async fn clear_credentials(&self, platform: &str) -> Result<AIConfig> {
+        let status = AIPlatformStatus::Inactive.to_string();
         let now = chrono::Utc::now().timestamp();
-        
-        sqlx::query!(
+
+        let result = sqlx::query!(
             r#"
-            UPDATE ai_configs 
-            SET status = ?, updated_at = ?
+            UPDATE ai_configs
+            SET auth_encrypted = NULL, auth_nonce = NULL, status = ?, updated_at = ?
             WHERE platform = ?
             "#,
-            new_status,
+            status,
             now,
             platform
         )
         .execute(&self.pool)
         .await?;
 
-        // Fetch updated config
+        if result.rows_affected() == 0 {
+            return Err(PubCastError::NotFound(format!("AI platform not found: {}", platform)));
+        }
+
+        self.get_config(platform).await
+    }
+
+    /// Decrypt a platform's stored credentials
+    pub async fn get_credentials(&self, platform: &str) -> Result<Option<serde_json::Value>> {
         let row = sqlx::query!(
-            "SELECT id, platform, status, auth_data, created_at, updated_at FROM ai_configs WHERE platform = ?",
+            "SELECT auth_encrypted, auth_nonce FROM ai_configs WHERE platform = ?",
             platform
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| PubCastError::NotFound(format!("AI platform not found: {}", platform)))?;
+
+        self.decrypt_credentials(row.auth_encrypted, row.auth_nonce)
+    }
+
+    fn decrypt_credentials(
+        &self,
+        encrypted: Option<Vec<u8>>,
+        nonce: Option<Vec<u8>>,
+    ) -> Result<Option<serde_json::Value>> {
+        match (encrypted, nonce) {
+            (Some(encrypted), Some(nonce)) => {
+                let decrypted = self.encryption.decrypt(&encrypted, &nonce)?;
+                Ok(Some(serde_json::from_slice(&decrypted)?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Pause or resume checks for a platform; activating requires stored credentials
+    pub async fn toggle_auth(&self, platform: &str) -> Result<AIConfig> {
+        let current = self.get_config(platform).await?;
+
+        let new_status = match current.status {
+            AIPlatformStatus::Active => AIPlatformStatus::Inactive,
+            _ if current.has_credentials => AIPlatformStatus::Active,
+            _ => {
+                return Err(PubCastError::Validation(format!(
+                    "{} has no credentials; set them before enabling",
+                    platform
+                )))
+            }
+        }
+        .to_string();
+
+        let now = chrono::Utc::now().timestamp();
+
+        sqlx::query!(
+            r#"
+            UPDATE ai_configs 
+            SET status = ?, updated_at = ?
+            WHERE platform = ?
+            "#,
+            new_status,
+            now,
+            platform
+        )
+        .execute(&self.pool)
         .await?;
 
-        Ok(AIConfig {
-            id: row.id.unwrap_or_default(),
-            platform: row.platform,
-            status: row.status.parse().unwrap_or(AIPlatformStatus::Inactive),
-            auth_data: row.auth_data,
-            created_at: row.created_at,
-            updated_at: row.updated_at,
-        })
+        self.get_config(platform).await
     }
 
-    /// Run check task (Simulation)
+    /// Check whether recently published articles are indexed by each active platform
     pub async fn run_check(&self) -> Result<()> {
         let active_platforms = sqlx::query!(
-            "SELECT platform FROM ai_configs WHERE status = 'active'"
+            "SELECT platform, auth_encrypted, auth_nonce FROM ai_configs WHERE status = 'active'"
         )
         .fetch_all(&self.pool)
         .await?;
@@ -131,45 +263,136 @@ impl AIService {
             return Ok(());
         }
 
-        let now = chrono::Utc::now().timestamp();
-        
-        // Create a log entry for each active platform
+        let targets = self.check_targets().await?;
+        if targets.is_empty() {
+            tracing::info!("No published content to run AI checks against");
+            return Ok(());
+        }
+
         for row in active_platforms {
-            let id = Uuid::new_v4().to_string();
-            // Simulate random success/fail
-            let success = rand::random::<bool>();
-            let status = if success { "success" } else { "failed" };
-            let message = if success { "收录检查通过" } else { "未收录或请求超时" };
-            
-            sqlx::query!(
-                r#"
-                INSERT INTO ai_check_logs (id, platform, status, message, duration_ms, created_at)
-                VALUES (?, ?, ?, ?, ?, ?)
-                "#,
-                id,
-                row.platform,
-                status,
-                message,
-                1500, // Simulated duration
-                now
-            )
-            .execute(&self.pool)
-            .await?;
+            let checker = self.checker_for(&row.platform);
+            let auth_data = match self.decrypt_credentials(row.auth_encrypted, row.auth_nonce) {
+                Ok(credentials) => credentials.map(|c| c.to_string()),
+                Err(e) => {
+                    self.insert_log(&row.platform, "failed", &format!("凭据解密失败：{}", e), 0)
+                        .await?;
+                    continue;
+                }
+            };
+
+            for target in &targets {
+                let started = Instant::now();
+                let result = checker.check(target, auth_data.as_deref()).await;
+                let duration_ms = started.elapsed().as_millis() as i64;
+
+                let (status, message) = match &result {
+                    Ok(CheckOutcome::Indexed) => ("success", format!("已收录：{}", target.title)),
+                    Ok(CheckOutcome::NotIndexed) => ("failed", format!("未收录：{}", target.title)),
+                    Ok(CheckOutcome::NotImplemented) => (
+                        "not_implemented",
+                        format!("{} 暂不支持收录检查", row.platform),
+                    ),
+                    Err(e) => ("failed", format!("检查失败：{}（{}）", target.title, e)),
+                };
+                self.insert_log(&row.platform, status, &message, duration_ms).await?;
+
+                // One entry is enough to tell the user the platform isn't supported
+                if matches!(result, Ok(CheckOutcome::NotImplemented)) {
+                    break;
+                }
+            }
         }
 
         Ok(())
     }
 
-    /// List logs
-    pub async fn list_logs(&self, limit: i32) -> Result<Vec<AICheckLog>> {
+    /// Latest published articles with a public URL
+    async fn check_targets(&self) -> Result<Vec<CheckTarget>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT c.title, j.published_url as "published_url!"
+            FROM publish_jobs j
+            JOIN contents c ON c.id = j.content_id
+            WHERE j.status = 'success' AND j.published_url IS NOT NULL
+            ORDER BY j.completed_at DESC
+            LIMIT ?
+            "#,
+            MAX_CHECK_TARGETS
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| CheckTarget {
+                title: row.title,
+                url: row.published_url,
+            })
+            .collect())
+    }
+
+    async fn insert_log(
+        &self,
+        platform: &str,
+        status: &str,
+        message: &str,
+        duration_ms: i64,
+    ) -> Result<()> {
+        let id = Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().timestamp();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO ai_check_logs (id, platform, status, message, duration_ms, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            id,
+            platform,
+            status,
+            message,
+            duration_ms,
+            now
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List logs newest-first, optionally filtered by platform and status
+    pub async fn list_logs(&self, query: &AICheckLogQuery) -> Result<AICheckLogList> {
+        let limit = query.limit.unwrap_or(DEFAULT_LOG_LIMIT).clamp(1, MAX_LOG_LIMIT);
+        let offset = query.offset.unwrap_or(0).max(0);
+        let platform = query.platform.as_deref();
+        let status = query.status.as_deref();
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) FROM ai_check_logs
+            WHERE (? IS NULL OR platform = ?) AND (? IS NULL OR status = ?)
+            "#,
+            platform,
+            platform,
+            status,
+            status
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
         let rows = sqlx::query!(
             r#"
             SELECT id, platform, status, message, duration_ms, created_at
             FROM ai_check_logs
-            ORDER BY created_at DESC
-            LIMIT ?
+            WHERE (? IS NULL OR platform = ?) AND (? IS NULL OR status = ?)
+            ORDER BY created_at DESC, rowid DESC
+            LIMIT ? OFFSET ?
             "#,
-            limit
+            platform,
+            platform,
+            status,
+            status,
+            limit,
+            offset
         )
         .fetch_all(&self.pool)
         .await?;
@@ -186,7 +409,7 @@ impl AIService {
             })
             .collect();
 
-        Ok(logs)
+        Ok(AICheckLogList { logs, total })
     }
 
     /// Clear logs
@@ -195,3 +418,213 @@ impl AIService {
         Ok(())
     }
 }
+
+/// Insert the built-in AI platforms, leaving existing rows untouched
+pub async fn ensure_default_platforms(pool: &SqlitePool) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+    for platform in DEFAULT_PLATFORMS {
+        let id = Uuid::new_v4().to_string();
+        sqlx::query!(
+            r#"
+            INSERT OR IGNORE INTO ai_configs (id, platform, status, created_at, updated_at)
+            VALUES (?, ?, 'inactive', ?, ?)
+            "#,
+            id,
+            platform,
+            now,
+            now
+        )
+        .execute(pool)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Credentials must be a JSON object with at least one non-empty value
+fn validate_credentials(credentials: &serde_json::Value) -> Result<()> {
+    let has_value = credentials.as_object().is_some_and(|fields| {
+        fields.values().any(|v| match v {
+            serde_json::Value::String(s) => !s.trim().is_empty(),
+            serde_json::Value::Null => false,
+            _ => true,
+        })
+    });
+
+    if has_value {
+        Ok(())
+    } else {
+        Err(PubCastError::Validation(
+            "Credentials must be a JSON object such as {\"api_key\": \"...\"}".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+    use async_trait::async_trait;
+
+    /// Checker returning a fixed outcome, or an error when `outcome` is None
+    struct MockChecker {
+        platform: &'static str,
+        outcome: Option<CheckOutcome>,
+    }
+
+    #[async_trait]
+    impl AIChecker for MockChecker {
+        fn platform(&self) -> &str {
+            self.platform
+        }
+
+        async fn check(&self, _target: &CheckTarget, _auth_data: Option<&str>) -> Result<CheckOutcome> {
+            self.outcome
+                .clone()
+                .ok_or_else(|| PubCastError::Network("timed out".to_string()))
+        }
+    }
+
+    fn test_service(pool: SqlitePool) -> AIService {
+        let salt = EncryptionService::generate_salt();
+        AIService::new(pool, EncryptionService::new(b"test_master_key", &salt).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_credentials_round_trip_encrypted() {
+        let (_dir, pool) = init_test_database().await;
+        let service = test_service(pool.clone());
+        let credentials = serde_json::json!({ "api_key": "sk-secret" });
+
+        let config = service.set_credentials("kimi", credentials.clone()).await.unwrap();
+        assert!(config.has_credentials);
+        assert!(matches!(config.status, AIPlatformStatus::Active));
+        assert_eq!(service.get_credentials("kimi").await.unwrap(), Some(credentials));
+
+        // Nothing readable is stored at rest
+        let stored: Vec<u8> =
+            sqlx::query_scalar("SELECT auth_encrypted FROM ai_configs WHERE platform = 'kimi'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(!String::from_utf8_lossy(&stored).contains("sk-secret"));
+
+        let config = service.clear_credentials("kimi").await.unwrap();
+        assert!(!config.has_credentials);
+        assert!(matches!(config.status, AIPlatformStatus::Inactive));
+        assert_eq!(service.get_credentials("kimi").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_credentials_are_required_to_activate() {
+        let (_dir, pool) = init_test_database().await;
+        let service = test_service(pool);
+        service.ensure_defaults().await.unwrap();
+
+        assert!(matches!(
+            service.toggle_auth("deepseek").await,
+            Err(PubCastError::Validation(_))
+        ));
+        assert!(matches!(
+            service.set_credentials("deepseek", serde_json::json!({ "api_key": "" })).await,
+            Err(PubCastError::Validation(_))
+        ));
+        assert!(matches!(
+            service.set_credentials("nope", serde_json::json!({ "api_key": "x" })).await,
+            Err(PubCastError::NotFound(_))
+        ));
+
+        service
+            .set_credentials("deepseek", serde_json::json!({ "api_key": "x" }))
+            .await
+            .unwrap();
+        let paused = service.toggle_auth("deepseek").await.unwrap();
+        assert!(matches!(paused.status, AIPlatformStatus::Inactive));
+        let resumed = service.toggle_auth("deepseek").await.unwrap();
+        assert!(matches!(resumed.status, AIPlatformStatus::Active));
+    }
+
+    #[tokio::test]
+    async fn test_list_logs_filters_and_pages() {
+        let (_dir, pool) = init_test_database().await;
+        let service = test_service(pool);
+
+        for (platform, status) in [
+            ("kimi", "failed"),
+            ("kimi", "success"),
+            ("kimi", "failed"),
+            ("deepseek", "failed"),
+            ("deepseek", "success"),
+        ] {
+            service.insert_log(platform, status, "msg", 10).await.unwrap();
+        }
+
+        let all = service.list_logs(&AICheckLogQuery::default()).await.unwrap();
+        assert_eq!(all.total, 5);
+        assert_eq!(all.logs.len(), 5);
+
+        let kimi_failures = AICheckLogQuery {
+            platform: Some("kimi".to_string()),
+            status: Some("failed".to_string()),
+            ..Default::default()
+        };
+        let page = service.list_logs(&kimi_failures).await.unwrap();
+        assert_eq!(page.total, 2);
+        assert!(page.logs.iter().all(|l| l.platform == "kimi" && l.status == "failed"));
+
+        let failures = AICheckLogQuery {
+            status: Some("failed".to_string()),
+            limit: Some(2),
+            offset: Some(2),
+            ..Default::default()
+        };
+        let page = service.list_logs(&failures).await.unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.logs.len(), 1);
+        // Newest first: the last page holds the oldest failure
+        assert_eq!(page.logs[0].platform, "kimi");
+    }
+
+    #[tokio::test]
+    async fn test_run_check_records_checker_results() {
+        let (_dir, pool) = init_test_database().await;
+
+        for sql in [
+            "INSERT INTO contents (id, title) VALUES ('c1', 'Hello')",
+            "INSERT INTO accounts (id, platform, name, status) VALUES ('a1', 'wechat', 'a1', 'active')",
+            "INSERT INTO distribution_tasks (id, content_id, target_accounts) VALUES ('t1', 'c1', '[]')",
+            "INSERT INTO publish_jobs (id, distribution_task_id, content_id, account_id, platform, status, published_url, completed_at)
+             VALUES ('j1', 't1', 'c1', 'a1', 'wechat', 'success', 'https://mp.weixin.qq.com/s/1', 100)",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+
+        let mut service = test_service(pool.clone());
+        for platform in ["deepseek", "kimi", "zhipu", "yuanbao"] {
+            service
+                .set_credentials(platform, serde_json::json!({ "api_key": "sk-test" }))
+                .await
+                .unwrap();
+        }
+        service.register_checker(Arc::new(MockChecker { platform: "deepseek", outcome: Some(CheckOutcome::Indexed) }));
+        service.register_checker(Arc::new(MockChecker { platform: "kimi", outcome: Some(CheckOutcome::NotIndexed) }));
+        service.register_checker(Arc::new(MockChecker { platform: "zhipu", outcome: None }));
+
+        service.run_check().await.unwrap();
+
+        let logs = service.list_logs(&AICheckLogQuery::default()).await.unwrap().logs;
+        let status_of = |platform: &str| {
+            logs.iter()
+                .find(|log| log.platform == platform)
+                .map(|log| log.status.as_str())
+                .unwrap()
+        };
+
+        assert_eq!(logs.len(), 4);
+        assert_eq!(status_of("deepseek"), "success");
+        assert_eq!(status_of("kimi"), "failed");
+        assert_eq!(status_of("zhipu"), "failed");
+        // yuanbao has no checker and must not be reported as a pass
+        assert_eq!(status_of("yuanbao"), "not_implemented");
+        assert!(logs.iter().all(|log| log.duration_ms.is_some()));
+    }
+}
@@ -0,0 +1,275 @@
+//! AI platform indexing checkers
+//!
+//! Each checker asks one AI platform about a published article and reports
+//! whether the article's URL shows up in the answer.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::error::{PubCastError, Result};
+use crate::infrastructure::http::HttpClientFactory;
+
+/// Request timeout for a single check
+const CHECK_TIMEOUT_SECS: u64 = 60;
+
+/// A published article to look for
+#[derive(Debug, Clone)]
+pub struct CheckTarget {
+    pub title: String,
+    pub url: String,
+}
+
+/// Result of checking one article on one platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckOutcome {
+    /// The platform's answer cites the article URL
+    Indexed,
+    /// The platform answered without citing the article
+    NotIndexed,
+    /// No checker exists for this platform yet
+    NotImplemented,
+}
+
+/// Per-platform indexing check
+#[async_trait]
+pub trait AIChecker: Send + Sync {
+    /// Platform ID matching `ai_configs.platform`
+    fn platform(&self) -> &str;
+
+    /// Check whether `target` is indexed, using the platform's stored `auth_data`
+    async fn check(&self, target: &CheckTarget, auth_data: Option<&str>) -> Result<CheckOutcome>;
+}
+
+/// Checker for platforms exposing an OpenAI-compatible chat completions API
+pub struct OpenAICompatibleChecker {
+    platform: String,
+    base_url: String,
+    model: String,
+    client: reqwest::Client,
+}
+
+impl OpenAICompatibleChecker {
+    pub fn new(platform: &str, base_url: &str, model: &str) -> Self {
+        Self {
+            platform: platform.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            client: Self::build_client(&HttpClientFactory::new()),
+        }
+    }
+
+    /// Send requests with a client from `http`, e.g. through the global proxy
+    pub fn with_http(mut self, http: &HttpClientFactory) -> Self {
+        self.client = Self::build_client(http);
+        self
+    }
+
+    fn build_client(http: &HttpClientFactory) -> reqwest::Client {
+        http.client_with_timeout(Duration::from_secs(CHECK_TIMEOUT_SECS))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    content: Option<String>,
+}
+
+#[async_trait]
+impl AIChecker for OpenAICompatibleChecker {
+    fn platform(&self) -> &str {
+        &self.platform
+    }
+
+    async fn check(&self, target: &CheckTarget, auth_data: Option<&str>) -> Result<CheckOutcome> {
+        let api_key = auth_data.and_then(api_key_from_auth).ok_or_else(|| {
+            PubCastError::Configuration(format!("{} 未配置 API Key", self.platform))
+        })?;
+
+        let prompt = format!(
+            "请联网搜索文章《{}》，并列出你找到的相关文章链接（完整 URL）。",
+            target.title
+        );
+        let body = serde_json::json!({
+            "model": self.model,
+            "messages": [{ "role": "user", "content": prompt }],
+        });
+
+        let response = self
+            .client
+            .post(format!("{}/chat/completions", self.base_url))
+            .bearer_auth(api_key)
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(PubCastError::PlatformAdapter(format!(
+                "{} returned HTTP {}",
+                self.platform, status
+            )));
+        }
+
+        let answer: ChatResponse = response.json().await?;
+        let cited = answer
+            .choices
+            .iter()
+            .filter_map(|choice| choice.message.content.as_deref())
+            .any(|text| mentions_url(text, &target.url));
+
+        Ok(if cited {
+            CheckOutcome::Indexed
+        } else {
+            CheckOutcome::NotIndexed
+        })
+    }
+}
+
+/// Placeholder for platforms without a public query API
+pub struct UnsupportedChecker {
+    platform: String,
+}
+
+impl UnsupportedChecker {
+    pub fn new(platform: &str) -> Self {
+        Self {
+            platform: platform.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl AIChecker for UnsupportedChecker {
+    fn platform(&self) -> &str {
+        &self.platform
+    }
+
+    async fn check(&self, _target: &CheckTarget, _auth_data: Option<&str>) -> Result<CheckOutcome> {
+        Ok(CheckOutcome::NotImplemented)
+    }
+}
+
+/// Built-in checkers for the platforms with OpenAI-compatible APIs
+pub fn default_checkers() -> Vec<OpenAICompatibleChecker> {
+    vec![
+        OpenAICompatibleChecker::new("deepseek", "https://api.deepseek.com", "deepseek-chat"),
+        OpenAICompatibleChecker::new("kimi", "https://api.moonshot.cn/v1", "moonshot-v1-8k"),
+        OpenAICompatibleChecker::new(
+            "tongyi",
+            "https://dashscope.aliyuncs.com/compatible-mode/v1",
+            "qwen-plus",
+        ),
+        OpenAICompatibleChecker::new("zhipu", "https://open.bigmodel.cn/api/paas/v4", "glm-4"),
+    ]
+}
+
+/// Read an API key from `auth_data`, stored either as `{"api_key": "..."}` or a bare key
+fn api_key_from_auth(auth_data: &str) -> Option<String> {
+    let auth_data = auth_data.trim();
+    let key = match serde_json::from_str::<serde_json::Value>(auth_data) {
+        Ok(value) => value.get("api_key")?.as_str()?.to_string(),
+        Err(_) => auth_data.to_string(),
+    };
+    (!key.is_empty()).then_some(key)
+}
+
+/// Whether `text` mentions `url`, ignoring scheme, `www.` and a trailing slash
+fn mentions_url(text: &str, url: &str) -> bool {
+    fn normalize(url: &str) -> String {
+        let url = url.trim();
+        let url = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .unwrap_or(url);
+        let url = url.strip_prefix("www.").unwrap_or(url);
+        url.trim_end_matches('/').to_lowercase()
+    }
+
+    let needle = normalize(url);
+    !needle.is_empty() && text.to_lowercase().contains(&needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn target() -> CheckTarget {
+        CheckTarget {
+            title: "PubCast 发布指南".to_string(),
+            url: "https://mp.weixin.qq.com/s/abc123/".to_string(),
+        }
+    }
+
+    fn answer(content: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "choices": [{ "message": { "role": "assistant", "content": content } }]
+        }))
+    }
+
+    #[test]
+    fn test_mentions_url_ignores_scheme_and_trailing_slash() {
+        assert!(mentions_url("见 http://MP.weixin.qq.com/s/abc123", "https://mp.weixin.qq.com/s/abc123/"));
+        assert!(!mentions_url("见 https://mp.weixin.qq.com/s/other", "https://mp.weixin.qq.com/s/abc123"));
+        assert!(!mentions_url("anything", ""));
+    }
+
+    #[test]
+    fn test_api_key_from_auth() {
+        assert_eq!(api_key_from_auth(r#"{"api_key":"sk-1"}"#), Some("sk-1".to_string()));
+        assert_eq!(api_key_from_auth(" sk-2 "), Some("sk-2".to_string()));
+        assert_eq!(api_key_from_auth(r#"{"token":"x"}"#), None);
+    }
+
+    #[tokio::test]
+    async fn test_openai_compatible_checker_detects_citation() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(header("authorization", "Bearer sk-test"))
+            .respond_with(answer("相关文章：https://mp.weixin.qq.com/s/abc123"))
+            .mount(&server)
+            .await;
+
+        let checker = OpenAICompatibleChecker::new("deepseek", &server.uri(), "deepseek-chat");
+        let outcome = checker
+            .check(&target(), Some(r#"{"api_key":"sk-test"}"#))
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, CheckOutcome::Indexed);
+    }
+
+    #[tokio::test]
+    async fn test_openai_compatible_checker_reports_missing_citation() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(answer("没有找到这篇文章。"))
+            .mount(&server)
+            .await;
+
+        let checker = OpenAICompatibleChecker::new("kimi", &server.uri(), "moonshot-v1-8k");
+        assert_eq!(
+            checker.check(&target(), Some("sk-test")).await.unwrap(),
+            CheckOutcome::NotIndexed
+        );
+        assert!(matches!(
+            checker.check(&target(), None).await,
+            Err(PubCastError::Configuration(_))
+        ));
+    }
+}
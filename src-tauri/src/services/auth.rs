@@ -1,21 +1,53 @@
 //! Auth service for managing platform authorization
 //! Handles cookie backup/restore for cross-device migration
 
-use sqlx::{Row, SqlitePool};
-use std::time::{SystemTime, UNIX_EPOCH};
+use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
 
-use crate::error::Result;
-use crate::infrastructure::encryption::EncryptionService;
-use crate::models::account::{AuthBackup, AuthStatus};
+use crate::error::{PubCastError, Result};
+use crate::infrastructure::encryption::{EncryptionService, KeyMaterial, NONCE_SIZE};
+use crate::infrastructure::http::{HttpClientFactory, DEFAULT_TIMEOUT};
+use crate::models::account::{AccountStatus, AuthBackup, AuthStatus, BackupVerification};
+
+use super::browser::{BrowserService, LoginStateResponse};
+use super::sidecar_manager::{sidecar_base_url, DEFAULT_SIDECAR_PORT};
+
+/// Magic header identifying an encrypted auth export file
+const EXPORT_MAGIC: &[u8; 6] = b"PCAUTH";
+/// Current auth export file format version
+const EXPORT_VERSION: u8 = 1;
+
+/// How often the background monitor re-verifies stored sessions
+const AUTH_CHECK_INTERVAL: Duration = Duration::from_secs(6 * 3600);
 
 pub struct AuthService {
     pool: SqlitePool,
     encryption: EncryptionService,
+    client: reqwest::Client,
+    sidecar_url: String,
 }
 
 impl AuthService {
     pub fn new(pool: SqlitePool, encryption: EncryptionService) -> Self {
-        Self { pool, encryption }
+        Self {
+            pool,
+            encryption,
+            client: HttpClientFactory::new().direct_client(DEFAULT_TIMEOUT).unwrap_or_default(),
+            sidecar_url: sidecar_base_url(DEFAULT_SIDECAR_PORT),
+        }
+    }
+
+    /// Talk to a sidecar at a non-default base URL
+    pub fn with_sidecar_url(mut self, sidecar_url: impl Into<String>) -> Self {
+        self.sidecar_url = sidecar_url.into();
+        self
+    }
+
+    /// Replace the encryption service after a master key rotation
+    pub fn set_encryption(&mut self, encryption: EncryptionService) {
+        self.encryption = encryption;
     }
     
     fn now() -> i64 {
@@ -87,10 +119,17 @@ impl AuthService {
         Ok(())
     }
 
+    /// Back up the cookies and fingerprint saved in an account's browser profile
+    pub async fn backup_from_browser(&self, browser: &BrowserService, account_id: &str) -> Result<()> {
+        let cookies = browser.get_cookies(account_id).await?;
+        let fingerprint = browser.get_fingerprint(account_id).await?;
+        self.backup_auth(account_id, &serde_json::Value::Array(cookies), &fingerprint).await
+    }
+
     /// Restore auth backup for a platform
     pub async fn restore_auth(&self, platform: &str) -> Result<Option<AuthBackup>> {
         let row = sqlx::query(
-            r#"SELECT profile_id, cookies_backup, cookies_nonce, 
+            r#"SELECT id, profile_id, cookies_backup, cookies_nonce, 
                       fingerprint_backup, fingerprint_nonce, last_auth_sync_at
                FROM accounts
                WHERE platform = ? AND auth_status = 'authorized'"#
@@ -100,50 +139,194 @@ impl AuthService {
         .await?;
 
         match row {
-            Some(r) => {
-                let profile_id: Option<String> = r.get("profile_id");
-                let cookies_backup: Option<Vec<u8>> = r.get("cookies_backup");
-                let cookies_nonce: Option<Vec<u8>> = r.get("cookies_nonce");
-                let fingerprint_backup: Option<Vec<u8>> = r.get("fingerprint_backup");
-                let fingerprint_nonce: Option<Vec<u8>> = r.get("fingerprint_nonce");
-                let last_auth_sync_at: Option<i64> = r.get("last_auth_sync_at");
-
-                // Check if we have backup data
-                let cookies = match (cookies_backup, cookies_nonce) {
-                    (Some(encrypted), Some(nonce)) => {
-                        let decrypted = self.encryption.decrypt(&encrypted, &nonce)?;
-                        serde_json::from_slice(&decrypted)?
-                    }
-                    _ => return Ok(None),
-                };
+            Some(r) => self.decode_backup(&r, platform, platform),
+            None => Ok(None),
+        }
+    }
 
-                let fingerprint = match (fingerprint_backup, fingerprint_nonce) {
-                    (Some(encrypted), Some(nonce)) => {
-                        let decrypted = self.encryption.decrypt(&encrypted, &nonce)?;
-                        serde_json::from_slice(&decrypted)?
-                    }
-                    _ => serde_json::json!({}),
-                };
+    /// Restore an account's backup into its own sidecar browser profile, so
+    /// accounts on the same platform never overwrite each other's session.
+    /// Returns `false` if the account has no backup.
+    pub async fn restore_to_browser(&self, account_id: &str) -> Result<bool> {
+        Ok(self.restore_backup(account_id).await?.is_some())
+    }
 
-                Ok(Some(AuthBackup {
-                    platform: platform.to_string(),
-                    profile_id: profile_id.unwrap_or_else(|| platform.to_string()),
-                    cookies,
-                    fingerprint,
-                    exported_at: last_auth_sync_at.unwrap_or(0),
-                }))
+    /// Restore an account's backup into its profile, returning the backup
+    async fn restore_backup(&self, account_id: &str) -> Result<Option<AuthBackup>> {
+        let row = sqlx::query(
+            r#"SELECT id, platform, profile_id, cookies_backup, cookies_nonce,
+                      fingerprint_backup, fingerprint_nonce, last_auth_sync_at
+               FROM accounts
+               WHERE id = ?"#
+        )
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| PubCastError::NotFound(format!("Account not found: {}", account_id)))?;
+
+        // Browser profiles are keyed by account until a profile is recorded
+        let platform: String = row.get("platform");
+        let Some(backup) = self.decode_backup(&row, &platform, account_id)? else {
+            return Ok(None);
+        };
+
+        let restored = self
+            .client
+            .post(format!(
+                "{}/profiles/{}/restore",
+                self.sidecar_url, backup.profile_id
+            ))
+            .json(&serde_json::json!({
+                "accountId": account_id,
+                "platform": backup.platform,
+                "cookies": backup.cookies,
+                "fingerprint": backup.fingerprint,
+            }))
+            .send()
+            .await?;
+        if !restored.status().is_success() {
+            return Err(PubCastError::BrowserAutomation(format!(
+                "Failed to restore profile {} for {}: HTTP {}",
+                backup.profile_id,
+                account_id,
+                restored.status()
+            )));
+        }
+        Ok(Some(backup))
+    }
+
+    /// Decrypt a backup row, or `None` if it has no cookies. Rows without a
+    /// profile ID get `default_profile`.
+    fn decode_backup(
+        &self,
+        r: &SqliteRow,
+        platform: &str,
+        default_profile: &str,
+    ) -> Result<Option<AuthBackup>> {
+        let profile_id: Option<String> = r.get("profile_id");
+        let cookies_backup: Option<Vec<u8>> = r.get("cookies_backup");
+        let cookies_nonce: Option<Vec<u8>> = r.get("cookies_nonce");
+        let fingerprint_backup: Option<Vec<u8>> = r.get("fingerprint_backup");
+        let fingerprint_nonce: Option<Vec<u8>> = r.get("fingerprint_nonce");
+        let last_auth_sync_at: Option<i64> = r.get("last_auth_sync_at");
+
+        // Check if we have backup data
+        let cookies = match (cookies_backup, cookies_nonce) {
+            (Some(encrypted), Some(nonce)) => {
+                let decrypted = self.encryption.decrypt(&encrypted, &nonce)?;
+                serde_json::from_slice(&decrypted)?
             }
-            None => Ok(None),
+            _ => return Ok(None),
+        };
+
+        let fingerprint = match (fingerprint_backup, fingerprint_nonce) {
+            (Some(encrypted), Some(nonce)) => {
+                let decrypted = self.encryption.decrypt(&encrypted, &nonce)?;
+                serde_json::from_slice(&decrypted)?
+            }
+            _ => serde_json::json!({}),
+        };
+
+        Ok(Some(AuthBackup {
+            account_id: r.get("id"),
+            platform: platform.to_string(),
+            profile_id: profile_id.unwrap_or_else(|| default_profile.to_string()),
+            cookies,
+            fingerprint,
+            exported_at: last_auth_sync_at.unwrap_or(0),
+        }))
+    }
+
+    /// Check whether an account's backed-up session is still logged in.
+    ///
+    /// Restores the backup into the account's browser profile, then has the
+    /// sidecar open the platform in a headless browser on that profile and
+    /// check the login. A logged-out session marks both the auth status and
+    /// the account as expired.
+    pub async fn verify_auth(&self, account_id: &str) -> Result<AuthStatus> {
+        let Some(backup) = self.restore_backup(account_id).await? else {
+            return Ok(AuthStatus::None);
+        };
+
+        let state: LoginStateResponse = self
+            .client
+            .post(format!(
+                "{}/profiles/{}/verify",
+                self.sidecar_url, backup.profile_id
+            ))
+            .json(&serde_json::json!({ "platformId": backup.platform }))
+            .send()
+            .await?
+            .json()
+            .await?;
+        if !state.success {
+            return Err(PubCastError::BrowserAutomation(format!(
+                "Login state unavailable for {}: {}",
+                account_id,
+                state.error.unwrap_or_default()
+            )));
+        }
+
+        let now = Self::now();
+        if state.is_logged_in {
+            sqlx::query(
+                r#"UPDATE accounts SET auth_status = ?, last_auth_sync_at = ?, updated_at = ?
+                   WHERE id = ?"#
+            )
+            .bind(AuthStatus::Authorized.to_string())
+            .bind(now)
+            .bind(now)
+            .bind(account_id)
+            .execute(&self.pool)
+            .await?;
+            Ok(AuthStatus::Authorized)
+        } else {
+            tracing::warn!("Session for account {} has expired", account_id);
+            sqlx::query(
+                r#"UPDATE accounts SET auth_status = ?, status = ?, updated_at = ?
+                   WHERE id = ?"#
+            )
+            .bind(AuthStatus::Expired.to_string())
+            .bind(AccountStatus::Expired.to_string())
+            .bind(now)
+            .bind(account_id)
+            .execute(&self.pool)
+            .await?;
+            Ok(AuthStatus::Expired)
         }
     }
 
+    /// Verify every authorized account, returning how many have expired.
+    ///
+    /// Accounts that can't be checked right now are logged and skipped.
+    pub async fn verify_all_auth(&self) -> Result<usize> {
+        let account_ids: Vec<String> = sqlx::query_scalar(
+            r#"SELECT id FROM accounts
+               WHERE auth_status = 'authorized' AND cookies_backup IS NOT NULL
+                 AND deleted_at IS NULL"#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut expired = 0;
+        for account_id in account_ids {
+            match self.verify_auth(&account_id).await {
+                Ok(AuthStatus::Expired) => expired += 1,
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Could not verify auth for {}: {}", account_id, e),
+            }
+        }
+        Ok(expired)
+    }
+
     /// Export all auth backups for migration
     pub async fn export_all_auth(&self) -> Result<Vec<AuthBackup>> {
         let rows = sqlx::query(
-            r#"SELECT platform, profile_id, cookies_backup, cookies_nonce,
+            r#"SELECT id, platform, profile_id, cookies_backup, cookies_nonce,
                       fingerprint_backup, fingerprint_nonce, last_auth_sync_at
                FROM accounts
-               WHERE auth_status = 'authorized' AND cookies_backup IS NOT NULL"#
+               WHERE auth_status = 'authorized' AND cookies_backup IS NOT NULL
+                 AND deleted_at IS NULL"#
         )
         .fetch_all(&self.pool)
         .await?;
@@ -151,6 +334,7 @@ impl AuthService {
         let mut backups = Vec::new();
 
         for r in rows {
+            let account_id: String = r.get("id");
             let platform: String = r.get("platform");
             let profile_id: Option<String> = r.get("profile_id");
             let cookies_backup: Option<Vec<u8>> = r.get("cookies_backup");
@@ -172,6 +356,7 @@ impl AuthService {
                 };
 
                 backups.push(AuthBackup {
+                    account_id,
                     platform,
                     profile_id: profile_id.unwrap_or_default(),
                     cookies,
@@ -184,49 +369,170 @@ impl AuthService {
         Ok(backups)
     }
 
-    /// Import auth backups from another device
-    pub async fn import_auth(&self, backup: &AuthBackup) -> Result<()> {
-        // First check if account exists, if not create it
-        let existing = sqlx::query("SELECT id FROM accounts WHERE platform = ?")
-            .bind(&backup.platform)
-            .fetch_optional(&self.pool)
-            .await?;
+    /// Check every stored auth backup still decrypts with the current key,
+    /// e.g. before a migration or after a key rotation. Read-only; the
+    /// decrypted data is discarded.
+    pub async fn verify_backups(&self) -> Result<Vec<BackupVerification>> {
+        let rows = sqlx::query(
+            r#"SELECT id, platform, profile_id, cookies_backup, cookies_nonce,
+                      fingerprint_backup, fingerprint_nonce, last_auth_sync_at
+               FROM accounts
+               WHERE cookies_backup IS NOT NULL
+               ORDER BY created_at"#
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-        if existing.is_none() {
-            // Create new account
-            let id = uuid::Uuid::new_v4().to_string();
-            let now = Self::now();
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let account_id: String = r.get("id");
+                let platform: String = r.get("platform");
+                let error = match self.decode_backup(r, &platform, &account_id) {
+                    Ok(Some(_)) => None,
+                    Ok(None) => Some("Backup is missing its nonce".to_string()),
+                    Err(e) => Some(e.to_string()),
+                };
+                BackupVerification {
+                    account_id,
+                    platform,
+                    ok: error.is_none(),
+                    error,
+                }
+            })
+            .collect())
+    }
 
-            sqlx::query(
-                r#"INSERT INTO accounts (id, platform, name, status, created_at, updated_at)
-                   VALUES (?, ?, ?, 'unknown', ?, ?)"#
-            )
-            .bind(&id)
-            .bind(&backup.platform)
-            .bind(&backup.platform)
-            .bind(now)
-            .bind(now)
-            .execute(&self.pool)
-            .await?;
+    /// Export all auth backups encrypted with a key derived from `passphrase`.
+    ///
+    /// Independent of the machine keychain, so the file can be imported on
+    /// another device. Layout: magic + version + material length (1 byte) +
+    /// key material + nonce + ciphertext.
+    pub async fn export_encrypted(&self, passphrase: &str) -> Result<Vec<u8>> {
+        if passphrase.is_empty() {
+            return Err(PubCastError::Validation("Passphrase must not be empty".to_string()));
+        }
+
+        let backups = self.export_all_auth().await?;
+        let plaintext = zeroize::Zeroizing::new(serde_json::to_vec(&backups)?);
+
+        let material = KeyMaterial::generate();
+        let service = EncryptionService::from_material(passphrase.as_bytes(), &material)?;
+        let (ciphertext, nonce) = service.encrypt(&plaintext)?;
+
+        let encoded = material.encode();
+        let mut bytes = Vec::with_capacity(
+            EXPORT_MAGIC.len() + 2 + encoded.len() + nonce.len() + ciphertext.len(),
+        );
+        bytes.extend_from_slice(EXPORT_MAGIC);
+        bytes.push(EXPORT_VERSION);
+        bytes.push(encoded.len() as u8);
+        bytes.extend_from_slice(&encoded);
+        bytes.extend_from_slice(&nonce);
+        bytes.extend_from_slice(&ciphertext);
+
+        tracing::info!("Exported {} auth backups", backups.len());
+        Ok(bytes)
+    }
+
+    /// Import a file produced by [`Self::export_encrypted`], returning the
+    /// number of backups restored
+    pub async fn import_encrypted(&self, bytes: &[u8], passphrase: &str) -> Result<usize> {
+        let invalid = || PubCastError::Validation("Not a PubCast auth export file".to_string());
+
+        let rest = bytes.strip_prefix(EXPORT_MAGIC.as_slice()).ok_or_else(invalid)?;
+        let (&version, rest) = rest.split_first().ok_or_else(invalid)?;
+        if version != EXPORT_VERSION {
+            return Err(PubCastError::Validation(format!(
+                "Unsupported auth export version: {}",
+                version
+            )));
         }
+        let (&material_len, rest) = rest.split_first().ok_or_else(invalid)?;
+        let material_len = material_len as usize;
+        if rest.len() < material_len + NONCE_SIZE {
+            return Err(invalid());
+        }
+        let material = KeyMaterial::decode(&rest[..material_len])?;
+        let (nonce, ciphertext) = rest[material_len..].split_at(NONCE_SIZE);
+
+        let service = EncryptionService::from_material(passphrase.as_bytes(), &material)?;
+        let plaintext = zeroize::Zeroizing::new(service.decrypt(ciphertext, nonce).map_err(|_| {
+            PubCastError::Encryption("Wrong passphrase or corrupted export file".to_string())
+        })?);
+        let backups: Vec<AuthBackup> = serde_json::from_slice(&plaintext)?;
+
+        for backup in &backups {
+            self.import_auth(backup).await?;
+        }
+        tracing::info!("Imported {} auth backups", backups.len());
+        Ok(backups.len())
+    }
+
+    /// Import auth backups from another device
+    pub async fn import_auth(&self, backup: &AuthBackup) -> Result<()> {
+        // Match the exporting account by ID, then by browser profile; never
+        // by platform alone, which would merge different accounts
+        let existing: Option<String> = sqlx::query_scalar(
+            r#"SELECT id FROM accounts
+               WHERE platform = ? AND deleted_at IS NULL AND (id = ? OR profile_id = ?)
+               ORDER BY id = ? DESC
+               LIMIT 1"#,
+        )
+        .bind(&backup.platform)
+        .bind(&backup.account_id)
+        .bind(&backup.profile_id)
+        .bind(&backup.account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let account_id = match existing {
+            Some(id) => id,
+            None => {
+                // Create new account, keeping the exported ID when it is free
+                let taken: Option<String> = sqlx::query_scalar("SELECT id FROM accounts WHERE id = ?")
+                    .bind(&backup.account_id)
+                    .fetch_optional(&self.pool)
+                    .await?;
+                let id = if backup.account_id.is_empty() || taken.is_some() {
+                    uuid::Uuid::new_v4().to_string()
+                } else {
+                    backup.account_id.clone()
+                };
+                let now = Self::now();
+
+                sqlx::query(
+                    r#"INSERT INTO accounts (id, platform, name, status, created_at, updated_at)
+                       VALUES (?, ?, ?, 'unknown', ?, ?)"#
+                )
+                .bind(&id)
+                .bind(&backup.platform)
+                .bind(&backup.platform)
+                .bind(now)
+                .bind(now)
+                .execute(&self.pool)
+                .await?;
+                id
+            }
+        };
 
         // Now backup the auth data
-        self.backup_auth(&backup.platform, &backup.cookies, &backup.fingerprint).await?;
+        self.backup_auth(&account_id, &backup.cookies, &backup.fingerprint).await?;
 
         // Update profile_id
-        sqlx::query("UPDATE accounts SET profile_id = ? WHERE platform = ?")
+        sqlx::query("UPDATE accounts SET profile_id = ? WHERE id = ?")
             .bind(&backup.profile_id)
-            .bind(&backup.platform)
+            .bind(&account_id)
             .execute(&self.pool)
             .await?;
 
         Ok(())
     }
 
-    /// Get auth status for a platform
-    pub async fn get_auth_status(&self, platform: &str) -> Result<AuthStatus> {
-        let row = sqlx::query("SELECT auth_status FROM accounts WHERE platform = ?")
-            .bind(platform)
+    /// Get auth status for an account
+    pub async fn get_auth_status(&self, account_id: &str) -> Result<AuthStatus> {
+        let row = sqlx::query("SELECT auth_status FROM accounts WHERE id = ? AND deleted_at IS NULL")
+            .bind(account_id)
             .fetch_optional(&self.pool)
             .await?;
 
@@ -264,3 +570,290 @@ impl AuthService {
         Ok(())
     }
 }
+
+/// Periodically re-verify stored sessions in the background
+pub fn spawn_auth_monitor(auth_service: Arc<RwLock<AuthService>>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(AUTH_CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        // Skip the immediate first tick; the sidecar is still starting
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            match auth_service.read().await.verify_all_auth().await {
+                Ok(0) => {}
+                Ok(expired) => tracing::warn!("{} account sessions have expired", expired),
+                Err(e) => tracing::error!("Auth verification failed: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn service_with_backup(pool: &SqlitePool, sidecar_url: &str) -> AuthService {
+        sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES ('a1', 'wechat', 'a1', 'active')")
+            .execute(pool)
+            .await
+            .unwrap();
+
+        let salt = EncryptionService::generate_salt();
+        let service = AuthService::new(pool.clone(), EncryptionService::new(b"test_master_key", &salt).unwrap())
+            .with_sidecar_url(sidecar_url);
+        service
+            .backup_auth("a1", &serde_json::json!([{ "name": "sid", "value": "x" }]), &serde_json::json!({}))
+            .await
+            .unwrap();
+        service
+    }
+
+    /// Mock sidecar with no browser open for the account: the cached login
+    /// state is unavailable, so only a real profile check can answer
+    async fn mock_login_state(server: &MockServer, logged_in: bool) {
+        Mock::given(method("GET"))
+            .and(path("/browser/a1/login-state"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": false,
+                "error": "No active browser",
+            })))
+            .mount(server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/profiles/a1/restore"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true })))
+            .expect(1)
+            .mount(server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/profiles/a1/verify"))
+            .and(body_partial_json(serde_json::json!({ "platformId": "wechat" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "profileId": "a1",
+                "isLoggedIn": logged_in,
+            })))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    async fn statuses(pool: &SqlitePool) -> (String, String) {
+        sqlx::query_as("SELECT auth_status, status FROM accounts WHERE id = 'a1'")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verify_auth_marks_logged_out_session_expired() {
+        let (_dir, pool) = init_test_database().await;
+        let server = MockServer::start().await;
+        mock_login_state(&server, false).await;
+        let service = service_with_backup(&pool, &server.uri()).await;
+
+        assert_eq!(service.verify_all_auth().await.unwrap(), 1);
+        assert_eq!(statuses(&pool).await, ("expired".to_string(), "expired".to_string()));
+        assert!(matches!(service.verify_auth("missing").await, Err(PubCastError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_verify_auth_skips_profile_in_use() {
+        let (_dir, pool) = init_test_database().await;
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/profiles/a1/restore"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/profiles/a1/verify"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": false,
+                "error": "Browser in use for this profile",
+            })))
+            .mount(&server)
+            .await;
+        let service = service_with_backup(&pool, &server.uri()).await;
+
+        assert!(matches!(
+            service.verify_auth("a1").await,
+            Err(PubCastError::BrowserAutomation(_))
+        ));
+        // Unknown state is not treated as expired
+        assert_eq!(service.verify_all_auth().await.unwrap(), 0);
+        assert_eq!(statuses(&pool).await, ("authorized".to_string(), "active".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_export_round_trip() {
+        let (_dir, pool) = init_test_database().await;
+        let service = service_with_backup(&pool, "http://127.0.0.1:1").await;
+        let exported = service.export_encrypted("correct horse").await.unwrap();
+        assert!(exported.starts_with(EXPORT_MAGIC));
+
+        // A different device: fresh database and machine key
+        let (_other_dir, other_pool) = init_test_database().await;
+        let salt = EncryptionService::generate_salt();
+        let other = AuthService::new(other_pool, EncryptionService::new(b"other_machine_key", &salt).unwrap());
+
+        assert!(matches!(
+            other.import_encrypted(&exported, "wrong horse").await,
+            Err(PubCastError::Encryption(_))
+        ));
+        assert!(matches!(
+            other.import_encrypted(b"PK\x03\x04", "correct horse").await,
+            Err(PubCastError::Validation(_))
+        ));
+        assert!(other.restore_auth("wechat").await.unwrap().is_none());
+
+        assert_eq!(other.import_encrypted(&exported, "correct horse").await.unwrap(), 1);
+        let restored = other.restore_auth("wechat").await.unwrap().unwrap();
+        assert_eq!(restored.cookies, serde_json::json!([{ "name": "sid", "value": "x" }]));
+        assert!(service.export_encrypted("").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_auth_matches_the_exporting_account() {
+        let (_dir, pool) = init_test_database().await;
+        let service = service_with_backup(&pool, "http://127.0.0.1:1").await;
+        sqlx::query(
+            "INSERT INTO accounts (id, platform, name, status, profile_id) \
+             VALUES ('a2', 'wechat', 'a2', 'active', 'profile-a2')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO accounts (id, platform, name, status, profile_id, deleted_at) \
+             VALUES ('a3', 'wechat', 'a3', 'active', 'profile-a3', 1)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let backup = |account_id: &str, profile_id: &str| AuthBackup {
+            account_id: account_id.to_string(),
+            platform: "wechat".to_string(),
+            profile_id: profile_id.to_string(),
+            cookies: serde_json::json!([{ "name": "sid", "value": account_id }]),
+            fingerprint: serde_json::json!({}),
+            exported_at: 0,
+        };
+        let count = "SELECT COUNT(*) FROM accounts";
+
+        // Matched by account ID, then by profile: a1's backup is left alone
+        service.import_auth(&backup("a2", "profile-a2")).await.unwrap();
+        service.import_auth(&backup("", "profile-a2")).await.unwrap();
+        assert_eq!(sqlx::query_scalar::<_, i64>(count).fetch_one(&pool).await.unwrap(), 3);
+        assert_eq!(service.get_auth_status("a2").await.unwrap(), AuthStatus::Authorized);
+        let exported = service.export_all_auth().await.unwrap();
+        let a1 = exported.iter().find(|b| b.account_id == "a1").unwrap();
+        assert_eq!(a1.cookies[0]["value"], "x");
+
+        // A trashed account is never revived; the backup gets a new account
+        service.import_auth(&backup("a3", "profile-a3")).await.unwrap();
+        assert_eq!(sqlx::query_scalar::<_, i64>(count).fetch_one(&pool).await.unwrap(), 4);
+        assert_eq!(service.get_auth_status("a3").await.unwrap(), AuthStatus::None);
+    }
+
+    #[tokio::test]
+    async fn test_verify_auth_keeps_logged_in_session() {
+        let (_dir, pool) = init_test_database().await;
+        let server = MockServer::start().await;
+        mock_login_state(&server, true).await;
+        let service = service_with_backup(&pool, &server.uri()).await;
+
+        assert_eq!(service.verify_all_auth().await.unwrap(), 0);
+        assert_eq!(statuses(&pool).await, ("authorized".to_string(), "active".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_restore_to_browser_targets_the_account_profile() {
+        let (_dir, pool) = init_test_database().await;
+        let server = MockServer::start().await;
+        let service = service_with_backup(&pool, &server.uri()).await;
+        // A second account on the same platform with its own profile
+        sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES ('a2', 'wechat', 'a2', 'active')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        service
+            .backup_auth("a2", &serde_json::json!([{ "name": "sid", "value": "y" }]), &serde_json::json!({}))
+            .await
+            .unwrap();
+        sqlx::query("UPDATE accounts SET profile_id = 'wechat-second' WHERE id = 'a2'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES ('a3', 'wechat', 'a3', 'active')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for (profile, account, cookie) in [("a1", "a1", "x"), ("wechat-second", "a2", "y")] {
+            Mock::given(method("POST"))
+                .and(path(format!("/profiles/{}/restore", profile)))
+                .and(body_partial_json(serde_json::json!({
+                    "accountId": account,
+                    "platform": "wechat",
+                    "cookies": [{ "name": "sid", "value": cookie }],
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        assert!(service.restore_to_browser("a1").await.unwrap());
+        assert!(service.restore_to_browser("a2").await.unwrap());
+        // No backup, nothing sent
+        assert!(!service.restore_to_browser("a3").await.unwrap());
+        assert!(matches!(
+            service.restore_to_browser("missing").await,
+            Err(PubCastError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_backups_flags_corrupt_ciphertext() {
+        let (_dir, pool) = init_test_database().await;
+        let service = service_with_backup(&pool, "http://127.0.0.1:9").await;
+        sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES ('a2', 'douyin', 'a2', 'active')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        service
+            .backup_auth("a2", &serde_json::json!([{ "name": "sid", "value": "y" }]), &serde_json::json!({}))
+            .await
+            .unwrap();
+        // Flip a ciphertext bit so authentication fails
+        let mut corrupted: Vec<u8> = sqlx::query_scalar("SELECT cookies_backup FROM accounts WHERE id = 'a2'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        corrupted[0] ^= 1;
+        sqlx::query("UPDATE accounts SET cookies_backup = ? WHERE id = 'a2'")
+            .bind(corrupted)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let results = service.verify_backups().await.unwrap();
+        assert_eq!(results.len(), 2);
+        let a1 = results.iter().find(|r| r.account_id == "a1").unwrap();
+        assert!(a1.ok);
+        assert!(a1.error.is_none());
+        let a2 = results.iter().find(|r| r.account_id == "a2").unwrap();
+        assert!(!a2.ok);
+        assert_eq!(a2.platform, "douyin");
+        assert!(a2.error.is_some());
+        // Nothing leaks the plaintext
+        assert!(!serde_json::to_string(&results).unwrap().contains("sid"));
+    }
+}
@@ -1,12 +1,37 @@
 //! Browser automation service
 //! Communicates with the Playwright sidecar for browser automation
 
-use reqwest::Client;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::{AbortHandle, JoinHandle};
+use crate::adapters::SessionProbe;
 use crate::error::{PubCastError, Result};
+use crate::infrastructure::http::HttpClientFactory;
+use crate::infrastructure::http_log;
 use crate::models::Proxy;
 
-const SIDECAR_URL: &str = "http://localhost:8857";
+use super::auth::AuthService;
+use super::sidecar_manager::{sidecar_base_url, RestartHook, DEFAULT_SIDECAR_PORT};
+
+/// Default timeout for a single sidecar request
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Pause before retrying a refused connection (the sidecar may be restarting)
+const CONNECT_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// How often a login watch polls the sidecar's login state
+const LOGIN_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How long a login watch waits for the user to finish logging in
+const LOGIN_WATCH_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Event name for login state changes seen by a login watch, payload is [`LoginStateResponse`]
+pub const LOGIN_STATE_EVENT: &str = "browser://login-state";
+/// Event name for sessions lost when the sidecar restarted, payload is [`SessionsReset`]
+pub const SESSIONS_RESET_EVENT: &str = "browser://sessions-reset";
 
 #[derive(Debug, Clone, Serialize)]
 pub struct LaunchBrowserRequest {
@@ -76,29 +101,181 @@ pub struct SessionInfo {
     pub has_proxy: bool,
 }
 
+/// An active session with its account resolved from the database
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDetail {
+    pub account_id: String,
+    pub launched_at: i64,
+    pub has_proxy: bool,
+    pub platform: Option<String>,
+    pub account_name: Option<String>,
+    /// The session's account was deleted or moved to the trash
+    pub orphaned: bool,
+}
+
+/// Outcome of reconciling our view of open browsers with the sidecar's
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionsReset {
+    /// Accounts we thought had a browser open that the sidecar no longer has, sorted
+    pub stale_account_ids: Vec<String>,
+    /// Accounts with a browser open in the sidecar, sorted
+    pub active_account_ids: Vec<String>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct SessionsResponse {
     pub success: bool,
     pub sessions: Vec<SessionInfo>,
 }
 
+/// Cookies saved in an account's browser profile
+#[derive(Debug, Clone, Deserialize)]
+pub struct CookiesResponse {
+    pub success: bool,
+    #[serde(default)]
+    pub cookies: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Fingerprint saved in an account's browser profile
+#[derive(Debug, Clone, Deserialize)]
+pub struct FingerprintResponse {
+    pub success: bool,
+    #[serde(default)]
+    pub fingerprint: serde_json::Value,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Login state reported by the sidecar's login watcher
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginStateResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    #[serde(default)]
+    pub is_logged_in: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub login_detected_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Set on a login watch's final event when it gave up waiting for the login
+    #[serde(default)]
+    pub timed_out: bool,
+}
+
+#[derive(Clone)]
 pub struct BrowserService {
     client: Client,
     sidecar_url: String,
+    request_timeout: Duration,
+    login_poll_interval: Duration,
+    login_watch_timeout: Duration,
+    login_events: Option<mpsc::UnboundedSender<LoginStateResponse>>,
+    session_events: Option<mpsc::UnboundedSender<SessionsReset>>,
+    /// Running login watches by account, shared between clones
+    login_watches: Arc<Mutex<HashMap<String, AbortHandle>>>,
+    /// Accounts we launched a browser for and haven't closed, shared between clones
+    open_sessions: Arc<Mutex<HashSet<String>>>,
 }
 
 impl BrowserService {
     pub fn new() -> Self {
+        Self::with_port(DEFAULT_SIDECAR_PORT)
+    }
+
+    /// Talk to a sidecar listening on a non-default port
+    pub fn with_port(port: u16) -> Self {
+        Self::with_sidecar_url(sidecar_base_url(port))
+    }
+
+    /// Talk to a sidecar at an explicit base URL
+    pub fn with_sidecar_url(sidecar_url: impl Into<String>) -> Self {
+        Self::with_timeout(sidecar_url, DEFAULT_REQUEST_TIMEOUT)
+    }
+
+    /// Talk to a sidecar at an explicit base URL, giving up on requests after `request_timeout`
+    pub fn with_timeout(sidecar_url: impl Into<String>, request_timeout: Duration) -> Self {
+        // The sidecar is local, so never send it through the global proxy
+        let client = HttpClientFactory::new()
+            .direct_client(request_timeout)
+            .unwrap_or_default();
+
         Self {
-            client: Client::new(),
-            sidecar_url: SIDECAR_URL.to_string(),
+            client,
+            sidecar_url: sidecar_url.into(),
+            request_timeout,
+            login_poll_interval: LOGIN_POLL_INTERVAL,
+            login_watch_timeout: LOGIN_WATCH_TIMEOUT,
+            login_events: None,
+            session_events: None,
+            login_watches: Arc::default(),
+            open_sessions: Arc::default(),
+        }
+    }
+
+    /// Send login state changes seen by login watches to `events`
+    pub fn with_login_event_sender(mut self, events: mpsc::UnboundedSender<LoginStateResponse>) -> Self {
+        self.login_events = Some(events);
+        self
+    }
+
+    /// Send the outcome of each session reconciliation to `events`
+    pub fn with_session_event_sender(mut self, events: mpsc::UnboundedSender<SessionsReset>) -> Self {
+        self.session_events = Some(events);
+        self
+    }
+
+    /// Poll every `poll_interval` during login watches and give up after `timeout`
+    pub fn with_login_watch_timing(mut self, poll_interval: Duration, timeout: Duration) -> Self {
+        self.login_poll_interval = poll_interval;
+        self.login_watch_timeout = timeout;
+        self
+    }
+
+    /// Send a request, retrying once if the connection is refused
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let retry = request.try_clone();
+        match (http_log::send(request).await, retry) {
+            (Err(e), Some(retry)) if e.is_connect() => {
+                tracing::warn!("Sidecar unreachable, retrying: {}", e);
+                tokio::time::sleep(CONNECT_RETRY_DELAY).await;
+                http_log::send(retry).await.map_err(|e| self.request_error(e))
+            }
+            (result, _) => result.map_err(|e| self.request_error(e)),
         }
     }
 
+    /// Tell "sidecar not running" apart from "sidecar slow"
+    fn request_error(&self, e: reqwest::Error) -> PubCastError {
+        if e.is_connect() {
+            PubCastError::BrowserAutomation(format!(
+                "Sidecar is not running at {}: {}",
+                self.sidecar_url, e
+            ))
+        } else if e.is_timeout() {
+            PubCastError::Network(format!(
+                "Sidecar did not respond within {}s",
+                self.request_timeout.as_secs_f32()
+            ))
+        } else {
+            PubCastError::Network(e.to_string())
+        }
+    }
+
+    /// Base URL of the sidecar HTTP API
+    pub fn sidecar_url(&self) -> &str {
+        &self.sidecar_url
+    }
+
     /// Check if sidecar is running
     pub async fn health_check(&self) -> Result<bool> {
         let url = format!("{}/health", self.sidecar_url);
-        match self.client.get(&url).send().await {
+        match http_log::send(self.client.get(&url)).await {
             Ok(resp) => Ok(resp.status().is_success()),
             Err(_) => Ok(false),
         }
@@ -121,17 +298,16 @@ impl BrowserService {
             headless,
         };
 
-        let response = self.client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| PubCastError::Network(e.to_string()))?;
+        let response = self.send(self.client.post(&url).json(&request)).await?;
 
-        response
+        let result: BrowserResponse = response
             .json()
             .await
-            .map_err(|e| PubCastError::Network(e.to_string()))
+            .map_err(|e| PubCastError::Network(e.to_string()))?;
+        if result.success {
+            self.open_sessions.lock().unwrap().insert(account_id.to_string());
+        }
+        Ok(result)
     }
 
     /// Navigate to a URL
@@ -143,12 +319,7 @@ impl BrowserService {
             url: url.to_string(),
         };
 
-        let response = self.client
-            .post(&api_url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| PubCastError::Network(e.to_string()))?;
+        let response = self.send(self.client.post(&api_url).json(&request)).await?;
 
         response
             .json()
@@ -160,11 +331,7 @@ impl BrowserService {
     pub async fn get_page_info(&self, account_id: &str) -> Result<PageInfoResponse> {
         let url = format!("{}/browser/{}/info", self.sidecar_url, account_id);
 
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| PubCastError::Network(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
 
         response
             .json()
@@ -176,11 +343,7 @@ impl BrowserService {
     pub async fn save_session(&self, account_id: &str) -> Result<BrowserResponse> {
         let url = format!("{}/browser/{}/save", self.sidecar_url, account_id);
 
-        let response = self.client
-            .post(&url)
-            .send()
-            .await
-            .map_err(|e| PubCastError::Network(e.to_string()))?;
+        let response = self.send(self.client.post(&url)).await?;
 
         response
             .json()
@@ -192,28 +355,223 @@ impl BrowserService {
     pub async fn close_browser(&self, account_id: &str) -> Result<BrowserResponse> {
         let url = format!("{}/browser/{}/close", self.sidecar_url, account_id);
 
-        let response = self.client
+        let request = self.client
             .post(&url)
-            .json(&serde_json::json!({ "saveSession": true }))
-            .send()
+            .json(&serde_json::json!({ "saveSession": true }));
+        let response = self.send(request).await?;
+
+        let result: BrowserResponse = response
+            .json()
+            .await
+            .map_err(|e| PubCastError::Network(e.to_string()))?;
+        if result.success {
+            self.open_sessions.lock().unwrap().remove(account_id);
+        }
+        Ok(result)
+    }
+
+    /// Capture the account's browser viewport as PNG bytes
+    pub async fn screenshot(&self, account_id: &str) -> Result<Vec<u8>> {
+        let url = format!("{}/browser/{}/screenshot", self.sidecar_url, account_id);
+
+        let response = self.send(self.client.get(&url)).await?;
+
+        match response.status() {
+            status if status.is_success() => {}
+            reqwest::StatusCode::NOT_FOUND => {
+                return Err(PubCastError::NotFound(format!(
+                    "No active browser session for account {}",
+                    account_id
+                )))
+            }
+            status => {
+                return Err(PubCastError::BrowserAutomation(format!(
+                    "Screenshot failed: HTTP {}",
+                    status
+                )))
+            }
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| PubCastError::Network(e.to_string()))?;
+        Ok(bytes.to_vec())
+    }
+
+    /// Get the cookies saved in an account's browser profile
+    pub async fn get_cookies(&self, account_id: &str) -> Result<Vec<serde_json::Value>> {
+        let url = format!("{}/platforms/{}/cookies", self.sidecar_url, account_id);
+
+        let response = self.send(self.client.get(&url)).await?;
+
+        let result: CookiesResponse = response
+            .json()
             .await
             .map_err(|e| PubCastError::Network(e.to_string()))?;
 
+        if !result.success {
+            return Err(PubCastError::BrowserAutomation(format!(
+                "Failed to read cookies: {}",
+                result.error.unwrap_or_default()
+            )));
+        }
+        Ok(result.cookies)
+    }
+
+    /// Get the fingerprint saved in an account's browser profile
+    pub async fn get_fingerprint(&self, account_id: &str) -> Result<serde_json::Value> {
+        let url = format!("{}/platforms/{}/fingerprint", self.sidecar_url, account_id);
+
+        let response = self.send(self.client.get(&url)).await?;
+
+        let result: FingerprintResponse = response
+            .json()
+            .await
+            .map_err(|e| PubCastError::Network(e.to_string()))?;
+
+        if !result.success {
+            return Err(PubCastError::BrowserAutomation(format!(
+                "Failed to read fingerprint: {}",
+                result.error.unwrap_or_default()
+            )));
+        }
+        Ok(result.fingerprint)
+    }
+
+    /// Get the login state the sidecar's login watcher last saw for an account
+    pub async fn get_login_state(&self, account_id: &str) -> Result<LoginStateResponse> {
+        let url = format!("{}/browser/{}/login-state", self.sidecar_url, account_id);
+
+        let response = self.send(self.client.get(&url)).await?;
+
         response
             .json()
             .await
             .map_err(|e| PubCastError::Network(e.to_string()))
     }
 
+    /// Poll an account's login state until it logs in, then save its session.
+    ///
+    /// Each change in login state is sent to the login event sender. Failed polls are
+    /// retried until the watch timeout, after which it gives up with an error. A watch
+    /// that ends without a saved login sends a final event carrying the error and
+    /// whether it timed out.
+    pub async fn watch_login(&self, account_id: &str) -> Result<LoginStateResponse> {
+        let deadline = tokio::time::Instant::now() + self.login_watch_timeout;
+        let mut last_logged_in = None;
+        let mut last_error = None;
+
+        loop {
+            match self.get_login_state(account_id).await {
+                Ok(mut state) => {
+                    last_error = None;
+                    state.account_id = Some(account_id.to_string());
+
+                    if last_logged_in != Some(state.is_logged_in) {
+                        last_logged_in = Some(state.is_logged_in);
+                        self.emit_login_state(state.clone());
+                    }
+
+                    if state.is_logged_in {
+                        if let Err(e) = self.save_session(account_id).await {
+                            self.emit_login_state(LoginStateResponse {
+                                success: false,
+                                error: Some(e.to_string()),
+                                ..state
+                            });
+                            return Err(e);
+                        }
+                        return Ok(state);
+                    }
+                }
+                // The sidecar may be restarting; keep polling until the deadline
+                Err(e) => {
+                    tracing::debug!(
+                        "Failed to poll login state for account {}: {}",
+                        account_id,
+                        e
+                    );
+                    last_error = Some(e);
+                }
+            }
+
+            if tokio::time::Instant::now() + self.login_poll_interval > deadline {
+                let error = last_error.unwrap_or_else(|| {
+                    PubCastError::BrowserAutomation(format!(
+                        "Account {} did not log in within {}s",
+                        account_id,
+                        self.login_watch_timeout.as_secs()
+                    ))
+                });
+                self.emit_login_state(LoginStateResponse {
+                    success: false,
+                    account_id: Some(account_id.to_string()),
+                    is_logged_in: false,
+                    login_detected_at: None,
+                    error: Some(error.to_string()),
+                    timed_out: true,
+                });
+                return Err(error);
+            }
+            tokio::time::sleep(self.login_poll_interval).await;
+        }
+    }
+
+    fn emit_login_state(&self, state: LoginStateResponse) {
+        if let Some(events) = &self.login_events {
+            // The receiver is gone when the app is shutting down
+            let _ = events.send(state);
+        }
+    }
+
+    /// Watch an account's login in the background and back up its auth once it logs in.
+    ///
+    /// Replaces any login watch already running for the account.
+    pub fn start_login_watch(
+        &self,
+        auth_service: Arc<RwLock<AuthService>>,
+        account_id: &str,
+    ) -> JoinHandle<()> {
+        let service = self.clone();
+        let account_id = account_id.to_string();
+        let mut watches = self.login_watches.lock().unwrap();
+        watches.retain(|_, watch| !watch.is_finished());
+        if let Some(previous) = watches.remove(&account_id) {
+            previous.abort();
+        }
+
+        let key = account_id.clone();
+        let handle = tokio::spawn(async move {
+            if let Err(e) = service.watch_login(&account_id).await {
+                tracing::warn!("Login watch for account {} stopped: {}", account_id, e);
+                return;
+            }
+            match auth_service.read().await.backup_from_browser(&service, &account_id).await {
+                Ok(()) => tracing::info!("Backed up auth for account {} after login", account_id),
+                Err(e) => tracing::error!("Failed to back up auth for account {}: {}", account_id, e),
+            }
+        });
+        watches.insert(key, handle.abort_handle());
+        handle
+    }
+
+    /// Stop an account's login watch, returning whether one was running
+    pub fn stop_login_watch(&self, account_id: &str) -> bool {
+        match self.login_watches.lock().unwrap().remove(account_id) {
+            Some(watch) if !watch.is_finished() => {
+                watch.abort();
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Get all active sessions
     pub async fn get_sessions(&self) -> Result<Vec<SessionInfo>> {
         let url = format!("{}/sessions", self.sidecar_url);
 
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| PubCastError::Network(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
 
         let result: SessionsResponse = response
             .json()
@@ -227,15 +585,428 @@ impl BrowserService {
     pub async fn close_all(&self) -> Result<BrowserResponse> {
         let url = format!("{}/browser/close-all", self.sidecar_url);
 
-        let response = self.client
-            .post(&url)
-            .send()
+        let response = self.send(self.client.post(&url)).await?;
+
+        let result: BrowserResponse = response
+            .json()
             .await
             .map_err(|e| PubCastError::Network(e.to_string()))?;
+        if result.success {
+            self.open_sessions.lock().unwrap().clear();
+        }
+        Ok(result)
+    }
 
-        response
-            .json()
+    /// Reconcile the browsers we think are open with the sidecar's sessions,
+    /// e.g. after it restarted and lost them.
+    ///
+    /// Stale accounts are forgotten and their login watches stopped. The
+    /// outcome is sent to the session event sender.
+    pub async fn reconcile_sessions(&self) -> Result<SessionsReset> {
+        let active: HashSet<String> = self
+            .get_sessions()
+            .await?
+            .into_iter()
+            .map(|session| session.account_id)
+            .collect();
+
+        let mut stale_account_ids: Vec<String> = {
+            let mut open = self.open_sessions.lock().unwrap();
+            let stale = open.difference(&active).cloned().collect();
+            *open = active.clone();
+            stale
+        };
+        stale_account_ids.sort_unstable();
+        for account_id in &stale_account_ids {
+            self.stop_login_watch(account_id);
+        }
+        if !stale_account_ids.is_empty() {
+            tracing::warn!(
+                "Sidecar lost browser sessions for accounts: {}",
+                stale_account_ids.join(", ")
+            );
+        }
+
+        let mut active_account_ids: Vec<String> = active.into_iter().collect();
+        active_account_ids.sort_unstable();
+        let reset = SessionsReset {
+            stale_account_ids,
+            active_account_ids,
+        };
+        if let Some(events) = &self.session_events {
+            let _ = events.send(reset.clone());
+        }
+        Ok(reset)
+    }
+}
+
+/// Reconcile browser sessions once the sidecar is back up after a restart
+#[async_trait]
+impl RestartHook for BrowserService {
+    async fn after_restart(&self) {
+        if let Err(e) = self.reconcile_sessions().await {
+            tracing::warn!("Failed to reconcile browser sessions after restart: {}", e);
+        }
+    }
+}
+
+/// Auth checks for browser-automation adapters ask the sidecar whether the
+/// account's restored session is still logged in
+#[async_trait]
+impl SessionProbe for BrowserService {
+    async fn is_logged_in(&self, account_id: &str) -> Result<bool> {
+        Ok(self.get_login_state(account_id).await?.is_logged_in)
+    }
+}
+
+/// Forward login watch state changes to the frontend as `browser://login-state`
+pub fn spawn_login_event_forwarder(
+    app_handle: tauri::AppHandle,
+    mut events: mpsc::UnboundedReceiver<LoginStateResponse>,
+) {
+    use tauri::Emitter;
+
+    tokio::spawn(async move {
+        while let Some(state) = events.recv().await {
+            if let Err(e) = app_handle.emit(LOGIN_STATE_EVENT, &state) {
+                tracing::warn!("Failed to emit login state event: {}", e);
+            }
+        }
+    });
+}
+
+/// Forward session reconciliation outcomes to the frontend as `browser://sessions-reset`
+pub fn spawn_sessions_reset_forwarder(
+    app_handle: tauri::AppHandle,
+    mut events: mpsc::UnboundedReceiver<SessionsReset>,
+) {
+    use tauri::Emitter;
+
+    tokio::spawn(async move {
+        while let Some(reset) = events.recv().await {
+            if let Err(e) = app_handle.emit(SESSIONS_RESET_EVENT, &reset) {
+                tracing::warn!("Failed to emit sessions reset event: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+    use crate::infrastructure::encryption::EncryptionService;
+    use crate::models::account::AuthStatus;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+    #[tokio::test]
+    async fn test_get_cookies_and_fingerprint_unwrap_the_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/platforms/a1/cookies"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "cookies": [{ "name": "sid", "value": "abc", "domain": ".qq.com" }],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/platforms/a1/fingerprint"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "fingerprint": { "userAgent": "Mozilla/5.0" },
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/platforms/broken/cookies"))
+            .respond_with(ResponseTemplate::new(500).set_body_json(serde_json::json!({
+                "success": false,
+                "error": "EACCES",
+            })))
+            .mount(&server)
+            .await;
+
+        let service = BrowserService::with_sidecar_url(server.uri());
+        let cookies = service.get_cookies("a1").await.unwrap();
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0]["name"], "sid");
+        assert_eq!(
+            service.get_fingerprint("a1").await.unwrap(),
+            serde_json::json!({ "userAgent": "Mozilla/5.0" })
+        );
+        assert!(matches!(
+            service.get_cookies("broken").await,
+            Err(PubCastError::BrowserAutomation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_slow_sidecar_times_out() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/sessions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "success": true, "sessions": [] }))
+                    .set_delay(Duration::from_secs(2)),
+            )
+            .mount(&server)
+            .await;
+
+        let service = BrowserService::with_timeout(server.uri(), Duration::from_millis(200));
+        match service.get_sessions().await {
+            Err(PubCastError::Network(message)) => assert!(message.contains("did not respond")),
+            other => panic!("expected timeout, got {:?}", other.map(|s| s.len())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refused_connection_reports_sidecar_not_running() {
+        // Reserve a port, then free it so nothing is listening
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let service = BrowserService::with_port(port);
+        let started = std::time::Instant::now();
+        assert!(matches!(
+            service.get_sessions().await,
+            Err(PubCastError::BrowserAutomation(_))
+        ));
+        // One retry after the back-off
+        assert!(started.elapsed() >= CONNECT_RETRY_DELAY);
+    }
+
+    #[tokio::test]
+    async fn test_screenshot_returns_png_bytes() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/browser/a1/screenshot"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(PNG_HEADER, "image/png"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/browser/missing/screenshot"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "success": false,
+                "error": "No active browser for this account",
+            })))
+            .mount(&server)
+            .await;
+
+        let service = BrowserService::with_sidecar_url(server.uri());
+        assert_eq!(service.screenshot("a1").await.unwrap(), PNG_HEADER);
+        assert!(matches!(
+            service.screenshot("missing").await,
+            Err(PubCastError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_forgets_sessions_lost_in_restart() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/browser/launch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        // The restarted sidecar has no sessions
+        Mock::given(method("GET"))
+            .and(path("/sessions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "sessions": [],
+            })))
+            .mount(&server)
+            .await;
+
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let service = BrowserService::with_sidecar_url(server.uri()).with_session_event_sender(events_tx);
+        for account_id in ["a2", "a1"] {
+            service.launch_browser(account_id, "weibo", None, true).await.unwrap();
+        }
+
+        // Runs through the restart hook like a real sidecar restart
+        RestartHook::after_restart(&service).await;
+        let reset = events_rx.recv().await.unwrap();
+        assert_eq!(reset.stale_account_ids, ["a1", "a2"]);
+        assert!(reset.active_account_ids.is_empty());
+
+        // Nothing is left to reset the second time
+        let reset = service.reconcile_sessions().await.unwrap();
+        assert!(reset.stale_account_ids.is_empty());
+        assert!(events_rx.recv().await.unwrap().stale_account_ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_login_watch_saves_and_backs_up_once_logged_in() {
+        let server = MockServer::start().await;
+        // Logged out for the first three polls, then logged in
+        Mock::given(method("GET"))
+            .and(path("/browser/a1/login-state"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "isLoggedIn": false,
+            })))
+            .up_to_n_times(3)
+            .expect(3)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/browser/a1/login-state"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "isLoggedIn": true,
+                "loginDetectedAt": 1700000000,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/browser/a1/save"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/platforms/a1/cookies"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "cookies": [{ "name": "sid", "value": "abc" }],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/platforms/a1/fingerprint"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "fingerprint": { "userAgent": "Mozilla/5.0" },
+            })))
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES ('a1', 'wechat', 'a1', 'active')")
+            .execute(&pool)
             .await
-            .map_err(|e| PubCastError::Network(e.to_string()))
+            .unwrap();
+        let salt = EncryptionService::generate_salt();
+        let auth_service = Arc::new(RwLock::new(AuthService::new(
+            pool.clone(),
+            EncryptionService::new(b"test_master_key", &salt).unwrap(),
+        )));
+
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let service = BrowserService::with_sidecar_url(server.uri())
+            .with_login_event_sender(events_tx)
+            .with_login_watch_timing(Duration::from_millis(10), Duration::from_secs(5));
+        service.start_login_watch(auth_service.clone(), "a1").await.unwrap();
+
+        // Only the changes are emitted, not every poll
+        let logged_out = events_rx.recv().await.unwrap();
+        assert_eq!(logged_out.account_id.as_deref(), Some("a1"));
+        assert!(!logged_out.is_logged_in);
+        let logged_in = events_rx.recv().await.unwrap();
+        assert!(logged_in.is_logged_in);
+        assert_eq!(logged_in.login_detected_at, Some(1700000000));
+        assert!(events_rx.try_recv().is_err());
+
+        assert_eq!(
+            auth_service.read().await.get_auth_status("a1").await.unwrap(),
+            AuthStatus::Authorized
+        );
+        assert!(!service.stop_login_watch("a1"));
+    }
+
+    #[tokio::test]
+    async fn test_login_watch_times_out_without_login() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/browser/a1/login-state"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "isLoggedIn": false,
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/browser/a1/save"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true })))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let service = BrowserService::with_sidecar_url(server.uri())
+            .with_login_event_sender(events_tx)
+            .with_login_watch_timing(Duration::from_millis(10), Duration::from_millis(50));
+        assert!(matches!(
+            service.watch_login("a1").await,
+            Err(PubCastError::BrowserAutomation(_))
+        ));
+
+        assert!(!events_rx.recv().await.unwrap().timed_out);
+        let last = events_rx.recv().await.unwrap();
+        assert!(last.timed_out);
+        assert!(!last.success);
+        assert!(last.error.is_some());
+        assert!(events_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_login_watch_retries_failed_polls() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/browser/a1/login-state"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/browser/a1/login-state"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "isLoggedIn": true,
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/browser/a1/save"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let service = BrowserService::with_sidecar_url(server.uri())
+            .with_login_watch_timing(Duration::from_millis(10), Duration::from_secs(5));
+        assert!(service.watch_login("a1").await.unwrap().is_logged_in);
+    }
+
+    #[tokio::test]
+    async fn test_login_watch_reports_the_last_error_at_the_deadline() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/browser/a1/login-state"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let service = BrowserService::with_sidecar_url(server.uri())
+            .with_login_event_sender(events_tx)
+            .with_login_watch_timing(Duration::from_millis(10), Duration::from_millis(50));
+        assert!(service.watch_login("a1").await.is_err());
+
+        let last = events_rx.recv().await.unwrap();
+        assert_eq!(last.account_id.as_deref(), Some("a1"));
+        assert!(last.timed_out);
+        assert!(last.error.is_some());
+        assert!(events_rx.try_recv().is_err());
     }
 }
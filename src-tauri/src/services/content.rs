@@ -2,17 +2,40 @@
 //!
 //! Handles local content storage and remote API synchronization.
 
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 use sqlx::SqlitePool;
+use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use crate::adapters::{AdapterRegistry, PreparedContent};
 use crate::error::{PubCastError, Result};
-use crate::models::{Content, ContentStatus, RemoteContent, RemoteContentListResponse};
+use crate::infrastructure::encryption::EncryptionService;
+use crate::infrastructure::http::HttpClientFactory;
+use crate::infrastructure::http_log;
+use crate::models::{
+    ConflictResolution, Content, ContentListQuery, ContentListResponse, ContentStatus,
+    CreateContentRequest, DuplicateContentGroup, ProxyStrategy, RemoteContent,
+    RemoteContentListResponse, UpdateContentRequest,
+};
+use crate::services::{ProxyService, SettingsService};
 
 /// Content API client configuration
 #[derive(Debug, Clone)]
 pub struct ContentApiConfig {
     pub base_url: String,
     pub api_key: Option<String>,
+    /// Send `If-None-Match`/`If-Modified-Since` on sync; disable for servers
+    /// that mishandle conditional requests
+    pub conditional_requests: bool,
+    /// Retries after the first attempt for transient failures (network, 5xx, 429)
+    pub max_retries: u32,
+    /// Base delay for exponential backoff between retries
+    pub retry_base_delay_ms: u64,
 }
 
 impl Default for ContentApiConfig {
@@ -20,42 +43,435 @@ impl Default for ContentApiConfig {
         Self {
             base_url: "http://localhost:3001/api/v1".to_string(),
             api_key: None,
+            conditional_requests: true,
+            max_retries: 3,
+            retry_base_delay_ms: 500,
+        }
+    }
+}
+
+impl ContentApiConfig {
+    /// Defaults overridden by the URL and key saved with [`ContentApiConfig::save`]
+    pub async fn load(pool: &SqlitePool, encryption: &EncryptionService) -> Result<Self> {
+        let row = sqlx::query!(
+            "SELECT base_url, api_key_encrypted, api_key_nonce FROM content_api_config WHERE id = 1"
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(Self::default());
+        };
+        let api_key = match (row.api_key_encrypted, row.api_key_nonce) {
+            (Some(ciphertext), Some(nonce)) => {
+                let plaintext = encryption.decrypt(&ciphertext, &nonce)?;
+                Some(String::from_utf8(plaintext).map_err(|e| PubCastError::Encryption(e.to_string()))?)
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            base_url: row.base_url,
+            api_key,
+            ..Self::default()
+        })
+    }
+
+    /// Persist the base URL and API key, encrypting the key
+    pub async fn save(&self, pool: &SqlitePool, encryption: &EncryptionService) -> Result<()> {
+        let (api_key_encrypted, api_key_nonce) = match &self.api_key {
+            Some(key) => {
+                let (ciphertext, nonce) = encryption.encrypt(key.as_bytes())?;
+                (Some(ciphertext), Some(nonce))
+            }
+            None => (None, None),
+        };
+        let now = chrono::Utc::now().timestamp();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO content_api_config (id, base_url, api_key_encrypted, api_key_nonce, updated_at)
+            VALUES (1, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                base_url = excluded.base_url,
+                api_key_encrypted = excluded.api_key_encrypted,
+                api_key_nonce = excluded.api_key_nonce,
+                updated_at = excluded.updated_at
+            "#,
+            self.base_url,
+            api_key_encrypted,
+            api_key_nonce,
+            now
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Normalize a user-entered CMS base URL, rejecting anything but absolute
+/// http(s) URLs without query or fragment
+pub fn validate_base_url(base_url: &str) -> Result<String> {
+    let url = reqwest::Url::parse(base_url.trim())
+        .map_err(|e| PubCastError::Validation(format!("Invalid CMS URL {:?}: {}", base_url, e)))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(PubCastError::Validation(format!(
+            "CMS URL must be an http(s) address: {}",
+            base_url
+        )));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(PubCastError::Validation(format!(
+            "CMS URL must not include a query or fragment: {}",
+            base_url
+        )));
+    }
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
+/// CMS API settings safe to show in the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentApiSettings {
+    pub base_url: String,
+    /// Whether a key is stored; the key itself is never returned
+    pub has_api_key: bool,
+}
+
+/// HTTP cache validators returned by the CMS for a content listing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CacheValidators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let get = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+
+        Self {
+            etag: get(reqwest::header::ETAG),
+            last_modified: get(reqwest::header::LAST_MODIFIED),
         }
     }
+
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// Result of a (possibly conditional) listing fetch
+enum FetchOutcome {
+    NotModified,
+    Modified(RemoteContentListResponse, CacheValidators),
+}
+
+/// Page size used when the caller does not specify one
+const DEFAULT_PAGE_SIZE: i32 = 50;
+
+/// Upper bound on the page size a caller may request
+const MAX_PAGE_SIZE: i32 = 200;
+
+/// Maximum backoff between CMS retries, including server-provided Retry-After
+const MAX_RETRY_DELAY_MS: u64 = 60_000;
+
+/// Largest cover image we are willing to cache locally
+const MAX_COVER_BYTES: usize = 10 * 1024 * 1024;
+
+/// Setting with a proxy id to always use for CMS requests
+pub const CMS_PROXY_ID_KEY: &str = "content.proxy_id";
+/// Setting with a [`ProxyStrategy`] for picking a CMS proxy from the pool
+pub const CMS_PROXY_STRATEGY_KEY: &str = "content.proxy_strategy";
+/// Setting allowing CMS requests to go direct when the proxy fails
+pub const CMS_PROXY_FALLBACK_KEY: &str = "content.proxy_fallback_direct";
+/// Settings that decide the CMS proxy, see [`select_cms_proxy`]
+pub const CMS_PROXY_KEYS: &[&str] = &[
+    CMS_PROXY_ID_KEY,
+    CMS_PROXY_STRATEGY_KEY,
+    CMS_PROXY_FALLBACK_KEY,
+];
+/// Setting enabling duplicate detection while syncing, on by default
+pub const DETECT_DUPLICATES_KEY: &str = "content.detect_duplicates";
+
+/// Hash of the normalized title and body: case-folded with whitespace runs
+/// collapsed, so re-imports differing only in formatting match
+pub fn content_hash(title: &str, body: Option<&str>) -> String {
+    let normalize = |text: &str| {
+        text.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(normalize(title));
+    hasher.update("\n");
+    hasher.update(normalize(body.unwrap_or_default()));
+    format!("{:x}", hasher.finalize())
+}
+
+/// Proxy chosen for CMS requests
+#[derive(Debug, Clone)]
+pub struct CmsProxy {
+    pub proxy_id: String,
+    /// Connect URL including credentials
+    pub url: String,
+    /// Retry without the proxy when it cannot be reached
+    pub fallback_direct: bool,
+}
+
+/// Pick the CMS proxy from settings.
+///
+/// A fixed `content.proxy_id` wins over `content.proxy_strategy`; with
+/// neither set, or no healthy proxy for the strategy, requests go direct.
+/// A fixed proxy that has since been deleted is skipped with a warning.
+pub async fn select_cms_proxy(
+    settings: &SettingsService,
+    proxies: &ProxyService,
+) -> Result<Option<CmsProxy>> {
+    let pinned = match settings.get::<String>(CMS_PROXY_ID_KEY).await? {
+        Some(id) => match proxies.get_proxy(&id).await {
+            Ok(proxy) => Some(proxy),
+            Err(PubCastError::NotFound(_)) => {
+                tracing::warn!("CMS proxy {} no longer exists, ignoring it", id);
+                None
+            }
+            Err(e) => return Err(e),
+        },
+        None => None,
+    };
+
+    let proxy = if pinned.is_some() {
+        pinned
+    } else if let Some(strategy) = settings.get::<String>(CMS_PROXY_STRATEGY_KEY).await? {
+        let strategy: ProxyStrategy = strategy.parse().map_err(PubCastError::Validation)?;
+        let selected = proxies.select_proxy(&strategy).await?;
+        if selected.is_none() {
+            tracing::warn!("No healthy proxy for CMS requests, connecting directly");
+        }
+        selected
+    } else {
+        None
+    };
+
+    let Some(proxy) = proxy else {
+        return Ok(None);
+    };
+
+    Ok(Some(CmsProxy {
+        url: proxies.connect_url(&proxy).await?,
+        proxy_id: proxy.id,
+        fallback_direct: settings.get_or(CMS_PROXY_FALLBACK_KEY, false).await?,
+    }))
+}
+
+/// Select the CMS proxy from settings and apply it to `content`, holding
+/// the content service's write lock only while its clients are swapped
+pub async fn apply_cms_proxy(
+    settings: &RwLock<SettingsService>,
+    proxies: &RwLock<ProxyService>,
+    content: &RwLock<ContentService>,
+) -> Result<()> {
+    let proxy = {
+        let settings = settings.read().await;
+        let proxies = proxies.read().await;
+        select_cms_proxy(&settings, &proxies).await?
+    };
+    content.write().await.set_proxy(proxy)
 }
 
 /// Content service for managing local content and syncing with remote API
 pub struct ContentService {
     pool: SqlitePool,
+    http: HttpClientFactory,
     http_client: reqwest::Client,
     api_config: ContentApiConfig,
+    covers_dir: Option<PathBuf>,
+    proxy_id: Option<String>,
+    /// Client without the CMS proxy, set when falling back is allowed
+    fallback_client: Option<reqwest::Client>,
+    fell_back: AtomicBool,
 }
 
 impl ContentService {
     /// Create a new content service
     pub fn new(pool: SqlitePool, api_config: ContentApiConfig) -> Self {
-        let http_client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
-
+        let http = HttpClientFactory::new();
         Self {
             pool,
-            http_client,
+            http_client: Self::build_client(&http),
+            http,
             api_config,
+            covers_dir: None,
+            proxy_id: None,
+            fallback_client: None,
+            fell_back: AtomicBool::new(false),
+        }
+    }
+
+    /// Make CMS requests with clients from `http`, e.g. to go through the global proxy
+    pub fn with_http(mut self, http: &HttpClientFactory) -> Self {
+        self.http_client = Self::build_client(http);
+        self.http = http.clone();
+        self
+    }
+
+    /// Send CMS requests through `proxy`, or with the default client for `None`
+    pub fn set_proxy(&mut self, proxy: Option<CmsProxy>) -> Result<()> {
+        match proxy {
+            Some(proxy) => {
+                self.http_client = self
+                    .http
+                    .client_with_proxy(&proxy.url, self.http.timeout())?;
+                self.fallback_client = if proxy.fallback_direct {
+                    Some(self.http.client()?)
+                } else {
+                    None
+                };
+                tracing::info!("CMS requests go through proxy {}", proxy.proxy_id);
+                self.proxy_id = Some(proxy.proxy_id);
+            }
+            None => {
+                self.http_client = self.http.client()?;
+                self.fallback_client = None;
+                self.proxy_id = None;
+            }
+        }
+        self.fell_back.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Client for the next CMS request, honouring an earlier proxy fallback
+    fn client(&self) -> &reqwest::Client {
+        match &self.fallback_client {
+            Some(client) if self.fell_back.load(Ordering::Relaxed) => client,
+            _ => &self.http_client,
+        }
+    }
+
+    /// Switch to the fallback client after the proxy could not be reached.
+    ///
+    /// Returns `false` if falling back is disabled or already happened.
+    fn fall_back_to_direct(&self, error: &reqwest::Error) -> bool {
+        if self.fallback_client.is_none() || !error.is_connect() {
+            return false;
+        }
+        if self.fell_back.swap(true, Ordering::Relaxed) {
+            return false;
         }
+        tracing::warn!(
+            "CMS proxy {} failed, connecting directly: {}",
+            self.proxy_id.as_deref().unwrap_or_default(),
+            error
+        );
+        true
+    }
+
+    fn build_client(http: &HttpClientFactory) -> reqwest::Client {
+        http.client().expect("Failed to create HTTP client")
+    }
+
+    /// URL and key status of the current API configuration
+    pub fn api_settings(&self) -> ContentApiSettings {
+        ContentApiSettings {
+            base_url: self.api_config.base_url.clone(),
+            has_api_key: self.api_config.api_key.is_some(),
+        }
+    }
+
+    /// Point the service at a different API; takes effect on the next request
+    pub fn set_api_config(&mut self, api_config: ContentApiConfig) {
+        self.api_config = api_config;
     }
 
-    /// List all local contents
-    pub async fn list_contents(&self) -> Result<Vec<Content>> {
+    /// Validate, persist and switch to a new base URL and API key.
+    ///
+    /// `api_key` of `None` keeps the stored key and an empty string removes it.
+    pub async fn configure_api(
+        &mut self,
+        encryption: &EncryptionService,
+        base_url: &str,
+        api_key: Option<&str>,
+    ) -> Result<ContentApiSettings> {
+        let mut config = ContentApiConfig {
+            base_url: validate_base_url(base_url)?,
+            ..self.api_config.clone()
+        };
+        if let Some(api_key) = api_key.map(str::trim) {
+            config.api_key = (!api_key.is_empty()).then(|| api_key.to_string());
+        }
+
+        config.save(&self.pool, encryption).await?;
+        self.set_api_config(config);
+        tracing::info!("CMS API set to {}", self.api_config.base_url);
+        Ok(self.api_settings())
+    }
+
+    /// Cache remote cover images under `dir` during sync
+    pub fn with_covers_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.covers_dir = Some(dir.into());
+        self
+    }
+
+    /// List local contents a page at a time.
+    ///
+    /// Soft-deleted contents are hidden unless explicitly requested through the
+    /// status filter. `search` matches against title and body.
+    pub async fn list_contents(&self, query: &ContentListQuery) -> Result<ContentListResponse> {
+        let page = query.page.unwrap_or(1).max(1);
+        let per_page = query
+            .per_page
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+        let offset = i64::from(page - 1) * i64::from(per_page);
+
+        let status = query.status.as_ref().map(|s| s.to_string());
+        let pattern = query
+            .search
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| format!("%{}%", escape_like(s)));
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "total!: i64"
+            FROM contents
+            WHERE ((? IS NULL AND status != 'deleted') OR status = ?)
+              AND (? IS NULL OR title LIKE ? ESCAPE '\' OR body LIKE ? ESCAPE '\')
+            "#,
+            status,
+            status,
+            pattern,
+            pattern,
+            pattern
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
         let rows = sqlx::query!(
             r#"
             SELECT id, remote_id, title, body, cover_image_url, cover_image_local,
                    tags, category, author, source_url, status, remote_status,
-                   remote_updated_at, local_updated_at, metadata, created_at, updated_at
+                   remote_updated_at, local_updated_at, metadata, sync_conflict,
+                   created_at, updated_at
             FROM contents
-            ORDER BY updated_at DESC
-            "#
+            WHERE ((? IS NULL AND status != 'deleted') OR status = ?)
+              AND (? IS NULL OR title LIKE ? ESCAPE '\' OR body LIKE ? ESCAPE '\')
+            ORDER BY updated_at DESC, id
+            LIMIT ? OFFSET ?
+            "#,
+            status,
+            status,
+            pattern,
+            pattern,
+            pattern,
+            per_page,
+            offset
         )
         .fetch_all(&self.pool)
         .await?;
@@ -78,48 +494,238 @@ impl ContentService {
                 remote_updated_at: row.remote_updated_at,
                 local_updated_at: row.local_updated_at,
                 metadata: row.metadata.as_ref().and_then(|m| serde_json::from_str(m).ok()),
+                sync_conflict: row.sync_conflict != 0,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
             })
-            .collect();
+            .collect::<Vec<_>>();
 
-        Ok(contents)
+        let has_more = offset + (contents.len() as i64) < total;
+
+        Ok(ContentListResponse {
+            contents,
+            total,
+            page,
+            per_page,
+            has_more,
+        })
     }
 
     /// Get a single content by ID
     pub async fn get_content(&self, id: &str) -> Result<Content> {
-        let row = sqlx::query!(
+        load_content(&self.pool, id).await
+    }
+
+    /// Render a content the way `platform`'s adapter would publish it,
+    /// without creating any task or job
+    pub async fn preview_content(
+        &self,
+        registry: &AdapterRegistry,
+        content_id: &str,
+        platform: &str,
+    ) -> Result<PreparedContent> {
+        let adapter = registry.get(platform)?;
+        let content = self.get_content(content_id).await?;
+        adapter.prepare_content(&content).await
+    }
+
+    /// Create a local draft content
+    pub async fn create_content(&self, req: CreateContentRequest) -> Result<Content> {
+        if req.title.trim().is_empty() {
+            return Err(PubCastError::Validation("Title is required".to_string()));
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().timestamp();
+        let status = ContentStatus::Draft.to_string();
+        let tags_json = req.tags.as_ref().and_then(|t| serde_json::to_string(t).ok());
+        let metadata_json = req.metadata.as_ref().map(|m| m.to_string());
+        let hash = content_hash(&req.title, req.body.as_deref());
+
+        sqlx::query!(
             r#"
-            SELECT id, remote_id, title, body, cover_image_url, cover_image_local,
-                   tags, category, author, source_url, status, remote_status,
-                   remote_updated_at, local_updated_at, metadata, created_at, updated_at
-            FROM contents WHERE id = ?
+            INSERT INTO contents (id, remote_id, title, body, content_hash, cover_image_url, tags,
+                category, author, source_url, status, metadata,
+                local_updated_at, created_at, updated_at)
+            VALUES (?, NULL, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            id,
+            req.title,
+            req.body,
+            hash,
+            req.cover_image_url,
+            tags_json,
+            req.category,
+            req.author,
+            req.source_url,
+            status,
+            metadata_json,
+            now,
+            now,
+            now
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.get_content(&id).await
+    }
+
+    /// Update a content item
+    pub async fn update_content(&self, id: &str, req: UpdateContentRequest) -> Result<Content> {
+        let now = chrono::Utc::now().timestamp();
+
+        // Get existing content first
+        let existing = self.get_content(id).await?;
+
+        let title = req.title.unwrap_or(existing.title);
+        if title.trim().is_empty() {
+            return Err(PubCastError::Validation("Title is required".to_string()));
+        }
+        let body = req.body.or(existing.body);
+        let cover_image_url = req.cover_image_url.or(existing.cover_image_url);
+        let tags_json = req
+            .tags
+            .or(existing.tags)
+            .and_then(|t| serde_json::to_string(&t).ok());
+        let category = req.category.or(existing.category);
+        let author = req.author.or(existing.author);
+        let source_url = req.source_url.or(existing.source_url);
+        let status = req.status.unwrap_or(existing.status).to_string();
+        let metadata_json = req.metadata.or(existing.metadata).map(|m| m.to_string());
+        let hash = content_hash(&title, body.as_deref());
+
+        sqlx::query!(
+            r#"
+            UPDATE contents SET
+                title = ?, body = ?, content_hash = ?, cover_image_url = ?, tags = ?,
+                category = ?, author = ?, source_url = ?, status = ?, metadata = ?,
+                local_updated_at = ?, updated_at = ?
+            WHERE id = ?
             "#,
+            title,
+            body,
+            hash,
+            cover_image_url,
+            tags_json,
+            category,
+            author,
+            source_url,
+            status,
+            metadata_json,
+            now,
+            now,
             id
         )
-        .fetch_optional(&self.pool)
-        .await?
-        .ok_or_else(|| PubCastError::NotFound(format!("Content not found: {}", id)))?;
+        .execute(&self.pool)
+        .await?;
 
-        Ok(Content {
-            id: row.id,
-            remote_id: row.remote_id,
-            title: row.title,
-            body: row.body,
-            cover_image_url: row.cover_image_url,
-            cover_image_local: row.cover_image_local,
-            tags: row.tags.as_ref().and_then(|t| serde_json::from_str(t).ok()),
-            category: row.category,
-            author: row.author,
-            source_url: row.source_url,
-            status: row.status.parse().unwrap_or(ContentStatus::Draft),
-            remote_status: row.remote_status,
-            remote_updated_at: row.remote_updated_at,
-            local_updated_at: row.local_updated_at,
-            metadata: row.metadata.as_ref().and_then(|m| serde_json::from_str(m).ok()),
-            created_at: row.created_at,
-            updated_at: row.updated_at,
-        })
+        self.get_content(id).await
+    }
+
+    /// Delete a content item; soft-deletes unless `hard` is set
+    pub async fn delete_content(&self, id: &str, hard: bool) -> Result<()> {
+        let result = if hard {
+            sqlx::query!("DELETE FROM contents WHERE id = ?", id)
+                .execute(&self.pool)
+                .await?
+        } else {
+            let now = chrono::Utc::now().timestamp();
+            let status = ContentStatus::Deleted.to_string();
+            sqlx::query!(
+                "UPDATE contents SET status = ?, local_updated_at = ?, updated_at = ? WHERE id = ?",
+                status,
+                now,
+                now,
+                id
+            )
+            .execute(&self.pool)
+            .await?
+        };
+
+        if result.rows_affected() == 0 {
+            return Err(PubCastError::NotFound(format!("Content not found: {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// List every tag on non-deleted contents with the number of contents using it,
+    /// most used first
+    pub async fn list_all_tags(&self) -> Result<Vec<(String, i64)>> {
+        let rows = sqlx::query_scalar!(
+            "SELECT tags FROM contents WHERE status != 'deleted' AND tags IS NOT NULL"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut counts: HashMap<String, i64> = HashMap::new();
+        for tags in rows.iter().flatten() {
+            let tags: Vec<String> = serde_json::from_str(tags).unwrap_or_default();
+            for tag in dedup_tags(tags) {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(counts)
+    }
+
+    /// Rename a tag on every live content, returning how many contents changed
+    pub async fn rename_tag(&self, from: &str, to: &str) -> Result<u64> {
+        self.merge_tags(&[from.to_string()], to).await
+    }
+
+    /// Replace each of `from` with `into` on every content, dropping the
+    /// duplicates this creates. Returns how many contents changed.
+    ///
+    /// Soft-deleted contents are left as they are, matching
+    /// [`Self::list_all_tags`].
+    pub async fn merge_tags(&self, from: &[String], into: &str) -> Result<u64> {
+        let into = into.trim();
+        if into.is_empty() {
+            return Err(PubCastError::Validation("Tag name is required".to_string()));
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let mut tx = self.pool.begin().await?;
+        let rows = sqlx::query!(
+            "SELECT id, tags FROM contents WHERE status != 'deleted' AND tags IS NOT NULL"
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut changed = 0;
+        for row in rows {
+            let Some(tags) = row.tags.and_then(|t| serde_json::from_str::<Vec<String>>(&t).ok()) else {
+                continue;
+            };
+            if !tags.iter().any(|tag| from.contains(tag)) {
+                continue;
+            }
+
+            let merged = dedup_tags(tags.into_iter().map(|tag| {
+                if from.contains(&tag) {
+                    into.to_string()
+                } else {
+                    tag
+                }
+            }));
+            let tags_json = serde_json::to_string(&merged)?;
+            sqlx::query!(
+                "UPDATE contents SET tags = ?, local_updated_at = ?, updated_at = ? WHERE id = ?",
+                tags_json,
+                now,
+                now,
+                row.id
+            )
+            .execute(&mut *tx)
+            .await?;
+            changed += 1;
+        }
+
+        tx.commit().await?;
+        Ok(changed)
     }
 
     /// Fetch contents from remote API
@@ -129,6 +735,96 @@ impl ContentService {
         per_page: i32,
         status: Option<&str>,
     ) -> Result<RemoteContentListResponse> {
+        match self.fetch_page(page, per_page, status, None).await? {
+            FetchOutcome::Modified(data, _) => Ok(data),
+            FetchOutcome::NotModified => Err(PubCastError::Network(
+                "Unexpected 304 for an unconditional request".to_string(),
+            )),
+        }
+    }
+
+    /// Probe the remote API with a one-item listing, without retries.
+    /// Returns the response status; connection failures are errors.
+    pub async fn ping_api(&self) -> Result<reqwest::StatusCode> {
+        Ok(http_log::send(self.probe_request()).await?.status())
+    }
+
+    /// Check that the API is reachable and accepts the configured key.
+    ///
+    /// Failures are reported in the returned [`ConnectionInfo`] rather than as
+    /// errors, so the settings page can tell a bad host from a bad key.
+    pub async fn test_connection(&self) -> Result<ConnectionInfo> {
+        let started = std::time::Instant::now();
+        let response = http_log::send(self.probe_request()).await;
+
+        let mut info = ConnectionInfo {
+            status: ConnectionStatus::Connected,
+            base_url: self.api_config.base_url.clone(),
+            http_status: None,
+            total_contents: None,
+            latency_ms: 0,
+            message: None,
+        };
+
+        match response {
+            Err(e) => {
+                info.status = if is_dns_error(&e) {
+                    ConnectionStatus::DnsError
+                } else {
+                    ConnectionStatus::Unreachable
+                };
+                info.message = Some(e.to_string());
+            }
+            Ok(response) => {
+                let status = response.status();
+                info.http_status = Some(status.as_u16());
+                if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+                    info.status = ConnectionStatus::Unauthorized;
+                    info.message = Some(match self.api_config.api_key {
+                        Some(_) => "API key was rejected".to_string(),
+                        None => "API requires a key".to_string(),
+                    });
+                } else if !status.is_success() {
+                    info.status = ConnectionStatus::HttpError;
+                    info.message = Some(format!("API returned HTTP {}", status));
+                } else {
+                    match response.json::<RemoteContentListResponse>().await {
+                        Ok(listing) => info.total_contents = Some(listing.total),
+                        Err(e) => {
+                            info.status = ConnectionStatus::HttpError;
+                            info.message = Some(format!("Unexpected response from API: {}", e));
+                        }
+                    }
+                }
+            }
+        }
+
+        info.latency_ms = started.elapsed().as_millis() as u64;
+        Ok(info)
+    }
+
+    /// One-item authenticated listing request used to probe the API
+    fn probe_request(&self) -> reqwest::RequestBuilder {
+        let url = format!("{}/contents?page=1&per_page=1", self.api_config.base_url);
+        self.authorize(self.client().get(&url))
+    }
+
+    /// Add the configured API key to a CMS request
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.api_config.api_key {
+            Some(api_key) => request.header("Authorization", format!("Bearer {}", api_key)),
+            None => request,
+        }
+    }
+
+    /// Fetch a listing page, sending cache validators when given
+    async fn fetch_page(
+        &self,
+        page: i32,
+        per_page: i32,
+        status: Option<&str>,
+        validators: Option<&CacheValidators>,
+    ) -> Result<FetchOutcome> {
         let mut url = format!(
             "{}/contents?page={}&per_page={}",
             self.api_config.base_url, page, per_page
@@ -138,13 +834,27 @@ impl ContentService {
             url.push_str(&format!("&status={}", status));
         }
 
-        let mut request = self.http_client.get(&url);
+        let response = self
+            .send_with_retry(|client| {
+                let mut request = self.authorize(client.get(&url));
 
-        if let Some(api_key) = &self.api_config.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
+                if let Some(validators) = validators {
+                    if let Some(etag) = &validators.etag {
+                        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+                    }
+                    if let Some(last_modified) = &validators.last_modified {
+                        request =
+                            request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+                    }
+                }
 
-        let response = request.send().await?;
+                request
+            })
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(FetchOutcome::NotModified);
+        }
 
         if !response.status().is_success() {
             return Err(PubCastError::Http(
@@ -152,102 +862,548 @@ impl ContentService {
             ));
         }
 
+        let validators = CacheValidators::from_headers(response.headers());
         let data: RemoteContentListResponse = response.json().await?;
-        Ok(data)
+        Ok(FetchOutcome::Modified(data, validators))
+    }
+
+    /// Load the validators stored by the last complete sync
+    async fn load_validators(&self) -> Result<Option<CacheValidators>> {
+        let row = sqlx::query!(
+            "SELECT etag, last_modified FROM content_sync_status WHERE api_endpoint = ?",
+            self.api_config.base_url
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row
+            .map(|r| CacheValidators {
+                etag: r.etag,
+                last_modified: r.last_modified,
+            })
+            .filter(|v| !v.is_empty()))
+    }
+
+    /// Record a sync run for the configured endpoint
+    async fn record_sync(&self, validators: &CacheValidators, synced: i32) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        let id = Uuid::new_v4().to_string();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO content_sync_status (id, api_endpoint, last_sync_at, sync_status,
+                total_synced, etag, last_modified, created_at, updated_at)
+            VALUES (?, ?, ?, 'idle', ?, ?, ?, ?, ?)
+            ON CONFLICT(api_endpoint) DO UPDATE SET
+                last_sync_at = excluded.last_sync_at,
+                sync_status = 'idle',
+                total_synced = content_sync_status.total_synced + excluded.total_synced,
+                etag = excluded.etag,
+                last_modified = excluded.last_modified,
+                updated_at = excluded.updated_at
+            "#,
+            id,
+            self.api_config.base_url,
+            now,
+            synced,
+            validators.etag,
+            validators.last_modified,
+            now,
+            now
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
     }
 
-    /// Sync content from remote to local database
+    /// Sync content from remote to local database.
+    ///
+    /// If the local row was edited since the last sync and the remote version
+    /// changed too, the local values are kept and the row is flagged as a
+    /// conflict instead (see [`ContentService::resolve_conflict`]).
     pub async fn sync_remote_content(&self, remote: &RemoteContent) -> Result<Content> {
         let now = chrono::Utc::now().timestamp();
 
         // Check if content already exists
         let existing = sqlx::query!(
-            "SELECT id FROM contents WHERE remote_id = ?",
+            r#"
+            SELECT id, cover_image_url, cover_image_local, remote_updated_at,
+                   local_updated_at, last_synced_at
+            FROM contents WHERE remote_id = ?
+            "#,
             remote.id
         )
         .fetch_optional(&self.pool)
         .await?;
 
-        let tags_json = remote.tags.as_ref().map(|t| serde_json::to_string(t).ok()).flatten();
-        let status = ContentStatus::Ready.to_string();
+        let Some(existing) = existing else {
+            let id = self.insert_remote(remote, now).await?;
+            self.refresh_cover(&id, remote, None).await?;
+            return self.get_content(&id).await;
+        };
 
-        let id = if let Some(existing) = existing {
-            // Update existing content
-            sqlx::query!(
-                r#"
-                UPDATE contents SET
-                    title = ?, body = ?, cover_image_url = ?, tags = ?,
-                    category = ?, author = ?, source_url = ?, status = ?,
-                    remote_status = ?, remote_updated_at = ?, local_updated_at = ?, updated_at = ?
-                WHERE id = ?
-                "#,
-                remote.title,
-                remote.body,
-                remote.cover_image_url,
-                tags_json,
-                remote.category,
-                remote.author,
-                remote.source_url,
-                status,
-                remote.status,
-                remote.updated_at,
-                now,
-                now,
-                existing.id
-            )
-            .execute(&self.pool)
-            .await?;
+        let locally_modified = existing
+            .last_synced_at
+            .is_some_and(|synced_at| existing.local_updated_at > synced_at);
+        let remote_changed = existing.remote_updated_at != Some(remote.updated_at);
+
+        if locally_modified {
+            if remote_changed {
+                let snapshot = serde_json::to_string(remote)?;
+                sqlx::query!(
+                    "UPDATE contents SET sync_conflict = 1, conflict_remote = ? WHERE id = ?",
+                    snapshot,
+                    existing.id
+                )
+                .execute(&self.pool)
+                .await?;
+                tracing::warn!("Sync conflict on content {}", existing.id);
+            }
+            return self.get_content(&existing.id).await;
+        }
 
-            existing.id
+        // A previously cached cover is only reusable if the URL did not change
+        let cached_cover = if existing.cover_image_url == remote.cover_image_url {
+            existing.cover_image_local
         } else {
-            // Insert new content
-            let id = Uuid::new_v4().to_string();
+            None
+        };
 
-            sqlx::query!(
-                r#"
-                INSERT INTO contents (id, remote_id, title, body, cover_image_url, tags,
-                    category, author, source_url, status, remote_status, remote_updated_at,
-                    local_updated_at, created_at, updated_at)
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-                "#,
-                id,
-                remote.id,
-                remote.title,
-                remote.body,
-                remote.cover_image_url,
-                tags_json,
-                remote.category,
-                remote.author,
-                remote.source_url,
-                status,
-                remote.status,
-                remote.updated_at,
-                now,
-                now,
-                now
+        self.apply_remote(&existing.id, remote, now).await?;
+        self.refresh_cover(&existing.id, remote, cached_cover.as_deref())
+            .await?;
+
+        self.get_content(&existing.id).await
+    }
+
+    /// Fetch one content from the remote API and sync it like [`sync_all`](Self::sync_all)
+    /// would, returning the local copy
+    pub async fn sync_one(&self, remote_id: &str) -> Result<Content> {
+        let invalid = || {
+            PubCastError::Configuration(format!("Invalid CMS URL: {}", self.api_config.base_url))
+        };
+        // Escaped as one path segment so an ID can't reach other endpoints
+        let mut url = reqwest::Url::parse(&self.api_config.base_url).map_err(|_| invalid())?;
+        url.path_segments_mut()
+            .map_err(|_| invalid())?
+            .pop_if_empty()
+            .push("contents")
+            .push(remote_id);
+        let response = self
+            .send_with_retry(|client| self.authorize(client.get(url.clone())))
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(PubCastError::NotFound(format!(
+                "Remote content not found: {}",
+                remote_id
+            )));
+        }
+        if !response.status().is_success() {
+            return Err(PubCastError::Http(
+                reqwest::Error::from(response.error_for_status().unwrap_err()),
+            ));
+        }
+
+        let remote: RemoteContent = response.json().await?;
+        self.sync_remote_content(&remote).await
+    }
+
+    /// List contents whose local edits conflict with a newer remote version
+    pub async fn get_conflicts(&self) -> Result<Vec<Content>> {
+        let ids = sqlx::query_scalar!(
+            "SELECT id FROM contents WHERE sync_conflict = 1 ORDER BY updated_at DESC"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut contents = Vec::with_capacity(ids.len());
+        for id in ids {
+            contents.push(self.get_content(&id).await?);
+        }
+
+        Ok(contents)
+    }
+
+    /// Resolve a sync conflict by keeping either the local or the remote version
+    pub async fn resolve_conflict(&self, id: &str, keep: ConflictResolution) -> Result<Content> {
+        let row = sqlx::query!(
+            "SELECT sync_conflict, conflict_remote FROM contents WHERE id = ?",
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| PubCastError::NotFound(format!("Content not found: {}", id)))?;
+
+        let snapshot = match row.conflict_remote {
+            Some(snapshot) if row.sync_conflict != 0 => snapshot,
+            _ => {
+                return Err(PubCastError::Validation(format!(
+                    "Content {} has no sync conflict",
+                    id
+                )))
+            }
+        };
+        let remote: RemoteContent = serde_json::from_str(&snapshot)?;
+
+        match keep {
+            ConflictResolution::KeepLocal => {
+                // Acknowledge this remote revision; the local edits stay
+                // pending, so a later remote change conflicts again.
+                sqlx::query!(
+                    r#"
+                    UPDATE contents SET
+                        remote_status = ?, remote_updated_at = ?,
+                        sync_conflict = 0, conflict_remote = NULL
+                    WHERE id = ?
+                    "#,
+                    remote.status,
+                    remote.updated_at,
+                    id
+                )
+                .execute(&self.pool)
+                .await?;
+            }
+            ConflictResolution::KeepRemote => {
+                let existing = self.get_content(id).await?;
+                let cached_cover = if existing.cover_image_url == remote.cover_image_url {
+                    existing.cover_image_local
+                } else {
+                    None
+                };
+
+                let now = chrono::Utc::now().timestamp();
+                self.apply_remote(id, &remote, now).await?;
+                self.refresh_cover(id, &remote, cached_cover.as_deref())
+                    .await?;
+            }
+        }
+
+        self.get_content(id).await
+    }
+
+    /// Whether another non-deleted content has the same normalized text
+    async fn has_duplicate(&self, content: &Content) -> Result<bool> {
+        let hash = content_hash(&content.title, content.body.as_deref());
+        let duplicate = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM contents
+                WHERE content_hash = ? AND id != ? AND status != 'deleted'
+            ) as "duplicate!: bool"
+            "#,
+            hash,
+            content.id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(duplicate)
+    }
+
+    /// Groups of non-deleted contents with identical normalized title and
+    /// body. Nothing is merged; the user decides what to keep.
+    pub async fn find_duplicate_contents(&self) -> Result<Vec<DuplicateContentGroup>> {
+        self.backfill_content_hashes().await?;
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, content_hash as "content_hash!"
+            FROM contents
+            WHERE status != 'deleted' AND content_hash IN (
+                SELECT content_hash FROM contents
+                WHERE status != 'deleted' AND content_hash IS NOT NULL
+                GROUP BY content_hash HAVING COUNT(*) > 1
+            )
+            ORDER BY content_hash, created_at, id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut groups: Vec<DuplicateContentGroup> = Vec::new();
+        for row in rows {
+            let content = self.get_content(&row.id).await?;
+            match groups.last_mut() {
+                Some(group) if group.content_hash == row.content_hash => {
+                    group.contents.push(content)
+                }
+                _ => groups.push(DuplicateContentGroup {
+                    content_hash: row.content_hash,
+                    contents: vec![content],
+                }),
+            }
+        }
+
+        Ok(groups)
+    }
+
+    /// Hash contents stored before content hashes existed
+    async fn backfill_content_hashes(&self) -> Result<()> {
+        let rows = sqlx::query!("SELECT id, title, body FROM contents WHERE content_hash IS NULL")
+            .fetch_all(&self.pool)
+            .await?;
+
+        for row in rows {
+            let hash = content_hash(&row.title, row.body.as_deref());
+            sqlx::query!(
+                "UPDATE contents SET content_hash = ? WHERE id = ?",
+                hash,
+                row.id
             )
             .execute(&self.pool)
             .await?;
+        }
+
+        Ok(())
+    }
 
+    /// Insert a new row for a remote content, returning the local ID
+    async fn insert_remote(&self, remote: &RemoteContent, now: i64) -> Result<String> {
+        let id = Uuid::new_v4().to_string();
+        let tags_json = remote.tags.as_ref().and_then(|t| serde_json::to_string(t).ok());
+        let status = ContentStatus::Ready.to_string();
+        let hash = content_hash(&remote.title, remote.body.as_deref());
+
+        sqlx::query!(
+            r#"
+            INSERT INTO contents (id, remote_id, title, body, content_hash, cover_image_url, tags,
+                category, author, source_url, status, remote_status, remote_updated_at,
+                local_updated_at, last_synced_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            id,
+            remote.id,
+            remote.title,
+            remote.body,
+            hash,
+            remote.cover_image_url,
+            tags_json,
+            remote.category,
+            remote.author,
+            remote.source_url,
+            status,
+            remote.status,
+            remote.updated_at,
+            now,
+            now,
+            now,
+            now
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Overwrite an existing row with remote values and clear any conflict
+    async fn apply_remote(&self, id: &str, remote: &RemoteContent, now: i64) -> Result<()> {
+        let tags_json = remote.tags.as_ref().and_then(|t| serde_json::to_string(t).ok());
+        let status = ContentStatus::Ready.to_string();
+        let hash = content_hash(&remote.title, remote.body.as_deref());
+
+        sqlx::query!(
+            r#"
+            UPDATE contents SET
+                title = ?, body = ?, content_hash = ?, cover_image_url = ?, tags = ?,
+                category = ?, author = ?, source_url = ?, status = ?,
+                remote_status = ?, remote_updated_at = ?, local_updated_at = ?,
+                last_synced_at = ?, sync_conflict = 0, conflict_remote = NULL, updated_at = ?
+            WHERE id = ?
+            "#,
+            remote.title,
+            remote.body,
+            hash,
+            remote.cover_image_url,
+            tags_json,
+            remote.category,
+            remote.author,
+            remote.source_url,
+            status,
+            remote.status,
+            remote.updated_at,
+            now,
+            now,
+            now,
             id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Update `cover_image_local` for a remote content when cover caching is enabled
+    async fn refresh_cover(
+        &self,
+        id: &str,
+        remote: &RemoteContent,
+        cached_cover: Option<&str>,
+    ) -> Result<()> {
+        if self.covers_dir.is_none() {
+            return Ok(());
+        }
+
+        let cover_local = match &remote.cover_image_url {
+            Some(url) => self.cache_cover(id, url, cached_cover).await,
+            None => None,
         };
 
-        self.get_content(&id).await
+        sqlx::query!(
+            "UPDATE contents SET cover_image_local = ? WHERE id = ?",
+            cover_local,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Make sure a local copy of the cover exists, returning its path.
+    ///
+    /// Failures are logged and yield `None` so that sync keeps going with only
+    /// the remote URL.
+    async fn cache_cover(
+        &self,
+        content_id: &str,
+        url: &str,
+        cached: Option<&str>,
+    ) -> Option<String> {
+        if let Some(path) = cached {
+            if is_non_empty_file(Path::new(path)).await {
+                return Some(path.to_string());
+            }
+        }
+
+        match self.download_cover(content_id, url).await {
+            Ok(path) => Some(path.to_string_lossy().into_owned()),
+            Err(e) => {
+                tracing::warn!("Failed to cache cover image for content {}: {}", content_id, e);
+                None
+            }
+        }
+    }
+
+    /// Download a cover image into the covers directory
+    async fn download_cover(&self, content_id: &str, url: &str) -> Result<PathBuf> {
+        let dir = self
+            .covers_dir
+            .as_ref()
+            .ok_or_else(|| PubCastError::Configuration("Covers directory not set".to_string()))?;
+
+        let mut response = http_log::send(self.client().get(url)).await?.error_for_status()?;
+
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let extension = cover_extension(&content_type).ok_or_else(|| {
+            PubCastError::Validation(format!("Unsupported cover content type: {:?}", content_type))
+        })?;
+
+        if response
+            .content_length()
+            .is_some_and(|len| len > MAX_COVER_BYTES as u64)
+        {
+            return Err(PubCastError::Validation(format!(
+                "Cover image exceeds {} bytes",
+                MAX_COVER_BYTES
+            )));
+        }
+
+        let mut data = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if data.len() + chunk.len() > MAX_COVER_BYTES {
+                return Err(PubCastError::Validation(format!(
+                    "Cover image exceeds {} bytes",
+                    MAX_COVER_BYTES
+                )));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        if data.is_empty() {
+            return Err(PubCastError::Validation("Cover image is empty".to_string()));
+        }
+
+        let io_err = |e: std::io::Error| {
+            PubCastError::Configuration(format!("Failed to write cover image: {}", e))
+        };
+
+        tokio::fs::create_dir_all(dir).await.map_err(io_err)?;
+
+        let path = dir.join(format!("{}.{}", content_id, extension));
+        let tmp = dir.join(format!("{}.{}.tmp", content_id, extension));
+        tokio::fs::write(&tmp, &data).await.map_err(io_err)?;
+        tokio::fs::rename(&tmp, &path).await.map_err(io_err)?;
+
+        Ok(path)
     }
 
-    /// Sync all contents from remote API
+    /// Sync all contents from remote API.
+    ///
+    /// With conditional requests enabled, the first page is fetched with the
+    /// validators from the last complete sync and a `304 Not Modified` ends
+    /// the run early. Validators are only stored when the first page held the
+    /// whole listing, since they say nothing about later pages.
     pub async fn sync_all(&self) -> Result<SyncResult> {
+        // Give the proxy another chance on every run
+        self.fell_back.store(false, Ordering::Relaxed);
+
         let mut synced = 0;
         let mut failed = 0;
+        let mut conflicts = 0;
+        let mut duplicates = 0;
         let mut page = 1;
         let per_page = 50;
+        let detect_duplicates = SettingsService::new(self.pool.clone())
+            .get_or(DETECT_DUPLICATES_KEY, true)
+            .await?;
+        if detect_duplicates {
+            self.backfill_content_hashes().await?;
+        }
+
+        let stored = if self.api_config.conditional_requests {
+            self.load_validators().await?
+        } else {
+            None
+        };
+        let mut validators = CacheValidators::default();
 
         loop {
-            let response = self.fetch_remote_contents(page, per_page, Some("ready")).await?;
+            let conditional = if page == 1 { stored.as_ref() } else { None };
+            let response = match self
+                .fetch_page(page, per_page, Some("ready"), conditional)
+                .await?
+            {
+                FetchOutcome::Modified(response, page_validators) => {
+                    if page == 1 {
+                        validators = page_validators;
+                    }
+                    response
+                }
+                FetchOutcome::NotModified => {
+                    tracing::info!("Remote contents not modified since last sync");
+                    self.record_sync(stored.as_ref().unwrap_or(&validators), 0)
+                        .await?;
+                    return Ok(self.sync_result(0, 0, 0, 0));
+                }
+            };
 
             for remote in response.contents {
                 match self.sync_remote_content(&remote).await {
-                    Ok(_) => synced += 1,
+                    Ok(content) if content.sync_conflict => conflicts += 1,
+                    Ok(content) => {
+                        synced += 1;
+                        if detect_duplicates && self.has_duplicate(&content).await? {
+                            tracing::info!("Content {} looks like a duplicate", content.id);
+                            duplicates += 1;
+                        }
+                    }
                     Err(e) => {
                         tracing::warn!("Failed to sync content {}: {}", remote.id, e);
                         failed += 1;
@@ -262,9 +1418,33 @@ impl ContentService {
             page += 1;
         }
 
-        tracing::info!("Sync completed: {} synced, {} failed", synced, failed);
+        tracing::info!(
+            "Sync completed: {} synced, {} failed, {} conflicts, {} likely duplicates",
+            synced,
+            failed,
+            conflicts,
+            duplicates
+        );
+
+        // Only trust the validators if they cover the whole listing and every
+        // item made it in; otherwise the next run must fetch everything again
+        if page > 1 || failed > 0 {
+            validators = CacheValidators::default();
+        }
+        self.record_sync(&validators, synced).await?;
+
+        Ok(self.sync_result(synced, failed, conflicts, duplicates))
+    }
 
-        Ok(SyncResult { synced, failed })
+    fn sync_result(&self, synced: i32, failed: i32, conflicts: i32, duplicates: i32) -> SyncResult {
+        SyncResult {
+            synced,
+            failed,
+            conflicts,
+            duplicates,
+            proxy_id: self.proxy_id.clone(),
+            fell_back_to_direct: self.fell_back.load(Ordering::Relaxed),
+        }
     }
 
     /// Report publish status to remote API
@@ -291,13 +1471,9 @@ impl ContentService {
             "published_at": chrono::Utc::now().timestamp_millis()
         });
 
-        let mut request = self.http_client.post(&url).json(&payload);
-
-        if let Some(api_key) = &self.api_config.api_key {
-            request = request.header("Authorization", format!("Bearer {}", api_key));
-        }
-
-        let response = request.send().await?;
+        let response = self
+            .send_with_retry(|client| self.authorize(client.post(&url).json(&payload)))
+            .await?;
 
         if !response.status().is_success() {
             return Err(PubCastError::Http(
@@ -307,11 +1483,1260 @@ impl ContentService {
 
         Ok(())
     }
+
+    /// Send a CMS request, retrying transient failures with exponential backoff.
+    ///
+    /// Network errors, 5xx and 429 responses are retried up to
+    /// `max_retries` times; other responses are returned as-is for the caller
+    /// to handle. A `Retry-After` header on a 429 overrides the backoff.
+    /// If the CMS proxy cannot be reached and falling back is allowed, the
+    /// request is repeated directly without counting as a retry.
+    async fn send_with_retry<F>(&self, build: F) -> Result<reqwest::Response>
+    where
+        F: Fn(&reqwest::Client) -> reqwest::RequestBuilder,
+    {
+        let mut attempt = 0;
+
+        loop {
+            let can_retry = attempt < self.api_config.max_retries;
+
+            let delay = match http_log::send(build(self.client())).await {
+                Ok(response) => {
+                    let status = response.status();
+                    let transient = status.is_server_error()
+                        || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+                    if !transient || !can_retry {
+                        return Ok(response);
+                    }
+
+                    tracing::warn!(
+                        "CMS request to {} returned {}, retrying",
+                        response.url(),
+                        status
+                    );
+                    retry_after(response.headers())
+                        .unwrap_or_else(|| self.retry_delay(attempt))
+                        .min(std::time::Duration::from_millis(MAX_RETRY_DELAY_MS))
+                }
+                Err(e) if self.fall_back_to_direct(&e) => continue,
+                Err(e) if can_retry && !e.is_builder() => {
+                    tracing::warn!("CMS request failed, retrying: {}", e);
+                    self.retry_delay(attempt)
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Calculate retry delay with exponential backoff
+    fn retry_delay(&self, attempt: u32) -> std::time::Duration {
+        let delay = self
+            .api_config
+            .retry_base_delay_ms
+            .saturating_mul(2u64.saturating_pow(attempt));
+        std::time::Duration::from_millis(delay.min(MAX_RETRY_DELAY_MS))
+    }
+}
+
+/// Parse a `Retry-After` header given either as seconds or as an HTTP date
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<std::time::Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(std::time::Duration::from_secs(secs));
+    }
+
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = at.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(wait.to_std().unwrap_or_default())
+}
+
+/// Escape `%`, `_` and the escape character itself for a LIKE pattern
+fn escape_like(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// File extension for a supported cover image content type
+fn cover_extension(content_type: &str) -> Option<&'static str> {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    match mime.as_str() {
+        "image/jpeg" | "image/jpg" => Some("jpg"),
+        "image/png" => Some("png"),
+        "image/gif" => Some("gif"),
+        "image/webp" => Some("webp"),
+        "image/avif" => Some("avif"),
+        "image/bmp" => Some("bmp"),
+        _ => None,
+    }
+}
+
+async fn is_non_empty_file(path: &Path) -> bool {
+    tokio::fs::metadata(path)
+        .await
+        .map(|m| m.is_file() && m.len() > 0)
+        .unwrap_or(false)
+}
+
+/// Result of a CMS API connection test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionStatus {
+    /// Reachable and the credentials were accepted
+    Connected,
+    /// Reachable, but the API key is missing or rejected (401/403)
+    Unauthorized,
+    /// Reachable, but answered with another error or an unexpected body
+    HttpError,
+    /// The host name could not be resolved
+    DnsError,
+    /// Connection refused, reset or timed out
+    Unreachable,
+}
+
+/// CMS API connection test report
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionInfo {
+    pub status: ConnectionStatus,
+    pub base_url: String,
+    pub http_status: Option<u16>,
+    /// Total content count reported by the server
+    pub total_contents: Option<i64>,
+    pub latency_ms: u64,
+    pub message: Option<String>,
+}
+
+/// Whether a request failed while resolving the host name
+fn is_dns_error(error: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        if cause.to_string().contains("dns error") {
+            return true;
+        }
+        source = cause.source();
+    }
+    false
 }
 
 /// Sync operation result
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct SyncResult {
     pub synced: i32,
     pub failed: i32,
+    pub conflicts: i32,
+    /// Synced contents whose normalized text matches another content
+    pub duplicates: i32,
+    /// Proxy the CMS requests were sent through
+    pub proxy_id: Option<String>,
+    /// Whether the proxy failed and requests went direct instead
+    pub fell_back_to_direct: bool,
+}
+
+/// Drop repeated tags, keeping the first occurrence of each
+fn dedup_tags(tags: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    tags.into_iter().filter(|tag| seen.insert(tag.clone())).collect()
+}
+
+/// Load a single content by ID
+pub(crate) async fn load_content(pool: &SqlitePool, id: &str) -> Result<Content> {
+    let row = sqlx::query!(
+        r#"
+        SELECT id, remote_id, title, body, cover_image_url, cover_image_local,
+               tags, category, author, source_url, status, remote_status,
+               remote_updated_at, local_updated_at, metadata, sync_conflict,
+               created_at, updated_at
+        FROM contents WHERE id = ?
+        "#,
+        id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| PubCastError::NotFound(format!("Content not found: {}", id)))?;
+
+    Ok(Content {
+        id: row.id,
+        remote_id: row.remote_id,
+        title: row.title,
+        body: row.body,
+        cover_image_url: row.cover_image_url,
+        cover_image_local: row.cover_image_local,
+        tags: row.tags.as_ref().and_then(|t| serde_json::from_str(t).ok()),
+        category: row.category,
+        author: row.author,
+        source_url: row.source_url,
+        status: row.status.parse().unwrap_or(ContentStatus::Draft),
+        remote_status: row.remote_status,
+        remote_updated_at: row.remote_updated_at,
+        local_updated_at: row.local_updated_at,
+        metadata: row.metadata.as_ref().and_then(|m| serde_json::from_str(m).ok()),
+        sync_conflict: row.sync_conflict != 0,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn service() -> (tempfile::TempDir, ContentService) {
+        let (dir, pool) = init_test_database().await;
+        (dir, ContentService::new(pool, ContentApiConfig::default()))
+    }
+
+    #[tokio::test]
+    async fn test_local_content_lifecycle() {
+        let (_dir, service) = service().await;
+
+        let created = service
+            .create_content(CreateContentRequest {
+                title: "Hello".to_string(),
+                body: Some("# Draft".to_string()),
+                tags: Some(vec!["rust".to_string(), "tauri".to_string()]),
+                metadata: Some(serde_json::json!({ "source": "local" })),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(created.status, ContentStatus::Draft);
+        assert_eq!(created.remote_id, None);
+        assert_eq!(created.tags, Some(vec!["rust".to_string(), "tauri".to_string()]));
+        assert_eq!(created.metadata, Some(serde_json::json!({ "source": "local" })));
+
+        let updated = service
+            .update_content(
+                &created.id,
+                UpdateContentRequest {
+                    title: Some("Hello, world".to_string()),
+                    status: Some(ContentStatus::Ready),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.title, "Hello, world");
+        assert_eq!(updated.status, ContentStatus::Ready);
+        assert_eq!(updated.body.as_deref(), Some("# Draft"));
+        assert_eq!(updated.tags, created.tags);
+
+        // Soft delete hides it from the list but keeps the row
+        service.delete_content(&created.id, false).await.unwrap();
+        let listed = service
+            .list_contents(&ContentListQuery::default())
+            .await
+            .unwrap();
+        assert!(listed.contents.is_empty());
+        assert_eq!(listed.total, 0);
+        assert_eq!(
+            service.get_content(&created.id).await.unwrap().status,
+            ContentStatus::Deleted
+        );
+
+        service.delete_content(&created.id, true).await.unwrap();
+        assert!(matches!(
+            service.get_content(&created.id).await,
+            Err(PubCastError::NotFound(_))
+        ));
+        assert!(matches!(
+            service.delete_content(&created.id, true).await,
+            Err(PubCastError::NotFound(_))
+        ));
+    }
+
+    async fn create_titled(service: &ContentService, title: &str, body: &str) -> Content {
+        service
+            .create_content(CreateContentRequest {
+                title: title.to_string(),
+                body: Some(body.to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_contents_pagination() {
+        let (_dir, service) = service().await;
+        for i in 0..5 {
+            create_titled(&service, &format!("Post {}", i), "body").await;
+        }
+
+        let query = |page, per_page| ContentListQuery {
+            page: Some(page),
+            per_page: Some(per_page),
+            ..Default::default()
+        };
+
+        let first = service.list_contents(&query(1, 2)).await.unwrap();
+        assert_eq!(first.contents.len(), 2);
+        assert_eq!(first.total, 5);
+        assert!(first.has_more);
+
+        let last = service.list_contents(&query(3, 2)).await.unwrap();
+        assert_eq!(last.contents.len(), 1);
+        assert!(!last.has_more);
+
+        let exact = service.list_contents(&query(1, 5)).await.unwrap();
+        assert_eq!(exact.contents.len(), 5);
+        assert!(!exact.has_more);
+
+        let beyond = service.list_contents(&query(4, 2)).await.unwrap();
+        assert!(beyond.contents.is_empty());
+        assert_eq!(beyond.total, 5);
+        assert!(!beyond.has_more);
+
+        // Out-of-range paging is clamped rather than rejected
+        let clamped = service.list_contents(&query(0, 0)).await.unwrap();
+        assert_eq!(clamped.page, 1);
+        assert_eq!(clamped.per_page, 1);
+        assert_eq!(clamped.contents.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_list_contents_filters() {
+        let (_dir, service) = service().await;
+        let draft = create_titled(&service, "Rust tips", "ownership").await;
+        let ready = create_titled(&service, "Cooking", "100% butter").await;
+        let gone = create_titled(&service, "Old news", "rust").await;
+        service
+            .update_content(
+                &ready.id,
+                UpdateContentRequest {
+                    status: Some(ContentStatus::Ready),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        service.delete_content(&gone.id, false).await.unwrap();
+
+        let by_status = |status| ContentListQuery {
+            status: Some(status),
+            ..Default::default()
+        };
+        let ids = |resp: ContentListResponse| {
+            resp.contents.into_iter().map(|c| c.id).collect::<Vec<_>>()
+        };
+
+        let drafts = service.list_contents(&by_status(ContentStatus::Draft)).await.unwrap();
+        assert_eq!(ids(drafts), vec![draft.id.clone()]);
+
+        let deleted = service.list_contents(&by_status(ContentStatus::Deleted)).await.unwrap();
+        assert_eq!(ids(deleted), vec![gone.id.clone()]);
+
+        // Search covers title and body, case-insensitively, and skips deleted rows
+        let search = |term: &str| ContentListQuery {
+            search: Some(term.to_string()),
+            ..Default::default()
+        };
+        let rust = service.list_contents(&search("rust")).await.unwrap();
+        assert_eq!(ids(rust), vec![draft.id.clone()]);
+
+        // LIKE wildcards in the search term are matched literally
+        let percent = service.list_contents(&search("100%")).await.unwrap();
+        assert_eq!(ids(percent), vec![ready.id.clone()]);
+        let wildcard = service.list_contents(&search("%")).await.unwrap();
+        assert_eq!(wildcard.total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_content_requires_title() {
+        let (_dir, service) = service().await;
+        let result = service
+            .create_content(CreateContentRequest {
+                title: "  ".to_string(),
+                ..Default::default()
+            })
+            .await;
+        assert!(matches!(result, Err(PubCastError::Validation(_))));
+    }
+
+    fn remote(id: &str, cover_image_url: Option<String>) -> RemoteContent {
+        RemoteContent {
+            id: id.to_string(),
+            title: format!("Remote {}", id),
+            body: None,
+            cover_image_url,
+            tags: None,
+            category: None,
+            author: None,
+            source_url: None,
+            status: Some("published".to_string()),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_caches_cover_image() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cover.png"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "image/png")
+                    .set_body_bytes(vec![0x89, b'P', b'N', b'G']),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/page.html"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/html")
+                    .set_body_string("<html></html>"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/huge.jpg"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "image/jpeg")
+                    .set_body_bytes(vec![0u8; MAX_COVER_BYTES + 1]),
+            )
+            .mount(&server)
+            .await;
+
+        let (dir, pool) = init_test_database().await;
+        let covers = dir.path().join("covers");
+        let service =
+            ContentService::new(pool, ContentApiConfig::default()).with_covers_dir(&covers);
+
+        let cover_url = format!("{}/cover.png", server.uri());
+        let content = service
+            .sync_remote_content(&remote("r1", Some(cover_url.clone())))
+            .await
+            .unwrap();
+        let local = content.cover_image_local.expect("cover should be cached");
+        assert!(local.ends_with(".png"));
+        assert_eq!(std::fs::read(&local).unwrap(), vec![0x89, b'P', b'N', b'G']);
+
+        // Re-syncing reuses the cached file (the mock expects a single request)
+        let again = service
+            .sync_remote_content(&remote("r1", Some(cover_url.clone())))
+            .await
+            .unwrap();
+        assert_eq!(again.cover_image_local.as_deref(), Some(local.as_str()));
+        assert_eq!(again.cover_image_url, Some(cover_url));
+
+        // Non-image responses and oversized images keep only the URL
+        for (remote_id, file) in [("r2", "page.html"), ("r3", "huge.jpg")] {
+            let url = format!("{}/{}", server.uri(), file);
+            let content = service
+                .sync_remote_content(&remote(remote_id, Some(url.clone())))
+                .await
+                .unwrap();
+            assert_eq!(content.cover_image_url, Some(url));
+            assert_eq!(content.cover_image_local, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_conflict_lifecycle() {
+        let (_dir, pool) = init_test_database().await;
+        let service = ContentService::new(pool.clone(), ContentApiConfig::default());
+
+        let revision = |title: &str, updated_at: i64| RemoteContent {
+            title: title.to_string(),
+            updated_at,
+            ..remote("r1", None)
+        };
+
+        let synced = service.sync_remote_content(&revision("v1", 1)).await.unwrap();
+        assert!(!synced.sync_conflict);
+
+        // Remote-only changes are applied as before
+        let synced = service.sync_remote_content(&revision("v2", 2)).await.unwrap();
+        assert_eq!(synced.title, "v2");
+
+        // Pretend the last sync happened a while ago, then edit locally
+        sqlx::query(
+            "UPDATE contents SET last_synced_at = last_synced_at - 60, \
+             local_updated_at = local_updated_at - 60",
+        )
+            .execute(&pool)
+            .await
+            .unwrap();
+        service
+            .update_content(
+                &synced.id,
+                UpdateContentRequest {
+                    title: Some("local edit".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        // An unchanged remote leaves the local edit alone without a conflict
+        let same = service.sync_remote_content(&revision("v2", 2)).await.unwrap();
+        assert_eq!(same.title, "local edit");
+        assert!(!same.sync_conflict);
+
+        // Both sides changed: keep local values and flag the conflict
+        let conflicted = service.sync_remote_content(&revision("v3", 3)).await.unwrap();
+        assert_eq!(conflicted.title, "local edit");
+        assert!(conflicted.sync_conflict);
+        let conflicts = service.get_conflicts().await.unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].id, synced.id);
+
+        let kept = service
+            .resolve_conflict(&synced.id, ConflictResolution::KeepLocal)
+            .await
+            .unwrap();
+        assert_eq!(kept.title, "local edit");
+        assert!(!kept.sync_conflict);
+        assert_eq!(kept.remote_updated_at, Some(3));
+        assert!(service.get_conflicts().await.unwrap().is_empty());
+
+        // The local edit is still pending, so the next remote change conflicts again
+        let again = service.sync_remote_content(&revision("v4", 4)).await.unwrap();
+        assert!(again.sync_conflict);
+
+        let taken = service
+            .resolve_conflict(&synced.id, ConflictResolution::KeepRemote)
+            .await
+            .unwrap();
+        assert_eq!(taken.title, "v4");
+        assert!(!taken.sync_conflict);
+
+        // Back in sync: later remote updates apply normally
+        let next = service.sync_remote_content(&revision("v5", 5)).await.unwrap();
+        assert_eq!(next.title, "v5");
+        assert!(!next.sync_conflict);
+
+        assert!(matches!(
+            service
+                .resolve_conflict(&synced.id, ConflictResolution::KeepRemote)
+                .await,
+            Err(PubCastError::Validation(_))
+        ));
+    }
+
+    fn listing(ids: &[&str]) -> serde_json::Value {
+        let contents: Vec<_> = ids.iter().map(|id| remote(id, None)).collect();
+        serde_json::json!({
+            "contents": contents,
+            "total": ids.len(),
+            "page": 1,
+            "per_page": 50,
+            "has_more": false,
+        })
+    }
+
+    async fn mount_conditional_listing(server: &MockServer, full_fetches: u64) {
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .and(header("If-None-Match", "\"v1\""))
+            .and(|req: &wiremock::Request| {
+                // `header` would split this HTTP-date on its comma
+                req.headers
+                    .get("If-Modified-Since")
+                    .is_some_and(|v| v == "Wed, 21 Oct 2015 07:28:00 GMT")
+            })
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .insert_header("Last-Modified", "Wed, 21 Oct 2015 07:28:00 GMT")
+                    .set_body_json(listing(&["r1", "r2"])),
+            )
+            .expect(full_fetches)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_sync_all_short_circuits_on_not_modified() {
+        let server = MockServer::start().await;
+        mount_conditional_listing(&server, 1).await;
+
+        let (_dir, pool) = init_test_database().await;
+        let config = ContentApiConfig {
+            base_url: server.uri(),
+            ..Default::default()
+        };
+        let service = ContentService::new(pool, config);
+
+        let first = service.sync_all().await.unwrap();
+        assert_eq!(first.synced, 2);
+
+        // The second run sends the stored validators and gets a 304
+        let second = service.sync_all().await.unwrap();
+        assert_eq!((second.synced, second.failed, second.conflicts), (0, 0, 0));
+        assert_eq!(
+            service
+                .list_contents(&ContentListQuery::default())
+                .await
+                .unwrap()
+                .total,
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_all_ignores_validators_of_a_partial_listing() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .and(header("If-None-Match", "\"p1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .expect(0)
+            .mount(&server)
+            .await;
+        let mut first_page = listing(&["r1"]);
+        first_page["has_more"] = serde_json::json!(true);
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .and(query_param("page", "1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"p1\"")
+                    .set_body_json(first_page),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(listing(&["r2"])))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let config = ContentApiConfig {
+            base_url: server.uri(),
+            ..Default::default()
+        };
+        let service = ContentService::new(pool, config);
+
+        // Page 1's ETag does not cover page 2, so the second run fetches both again
+        assert_eq!(service.sync_all().await.unwrap().synced, 2);
+        assert_eq!(service.sync_all().await.unwrap().synced, 2);
+    }
+
+    #[tokio::test]
+    async fn test_sync_all_without_conditional_requests() {
+        let server = MockServer::start().await;
+        mount_conditional_listing(&server, 2).await;
+
+        let (_dir, pool) = init_test_database().await;
+        let config = ContentApiConfig {
+            base_url: server.uri(),
+            conditional_requests: false,
+            ..Default::default()
+        };
+        let service = ContentService::new(pool, config);
+
+        assert_eq!(service.sync_all().await.unwrap().synced, 2);
+        assert_eq!(service.sync_all().await.unwrap().synced, 2);
+    }
+
+    #[tokio::test]
+    async fn test_sync_flags_duplicates_with_different_remote_ids() {
+        assert_eq!(
+            content_hash("Launch  Post", Some("Hello\n\nworld ")),
+            content_hash("launch post", Some("hello world"))
+        );
+
+        let with_text = |id: &str, title: &str, body: &str| RemoteContent {
+            title: title.to_string(),
+            body: Some(body.to_string()),
+            ..remote(id, None)
+        };
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "contents": [
+                    with_text("r1", "Launch Post", "Hello  world"),
+                    with_text("r2", "launch post", "hello world\n"),
+                    with_text("r3", "Launch Post", "Something else"),
+                ],
+                "total": 3,
+                "page": 1,
+                "per_page": 50,
+                "has_more": false,
+            })))
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let config = ContentApiConfig {
+            base_url: server.uri(),
+            ..Default::default()
+        };
+        let service = ContentService::new(pool, config);
+
+        let result = service.sync_all().await.unwrap();
+        assert_eq!(result.synced, 3);
+        assert_eq!(result.duplicates, 2);
+
+        // Flagged only: both rows are kept for the user to decide
+        let groups = service.find_duplicate_contents().await.unwrap();
+        assert_eq!(groups.len(), 1);
+        let mut remote_ids: Vec<_> = groups[0]
+            .contents
+            .iter()
+            .filter_map(|c| c.remote_id.clone())
+            .collect();
+        remote_ids.sort();
+        assert_eq!(remote_ids, ["r1", "r2"]);
+
+        // A deleted copy no longer counts
+        service
+            .delete_content(&groups[0].contents[0].id, false)
+            .await
+            .unwrap();
+        assert!(service.find_duplicate_contents().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicate_contents_hashes_older_rows() {
+        let (_dir, service) = service().await;
+        let first = create_titled(&service, "Same", "Body text").await;
+        let second = create_titled(&service, "same", "body   text").await;
+        create_titled(&service, "Other", "Body text").await;
+        sqlx::query("UPDATE contents SET content_hash = NULL")
+            .execute(&service.pool)
+            .await
+            .unwrap();
+
+        let groups = service.find_duplicate_contents().await.unwrap();
+        assert_eq!(groups.len(), 1);
+        let ids: Vec<_> = groups[0].contents.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&first.id.as_str()) && ids.contains(&second.id.as_str()));
+    }
+
+    fn retrying_service(pool: SqlitePool, server: &MockServer) -> ContentService {
+        let config = ContentApiConfig {
+            base_url: server.uri(),
+            max_retries: 3,
+            retry_base_delay_ms: 1,
+            ..Default::default()
+        };
+        ContentService::new(pool, config)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_retries_transient_failures() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .with_priority(1)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(listing(&["r1"])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let service = retrying_service(pool, &server);
+
+        let response = service.fetch_remote_contents(1, 50, None).await.unwrap();
+        assert_eq!(response.contents.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_does_not_retry_client_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let service = retrying_service(pool, &server);
+
+        assert!(matches!(
+            service.fetch_remote_contents(1, 50, None).await,
+            Err(PubCastError::Http(_))
+        ));
+    }
+
+    fn keyed_service(pool: SqlitePool, base_url: String) -> ContentService {
+        let config = ContentApiConfig {
+            base_url,
+            api_key: Some("good-key".to_string()),
+            ..Default::default()
+        };
+        ContentService::new(pool, config)
+    }
+
+    #[tokio::test]
+    async fn test_sync_one_fetches_a_single_content() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contents/r1"))
+            .and(header("authorization", "Bearer good-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(remote("r1", None)))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/contents/gone"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let service = keyed_service(pool, server.uri());
+        let content = service.sync_one("r1").await.unwrap();
+        assert_eq!(content.remote_id.as_deref(), Some("r1"));
+        assert_eq!(content.title, "Remote r1");
+        assert_eq!(service.get_content(&content.id).await.unwrap().title, "Remote r1");
+
+        assert!(matches!(
+            service.sync_one("gone").await,
+            Err(PubCastError::NotFound(_))
+        ));
+
+        // Reserved characters stay inside the ID's path segment
+        Mock::given(method("GET"))
+            .and(path("/contents/..%2Fadmin%3Fx=1"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        assert!(matches!(
+            service.sync_one("../admin?x=1").await,
+            Err(PubCastError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_connection_reports_total_for_valid_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .and(header("authorization", "Bearer good-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "contents": [], "total": 42, "page": 1, "per_page": 1, "has_more": true
+            })))
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let info = keyed_service(pool, server.uri()).test_connection().await.unwrap();
+
+        assert_eq!(info.status, ConnectionStatus::Connected);
+        assert_eq!(info.http_status, Some(200));
+        assert_eq!(info.total_contents, Some(42));
+    }
+
+    #[tokio::test]
+    async fn test_connection_distinguishes_rejected_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let info = keyed_service(pool, server.uri()).test_connection().await.unwrap();
+
+        assert_eq!(info.status, ConnectionStatus::Unauthorized);
+        assert_eq!(info.http_status, Some(401));
+        assert_eq!(info.total_contents, None);
+    }
+
+    #[tokio::test]
+    async fn test_connection_reports_refused_connection() {
+        // Grab a free port, then close it so nothing is listening
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let (_dir, pool) = init_test_database().await;
+        let info = keyed_service(pool, format!("http://127.0.0.1:{}", port))
+            .test_connection()
+            .await
+            .unwrap();
+
+        assert_eq!(info.status, ConnectionStatus::Unreachable);
+        assert_eq!(info.http_status, None);
+        assert!(info.message.is_some());
+    }
+
+    fn encryption() -> EncryptionService {
+        EncryptionService::new(b"test_master_key", &EncryptionService::generate_salt()).unwrap()
+    }
+
+    async fn mock_contents(total: i64) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "contents": [], "total": total, "page": 1, "per_page": 1, "has_more": false
+            })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_reconfigured_service_uses_new_base_url() {
+        let old = mock_contents(1).await;
+        let new = mock_contents(2).await;
+        let (_dir, pool) = init_test_database().await;
+        let encryption = encryption();
+        let mut service = keyed_service(pool.clone(), old.uri());
+        assert_eq!(service.test_connection().await.unwrap().total_contents, Some(1));
+
+        let settings = service
+            .configure_api(&encryption, &format!("{}/", new.uri()), None)
+            .await
+            .unwrap();
+        assert_eq!(settings.base_url, new.uri());
+        assert!(settings.has_api_key);
+        assert_eq!(service.test_connection().await.unwrap().total_contents, Some(2));
+
+        // The saved settings survive a restart
+        let reloaded = ContentApiConfig::load(&pool, &encryption).await.unwrap();
+        assert_eq!(reloaded.base_url, new.uri());
+        assert_eq!(reloaded.api_key.as_deref(), Some("good-key"));
+    }
+
+    #[tokio::test]
+    async fn test_api_config_round_trip_encrypts_key() {
+        let (_dir, pool) = init_test_database().await;
+        let encryption = encryption();
+        let default = ContentApiConfig::load(&pool, &encryption).await.unwrap();
+        assert_eq!(default.base_url, ContentApiConfig::default().base_url);
+
+        let mut service = ContentService::new(pool.clone(), default);
+        service
+            .configure_api(&encryption, "https://cms.example.com/api", Some(" secret-key "))
+            .await
+            .unwrap();
+        let stored: Vec<u8> =
+            sqlx::query_scalar("SELECT api_key_encrypted FROM content_api_config WHERE id = 1")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_ne!(stored, b"secret-key");
+        let loaded = ContentApiConfig::load(&pool, &encryption).await.unwrap();
+        assert_eq!(loaded.api_key.as_deref(), Some("secret-key"));
+
+        // An empty key removes the stored one
+        let settings = service
+            .configure_api(&encryption, "https://cms.example.com/api", Some(""))
+            .await
+            .unwrap();
+        assert!(!settings.has_api_key);
+        let loaded = ContentApiConfig::load(&pool, &encryption).await.unwrap();
+        assert_eq!(loaded.api_key, None);
+    }
+
+    #[tokio::test]
+    async fn test_configure_api_rejects_invalid_url() {
+        let (_dir, mut service) = service().await;
+        let encryption = encryption();
+        for url in ["", "localhost:3001", "ftp://cms.example.com", "https://cms.example.com/?x=1"] {
+            assert!(
+                matches!(
+                    service.configure_api(&encryption, url, None).await,
+                    Err(PubCastError::Validation(_))
+                ),
+                "{:?} should be rejected",
+                url
+            );
+        }
+        assert_eq!(service.api_settings().base_url, ContentApiConfig::default().base_url);
+    }
+
+    #[tokio::test]
+    async fn test_report_honors_retry_after() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/contents/r1/published"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/contents/r1/published"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let service = retrying_service(pool, &server);
+        let content = service
+            .sync_remote_content(&remote("r1", None))
+            .await
+            .unwrap();
+
+        let started = std::time::Instant::now();
+        service
+            .report_publish_status(&content.id, "wechat", "https://example.com/p/1")
+            .await
+            .unwrap();
+        assert!(started.elapsed() >= std::time::Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_preview_content_applies_platform_rules() {
+        let (_dir, service) = service().await;
+        let registry = AdapterRegistry::new();
+        let title = "A".repeat(70);
+        let content = service
+            .create_content(CreateContentRequest {
+                title: title.clone(),
+                body: Some("Shipping today #rust".to_string()),
+                tags: Some(vec!["tauri".to_string()]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let xiaohongshu = service
+            .preview_content(&registry, &content.id, "xiaohongshu")
+            .await
+            .unwrap();
+        assert_eq!(xiaohongshu.title, format!("{}...", "A".repeat(17)));
+        assert_eq!(xiaohongshu.tags, ["tauri", "rust"]);
+
+        let wechat = service.preview_content(&registry, &content.id, "wechat").await.unwrap();
+        assert_eq!(wechat.title, format!("{}...", "A".repeat(61)));
+
+        let zhihu = service.preview_content(&registry, &content.id, "zhihu").await.unwrap();
+        assert_eq!(zhihu.title, title);
+
+        // Previewing creates nothing and leaves the content as it was
+        assert_eq!(service.get_content(&content.id).await.unwrap().title, title);
+
+        assert!(matches!(
+            service.preview_content(&registry, "missing", "wechat").await,
+            Err(PubCastError::NotFound(_))
+        ));
+        assert!(matches!(
+            service.preview_content(&registry, &content.id, "myspace").await,
+            Err(PubCastError::NotFound(_))
+        ));
+    }
+
+    async fn create_tagged(service: &ContentService, title: &str, tags: &[&str]) -> Content {
+        service
+            .create_content(CreateContentRequest {
+                title: title.to_string(),
+                tags: Some(tags.iter().map(|t| t.to_string()).collect()),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_and_rename_tags() {
+        let (_dir, service) = service().await;
+        let first = create_tagged(&service, "First", &["rust", "tauri"]).await;
+        let second = create_tagged(&service, "Second", &["rust", "Rust"]).await;
+        let deleted = create_tagged(&service, "Deleted", &["rust", "old"]).await;
+        service.delete_content(&deleted.id, false).await.unwrap();
+
+        assert_eq!(
+            service.list_all_tags().await.unwrap(),
+            [("rust".to_string(), 2), ("Rust".to_string(), 1), ("tauri".to_string(), 1)]
+        );
+
+        assert_eq!(service.rename_tag("tauri", "desktop").await.unwrap(), 1);
+        assert_eq!(
+            service.get_content(&first.id).await.unwrap().tags,
+            Some(vec!["rust".to_string(), "desktop".to_string()])
+        );
+        assert_eq!(
+            service.get_content(&second.id).await.unwrap().tags,
+            Some(vec!["rust".to_string(), "Rust".to_string()])
+        );
+
+        // Renaming onto an existing tag leaves one copy
+        assert_eq!(service.rename_tag("Rust", "rust").await.unwrap(), 1);
+        assert_eq!(
+            service.get_content(&second.id).await.unwrap().tags,
+            Some(vec!["rust".to_string()])
+        );
+
+        assert!(matches!(
+            service.rename_tag("rust", "  ").await,
+            Err(PubCastError::Validation(_))
+        ));
+        assert_eq!(service.rename_tag("missing", "other").await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_merge_tags_dedups_within_content() {
+        let (_dir, service) = service().await;
+        let both = create_tagged(&service, "Both", &["js", "tauri", "javascript"]).await;
+        let one = create_tagged(&service, "One", &["ecmascript"]).await;
+        let untouched = create_tagged(&service, "Untouched", &["rust"]).await;
+        let deleted = create_tagged(&service, "Deleted", &["js"]).await;
+        service.delete_content(&deleted.id, false).await.unwrap();
+
+        let from = vec!["js".to_string(), "ecmascript".to_string()];
+        assert_eq!(service.merge_tags(&from, "javascript").await.unwrap(), 2);
+
+        assert_eq!(
+            service.get_content(&both.id).await.unwrap().tags,
+            Some(vec!["javascript".to_string(), "tauri".to_string()])
+        );
+        assert_eq!(
+            service.get_content(&one.id).await.unwrap().tags,
+            Some(vec!["javascript".to_string()])
+        );
+        let untouched_after = service.get_content(&untouched.id).await.unwrap();
+        assert_eq!(untouched_after.tags, untouched.tags);
+        assert_eq!(untouched_after.updated_at, untouched.updated_at);
+        // Soft-deleted contents keep their tags
+        assert_eq!(
+            service.get_content(&deleted.id).await.unwrap().tags,
+            Some(vec!["js".to_string()])
+        );
+        assert_eq!(
+            service.list_all_tags().await.unwrap(),
+            [("javascript".to_string(), 2), ("rust".to_string(), 1), ("tauri".to_string(), 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_goes_through_configured_proxy() {
+        // A plain HTTP proxy receives the absolute URL; the mock matches its path
+        let proxy = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .and(header("Proxy-Authorization", "Basic dTpzZWNyZXQ="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(listing(&["p1", "p2"])))
+            .expect(1)
+            .mount(&proxy)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let proxies = ProxyService::new(pool.clone(), encryption());
+        let address = proxy.address();
+        let created = proxies
+            .create_proxy(crate::models::CreateProxyRequest {
+                protocol: crate::models::ProxyProtocol::Http,
+                host: address.ip().to_string(),
+                port: address.port(),
+                username: Some("u".to_string()),
+                password: Some("secret".to_string()),
+            })
+            .await
+            .unwrap();
+
+        let settings = SettingsService::new(pool.clone());
+        assert!(select_cms_proxy(&settings, &proxies).await.unwrap().is_none());
+        // A pinned proxy that was deleted is skipped rather than failing
+        settings.set(CMS_PROXY_ID_KEY, "deleted").await.unwrap();
+        assert!(select_cms_proxy(&settings, &proxies).await.unwrap().is_none());
+        settings.set(CMS_PROXY_ID_KEY, &created.id).await.unwrap();
+        let selected = select_cms_proxy(&settings, &proxies).await.unwrap().unwrap();
+        assert_eq!(selected.proxy_id, created.id);
+        assert!(!selected.fallback_direct);
+
+        let config = ContentApiConfig {
+            base_url: "http://cms.invalid".to_string(),
+            conditional_requests: false,
+            max_retries: 0,
+            ..Default::default()
+        };
+        let mut service = ContentService::new(pool, config);
+        service.set_proxy(Some(selected)).unwrap();
+
+        let result = service.sync_all().await.unwrap();
+        assert_eq!(result.synced, 2);
+        assert_eq!(result.proxy_id.as_deref(), Some(created.id.as_str()));
+        assert!(!result.fell_back_to_direct);
+    }
+
+    #[tokio::test]
+    async fn test_sync_falls_back_to_direct_when_proxy_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/contents"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(listing(&["d1"])))
+            .mount(&server)
+            .await;
+
+        // Grab a free port, then close it so the proxy refuses connections
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let dead_proxy = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+        drop(listener);
+
+        let (_dir, pool) = init_test_database().await;
+        let config = ContentApiConfig {
+            base_url: server.uri(),
+            conditional_requests: false,
+            max_retries: 0,
+            ..Default::default()
+        };
+        let mut service = ContentService::new(pool, config);
+        let proxy = CmsProxy {
+            proxy_id: "dead".to_string(),
+            url: dead_proxy,
+            fallback_direct: false,
+        };
+
+        service.set_proxy(Some(proxy.clone())).unwrap();
+        assert!(matches!(service.sync_all().await, Err(PubCastError::Http(_))));
+
+        service
+            .set_proxy(Some(CmsProxy {
+                fallback_direct: true,
+                ..proxy
+            }))
+            .unwrap();
+        let result = service.sync_all().await.unwrap();
+        assert_eq!(result.synced, 1);
+        assert_eq!(result.proxy_id.as_deref(), Some("dead"));
+        assert!(result.fell_back_to_direct);
+
+        // Clearing the proxy goes direct without a fallback
+        service.set_proxy(None).unwrap();
+        let result = service.sync_all().await.unwrap();
+        assert_eq!(result.proxy_id, None);
+        assert!(!result.fell_back_to_direct);
+    }
 }
@@ -0,0 +1,308 @@
+//! System health aggregation
+//!
+//! Combines the status of every subsystem the app depends on into a single
+//! report, so the frontend can answer "is everything OK" with one call.
+
+use std::future::Future;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+use crate::error::Result;
+use crate::services::SidecarStatusInfo;
+
+/// Time allowed for each subsystem check
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Status of a single subsystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+    Down,
+}
+
+/// Health of one subsystem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubsystemHealth {
+    pub status: HealthStatus,
+    pub message: String,
+}
+
+impl SubsystemHealth {
+    fn new(status: HealthStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Aggregated health of all subsystems
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemHealth {
+    /// Worst status across all subsystems
+    pub status: HealthStatus,
+    pub sidecar: SubsystemHealth,
+    pub browser: SubsystemHealth,
+    pub database: SubsystemHealth,
+    pub content_api: SubsystemHealth,
+    pub proxies: SubsystemHealth,
+    pub accounts: SubsystemHealth,
+    pub healthy_proxies: i64,
+    pub authorized_accounts: i64,
+}
+
+/// Run every subsystem check concurrently, each bounded by `timeout`.
+///
+/// `browser` resolves to whether the sidecar's browser service answers its
+/// health endpoint; `content_api` to the HTTP status of a probe request.
+pub async fn check_system_health(
+    pool: &SqlitePool,
+    sidecar: &SidecarStatusInfo,
+    browser: impl Future<Output = Result<bool>>,
+    content_api: impl Future<Output = Result<reqwest::StatusCode>>,
+    timeout: Duration,
+) -> SystemHealth {
+    let (browser, database, content_api, proxies, accounts) = tokio::join!(
+        within(timeout, async { Ok(browser_health(browser.await)) }),
+        within(timeout, database_health(pool)),
+        within(timeout, async { Ok(content_api_health(content_api.await)) }),
+        within(timeout, proxy_health(pool)),
+        within(timeout, account_health(pool)),
+    );
+    let (proxies, healthy_proxies) = proxies.unwrap_or_else(|health| (health, 0));
+    let (accounts, authorized_accounts) = accounts.unwrap_or_else(|health| (health, 0));
+    let browser = browser.unwrap_or_else(|health| health);
+    let database = database.unwrap_or_else(|health| health);
+    let content_api = content_api.unwrap_or_else(|health| health);
+    let sidecar = sidecar_health(sidecar);
+
+    let status = [&sidecar, &browser, &database, &content_api, &proxies, &accounts]
+        .iter()
+        .map(|health| health.status)
+        .max()
+        .unwrap_or(HealthStatus::Ok);
+
+    SystemHealth {
+        status,
+        sidecar,
+        browser,
+        database,
+        content_api,
+        proxies,
+        accounts,
+        healthy_proxies,
+        authorized_accounts,
+    }
+}
+
+/// Await `check`, turning a timeout or error into a `Down` report
+async fn within<T>(
+    timeout: Duration,
+    check: impl Future<Output = Result<T>>,
+) -> std::result::Result<T, SubsystemHealth> {
+    match tokio::time::timeout(timeout, check).await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(SubsystemHealth::new(HealthStatus::Down, e.to_string())),
+        Err(_) => Err(SubsystemHealth::new(
+            HealthStatus::Down,
+            format!("No response within {}ms", timeout.as_millis()),
+        )),
+    }
+}
+
+fn sidecar_health(info: &SidecarStatusInfo) -> SubsystemHealth {
+    let status = match info.state.as_str() {
+        "running" => HealthStatus::Ok,
+        "starting" | "stopping" => HealthStatus::Degraded,
+        _ => HealthStatus::Down,
+    };
+    let message = info
+        .message
+        .clone()
+        .unwrap_or_else(|| format!("Sidecar is {}", info.state));
+    SubsystemHealth::new(status, message)
+}
+
+fn browser_health(reachable: Result<bool>) -> SubsystemHealth {
+    match reachable {
+        Ok(true) => SubsystemHealth::new(HealthStatus::Ok, "Browser service is reachable"),
+        Ok(false) => SubsystemHealth::new(HealthStatus::Down, "Browser service is not responding"),
+        Err(e) => SubsystemHealth::new(HealthStatus::Down, e.to_string()),
+    }
+}
+
+fn content_api_health(status: Result<reqwest::StatusCode>) -> SubsystemHealth {
+    match status {
+        Ok(status) if status.is_success() => {
+            SubsystemHealth::new(HealthStatus::Ok, "CMS API is reachable")
+        }
+        Ok(status) => SubsystemHealth::new(
+            HealthStatus::Degraded,
+            format!("CMS API returned HTTP {}", status),
+        ),
+        Err(e) => SubsystemHealth::new(HealthStatus::Down, e.to_string()),
+    }
+}
+
+async fn database_health(pool: &SqlitePool) -> Result<SubsystemHealth> {
+    sqlx::query("SELECT 1").execute(pool).await?;
+    Ok(SubsystemHealth::new(HealthStatus::Ok, "Database is reachable"))
+}
+
+async fn proxy_health(pool: &SqlitePool) -> Result<(SubsystemHealth, i64)> {
+    let row = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "total!: i64",
+               COALESCE(SUM(status = 'healthy'), 0) as "healthy!: i64"
+        FROM proxies
+        "#
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let health = ratio_health(row.healthy, row.total, "proxies healthy", "No proxies configured");
+    Ok((health, row.healthy))
+}
+
+async fn account_health(pool: &SqlitePool) -> Result<(SubsystemHealth, i64)> {
+    let row = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "total!: i64",
+               COALESCE(SUM(auth_status = 'authorized'), 0) as "authorized!: i64"
+        FROM accounts
+        WHERE deleted_at IS NULL
+        "#
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let health = ratio_health(row.authorized, row.total, "accounts authorized", "No accounts added");
+    Ok((health, row.authorized))
+}
+
+/// Ok when all (or none) are good, degraded when some are, down when none are
+fn ratio_health(good: i64, total: i64, what: &str, empty: &str) -> SubsystemHealth {
+    if total == 0 {
+        return SubsystemHealth::new(HealthStatus::Ok, empty);
+    }
+    let status = if good == total {
+        HealthStatus::Ok
+    } else if good > 0 {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Down
+    };
+    SubsystemHealth::new(status, format!("{} of {} {}", good, total, what))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PubCastError;
+    use crate::infrastructure::database::init_test_database;
+
+    fn sidecar(state: &str) -> SidecarStatusInfo {
+        SidecarStatusInfo {
+            state: state.to_string(),
+            message: None,
+            stage: None,
+            uptime: None,
+            restart_count: None,
+            pid: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_system_health_all_ok() {
+        let (_dir, pool) = init_test_database().await;
+        sqlx::query("INSERT INTO accounts (id, platform, name, auth_status) VALUES ('a1', 'wechat', 'a1', 'authorized')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let health = check_system_health(
+            &pool,
+            &sidecar("running"),
+            async { Ok(true) },
+            async { Ok(reqwest::StatusCode::OK) },
+            HEALTH_CHECK_TIMEOUT,
+        )
+        .await;
+
+        assert_eq!(health.status, HealthStatus::Ok);
+        assert_eq!(health.proxies.message, "No proxies configured");
+        assert_eq!(health.authorized_accounts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_system_health_reports_worst_subsystem() {
+        let (_dir, pool) = init_test_database().await;
+        for (id, port, status) in [("p1", 8080, "healthy"), ("p2", 8081, "unhealthy")] {
+            sqlx::query("INSERT INTO proxies (id, protocol, host, port, status) VALUES (?, 'http', '127.0.0.1', ?, ?)")
+                .bind(id)
+                .bind(port)
+                .bind(status)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let health = check_system_health(
+            &pool,
+            &sidecar("starting"),
+            async { Ok(true) },
+            async { Ok(reqwest::StatusCode::UNAUTHORIZED) },
+            HEALTH_CHECK_TIMEOUT,
+        )
+        .await;
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert_eq!(health.sidecar.status, HealthStatus::Degraded);
+        assert_eq!(health.content_api.status, HealthStatus::Degraded);
+        assert_eq!(health.proxies.status, HealthStatus::Degraded);
+        assert_eq!(health.healthy_proxies, 1);
+
+        let health = check_system_health(
+            &pool,
+            &sidecar("running"),
+            async { Ok(false) },
+            async { Err(PubCastError::Network("connection refused".to_string())) },
+            HEALTH_CHECK_TIMEOUT,
+        )
+        .await;
+        assert_eq!(health.status, HealthStatus::Down);
+        assert_eq!(health.browser.status, HealthStatus::Down);
+        assert_eq!(health.content_api.status, HealthStatus::Down);
+        assert_eq!(health.database.status, HealthStatus::Ok);
+    }
+
+    #[tokio::test]
+    async fn test_system_health_times_out_slow_checks() {
+        let (_dir, pool) = init_test_database().await;
+        let started = std::time::Instant::now();
+
+        let health = check_system_health(
+            &pool,
+            &sidecar("running"),
+            async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok(true)
+            },
+            async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok(reqwest::StatusCode::OK)
+            },
+            Duration::from_millis(100),
+        )
+        .await;
+
+        // Both slow checks ran concurrently and were cut off
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(health.browser.status, HealthStatus::Down);
+        assert_eq!(health.content_api.status, HealthStatus::Down);
+        assert_eq!(health.database.status, HealthStatus::Ok);
+    }
+}
@@ -2,11 +2,17 @@
 
 pub mod account;
 pub mod ai;
+pub mod ai_checker;
 pub mod auth;
 pub mod browser;
 pub mod content;
+pub mod health;
+pub mod notification;
 pub mod proxy;
+pub mod proxy_monitor;
 pub mod scheduler;
+pub mod seed;
+pub mod settings;
 pub mod sidecar_manager;
 pub mod stats;
 
@@ -14,8 +20,16 @@ pub use account::AccountService;
 pub use ai::AIService;
 pub use auth::AuthService;
 pub use browser::BrowserService;
-pub use content::{ContentService, ContentApiConfig};
+pub use content::{
+    apply_cms_proxy, select_cms_proxy, CmsProxy, ConnectionInfo, ConnectionStatus, ContentApiSettings, ContentService,
+    ContentApiConfig,
+};
+pub use health::{HealthStatus, SubsystemHealth, SystemHealth};
+pub use notification::NotificationService;
 pub use proxy::ProxyService;
+pub use proxy_monitor::ProxyHealthMonitor;
 pub use scheduler::SchedulerService;
-pub use sidecar_manager::{LogFileInfo, SidecarManager, SidecarStatusInfo, SidecarError};
+pub use seed::ensure_schema_seed;
+pub use settings::SettingsService;
+pub use sidecar_manager::{LogEntry, LogFileInfo, LogLevel, LogSearchMatch, RestartHook, SidecarManager, SidecarStatusInfo, SidecarError};
 pub use stats::StatsService;
@@ -0,0 +1,266 @@
+//! Outbound task notifications
+//!
+//! POSTs a short JSON summary to every configured webhook (Slack, Discord or
+//! anything else accepting JSON) when a distribution task finishes. Delivery
+//! is best-effort: a failing webhook is logged and never affects the task.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use crate::error::{PubCastError, Result};
+use crate::infrastructure::http::HttpClientFactory;
+use crate::models::{DistributionTask, DistributionTaskStatus};
+use crate::services::SettingsService;
+
+/// Setting holding the list of webhook URLs
+pub const WEBHOOKS_SETTING_KEY: &str = "notifications.webhooks";
+/// Timeout for a single webhook delivery
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Payload POSTed to webhooks when a task reaches a final status
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskNotification {
+    pub task_id: String,
+    pub name: Option<String>,
+    pub status: DistributionTaskStatus,
+    pub completed_jobs: i64,
+    pub failed_jobs: i64,
+}
+
+impl From<&DistributionTask> for TaskNotification {
+    fn from(task: &DistributionTask) -> Self {
+        Self {
+            task_id: task.id.clone(),
+            name: task.name.clone(),
+            status: task.status.clone(),
+            completed_jobs: task.completed_jobs,
+            failed_jobs: task.failed_jobs,
+        }
+    }
+}
+
+/// Notification service delivering task summaries to webhooks
+#[derive(Clone)]
+pub struct NotificationService {
+    pool: SqlitePool,
+    http_client: reqwest::Client,
+}
+
+impl NotificationService {
+    /// Create a new notification service
+    pub fn new(pool: SqlitePool) -> Self {
+        Self::with_http(pool, &HttpClientFactory::new())
+    }
+
+    /// Create a notification service sending through clients from `http`
+    pub fn with_http(pool: SqlitePool, http: &HttpClientFactory) -> Self {
+        Self {
+            pool,
+            http_client: http
+                .client_with_timeout(WEBHOOK_TIMEOUT)
+                .expect("Failed to create HTTP client"),
+        }
+    }
+
+    /// Configured webhook URLs
+    pub async fn webhooks(&self) -> Result<Vec<String>> {
+        SettingsService::new(self.pool.clone())
+            .get_or(WEBHOOKS_SETTING_KEY, Vec::new())
+            .await
+    }
+
+    /// Replace the webhook URLs; blanks and duplicates are dropped
+    pub async fn set_webhooks(&self, urls: &[String]) -> Result<Vec<String>> {
+        let mut webhooks: Vec<String> = Vec::new();
+        for url in urls
+            .iter()
+            .map(|url| url.trim())
+            .filter(|url| !url.is_empty())
+        {
+            let url = validate_webhook_url(url)?;
+            if !webhooks.contains(&url) {
+                webhooks.push(url);
+            }
+        }
+
+        SettingsService::new(self.pool.clone())
+            .set(WEBHOOKS_SETTING_KEY, &webhooks)
+            .await?;
+        Ok(webhooks)
+    }
+
+    /// Send `task`'s summary to every webhook. Returns how many accepted it;
+    /// failures are only logged.
+    pub async fn notify_task_finished(&self, task: &DistributionTask) -> usize {
+        let webhooks = match self.webhooks().await {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                tracing::warn!("Failed to load notification webhooks: {}", e);
+                return 0;
+            }
+        };
+
+        let notification = TaskNotification::from(task);
+        let results =
+            futures::future::join_all(webhooks.iter().map(|url| self.post(url, &notification)))
+                .await;
+
+        webhooks
+            .iter()
+            .zip(results)
+            .filter(|(url, result)| match result {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::warn!("Webhook {} failed for task {}: {}", url, task.id, e);
+                    false
+                }
+            })
+            .count()
+    }
+
+    /// Send a sample notification to `url` so the user can check it works
+    pub async fn test_webhook(&self, url: &str) -> Result<()> {
+        let url = validate_webhook_url(url.trim())?;
+        let notification = TaskNotification {
+            task_id: "test".to_string(),
+            name: Some("PubCast test notification".to_string()),
+            status: DistributionTaskStatus::Completed,
+            completed_jobs: 1,
+            failed_jobs: 0,
+        };
+        self.post(&url, &notification).await
+    }
+
+    async fn post(&self, url: &str, notification: &TaskNotification) -> Result<()> {
+        self.http_client
+            .post(url)
+            .json(notification)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Check a webhook URL is an absolute http(s) URL
+fn validate_webhook_url(url: &str) -> Result<String> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| PubCastError::Validation(format!("Invalid webhook URL {:?}: {}", url, e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(PubCastError::Validation(format!(
+            "Webhook URL must use http or https: {}",
+            url
+        )));
+    }
+    Ok(url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn finished_task() -> DistributionTask {
+        DistributionTask {
+            id: "t1".to_string(),
+            content_id: "c1".to_string(),
+            name: Some("Launch post".to_string()),
+            status: DistributionTaskStatus::Completed,
+            target_accounts: vec!["a1".to_string(), "a2".to_string()],
+            schedule_type: crate::models::ScheduleType::Immediate,
+            scheduled_at: None,
+            cron_expression: None,
+            next_run_at: None,
+            started_at: None,
+            completed_at: Some(0),
+            total_jobs: 2,
+            completed_jobs: 1,
+            failed_jobs: 1,
+            error_message: None,
+            dry_run: false,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notifies_every_webhook_and_tolerates_failures() {
+        let server = MockServer::start().await;
+        let payload = serde_json::json!({
+            "task_id": "t1",
+            "name": "Launch post",
+            "status": "completed",
+            "completed_jobs": 1,
+            "failed_jobs": 1,
+        });
+        for hook in ["/slack", "/discord"] {
+            Mock::given(method("POST"))
+                .and(path(hook))
+                .and(body_json(&payload))
+                .respond_with(ResponseTemplate::new(200))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("POST"))
+            .and(path("/broken"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let service = NotificationService::new(pool);
+        let hook = |name: &str| format!("{}/{}", server.uri(), name);
+        let urls = [
+            hook("slack"),
+            hook("broken"),
+            hook("discord"),
+            hook("slack"),
+            " ".to_string(),
+        ];
+        let stored = service.set_webhooks(&urls).await.unwrap();
+        assert_eq!(stored.len(), 3);
+        assert_eq!(service.webhooks().await.unwrap(), stored);
+
+        assert_eq!(service.notify_task_finished(&finished_task()).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_validation_and_test_delivery() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let service = NotificationService::new(pool);
+        assert!(matches!(
+            service
+                .set_webhooks(&["ftp://example.com/hook".to_string()])
+                .await,
+            Err(PubCastError::Validation(_))
+        ));
+        assert!(matches!(
+            service.test_webhook("not a url").await,
+            Err(PubCastError::Validation(_))
+        ));
+
+        service
+            .test_webhook(&format!("{}/hook", server.uri()))
+            .await
+            .unwrap();
+        assert!(matches!(
+            service
+                .test_webhook(&format!("{}/missing", server.uri()))
+                .await,
+            Err(PubCastError::Http(_))
+        ));
+    }
+}
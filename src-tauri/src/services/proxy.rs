@@ -1,21 +1,46 @@
 //! Proxy pool management service
 
+use futures::stream::{self, StreamExt};
 use rand::seq::SliceRandom;
 use sqlx::SqlitePool;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use uuid::Uuid;
 
 use crate::error::{PubCastError, Result};
 use crate::infrastructure::encryption::EncryptionService;
+use crate::infrastructure::http::HttpClientFactory;
+use crate::infrastructure::http_log;
 use crate::models::{
-    CreateProxyRequest, Proxy, ProxyHealthResult, ProxyProtocol, ProxyStatus, ProxyStrategy,
+    CreateProxyRequest, ImportResult, Proxy, ProxyHealthRecord, ProxyHealthResult, ProxyProtocol, ProxyStatus,
+    ProxyStrategy, ProxyUsageRecord, UpdateProxyRequest,
 };
+use crate::services::SettingsService;
+
+/// Default geo-IP endpoint; `{ip}` is replaced with the proxy exit IP
+pub const DEFAULT_GEO_ENDPOINT: &str = "http://ip-api.com/json/{ip}";
+/// Setting holding a custom geo-IP endpoint (ip-api.com or ipinfo.io style,
+/// with an `{ip}` placeholder)
+pub const GEO_ENDPOINT_SETTING_KEY: &str = "proxy.geo_endpoint";
+/// Timeout for geo-IP lookups in seconds
+const GEO_LOOKUP_TIMEOUT_SECS: u64 = 5;
+/// Number of health history rows kept per proxy
+const PROXY_HISTORY_RETENTION: i64 = 500;
+/// Number of usage rows kept per proxy
+const PROXY_USAGE_RETENTION: i64 = 500;
+/// Default number of proxies checked in parallel by `check_all_proxies`
+pub const DEFAULT_HEALTH_CHECK_CONCURRENCY: usize = 10;
+/// How far back failed checks count for `ProxyStrategy::LeastFailures`
+const RECENT_FAILURE_WINDOW_SECS: i64 = 24 * 60 * 60;
+/// Setting holding when `check_all_proxies` last finished
+pub const LAST_FULL_CHECK_SETTING_KEY: &str = "proxy.health_check.last_full_check_at";
 
 /// Proxy pool service for managing proxies
 pub struct ProxyService {
     pool: SqlitePool,
     encryption: EncryptionService,
     round_robin_index: AtomicUsize,
+    http: HttpClientFactory,
 }
 
 impl ProxyService {
@@ -25,16 +50,28 @@ impl ProxyService {
             pool,
             encryption,
             round_robin_index: AtomicUsize::new(0),
+            http: HttpClientFactory::new(),
         }
     }
 
+    /// Build HTTP clients with `http`; geo lookups go through its global proxy
+    pub fn with_http(mut self, http: HttpClientFactory) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Replace the encryption service after a master key rotation
+    pub fn set_encryption(&mut self, encryption: EncryptionService) {
+        self.encryption = encryption;
+    }
+
     /// List all proxies
     pub async fn list_proxies(&self) -> Result<Vec<Proxy>> {
         let rows = sqlx::query!(
             r#"
             SELECT id, protocol, host, port, username, status,
                    last_check_at, last_check_ip, last_check_location,
-                   fail_count, created_at, updated_at
+                   fail_count, last_latency_ms, last_used_at, created_at, updated_at
             FROM proxies
             ORDER BY created_at DESC
             "#
@@ -55,6 +92,8 @@ impl ProxyService {
                 last_check_ip: row.last_check_ip,
                 last_check_location: row.last_check_location,
                 fail_count: row.fail_count,
+                last_latency_ms: row.last_latency_ms,
+                last_used_at: row.last_used_at,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
             })
@@ -69,7 +108,7 @@ impl ProxyService {
             r#"
             SELECT id, protocol, host, port, username, status,
                    last_check_at, last_check_ip, last_check_location,
-                   fail_count, created_at, updated_at
+                   fail_count, last_latency_ms, last_used_at, created_at, updated_at
             FROM proxies WHERE id = ?
             "#,
             id
@@ -89,6 +128,8 @@ impl ProxyService {
             last_check_ip: row.last_check_ip,
             last_check_location: row.last_check_location,
             fail_count: row.fail_count,
+            last_latency_ms: row.last_latency_ms,
+            last_used_at: row.last_used_at,
             created_at: row.created_at,
             updated_at: row.updated_at,
         })
@@ -130,6 +171,111 @@ impl ProxyService {
         self.get_proxy(&id).await
     }
 
+    /// Update a proxy's connection details.
+    ///
+    /// Any change resets the status to unknown so the proxy gets re-checked.
+    pub async fn update_proxy(&self, id: &str, req: UpdateProxyRequest) -> Result<Proxy> {
+        let now = chrono::Utc::now().timestamp();
+
+        // Get existing proxy first
+        let existing = self.get_proxy(id).await?;
+
+        let changed = req.protocol.is_some()
+            || req.host.is_some()
+            || req.port.is_some()
+            || req.username.is_some()
+            || req.password.is_some();
+        if !changed {
+            return Ok(existing);
+        }
+
+        let protocol = req.protocol.unwrap_or(existing.protocol).to_string();
+        let host = req.host.unwrap_or(existing.host);
+        let port = req.port.unwrap_or(existing.port);
+        let username = req.username.unwrap_or(existing.username);
+        let status = ProxyStatus::Unknown.to_string();
+
+        // Only re-encrypt when a new password is supplied
+        if let Some(password) = &req.password {
+            let (encrypted, nonce) = self.encryption.encrypt(password.as_bytes())?;
+
+            sqlx::query!(
+                r#"
+                UPDATE proxies
+                SET protocol = ?, host = ?, port = ?, username = ?,
+                    password_encrypted = ?, password_nonce = ?,
+                    status = ?, updated_at = ?
+                WHERE id = ?
+                "#,
+                protocol,
+                host,
+                port,
+                username,
+                encrypted,
+                nonce,
+                status,
+                now,
+                id
+            )
+            .execute(&self.pool)
+            .await?;
+        } else {
+            sqlx::query!(
+                r#"
+                UPDATE proxies
+                SET protocol = ?, host = ?, port = ?, username = ?, status = ?, updated_at = ?
+                WHERE id = ?
+                "#,
+                protocol,
+                host,
+                port,
+                username,
+                status,
+                now,
+                id
+            )
+            .execute(&self.pool)
+            .await?;
+        }
+
+        self.get_proxy(id).await
+    }
+
+    /// Get the decrypted password for a proxy
+    pub async fn get_password(&self, id: &str) -> Result<Option<String>> {
+        let row = sqlx::query!(
+            "SELECT password_encrypted, password_nonce FROM proxies WHERE id = ?",
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| PubCastError::NotFound(format!("Proxy not found: {}", id)))?;
+
+        match (&row.password_encrypted, &row.password_nonce) {
+            (Some(encrypted), Some(nonce)) => {
+                let decrypted = self.encryption.decrypt(encrypted, nonce)?;
+                let password = String::from_utf8(decrypted)
+                    .map_err(|e| PubCastError::Encryption(e.to_string()))?;
+                Ok(Some(password))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Proxy URL with the decrypted password filled in, for building clients
+    pub async fn connect_url(&self, proxy: &Proxy) -> Result<String> {
+        let mut url = reqwest::Url::parse(&proxy.url())
+            .map_err(|e| PubCastError::Configuration(format!("Invalid proxy URL: {}", e)))?;
+
+        if let Some(password) = self.get_password(&proxy.id).await? {
+            url.set_password(Some(&password)).map_err(|_| {
+                PubCastError::Configuration(format!("Proxy {} cannot carry a password", proxy.id))
+            })?;
+        }
+
+        Ok(url.to_string())
+    }
+
     /// Delete a proxy
     pub async fn delete_proxy(&self, id: &str) -> Result<()> {
         let result = sqlx::query!("DELETE FROM proxies WHERE id = ?", id)
@@ -152,22 +298,26 @@ impl ProxyService {
             "unhealthy"
         };
 
+        let latency_ms = result.latency_ms.map(|ms| ms as i64);
+        let mut tx = self.pool.begin().await?;
+
         if result.is_healthy {
             sqlx::query!(
                 r#"
                 UPDATE proxies 
                 SET status = ?, last_check_at = ?, last_check_ip = ?, 
-                    last_check_location = ?, fail_count = 0, updated_at = ?
+                    last_check_location = ?, last_latency_ms = ?, fail_count = 0, updated_at = ?
                 WHERE id = ?
                 "#,
                 status,
                 now,
                 result.exit_ip,
                 result.location,
+                latency_ms,
                 now,
                 result.proxy_id
             )
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?;
         } else {
             sqlx::query!(
@@ -181,20 +331,91 @@ impl ProxyService {
                 now,
                 result.proxy_id
             )
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?;
         }
 
+        // Record history and trim to the retention cap in the same transaction
+        sqlx::query!(
+            r#"
+            INSERT INTO proxy_health_history (proxy_id, checked_at, is_healthy, exit_ip, latency_ms, error)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            result.proxy_id,
+            now,
+            result.is_healthy,
+            result.exit_ip,
+            latency_ms,
+            result.error
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM proxy_health_history
+            WHERE proxy_id = ? AND id NOT IN (
+                SELECT id FROM proxy_health_history
+                WHERE proxy_id = ?
+                ORDER BY id DESC
+                LIMIT ?
+            )
+            "#,
+            result.proxy_id,
+            result.proxy_id,
+            PROXY_HISTORY_RETENTION
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
         Ok(())
     }
 
+    /// Get recent health check history for a proxy (newest first)
+    pub async fn get_proxy_history(
+        &self,
+        proxy_id: &str,
+        limit: i64,
+    ) -> Result<Vec<ProxyHealthRecord>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id AS "id!", proxy_id, checked_at, is_healthy, exit_ip, latency_ms, error
+            FROM proxy_health_history
+            WHERE proxy_id = ?
+            ORDER BY id DESC
+            LIMIT ?
+            "#,
+            proxy_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let records = rows
+            .into_iter()
+            .map(|row| ProxyHealthRecord {
+                id: row.id,
+                proxy_id: row.proxy_id,
+                checked_at: row.checked_at,
+                is_healthy: row.is_healthy != 0,
+                exit_ip: row.exit_ip,
+                latency_ms: row.latency_ms,
+                error: row.error,
+            })
+            .collect();
+
+        Ok(records)
+    }
+
     /// Get healthy proxies
     pub async fn get_healthy_proxies(&self) -> Result<Vec<Proxy>> {
         let rows = sqlx::query!(
             r#"
             SELECT id, protocol, host, port, username, status,
                    last_check_at, last_check_ip, last_check_location,
-                   fail_count, created_at, updated_at
+                   fail_count, last_latency_ms, last_used_at, created_at, updated_at
             FROM proxies
             WHERE status = 'healthy'
             ORDER BY fail_count ASC, last_check_at DESC
@@ -216,6 +437,8 @@ impl ProxyService {
                 last_check_ip: row.last_check_ip,
                 last_check_location: row.last_check_location,
                 fail_count: row.fail_count,
+                last_latency_ms: row.last_latency_ms,
+                last_used_at: row.last_used_at,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
             })
@@ -224,7 +447,7 @@ impl ProxyService {
         Ok(proxies)
     }
 
-    /// Select a proxy based on strategy
+    /// Select a proxy based on strategy, stamping its `last_used_at`
     pub async fn select_proxy(&self, strategy: &ProxyStrategy) -> Result<Option<Proxy>> {
         let healthy_proxies = self.get_healthy_proxies().await?;
 
@@ -245,30 +468,168 @@ impl ProxyService {
                 let mut rng = rand::thread_rng();
                 healthy_proxies.choose(&mut rng).cloned()
             }
+            ProxyStrategy::LowestLatency => {
+                // Proxies without a measurement sort last
+                let best = best_candidates(&healthy_proxies, |p| {
+                    p.last_latency_ms.unwrap_or(i64::MAX)
+                });
+                self.round_robin_among(&best)
+            }
+            ProxyStrategy::LeastFailures => {
+                // `fail_count` resets on every passing check, so every healthy
+                // proxy would tie at zero; rank by recent history instead
+                let since = chrono::Utc::now().timestamp() - RECENT_FAILURE_WINDOW_SECS;
+                let failures = self.recent_failure_counts(since).await?;
+                let best = best_candidates(&healthy_proxies, |p| {
+                    failures.get(&p.id).copied().unwrap_or(0)
+                });
+                self.round_robin_among(&best)
+            }
+        };
+
+        let Some(mut selected) = selected else {
+            return Ok(None);
         };
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query!(
+            "UPDATE proxies SET last_used_at = ? WHERE id = ?",
+            now,
+            selected.id
+        )
+        .execute(&self.pool)
+        .await?;
+        selected.last_used_at = Some(now);
+
+        Ok(Some(selected))
+    }
+
+    /// Record that `account_id` was routed through a proxy, e.g. for a browser
+    /// launch, so flagged accounts can be traced back to their proxy
+    pub async fn record_usage(&self, proxy_id: &str, account_id: &str) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query!(
+            "UPDATE proxies SET last_used_at = ? WHERE id = ?",
+            now,
+            proxy_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(PubCastError::NotFound(format!("Proxy not found: {}", proxy_id)));
+        }
+
+        sqlx::query!(
+            "INSERT INTO proxy_usage (proxy_id, account_id, used_at) VALUES (?, ?, ?)",
+            proxy_id,
+            account_id,
+            now
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM proxy_usage
+            WHERE proxy_id = ? AND id NOT IN (
+                SELECT id FROM proxy_usage
+                WHERE proxy_id = ?
+                ORDER BY id DESC
+                LIMIT ?
+            )
+            "#,
+            proxy_id,
+            proxy_id,
+            PROXY_USAGE_RETENTION
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Get the accounts recently routed through a proxy (newest first)
+    pub async fn get_proxy_usage(
+        &self,
+        proxy_id: &str,
+        limit: i64,
+    ) -> Result<Vec<ProxyUsageRecord>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT u.id AS "id!", u.proxy_id, u.account_id, a.name AS "account_name?", u.used_at
+            FROM proxy_usage u
+            LEFT JOIN accounts a ON a.id = u.account_id
+            WHERE u.proxy_id = ?
+            ORDER BY u.id DESC
+            LIMIT ?
+            "#,
+            proxy_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(selected)
+        let records = rows
+            .into_iter()
+            .map(|row| ProxyUsageRecord {
+                id: row.id,
+                proxy_id: row.proxy_id,
+                account_id: row.account_id,
+                account_name: row.account_name,
+                used_at: row.used_at,
+            })
+            .collect();
+
+        Ok(records)
+    }
+
+    /// Failed health checks per proxy since `since`, from the check history
+    async fn recent_failure_counts(&self, since: i64) -> Result<HashMap<String, i64>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT proxy_id, COUNT(*) AS "failures!: i64"
+            FROM proxy_health_history
+            WHERE is_healthy = 0 AND checked_at >= ?
+            GROUP BY proxy_id
+            "#,
+            since
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.proxy_id, row.failures))
+            .collect())
+    }
+
+    /// Rotate through tied candidates so a single node isn't hammered
+    fn round_robin_among(&self, candidates: &[&Proxy]) -> Option<Proxy> {
+        if candidates.is_empty() {
+            return None;
+        }
+        let idx = self.round_robin_index.fetch_add(1, Ordering::Relaxed);
+        candidates.get(idx % candidates.len()).map(|p| (*p).clone())
     }
 
     /// Check proxy health (basic connectivity test)
     pub async fn check_proxy_health(&self, proxy_id: &str) -> Result<ProxyHealthResult> {
         let proxy = self.get_proxy(proxy_id).await?;
 
-        // Build proxy URL
-        let proxy_url = proxy.url();
+        // Probe with the credentials accounts would use, password included
+        let proxy_url = self.connect_url(&proxy).await?;
 
         // Try to connect through proxy to IP check service
-        let client = reqwest::Client::builder()
-            .proxy(reqwest::Proxy::all(&proxy_url).map_err(|e| {
-                PubCastError::Configuration(format!("Invalid proxy URL: {}", e))
-            })?)
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .map_err(|e| PubCastError::Http(e))?;
+        let client = self
+            .http
+            .client_with_proxy(&proxy_url, std::time::Duration::from_secs(10))?;
 
         let start = std::time::Instant::now();
 
-        match client.get("https://api.ipify.org?format=json").send().await {
+        match http_log::send(client.get("https://api.ipify.org?format=json")).await {
             Ok(response) => {
                 let latency_ms = start.elapsed().as_millis() as u64;
 
@@ -280,11 +641,17 @@ impl ProxyService {
                         .ok()
                         .and_then(|v| v.get("ip").and_then(|ip| ip.as_str().map(String::from)));
 
+                    // Geo lookup is best-effort and never affects health
+                    let location = match &ip_result {
+                        Some(ip) => self.lookup_location(ip).await,
+                        None => None,
+                    };
+
                     let result = ProxyHealthResult {
                         proxy_id: proxy_id.to_string(),
                         is_healthy: true,
                         exit_ip: ip_result,
-                        location: None, // Could add geo lookup later
+                        location,
                         latency_ms: Some(latency_ms),
                         error: None,
                     };
@@ -321,10 +688,127 @@ impl ProxyService {
         }
     }
 
+    /// Check every proxy with at most `concurrency` checks in flight.
+    ///
+    /// Individual failures are reported as unhealthy results; every proxy gets a result.
+    pub async fn check_all_proxies(&self, concurrency: usize) -> Result<Vec<ProxyHealthResult>> {
+        let proxies = self.list_proxies().await?;
+
+        let results = stream::iter(proxies)
+            .map(|proxy| async move {
+                match self.check_proxy_health(&proxy.id).await {
+                    Ok(result) => result,
+                    Err(e) => {
+                        tracing::warn!("Health check for proxy {} failed: {}", proxy.id, e);
+                        let result = ProxyHealthResult {
+                            proxy_id: proxy.id,
+                            is_healthy: false,
+                            exit_ip: None,
+                            location: None,
+                            latency_ms: None,
+                            error: Some(e.to_string()),
+                        };
+                        if let Err(e) = self.update_proxy_status(&result).await {
+                            tracing::warn!("Failed to record proxy status: {}", e);
+                        }
+                        result
+                    }
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let now = chrono::Utc::now().timestamp();
+        SettingsService::new(self.pool.clone())
+            .set(LAST_FULL_CHECK_SETTING_KEY, &now)
+            .await?;
+        Ok(results)
+    }
+
+    /// When every proxy was last checked, `None` if never
+    pub async fn last_full_check(&self) -> Result<Option<i64>> {
+        SettingsService::new(self.pool.clone())
+            .get(LAST_FULL_CHECK_SETTING_KEY)
+            .await
+    }
+
+    /// Look up a compact "City, Region, Country" location for an IP address
+    /// through the endpoint in the `proxy.geo_endpoint` setting.
+    ///
+    /// Returns `None` on any error or timeout.
+    pub async fn lookup_location(&self, ip: &str) -> Option<String> {
+        let endpoint = SettingsService::new(self.pool.clone())
+            .get_or(GEO_ENDPOINT_SETTING_KEY, DEFAULT_GEO_ENDPOINT.to_string())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Ignoring geo-IP endpoint setting: {}", e);
+                DEFAULT_GEO_ENDPOINT.to_string()
+            });
+        let url = endpoint.replace("{ip}", ip);
+        let client = self
+            .http
+            .client_with_timeout(std::time::Duration::from_secs(GEO_LOOKUP_TIMEOUT_SECS))
+            .ok()?;
+
+        let response = match http_log::send(client.get(&url)).await {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                tracing::warn!("Geo lookup for {} returned {}", ip, response.status());
+                return None;
+            }
+            Err(e) => {
+                tracing::warn!("Geo lookup for {} failed: {}", ip, e);
+                return None;
+            }
+        };
+
+        let body = response.json::<serde_json::Value>().await.ok()?;
+        Self::format_location(&body)
+    }
+
+    /// Build a compact location string from an ip-api.com or ipinfo.io response
+    fn format_location(body: &serde_json::Value) -> Option<String> {
+        // ip-api.com reports lookup failures in-band
+        if body.get("status").and_then(|s| s.as_str()) == Some("fail") {
+            return None;
+        }
+
+        let field = |key: &str| {
+            body.get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+
+        let city = field("city");
+        let region = field("regionName").or_else(|| field("region"));
+        let country = field("country");
+
+        let mut parts: Vec<&str> = Vec::new();
+        for part in [city, region, country].into_iter().flatten() {
+            if !parts.contains(&part) {
+                parts.push(part);
+            }
+        }
+
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(", "))
+        }
+    }
+
     /// Batch import proxies from text
     /// Format: protocol://[user:pass@]host:port (one per line)
-    pub async fn import_proxies(&self, text: &str) -> Result<Vec<Proxy>> {
-        let mut imported = Vec::new();
+    ///
+    /// Lines matching an existing (protocol, host, port, username) are skipped.
+    pub async fn import_proxies(&self, text: &str) -> Result<ImportResult> {
+        let mut result = ImportResult {
+            imported: Vec::new(),
+            skipped: 0,
+            invalid: 0,
+        };
 
         for line in text.lines() {
             let line = line.trim();
@@ -332,19 +816,46 @@ impl ProxyService {
                 continue;
             }
 
-            if let Some(req) = Self::parse_proxy_line(line) {
-                match self.create_proxy(req).await {
-                    Ok(proxy) => imported.push(proxy),
-                    Err(e) => {
-                        tracing::warn!("Failed to import proxy '{}': {}", line, e);
-                    }
-                }
-            } else {
+            let Some(req) = Self::parse_proxy_line(line) else {
                 tracing::warn!("Invalid proxy format: {}", line);
+                result.invalid += 1;
+                continue;
+            };
+
+            if self.find_duplicate(&req).await?.is_some() {
+                result.skipped += 1;
+                continue;
+            }
+
+            match self.create_proxy(req).await {
+                Ok(proxy) => result.imported.push(proxy),
+                Err(e) => {
+                    tracing::warn!("Failed to import proxy '{}': {}", line, e);
+                    result.invalid += 1;
+                }
             }
         }
 
-        Ok(imported)
+        Ok(result)
+    }
+
+    /// Find an existing proxy with the same protocol, host, port, and username
+    async fn find_duplicate(&self, req: &CreateProxyRequest) -> Result<Option<String>> {
+        let protocol = req.protocol.to_string();
+        let id = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM proxies
+            WHERE protocol = ? AND host = ? AND port = ? AND COALESCE(username, '') = COALESCE(?, '')
+            "#,
+            protocol,
+            req.host,
+            req.port,
+            req.username
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(id)
     }
 
     /// Parse a proxy line into CreateProxyRequest
@@ -352,18 +863,13 @@ impl ProxyService {
         // Try to parse as URL
         let url = url::Url::parse(line).ok()?;
 
-        let protocol = match url.scheme() {
-            "http" => ProxyProtocol::Http,
-            "https" => ProxyProtocol::Https,
-            "socks5" => ProxyProtocol::Socks5,
-            _ => return None,
-        };
+        let protocol: ProxyProtocol = url.scheme().parse().ok()?;
 
         let host = url.host_str()?.to_string();
         let port = url.port().unwrap_or(match protocol {
             ProxyProtocol::Http => 80,
             ProxyProtocol::Https => 443,
-            ProxyProtocol::Socks5 => 1080,
+            ProxyProtocol::Socks4 | ProxyProtocol::Socks5 | ProxyProtocol::Socks5h => 1080,
         });
 
         let username = if url.username().is_empty() {
@@ -383,3 +889,680 @@ impl ProxyService {
         })
     }
 }
+
+/// Return all proxies sharing the minimum value of `key`
+fn best_candidates<K: Ord + Copy>(proxies: &[Proxy], key: impl Fn(&Proxy) -> K) -> Vec<&Proxy> {
+    let Some(best) = proxies.iter().map(&key).min() else {
+        return Vec::new();
+    };
+    proxies.iter().filter(|p| key(p) == best).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_encryption() -> EncryptionService {
+        let salt = EncryptionService::generate_salt();
+        EncryptionService::new(b"test_master_key_32_bytes_long!!!", &salt).unwrap()
+    }
+
+    fn proxy(id: &str, latency_ms: Option<i64>, fail_count: i64) -> Proxy {
+        Proxy {
+            id: id.to_string(),
+            protocol: ProxyProtocol::Http,
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+            username: None,
+            status: ProxyStatus::Healthy,
+            last_check_at: None,
+            last_check_ip: None,
+            last_check_location: None,
+            fail_count,
+            last_latency_ms: latency_ms,
+            last_used_at: None,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    fn ids(proxies: &[&Proxy]) -> Vec<String> {
+        proxies.iter().map(|p| p.id.clone()).collect()
+    }
+
+    #[test]
+    fn test_best_candidates_lowest_latency() {
+        let proxies = vec![
+            proxy("slow", Some(800), 0),
+            proxy("fast", Some(50), 3),
+            proxy("unmeasured", None, 0),
+        ];
+        let best = best_candidates(&proxies, |p| p.last_latency_ms.unwrap_or(i64::MAX));
+        assert_eq!(ids(&best), vec!["fast"]);
+    }
+
+    #[tokio::test]
+    async fn test_select_least_failures_uses_recent_history() {
+        let (_dir, pool) = init_test_database().await;
+        let service = ProxyService::new(pool, test_encryption());
+
+        let check = |proxy_id: &str, is_healthy: bool| ProxyHealthResult {
+            proxy_id: proxy_id.to_string(),
+            is_healthy,
+            exit_ip: None,
+            location: None,
+            latency_ms: is_healthy.then_some(100),
+            error: (!is_healthy).then(|| "timeout".to_string()),
+        };
+
+        // "flaky" (port 8001) failed twice before recovering; all three are
+        // now healthy with `fail_count` reset to zero
+        let mut created = Vec::new();
+        for (port, failures) in [(8001, 2), (8002, 0), (8003, 0)] {
+            let p = service
+                .create_proxy(CreateProxyRequest {
+                    protocol: ProxyProtocol::Http,
+                    host: "127.0.0.1".to_string(),
+                    port,
+                    username: None,
+                    password: None,
+                })
+                .await
+                .unwrap();
+            for is_healthy in std::iter::repeat(false).take(failures).chain([true]) {
+                service
+                    .update_proxy_status(&check(&p.id, is_healthy))
+                    .await
+                    .unwrap();
+            }
+            created.push(p);
+        }
+        let flaky = service.get_proxy(&created[0].id).await.unwrap();
+        assert_eq!(flaky.status, ProxyStatus::Healthy);
+        assert_eq!(flaky.fail_count, 0);
+
+        let mut picked = std::collections::HashSet::new();
+        for _ in 0..4 {
+            let selected = service
+                .select_proxy(&ProxyStrategy::LeastFailures)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_ne!(selected.port, 8001);
+            picked.insert(selected.port);
+        }
+        assert_eq!(picked.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_select_lowest_latency_rotates_on_ties() {
+        let (_dir, pool) = init_test_database().await;
+        let service = ProxyService::new(pool, test_encryption());
+
+        let mut created = Vec::new();
+        for (port, latency) in [(8001, 120), (8002, 40), (8003, 40)] {
+            let p = service
+                .create_proxy(CreateProxyRequest {
+                    protocol: ProxyProtocol::Http,
+                    host: "127.0.0.1".to_string(),
+                    port,
+                    username: None,
+                    password: None,
+                })
+                .await
+                .unwrap();
+            service
+                .update_proxy_status(&ProxyHealthResult {
+                    proxy_id: p.id.clone(),
+                    is_healthy: true,
+                    exit_ip: None,
+                    location: None,
+                    latency_ms: Some(latency),
+                    error: None,
+                })
+                .await
+                .unwrap();
+            created.push(p);
+        }
+
+        let stored = service.get_proxy(&created[1].id).await.unwrap();
+        assert_eq!(stored.last_latency_ms, Some(40));
+
+        let mut picked = std::collections::HashSet::new();
+        for _ in 0..4 {
+            let selected = service
+                .select_proxy(&ProxyStrategy::LowestLatency)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_ne!(selected.port, 8001);
+            picked.insert(selected.port);
+        }
+        assert_eq!(picked.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_proxy_line_socks_schemes() {
+        let socks4 = ProxyService::parse_proxy_line("socks4://10.0.0.1").unwrap();
+        assert_eq!(socks4.protocol, ProxyProtocol::Socks4);
+        assert_eq!(socks4.port, 1080);
+
+        let socks5h = ProxyService::parse_proxy_line("socks5h://user:pw@10.0.0.2:9050").unwrap();
+        assert_eq!(socks5h.protocol, ProxyProtocol::Socks5h);
+        assert_eq!(socks5h.port, 9050);
+        assert_eq!(socks5h.username.as_deref(), Some("user"));
+        assert_eq!(socks5h.password.as_deref(), Some("pw"));
+
+        let socks5 = ProxyService::parse_proxy_line("socks5://10.0.0.3").unwrap();
+        assert_eq!(socks5.protocol, ProxyProtocol::Socks5);
+        assert_eq!(socks5.port, 1080);
+    }
+
+    #[test]
+    fn test_parse_proxy_line_rejects_malformed() {
+        for line in [
+            "socks6://10.0.0.1:1080",
+            "socks4:/10.0.0.1",
+            "socks5h://",
+            "10.0.0.1:1080",
+            "ftp://10.0.0.1:21",
+        ] {
+            assert!(ProxyService::parse_proxy_line(line).is_none(), "{}", line);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_skips_malformed_socks_lines() {
+        let (_dir, pool) = init_test_database().await;
+        let service = ProxyService::new(pool, test_encryption());
+
+        let imported = service
+            .import_proxies("socks4://10.0.0.1:1081\nsocks5h:/broken\nsocks5h://10.0.0.2\n")
+            .await
+            .unwrap();
+
+        assert_eq!(imported.imported.len(), 2);
+        assert_eq!(imported.invalid, 1);
+        let urls: Vec<String> = imported.imported.iter().map(|p| p.url()).collect();
+        assert!(urls.contains(&"socks4://10.0.0.1:1081".to_string()));
+        assert!(urls.contains(&"socks5h://10.0.0.2:1080".to_string()));
+        for url in &urls {
+            assert!(reqwest::Proxy::all(url).is_ok(), "{}", url);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_deduplicates_proxies() {
+        let (_dir, pool) = init_test_database().await;
+        let service = ProxyService::new(pool, test_encryption());
+
+        let list = "\
+http://10.0.0.1:8080
+http://user:a@10.0.0.1:8080
+http://10.0.0.1:8080
+# comment
+not a proxy
+http://user:b@10.0.0.1:8080
+socks5://10.0.0.1:8080
+";
+        let first = service.import_proxies(list).await.unwrap();
+        assert_eq!(first.imported.len(), 3);
+        assert_eq!(first.skipped, 2);
+        assert_eq!(first.invalid, 1);
+
+        // Re-importing the same list creates nothing new
+        let second = service.import_proxies(list).await.unwrap();
+        assert_eq!(second.imported.len(), 0);
+        assert_eq!(second.skipped, 5);
+        assert_eq!(second.invalid, 1);
+
+        assert_eq!(service.list_proxies().await.unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_strategy_round_trip() {
+        for strategy in [
+            ProxyStrategy::Fixed,
+            ProxyStrategy::RoundRobin,
+            ProxyStrategy::Random,
+            ProxyStrategy::LowestLatency,
+            ProxyStrategy::LeastFailures,
+        ] {
+            let parsed: ProxyStrategy = strategy.to_string().parse().unwrap();
+            assert_eq!(parsed, strategy);
+        }
+        assert!("fastest".parse::<ProxyStrategy>().is_err());
+    }
+
+    #[test]
+    fn test_format_location() {
+        let ip_api = serde_json::json!({
+            "status": "success",
+            "country": "Japan",
+            "regionName": "Tokyo",
+            "city": "Tokyo"
+        });
+        assert_eq!(
+            ProxyService::format_location(&ip_api).as_deref(),
+            Some("Tokyo, Japan")
+        );
+
+        let ipinfo = serde_json::json!({ "city": "Ashburn", "region": "Virginia", "country": "US" });
+        assert_eq!(
+            ProxyService::format_location(&ipinfo).as_deref(),
+            Some("Ashburn, Virginia, US")
+        );
+
+        let failed = serde_json::json!({ "status": "fail", "message": "reserved range" });
+        assert_eq!(ProxyService::format_location(&failed), None);
+    }
+
+    #[tokio::test]
+    async fn test_geo_location_is_persisted() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/json/203.0.113.7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "success",
+                "country": "Germany",
+                "regionName": "Hesse",
+                "city": "Frankfurt am Main"
+            })))
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let endpoint = format!("{}/json/{{ip}}", server.uri());
+        SettingsService::new(pool.clone())
+            .set(GEO_ENDPOINT_SETTING_KEY, &endpoint)
+            .await
+            .unwrap();
+        let service = ProxyService::new(pool, test_encryption());
+
+        let proxy = service
+            .create_proxy(CreateProxyRequest {
+                protocol: ProxyProtocol::Http,
+                host: "127.0.0.1".to_string(),
+                port: 8080,
+                username: None,
+                password: None,
+            })
+            .await
+            .unwrap();
+
+        let location = service.lookup_location("203.0.113.7").await;
+        assert_eq!(location.as_deref(), Some("Frankfurt am Main, Hesse, Germany"));
+
+        let result = ProxyHealthResult {
+            proxy_id: proxy.id.clone(),
+            is_healthy: true,
+            exit_ip: Some("203.0.113.7".to_string()),
+            location,
+            latency_ms: Some(42),
+            error: None,
+        };
+        service.update_proxy_status(&result).await.unwrap();
+
+        let stored = service.get_proxy(&proxy.id).await.unwrap();
+        assert_eq!(stored.status, ProxyStatus::Healthy);
+        assert_eq!(
+            stored.last_check_location.as_deref(),
+            Some("Frankfurt am Main, Hesse, Germany")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_all_proxies_bounds_concurrency() {
+        use std::sync::Arc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Mock proxy that holds each CONNECT briefly and then refuses it
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        {
+            let in_flight = in_flight.clone();
+            let max_in_flight = max_in_flight.clone();
+            tokio::spawn(async move {
+                loop {
+                    let (mut socket, _) = listener.accept().await.unwrap();
+                    let in_flight = in_flight.clone();
+                    let max_in_flight = max_in_flight.clone();
+                    tokio::spawn(async move {
+                        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_in_flight.fetch_max(current, Ordering::SeqCst);
+                        let mut buf = [0u8; 1024];
+                        let _ = socket.read(&mut buf).await;
+                        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                        in_flight.fetch_sub(1, Ordering::SeqCst);
+                        let _ = socket
+                            .write_all(b"HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\n\r\n")
+                            .await;
+                    });
+                }
+            });
+        }
+
+        let (_dir, pool) = init_test_database().await;
+        let service = ProxyService::new(pool, test_encryption());
+        for i in 0..6 {
+            service
+                .create_proxy(CreateProxyRequest {
+                    protocol: ProxyProtocol::Http,
+                    host: "127.0.0.1".to_string(),
+                    port,
+                    username: Some(format!("user{}", i)),
+                    password: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(service.last_full_check().await.unwrap(), None);
+        let results = service.check_all_proxies(2).await.unwrap();
+        assert!(service.last_full_check().await.unwrap().is_some());
+
+        assert_eq!(results.len(), 6);
+        assert!(results.iter().all(|r| !r.is_healthy));
+        let max = max_in_flight.load(Ordering::SeqCst);
+        assert!((1..=2).contains(&max), "max in-flight checks was {}", max);
+
+        let proxies = service.list_proxies().await.unwrap();
+        assert!(proxies.iter().all(|p| p.status == ProxyStatus::Unhealthy));
+    }
+
+    #[tokio::test]
+    async fn test_health_check_authenticates_with_password() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Mock proxy passing the CONNECT request on and refusing it
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (request_tx, mut request_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 1024];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let _ = request_tx.send(String::from_utf8_lossy(&buf[..n]).to_lowercase());
+                let _ = socket
+                    .write_all(b"HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\n\r\n")
+                    .await;
+            }
+        });
+
+        let (_dir, pool) = init_test_database().await;
+        let service = ProxyService::new(pool, test_encryption());
+        let proxy = service
+            .create_proxy(CreateProxyRequest {
+                protocol: ProxyProtocol::Http,
+                host: "127.0.0.1".to_string(),
+                port,
+                username: Some("alice".to_string()),
+                password: Some("s3cret".to_string()),
+            })
+            .await
+            .unwrap();
+
+        let result = service.check_proxy_health(&proxy.id).await.unwrap();
+        assert!(!result.is_healthy);
+        let request = request_rx.recv().await.unwrap();
+        // base64("alice:s3cret")
+        assert!(
+            request.contains("proxy-authorization: basic ywxpy2u6cznjcmv0"),
+            "{}",
+            request
+        );
+    }
+
+    #[tokio::test]
+    async fn test_proxy_usage_is_tracked() {
+        let (_dir, pool) = init_test_database().await;
+        let service = ProxyService::new(pool.clone(), test_encryption());
+        let proxy = service
+            .create_proxy(CreateProxyRequest {
+                protocol: ProxyProtocol::Http,
+                host: "127.0.0.1".to_string(),
+                port: 8080,
+                username: None,
+                password: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(proxy.last_used_at, None);
+        service
+            .update_proxy_status(&ProxyHealthResult {
+                proxy_id: proxy.id.clone(),
+                is_healthy: true,
+                exit_ip: None,
+                location: None,
+                latency_ms: Some(10),
+                error: None,
+            })
+            .await
+            .unwrap();
+
+        // Selection stamps last_used_at
+        let selected = service
+            .select_proxy(&ProxyStrategy::RoundRobin)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(selected.last_used_at.is_some());
+        assert_eq!(
+            service.get_proxy(&proxy.id).await.unwrap().last_used_at,
+            selected.last_used_at
+        );
+
+        let account = crate::services::AccountService::new(pool, test_encryption())
+            .create_account(crate::models::CreateAccountRequest {
+                platform: "weibo".to_string(),
+                name: "Flagged".to_string(),
+                username: None,
+                credentials: None,
+                timezone: None,
+            })
+            .await
+            .unwrap();
+        service.record_usage(&proxy.id, &account.id).await.unwrap();
+        service
+            .record_usage(&proxy.id, "purged-account")
+            .await
+            .unwrap();
+
+        let usage = service.get_proxy_usage(&proxy.id, 10).await.unwrap();
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].account_id, "purged-account");
+        assert_eq!(usage[0].account_name, None);
+        assert_eq!(usage[1].account_id, account.id);
+        assert_eq!(usage[1].account_name.as_deref(), Some("Flagged"));
+        assert_eq!(
+            service.get_proxy_usage(&proxy.id, 1).await.unwrap().len(),
+            1
+        );
+
+        assert!(matches!(
+            service.record_usage("missing", &account.id).await,
+            Err(PubCastError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_proxy_history_accumulates_and_is_capped() {
+        let (_dir, pool) = init_test_database().await;
+        let service = ProxyService::new(pool, test_encryption());
+        let proxy = service
+            .create_proxy(CreateProxyRequest {
+                protocol: ProxyProtocol::Http,
+                host: "127.0.0.1".to_string(),
+                port: 8080,
+                username: None,
+                password: None,
+            })
+            .await
+            .unwrap();
+
+        let check = |n: u64| ProxyHealthResult {
+            proxy_id: proxy.id.clone(),
+            is_healthy: n.is_multiple_of(2),
+            exit_ip: None,
+            location: None,
+            latency_ms: Some(n),
+            error: None,
+        };
+
+        for n in 0..3 {
+            service.update_proxy_status(&check(n)).await.unwrap();
+        }
+        let history = service.get_proxy_history(&proxy.id, 10).await.unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].latency_ms, Some(2));
+        assert!(history[0].is_healthy);
+        assert!(!history[1].is_healthy);
+
+        let total = PROXY_HISTORY_RETENTION as u64 + 5;
+        for n in 3..total {
+            service.update_proxy_status(&check(n)).await.unwrap();
+        }
+        let history = service
+            .get_proxy_history(&proxy.id, PROXY_HISTORY_RETENTION * 2)
+            .await
+            .unwrap();
+        assert_eq!(history.len() as i64, PROXY_HISTORY_RETENTION);
+        assert_eq!(history[0].latency_ms, Some(total as i64 - 1));
+        // The oldest five checks were trimmed
+        assert_eq!(history.last().unwrap().latency_ms, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_update_proxy_partial_fields() {
+        let (_dir, pool) = init_test_database().await;
+        let service = ProxyService::new(pool, test_encryption());
+        let proxy = service
+            .create_proxy(CreateProxyRequest {
+                protocol: ProxyProtocol::Http,
+                host: "proxy.example.com".to_string(),
+                port: 8080,
+                username: Some("alice".to_string()),
+                password: Some("secret".to_string()),
+            })
+            .await
+            .unwrap();
+        service
+            .update_proxy_status(&ProxyHealthResult {
+                proxy_id: proxy.id.clone(),
+                is_healthy: true,
+                exit_ip: None,
+                location: None,
+                latency_ms: Some(10),
+                error: None,
+            })
+            .await
+            .unwrap();
+
+        let updated = service
+            .update_proxy(
+                &proxy.id,
+                UpdateProxyRequest {
+                    host: Some("proxy2.example.com".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.host, "proxy2.example.com");
+        assert_eq!(updated.protocol, ProxyProtocol::Http);
+        assert_eq!(updated.port, 8080);
+        assert_eq!(updated.username.as_deref(), Some("alice"));
+        assert_eq!(updated.status, ProxyStatus::Unknown);
+        assert_eq!(
+            service.get_password(&proxy.id).await.unwrap().as_deref(),
+            Some("secret")
+        );
+        // History survives the edit
+        assert_eq!(service.get_proxy_history(&proxy.id, 10).await.unwrap().len(), 1);
+
+        let cleared = service
+            .update_proxy(
+                &proxy.id,
+                UpdateProxyRequest {
+                    username: Some(None),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(cleared.username, None);
+        assert_eq!(cleared.host, "proxy2.example.com");
+
+        // `null` clears, a missing field leaves the username alone
+        let req: UpdateProxyRequest =
+            serde_json::from_value(serde_json::json!({ "username": null })).unwrap();
+        assert_eq!(req.username, Some(None));
+        let req: UpdateProxyRequest = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(req.username, None);
+    }
+
+    #[tokio::test]
+    async fn test_update_proxy_rotates_password() {
+        let (_dir, pool) = init_test_database().await;
+        let service = ProxyService::new(pool, test_encryption());
+        let proxy = service
+            .create_proxy(CreateProxyRequest {
+                protocol: ProxyProtocol::Socks5,
+                host: "10.0.0.1".to_string(),
+                port: 1080,
+                username: Some("bob".to_string()),
+                password: Some("old-pass".to_string()),
+            })
+            .await
+            .unwrap();
+
+        let updated = service
+            .update_proxy(
+                &proxy.id,
+                UpdateProxyRequest {
+                    password: Some("new-pass".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.host, "10.0.0.1");
+        assert_eq!(updated.username.as_deref(), Some("bob"));
+        assert_eq!(
+            service.get_password(&proxy.id).await.unwrap().as_deref(),
+            Some("new-pass")
+        );
+
+        let missing = service
+            .update_proxy("missing", UpdateProxyRequest::default())
+            .await;
+        assert!(matches!(missing, Err(PubCastError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_geo_lookup_fails_soft() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        let endpoint = format!("{}/json/{{ip}}", server.uri());
+        SettingsService::new(pool.clone())
+            .set(GEO_ENDPOINT_SETTING_KEY, &endpoint)
+            .await
+            .unwrap();
+        let service = ProxyService::new(pool, test_encryption());
+
+        assert_eq!(service.lookup_location("203.0.113.7").await, None);
+    }
+}
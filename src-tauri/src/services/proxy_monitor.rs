@@ -0,0 +1,315 @@
+//! Periodic proxy health checks
+//!
+//! Opt-in background task running `check_all_proxies` on a jittered interval
+//! so `select_proxy` works from fresh health data. The interval and whether
+//! the task runs are kept in settings, so it resumes on the next launch.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::Rng;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
+
+use crate::error::{PubCastError, Result};
+use crate::services::proxy::DEFAULT_HEALTH_CHECK_CONCURRENCY;
+use crate::services::{ProxyService, SettingsService};
+
+/// Setting turning the periodic checks on
+pub const ENABLED_SETTING_KEY: &str = "proxy.health_check.enabled";
+/// Setting holding the interval between full checks in minutes, seeded with
+/// the other default settings
+pub const INTERVAL_SETTING_KEY: &str = "proxy_health_check_interval_minutes";
+/// Default interval between full checks
+const DEFAULT_INTERVAL_MINUTES: u64 = 30;
+/// Shortest interval that can be configured
+const MIN_INTERVAL_MINUTES: u64 = 1;
+/// Longest interval that can be configured (a day)
+const MAX_INTERVAL_MINUTES: u64 = 1440;
+/// Share of the interval randomized around it, so checks don't line up
+const INTERVAL_JITTER: f64 = 0.1;
+
+/// State of the periodic checks, for the settings screen
+#[derive(Debug, Clone, Serialize)]
+pub struct ProxyHealthCheckStatus {
+    pub running: bool,
+    pub interval_minutes: u64,
+    /// When every proxy was last checked, by this task or by hand
+    pub last_full_check_at: Option<i64>,
+}
+
+/// Runs the periodic proxy health checks while started
+pub struct ProxyHealthMonitor {
+    pool: SqlitePool,
+    proxies: Arc<RwLock<ProxyService>>,
+    /// Stops the worker when sent to or dropped, with the worker's handle
+    worker: Option<(mpsc::Sender<()>, JoinHandle<()>)>,
+}
+
+impl ProxyHealthMonitor {
+    /// Create a stopped monitor checking the proxies in `proxies`
+    pub fn new(pool: SqlitePool, proxies: Arc<RwLock<ProxyService>>) -> Self {
+        Self {
+            pool,
+            proxies,
+            worker: None,
+        }
+    }
+
+    /// Whether the background checks are running
+    pub fn is_running(&self) -> bool {
+        self.worker
+            .as_ref()
+            .is_some_and(|(_, handle)| !handle.is_finished())
+    }
+
+    /// Start the checks if they were left enabled. Returns whether they started.
+    pub async fn start_if_enabled(&mut self) -> Result<bool> {
+        let settings = SettingsService::new(self.pool.clone());
+        if !settings.get_or(ENABLED_SETTING_KEY, false).await? {
+            return Ok(false);
+        }
+        let interval = self.interval().await?;
+        self.start_with_interval(interval).await;
+        Ok(true)
+    }
+
+    /// Turn the checks on, optionally changing the interval, and start them
+    pub async fn enable(&mut self, interval_minutes: Option<u64>) -> Result<()> {
+        let settings = SettingsService::new(self.pool.clone());
+        if let Some(interval_minutes) = interval_minutes {
+            if !(MIN_INTERVAL_MINUTES..=MAX_INTERVAL_MINUTES).contains(&interval_minutes) {
+                return Err(PubCastError::Validation(format!(
+                    "Proxy health check interval must be between {} and {} minutes",
+                    MIN_INTERVAL_MINUTES, MAX_INTERVAL_MINUTES
+                )));
+            }
+            settings
+                .set(INTERVAL_SETTING_KEY, &interval_minutes)
+                .await?;
+        }
+        settings.set(ENABLED_SETTING_KEY, &true).await?;
+
+        let interval = self.interval().await?;
+        self.start_with_interval(interval).await;
+        Ok(())
+    }
+
+    /// Turn the checks off and stop them
+    pub async fn disable(&mut self) -> Result<()> {
+        SettingsService::new(self.pool.clone())
+            .set(ENABLED_SETTING_KEY, &false)
+            .await?;
+        self.stop().await;
+        Ok(())
+    }
+
+    /// Run a full check every `interval`, give or take the jitter, replacing
+    /// any running worker
+    pub async fn start_with_interval(&mut self, interval: Duration) {
+        self.stop().await;
+        let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
+        let handle = tokio::spawn(run_health_checks(self.proxies.clone(), interval, shutdown_rx));
+        self.worker = Some((shutdown_tx, handle));
+        tracing::info!("Proxy health checks running every {:?}", interval);
+    }
+
+    /// Stop the worker and wait for it to exit. A check in progress is abandoned.
+    pub async fn stop(&mut self) {
+        let Some((shutdown_tx, handle)) = self.worker.take() else {
+            return;
+        };
+        drop(shutdown_tx);
+        if let Err(e) = handle.await {
+            tracing::warn!("Proxy health check worker ended abnormally: {}", e);
+        }
+    }
+
+    /// Current state of the checks
+    pub async fn status(&self) -> Result<ProxyHealthCheckStatus> {
+        Ok(ProxyHealthCheckStatus {
+            running: self.is_running(),
+            interval_minutes: self.interval_minutes().await?,
+            last_full_check_at: self.proxies.read().await.last_full_check().await?,
+        })
+    }
+
+    async fn interval_minutes(&self) -> Result<u64> {
+        let interval = SettingsService::new(self.pool.clone())
+            .get_or(INTERVAL_SETTING_KEY, DEFAULT_INTERVAL_MINUTES)
+            .await?;
+        Ok(interval.clamp(MIN_INTERVAL_MINUTES, MAX_INTERVAL_MINUTES))
+    }
+
+    async fn interval(&self) -> Result<Duration> {
+        let minutes = self.interval_minutes().await?;
+        minutes
+            .checked_mul(60)
+            .map(Duration::from_secs)
+            .ok_or_else(|| {
+                PubCastError::Validation(format!(
+                    "Proxy health check interval of {} minutes is too long",
+                    minutes
+                ))
+            })
+    }
+}
+
+/// Check every proxy after each jittered `interval` until `shutdown` fires
+/// or its sender is dropped
+async fn run_health_checks(
+    proxies: Arc<RwLock<ProxyService>>,
+    interval: Duration,
+    mut shutdown: mpsc::Receiver<()>,
+) {
+    loop {
+        let delay = jittered(interval, INTERVAL_JITTER, &mut rand::thread_rng());
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown.recv() => break,
+        }
+
+        let check = async {
+            let proxies = proxies.read().await;
+            proxies.check_all_proxies(DEFAULT_HEALTH_CHECK_CONCURRENCY).await
+        };
+        tokio::select! {
+            result = check => match result {
+                Ok(results) => {
+                    let unhealthy = results.iter().filter(|r| !r.is_healthy).count();
+                    tracing::info!(
+                        "Checked {} proxies, {} unhealthy",
+                        results.len(),
+                        unhealthy
+                    );
+                }
+                Err(e) => tracing::error!("Proxy health check failed: {}", e),
+            },
+            _ = shutdown.recv() => break,
+        }
+    }
+}
+
+/// `interval` moved by up to `jitter` of itself in either direction, or
+/// `interval` itself if that doesn't fit in a `Duration`
+fn jittered(interval: Duration, jitter: f64, rng: &mut impl Rng) -> Duration {
+    let factor = 1.0 + rng.gen_range(-jitter..=jitter);
+    Duration::try_from_secs_f64(interval.as_secs_f64() * factor).unwrap_or(interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+    use crate::infrastructure::encryption::EncryptionService;
+    use crate::models::{CreateProxyRequest, ProxyProtocol, ProxyStatus};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let interval = Duration::from_secs(100);
+        for _ in 0..100 {
+            let delay = jittered(interval, 0.1, &mut rng);
+            assert!((Duration::from_secs(90)..=Duration::from_secs(110)).contains(&delay));
+        }
+    }
+
+    #[test]
+    fn test_jitter_does_not_overflow() {
+        let delay = jittered(Duration::MAX, 0.1, &mut rand::thread_rng());
+        assert!(delay <= Duration::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_monitor_checks_proxies_after_interval() {
+        // Mock proxy refusing every CONNECT, counting the attempts
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let attempts = Arc::new(AtomicUsize::new(0));
+        {
+            let attempts = attempts.clone();
+            tokio::spawn(async move {
+                loop {
+                    let (mut socket, _) = listener.accept().await.unwrap();
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    tokio::spawn(async move {
+                        let mut buf = [0u8; 1024];
+                        let _ = socket.read(&mut buf).await;
+                        let _ = socket
+                            .write_all(b"HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\n\r\n")
+                            .await;
+                    });
+                }
+            });
+        }
+
+        let (_dir, pool) = init_test_database().await;
+        let salt = EncryptionService::generate_salt();
+        let service = ProxyService::new(pool.clone(), EncryptionService::new(b"test_master_key", &salt).unwrap());
+        let proxy = service
+            .create_proxy(CreateProxyRequest {
+                protocol: ProxyProtocol::Http,
+                host: "127.0.0.1".to_string(),
+                port,
+                username: None,
+                password: None,
+            })
+            .await
+            .unwrap();
+
+        let mut monitor = ProxyHealthMonitor::new(pool, Arc::new(RwLock::new(service)));
+        // Opt-in: nothing runs until enabled
+        assert!(!monitor.start_if_enabled().await.unwrap());
+        let status = monitor.status().await.unwrap();
+        assert!(status.last_full_check_at.is_none());
+        // The interval comes from the seeded default setting
+        assert_eq!(status.interval_minutes, 30);
+
+        monitor.start_with_interval(Duration::from_millis(100)).await;
+        assert!(monitor.is_running());
+        let checked = async {
+            while monitor.status().await.unwrap().last_full_check_at.is_none() {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(5), checked).await.unwrap();
+        assert!(attempts.load(Ordering::SeqCst) >= 1);
+        let stored = monitor.proxies.read().await.get_proxy(&proxy.id).await.unwrap();
+        assert_eq!(stored.status, ProxyStatus::Unhealthy);
+
+        monitor.stop().await;
+        assert!(!monitor.is_running());
+        let after_stop = attempts.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(attempts.load(Ordering::SeqCst), after_stop);
+
+        assert!(matches!(
+            monitor.enable(Some(0)).await,
+            Err(PubCastError::Validation(_))
+        ));
+        assert!(matches!(
+            monitor.enable(Some(MAX_INTERVAL_MINUTES + 1)).await,
+            Err(PubCastError::Validation(_))
+        ));
+
+        // An out-of-range stored interval is clamped rather than overflowing
+        SettingsService::new(monitor.pool.clone())
+            .set(INTERVAL_SETTING_KEY, &u64::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            monitor.status().await.unwrap().interval_minutes,
+            MAX_INTERVAL_MINUTES
+        );
+        assert_eq!(
+            monitor.interval().await.unwrap(),
+            Duration::from_secs(MAX_INTERVAL_MINUTES * 60)
+        );
+    }
+}
@@ -2,66 +2,500 @@
 //!
 //! Manages publish job queue, concurrency control, and retry logic.
 
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, TimeZone};
+use croner::Cron;
+use rand::Rng;
+use serde::Serialize;
 use sqlx::SqlitePool;
-use tokio::sync::{mpsc, Semaphore};
+use tokio::sync::{mpsc, Mutex, RwLock, Semaphore};
 use uuid::Uuid;
 
+use crate::adapters::AdapterRegistry;
 use crate::error::{PubCastError, Result};
+use crate::infrastructure::database::with_sqlite_retry;
 use crate::models::{
-    CreateDistributionTaskRequest, DistributionTask, DistributionTaskStatus, PublishJob,
-    PublishJobStatus, ScheduleType,
+    classify_error, Content, ContentIssue, CreateDistributionTaskRequest, CreateDistributionTaskResponse,
+    DistributionTask, DistributionTaskListQuery, DistributionTaskListResponse,
+    DistributionTaskStatus, PublishJob, PublishJobStatus, PublishResult, ScheduleType,
+    TaskProgress,
 };
+use crate::services::content::load_content;
+use crate::services::{NotificationService, StatsService};
 
-/// Maximum concurrent publish jobs
-const MAX_CONCURRENT_JOBS: usize = 3;
+/// Tasks per page when the caller does not specify a page size
+const DEFAULT_TASK_PAGE_SIZE: i32 = 50;
+/// Upper bound on the task page size a caller may request
+const MAX_TASK_PAGE_SIZE: i32 = 200;
+/// Default maximum concurrent publish jobs
+const DEFAULT_CONCURRENCY: usize = 3;
+/// `app_settings` key holding the configured concurrency
+const CONCURRENCY_SETTING: &str = "max_concurrent_publishes";
 /// Base retry delay in seconds
 const RETRY_BASE_DELAY_SECS: u64 = 5;
 /// Maximum retry delay in seconds
 const MAX_RETRY_DELAY_SECS: u64 = 300;
+/// Default share of the retry delay that is randomized (equal jitter)
+const DEFAULT_RETRY_JITTER: f64 = 0.5;
+/// How often the background worker looks for due work
+const WORKER_TICK_INTERVAL: Duration = Duration::from_secs(30);
+/// Trailing window for per-platform rate limits
+const RATE_LIMIT_WINDOW_SECS: i64 = 3600;
+/// How long a publish may run when its platform declares no timeout
+const DEFAULT_PUBLISH_TIMEOUT: Duration = Duration::from_secs(300);
+/// `error_code` recorded for publishes abandoned after their timeout
+pub const TIMEOUT_ERROR_CODE: &str = "timeout";
+/// `error_code` recorded when the pre-publish hook rejects a job
+pub const PRE_PUBLISH_ERROR_CODE: &str = "pre_publish_failed";
+/// `error_code` recorded for jobs left running by a previous run of the app
+pub const INTERRUPTED_ERROR_CODE: &str = "interrupted";
+
+/// Event name for [`TaskEvent::Progress`], payload is [`TaskProgress`]
+pub const TASK_PROGRESS_EVENT: &str = "task://progress";
+/// Event name for [`TaskEvent::Completed`], payload is the finished [`DistributionTask`]
+pub const TASK_COMPLETED_EVENT: &str = "task://completed";
+
+/// Change in a distribution task's progress, read back from the database
+#[derive(Debug, Clone)]
+pub enum TaskEvent {
+    /// A job started, succeeded, failed or was cancelled
+    Progress(TaskProgress),
+    /// The task reached a final status
+    Completed(DistributionTask),
+}
+
+/// Outcome of [`SchedulerService::recover_stale_jobs`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StaleJobRecovery {
+    /// Jobs put back in the queue because their platform's adapter publishes
+    /// idempotently, so a re-run can't post twice
+    pub requeued: usize,
+    /// Jobs failed for manual review since they may already be published
+    pub failed: usize,
+}
+
+/// Outcome of [`SchedulerService::purge_old_tasks`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TaskPurge {
+    pub tasks: u64,
+    pub jobs: u64,
+}
+
+/// A purged task with its jobs, as written to the archive
+#[derive(Serialize)]
+struct ArchivedTask {
+    task: DistributionTask,
+    jobs: Vec<PublishJob>,
+}
+
+/// Executes a single publish job on its platform
+#[async_trait]
+pub trait JobExecutor: Send + Sync {
+    async fn execute(&self, job: &PublishJob) -> PublishResult;
+}
+
+/// Runs before each publish, e.g. to refresh credentials that are about to
+/// expire. An error fails the attempt without publishing.
+#[async_trait]
+pub trait PrePublishHook: Send + Sync {
+    async fn before_publish(&self, job: &PublishJob) -> Result<()>;
+}
 
 /// Scheduler service for managing publish jobs
 pub struct SchedulerService {
     pool: SqlitePool,
-    /// Semaphore for concurrency control (reserved for future use)
-    #[allow(dead_code)]
+    /// Maximum number of jobs executing at once
+    concurrency: AtomicUsize,
+    /// Serializes changes to `concurrency` and the semaphore's permits.
+    /// Resizing goes through `&self` so it never needs the write lock the
+    /// background worker would block for a whole tick.
+    resize_lock: Mutex<()>,
+    /// Semaphore for concurrency control, sized to `concurrency`
     semaphore: Arc<Semaphore>,
-    /// Shutdown signal sender (reserved for future use)
-    #[allow(dead_code)]
+    /// Permits a lowered limit still has to take back from running jobs.
+    /// Raising the limit pays this off before adding permits.
+    permit_debt: Arc<AtomicUsize>,
+    /// Publishes due jobs from the background worker when set
+    executor: Option<Arc<dyn JobExecutor>>,
+    /// Prepares each job right before it is published when set
+    pre_publish: Option<Arc<dyn PrePublishHook>>,
+    /// Keeps the background worker alive; dropping it stops the worker
     shutdown_tx: Option<mpsc::Sender<()>>,
+    /// Maximum publishes per hour by platform
+    rate_limits: HashMap<String, u32>,
+    /// Publish timeouts by platform, overriding `default_publish_timeout`
+    publish_timeouts: HashMap<String, Duration>,
+    /// Publish timeout for platforms without their own
+    default_publish_timeout: Duration,
+    /// Platforms whose interrupted publishes can safely be re-run
+    idempotent_platforms: HashSet<String>,
+    /// Receives a [`TaskEvent`] on every job transition when set
+    events: Option<mpsc::UnboundedSender<TaskEvent>>,
+    /// Share of each retry delay that is randomized, from 0.0 to 1.0
+    retry_jitter: f64,
+    /// Records every publish attempt in `publish_logs` and the daily statistics
+    stats: StatsService,
+    /// Sends webhook notifications when a task finishes when set
+    notifications: Option<NotificationService>,
+    /// Platform adapters used to check and dry-run content
+    adapters: Arc<RwLock<AdapterRegistry>>,
 }
 
 impl SchedulerService {
     /// Create a new scheduler service
     pub fn new(pool: SqlitePool) -> Self {
+        let adapters = AdapterRegistry::new();
         Self {
-            pool,
-            semaphore: Arc::new(Semaphore::new(MAX_CONCURRENT_JOBS)),
+            concurrency: AtomicUsize::new(DEFAULT_CONCURRENCY),
+            resize_lock: Mutex::new(()),
+            semaphore: Arc::new(Semaphore::new(DEFAULT_CONCURRENCY)),
+            permit_debt: Arc::new(AtomicUsize::new(0)),
+            executor: None,
+            pre_publish: None,
             shutdown_tx: None,
+            rate_limits: default_rate_limits(&adapters),
+            publish_timeouts: default_publish_timeouts(&adapters),
+            default_publish_timeout: DEFAULT_PUBLISH_TIMEOUT,
+            idempotent_platforms: default_idempotent_platforms(&adapters),
+            events: None,
+            retry_jitter: DEFAULT_RETRY_JITTER,
+            stats: StatsService::new(pool.clone()),
+            notifications: None,
+            adapters: Arc::new(RwLock::new(adapters)),
+            pool,
+        }
+    }
+
+    /// Create a scheduler running at most `concurrency` jobs at once
+    pub fn with_concurrency(pool: SqlitePool, concurrency: usize) -> Result<Self> {
+        validate_concurrency(concurrency)?;
+        Ok(Self {
+            concurrency: AtomicUsize::new(concurrency),
+            semaphore: Arc::new(Semaphore::new(concurrency)),
+            ..Self::new(pool)
+        })
+    }
+
+    /// Create a scheduler using the concurrency stored in `app_settings`
+    pub async fn load(pool: SqlitePool) -> Result<Self> {
+        let stored = sqlx::query_scalar!(
+            "SELECT value FROM app_settings WHERE key = ?",
+            CONCURRENCY_SETTING
+        )
+        .fetch_optional(&pool)
+        .await?;
+
+        let concurrency = match stored.as_deref().map(str::parse::<usize>) {
+            Some(Ok(n)) if n >= 1 => n,
+            None => DEFAULT_CONCURRENCY,
+            Some(_) => {
+                tracing::warn!(
+                    "Ignoring invalid {} setting {:?}, using {}",
+                    CONCURRENCY_SETTING,
+                    stored,
+                    DEFAULT_CONCURRENCY
+                );
+                DEFAULT_CONCURRENCY
+            }
+        };
+
+        Self::with_concurrency(pool, concurrency)
+    }
+
+    /// Set the executor the background worker publishes due jobs with
+    pub fn with_executor(mut self, executor: Arc<dyn JobExecutor>) -> Self {
+        self.executor = Some(executor);
+        self
+    }
+
+    /// Run `hook` before every publish
+    pub fn with_pre_publish_hook(mut self, hook: Arc<dyn PrePublishHook>) -> Self {
+        self.pre_publish = Some(hook);
+        self
+    }
+
+    /// Send progress events for job transitions to `events`
+    pub fn with_event_sender(mut self, events: mpsc::UnboundedSender<TaskEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Notify webhooks through `notifications` when a task finishes
+    pub fn with_notifications(mut self, notifications: NotificationService) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Check and dry-run content with the shared `adapters` instead of the
+    /// default registry
+    pub fn with_adapters(mut self, adapters: Arc<RwLock<AdapterRegistry>>) -> Self {
+        self.adapters = adapters;
+        self
+    }
+
+    /// Current maximum number of concurrent jobs
+    pub fn concurrency(&self) -> usize {
+        self.concurrency.load(Ordering::SeqCst)
+    }
+
+    /// Change the maximum number of concurrent jobs and persist it.
+    ///
+    /// Raising the limit takes effect immediately. Lowering it takes back idle
+    /// permits now and the rest as running jobs finish, so in-flight jobs are
+    /// never interrupted; raising it again first cancels what is still owed.
+    pub async fn set_concurrency(&self, concurrency: usize) -> Result<()> {
+        validate_concurrency(concurrency)?;
+        let _resizing = self.resize_lock.lock().await;
+
+        let now = chrono::Utc::now().timestamp();
+        let value = concurrency.to_string();
+        sqlx::query!(
+            r#"
+            INSERT INTO app_settings (key, value, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+            "#,
+            CONCURRENCY_SETTING,
+            value,
+            now,
+            now
+        )
+        .execute(&self.pool)
+        .await?;
+
+        let current = self.concurrency();
+        if concurrency > current {
+            let increase = concurrency - current;
+            let owed = self
+                .permit_debt
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |debt| {
+                    Some(debt.saturating_sub(increase))
+                })
+                .unwrap_or_default();
+            self.semaphore.add_permits(increase - owed.min(increase));
+        } else if concurrency < current {
+            let excess = current - concurrency;
+            let remaining = excess - self.semaphore.forget_permits(excess);
+            if remaining > 0 {
+                self.permit_debt.fetch_add(remaining, Ordering::SeqCst);
+                tokio::spawn(collect_permit_debt(
+                    self.semaphore.clone(),
+                    self.permit_debt.clone(),
+                ));
+            }
+        }
+
+        tracing::info!("Publish concurrency set to {}", concurrency);
+        self.concurrency.store(concurrency, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Override a platform's hourly publish limit (`None` removes it)
+    pub fn with_rate_limit(mut self, platform: &str, per_hour: Option<u32>) -> Self {
+        match per_hour {
+            Some(limit) => self.rate_limits.insert(platform.to_string(), limit),
+            None => self.rate_limits.remove(platform),
+        };
+        self
+    }
+
+    /// Override a platform's publish timeout (`None` falls back to the default)
+    pub fn with_publish_timeout(mut self, platform: &str, timeout: Option<Duration>) -> Self {
+        match timeout {
+            Some(timeout) => self.publish_timeouts.insert(platform.to_string(), timeout),
+            None => self.publish_timeouts.remove(platform),
+        };
+        self
+    }
+
+    /// Publish timeout for platforms that don't declare their own
+    pub fn with_default_publish_timeout(mut self, timeout: Duration) -> Self {
+        self.default_publish_timeout = timeout;
+        self
+    }
+
+    /// Override whether interrupted publishes on `platform` are re-queued on startup
+    pub fn with_idempotent_publish(mut self, platform: &str, idempotent: bool) -> Self {
+        if idempotent {
+            self.idempotent_platforms.insert(platform.to_string());
+        } else {
+            self.idempotent_platforms.remove(platform);
         }
+        self
+    }
+
+    /// How long a publish on `platform` may run before it is abandoned
+    pub fn publish_timeout(&self, platform: &str) -> Duration {
+        self.publish_timeouts
+            .get(platform)
+            .copied()
+            .unwrap_or(self.default_publish_timeout)
+    }
+
+    /// Randomize `factor` of each retry delay so failed jobs don't retry in lockstep.
+    ///
+    /// `0.0` keeps the plain exponential delays, `0.5` (the default) is equal
+    /// jitter and `1.0` is full jitter. Values outside that range are clamped.
+    pub fn with_retry_jitter(mut self, factor: f64) -> Self {
+        self.retry_jitter = if factor.is_nan() {
+            0.0
+        } else {
+            factor.clamp(0.0, 1.0)
+        };
+        self
     }
 
     /// Create a distribution task
     pub async fn create_distribution_task(
         &self,
         req: CreateDistributionTaskRequest,
-    ) -> Result<DistributionTask> {
+    ) -> Result<CreateDistributionTaskResponse> {
         let id = Uuid::new_v4().to_string();
         let now = chrono::Utc::now().timestamp();
-        let target_accounts = serde_json::to_string(&req.target_account_ids)?;
-        let schedule_type = match req.schedule_type {
-            ScheduleType::Immediate => "immediate",
-            ScheduleType::Scheduled => "scheduled",
+        let target_account_ids = self.resolve_target_accounts(&req).await?;
+        let target_accounts = serde_json::to_string(&target_account_ids)?;
+        let schedule_type = req.schedule_type.to_string();
+        let recurring = req.schedule_type == ScheduleType::Recurring;
+
+        // Recurring tasks materialize their jobs from the worker on each fire time
+        let next_run_at = match (&req.schedule_type, req.cron_expression.as_deref()) {
+            (ScheduleType::Recurring, Some(expr)) => {
+                Some(next_fire_time(&parse_cron(expr)?, &chrono::Local::now())?)
+            }
+            (ScheduleType::Recurring, None) => {
+                return Err(PubCastError::Validation(
+                    "Recurring schedules require a cron expression".to_string(),
+                ))
+            }
+            (_, Some(_)) => {
+                return Err(PubCastError::Validation(
+                    "A cron expression is only valid for recurring schedules".to_string(),
+                ))
+            }
+            (_, None) => None,
         };
+
+        if let Some(platform) = req
+            .per_platform_options
+            .iter()
+            .find_map(|(platform, options)| (!options.is_object()).then_some(platform))
+        {
+            return Err(PubCastError::Validation(format!(
+                "Publish options for {} must be an object",
+                platform
+            )));
+        }
+        let platform_options = if req.per_platform_options.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&req.per_platform_options)?)
+        };
+
+        if recurring && req.dry_run {
+            return Err(PubCastError::Validation(
+                "Dry runs are not supported for recurring schedules".to_string(),
+            ));
+        }
+        if recurring && !req.account_priorities.is_empty() {
+            return Err(PubCastError::Validation(
+                "Account priorities are not supported for recurring schedules".to_string(),
+            ));
+        }
+        if let Some(account_id) = req
+            .account_priorities
+            .keys()
+            .find(|id| !target_account_ids.contains(id))
+        {
+            return Err(PubCastError::Validation(format!(
+                "Priority given for account {} which is not a target",
+                account_id
+            )));
+        }
+
         let status = DistributionTaskStatus::Pending.to_string();
-        let total_jobs = req.target_account_ids.len() as i32;
+        let total_jobs = if recurring { 0 } else { target_account_ids.len() as i32 };
+
+        let local_time = req
+            .local_time
+            .as_deref()
+            .map(parse_local_time)
+            .transpose()?;
+
+        // Resolve platform and per-account UTC schedule before writing anything
+        let mut targets = Vec::with_capacity(target_account_ids.len());
+        for account_id in &target_account_ids {
+            let account = sqlx::query!(
+                "SELECT platform, timezone FROM accounts WHERE id = ? AND deleted_at IS NULL",
+                account_id
+            )
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| PubCastError::NotFound(format!("Account not found: {}", account_id)))?;
+
+            let scheduled_at = match &local_time {
+                Some(local) => Some(local_time_to_utc(local, account.timezone.as_deref())?),
+                None => req.scheduled_at,
+            };
+            targets.push((account_id, account.platform, scheduled_at));
+        }
+
+        let content = load_content(&self.pool, &req.content_id).await?;
+        let registry = self.adapters.read().await;
+
+        if req.schedule_type == ScheduleType::Scheduled {
+            let unschedulable = unschedulable_platforms(
+                &registry,
+                targets.iter().map(|(_, platform, _)| platform.as_str()),
+            );
+            if !unschedulable.is_empty() && !req.downgrade_unschedulable {
+                return Err(PubCastError::Validation(format!(
+                    "Scheduled publishing is not supported on: {}",
+                    unschedulable.join(", ")
+                )));
+            }
+            for (_, platform, scheduled_at) in targets.iter_mut() {
+                if unschedulable.contains(platform) {
+                    *scheduled_at = None;
+                }
+            }
+        }
+        let issues = content_issues(
+            &registry,
+            &content,
+            targets.iter().map(|(_, platform, _)| platform.as_str()),
+        );
+        if req.strict && !issues.is_empty() {
+            let messages: Vec<String> = issues
+                .iter()
+                .map(|issue| format!("{}: {}", issue.platform, issue.message))
+                .collect();
+            return Err(PubCastError::Validation(format!(
+                "Content is not ready for all target platforms: {}",
+                messages.join("; ")
+            )));
+        }
+
+        // Task-level schedule is the earliest job time
+        let task_scheduled_at = if recurring {
+            next_run_at
+        } else if local_time.is_some() {
+            targets.iter().filter_map(|(_, _, at)| *at).min()
+        } else {
+            req.scheduled_at
+        };
 
         sqlx::query!(
             r#"
             INSERT INTO distribution_tasks 
-            (id, content_id, name, status, target_accounts, schedule_type, scheduled_at, total_jobs, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            (id, content_id, name, status, target_accounts, schedule_type, scheduled_at,
+             cron_expression, next_run_at, total_jobs, dry_run, platform_options, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             id,
             req.content_id,
@@ -69,8 +503,12 @@ impl SchedulerService {
             status,
             target_accounts,
             schedule_type,
-            req.scheduled_at,
+            task_scheduled_at,
+            req.cron_expression,
+            next_run_at,
             total_jobs,
+            req.dry_run,
+            platform_options,
             now,
             now
         )
@@ -78,12 +516,119 @@ impl SchedulerService {
         .await?;
 
         // Create individual publish jobs
-        for account_id in &req.target_account_ids {
-            self.create_publish_job(&id, &req.content_id, account_id, req.scheduled_at)
-                .await?;
+        if !recurring {
+            for (account_id, platform, scheduled_at) in &targets {
+                let priority = req.account_priorities.get(*account_id).copied().unwrap_or(0);
+                let options = req.per_platform_options.get(platform.as_str());
+                if req.dry_run {
+                    self.create_dry_run_job(&registry, &id, &content, account_id, platform, options)
+                        .await?;
+                } else {
+                    self.create_publish_job(
+                        &id,
+                        &req.content_id,
+                        account_id,
+                        platform,
+                        *scheduled_at,
+                        priority,
+                        options,
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        let task = self.get_distribution_task(&id).await?;
+        Ok(CreateDistributionTaskResponse { task, issues })
+    }
+
+    /// Prepare `content` with the platform's adapter and record the result as
+    /// an already finished job, so the worker never picks it up
+    async fn create_dry_run_job(
+        &self,
+        registry: &AdapterRegistry,
+        task_id: &str,
+        content: &Content,
+        account_id: &str,
+        platform: &str,
+        options: Option<&serde_json::Value>,
+    ) -> Result<()> {
+        let prepared = match registry.get(platform) {
+            Ok(adapter) => adapter
+                .prepare_content(content)
+                .await
+                .map(|prepared| prepared.with_options(options.cloned())),
+            Err(e) => Err(e),
+        };
+        let success = prepared.is_ok();
+        let (status, metadata, error_message) = match prepared {
+            Ok(prepared) => (
+                PublishJobStatus::Success.to_string(),
+                Some(serde_json::json!({ "dry_run": true, "prepared": prepared }).to_string()),
+                None,
+            ),
+            Err(e) => (PublishJobStatus::Failed.to_string(), None, Some(e.to_string())),
+        };
+
+        let id = Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query!(
+            r#"
+            INSERT INTO publish_jobs
+            (id, distribution_task_id, content_id, account_id, platform, status, dry_run, metadata,
+             error_message, started_at, completed_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, 1, ?, ?, ?, ?, ?, ?)
+            "#,
+            id,
+            task_id,
+            content.id,
+            account_id,
+            platform,
+            status,
+            metadata,
+            error_message,
+            now,
+            now,
+            now,
+            now
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.update_task_counters_for_job(&id, success).await
+    }
+
+    /// Explicit account IDs plus every account on the requested platforms,
+    /// without duplicates. Fails if nothing is selected.
+    async fn resolve_target_accounts(&self, req: &CreateDistributionTaskRequest) -> Result<Vec<String>> {
+        let mut account_ids = req.target_account_ids.clone();
+
+        for platform in req.target_platforms.iter().flatten() {
+            let matching = sqlx::query_scalar!(
+                r#"
+                SELECT id FROM accounts
+                WHERE platform = ? AND deleted_at IS NULL AND (? = 0 OR status = 'active')
+                ORDER BY created_at ASC
+                "#,
+                platform,
+                req.only_active
+            )
+            .fetch_all(&self.pool)
+            .await?;
+
+            for account_id in matching {
+                if !account_ids.contains(&account_id) {
+                    account_ids.push(account_id);
+                }
+            }
         }
 
-        self.get_distribution_task(&id).await
+        if account_ids.is_empty() {
+            return Err(PubCastError::Validation(
+                "No accounts match the task's targets".to_string(),
+            ));
+        }
+        Ok(account_ids)
     }
 
     /// Create a single publish job
@@ -92,31 +637,31 @@ impl SchedulerService {
         task_id: &str,
         content_id: &str,
         account_id: &str,
+        platform: &str,
         scheduled_at: Option<i64>,
+        priority: i64,
+        options: Option<&serde_json::Value>,
     ) -> Result<String> {
         let id = Uuid::new_v4().to_string();
         let now = chrono::Utc::now().timestamp();
         let status = PublishJobStatus::Pending.to_string();
-
-        // Get platform from account
-        let account = sqlx::query!("SELECT platform FROM accounts WHERE id = ?", account_id)
-            .fetch_optional(&self.pool)
-            .await?
-            .ok_or_else(|| PubCastError::NotFound(format!("Account not found: {}", account_id)))?;
+        let metadata = options.map(|options| serde_json::json!({ "options": options }).to_string());
 
         sqlx::query!(
             r#"
             INSERT INTO publish_jobs 
-            (id, distribution_task_id, content_id, account_id, platform, status, scheduled_at, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            (id, distribution_task_id, content_id, account_id, platform, status, priority, scheduled_at, metadata, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             id,
             task_id,
             content_id,
             account_id,
-            account.platform,
+            platform,
             status,
+            priority,
             scheduled_at,
+            metadata,
             now,
             now
         )
@@ -131,8 +676,9 @@ impl SchedulerService {
         let row = sqlx::query!(
             r#"
             SELECT id, content_id, name, status, target_accounts, schedule_type,
-                   scheduled_at, started_at, completed_at, total_jobs, completed_jobs,
-                   failed_jobs, error_message, created_at, updated_at
+                   scheduled_at, cron_expression, next_run_at, started_at, completed_at,
+                   total_jobs, completed_jobs,
+                   failed_jobs, error_message, dry_run, created_at, updated_at
             FROM distribution_tasks WHERE id = ?
             "#,
             id
@@ -150,39 +696,67 @@ impl SchedulerService {
             name: row.name,
             status: row.status.parse().unwrap_or(DistributionTaskStatus::Pending),
             target_accounts,
-            schedule_type: if row.schedule_type == "scheduled" {
-                ScheduleType::Scheduled
-            } else {
-                ScheduleType::Immediate
-            },
+            schedule_type: row.schedule_type.parse().unwrap_or_default(),
             scheduled_at: row.scheduled_at,
+            cron_expression: row.cron_expression,
+            next_run_at: row.next_run_at,
             started_at: row.started_at,
             completed_at: row.completed_at,
             total_jobs: row.total_jobs,
             completed_jobs: row.completed_jobs,
             failed_jobs: row.failed_jobs,
             error_message: row.error_message,
+            dry_run: row.dry_run != 0,
             created_at: row.created_at,
             updated_at: row.updated_at,
         })
     }
 
-    /// List distribution tasks
-    pub async fn list_distribution_tasks(&self) -> Result<Vec<DistributionTask>> {
+    /// List distribution tasks a page at a time, newest first
+    pub async fn list_distribution_tasks(
+        &self,
+        query: &DistributionTaskListQuery,
+    ) -> Result<DistributionTaskListResponse> {
+        let page = query.page.unwrap_or(1).max(1);
+        let per_page = query
+            .per_page
+            .unwrap_or(DEFAULT_TASK_PAGE_SIZE)
+            .clamp(1, MAX_TASK_PAGE_SIZE);
+        let offset = i64::from(page - 1) * i64::from(per_page);
+        let status = query.status.as_ref().map(|s| s.to_string());
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "total!: i64"
+            FROM distribution_tasks
+            WHERE (? IS NULL OR status = ?)
+            "#,
+            status,
+            status
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
         let rows = sqlx::query!(
             r#"
             SELECT id, content_id, name, status, target_accounts, schedule_type,
-                   scheduled_at, started_at, completed_at, total_jobs, completed_jobs,
-                   failed_jobs, error_message, created_at, updated_at
+                   scheduled_at, cron_expression, next_run_at, started_at, completed_at,
+                   total_jobs, completed_jobs,
+                   failed_jobs, error_message, dry_run, created_at, updated_at
             FROM distribution_tasks
-            ORDER BY created_at DESC
-            LIMIT 100
-            "#
+            WHERE (? IS NULL OR status = ?)
+            ORDER BY created_at DESC, id
+            LIMIT ? OFFSET ?
+            "#,
+            status,
+            status,
+            per_page,
+            offset
         )
         .fetch_all(&self.pool)
         .await?;
 
-        let tasks = rows
+        let tasks: Vec<DistributionTask> = rows
             .into_iter()
             .map(|row| {
                 let target_accounts: Vec<String> =
@@ -194,30 +768,38 @@ impl SchedulerService {
                     name: row.name,
                     status: row.status.parse().unwrap_or(DistributionTaskStatus::Pending),
                     target_accounts,
-                    schedule_type: if row.schedule_type == "scheduled" {
-                        ScheduleType::Scheduled
-                    } else {
-                        ScheduleType::Immediate
-                    },
+                    schedule_type: row.schedule_type.parse().unwrap_or_default(),
                     scheduled_at: row.scheduled_at,
+                    cron_expression: row.cron_expression,
+                    next_run_at: row.next_run_at,
                     started_at: row.started_at,
                     completed_at: row.completed_at,
                     total_jobs: row.total_jobs,
                     completed_jobs: row.completed_jobs,
                     failed_jobs: row.failed_jobs,
                     error_message: row.error_message,
+                    dry_run: row.dry_run != 0,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 }
             })
             .collect();
 
-        Ok(tasks)
+        let has_more = offset + (tasks.len() as i64) < total;
+
+        Ok(DistributionTaskListResponse {
+            tasks,
+            total,
+            page,
+            per_page,
+            has_more,
+        })
     }
 
-    /// Get pending jobs ready for execution
+    /// Get pending jobs ready for execution, skipping those of paused tasks
     pub async fn get_pending_jobs(&self, limit: i32) -> Result<Vec<PublishJob>> {
         let now = chrono::Utc::now().timestamp();
+        self.defer_rate_limited_jobs(now).await?;
 
         let rows = sqlx::query!(
             r#"
@@ -228,6 +810,9 @@ impl SchedulerService {
             FROM publish_jobs
             WHERE status = 'pending'
               AND (scheduled_at IS NULL OR scheduled_at <= ?)
+              AND distribution_task_id NOT IN (
+                  SELECT id FROM distribution_tasks WHERE status = 'paused'
+              )
             ORDER BY priority DESC, created_at ASC
             LIMIT ?
             "#,
@@ -265,13 +850,196 @@ impl SchedulerService {
         Ok(jobs)
     }
 
-    /// Update job status to running
-    pub async fn mark_job_running(&self, job_id: &str) -> Result<()> {
+    /// List every job of a distribution task with its outcome, oldest first
+    pub async fn list_jobs_for_task(&self, task_id: &str) -> Result<Vec<PublishJob>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT id, distribution_task_id, content_id, account_id, platform, status,
+                   priority, retry_count, max_retries, scheduled_at, started_at, completed_at,
+                   published_url, published_id, error_code, error_message, metadata,
+                   created_at, updated_at
+            FROM publish_jobs
+            WHERE distribution_task_id = ?
+            ORDER BY created_at ASC, rowid ASC
+            "#,
+            task_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let jobs = rows
+            .into_iter()
+            .map(|row| PublishJob {
+                id: row.id,
+                distribution_task_id: row.distribution_task_id,
+                content_id: row.content_id,
+                account_id: row.account_id,
+                platform: row.platform,
+                status: row.status.parse().unwrap_or(PublishJobStatus::Pending),
+                priority: row.priority,
+                retry_count: row.retry_count,
+                max_retries: row.max_retries,
+                scheduled_at: row.scheduled_at,
+                started_at: row.started_at,
+                completed_at: row.completed_at,
+                published_url: row.published_url,
+                published_id: row.published_id,
+                error_code: row.error_code,
+                error_message: row.error_message,
+                metadata: row.metadata.as_ref().and_then(|m| serde_json::from_str(m).ok()),
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+            .collect();
+
+        Ok(jobs)
+    }
+
+    /// Get a publish job by ID
+    pub async fn get_publish_job(&self, job_id: &str) -> Result<PublishJob> {
+        let row = sqlx::query!(
+            r#"
+            SELECT id, distribution_task_id, content_id, account_id, platform, status,
+                   priority, retry_count, max_retries, scheduled_at, started_at, completed_at,
+                   published_url, published_id, error_code, error_message, metadata,
+                   created_at, updated_at
+            FROM publish_jobs WHERE id = ?
+            "#,
+            job_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| PubCastError::NotFound(format!("Publish job not found: {}", job_id)))?;
+
+        Ok(PublishJob {
+            id: row.id,
+            distribution_task_id: row.distribution_task_id,
+            content_id: row.content_id,
+            account_id: row.account_id,
+            platform: row.platform,
+            status: row.status.parse().unwrap_or(PublishJobStatus::Pending),
+            priority: row.priority,
+            retry_count: row.retry_count,
+            max_retries: row.max_retries,
+            scheduled_at: row.scheduled_at,
+            started_at: row.started_at,
+            completed_at: row.completed_at,
+            published_url: row.published_url,
+            published_id: row.published_id,
+            error_code: row.error_code,
+            error_message: row.error_message,
+            metadata: row.metadata.as_ref().and_then(|m| serde_json::from_str(m).ok()),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+
+    /// Execute up to `limit` due jobs with `executor`, never running more than
+    /// the configured concurrency at once. Returns the number of jobs executed.
+    pub async fn dispatch_pending_jobs(&self, executor: &dyn JobExecutor, limit: i32) -> Result<usize> {
+        let jobs = self.get_pending_jobs(limit).await?;
+        let results =
+            futures::future::join_all(jobs.iter().map(|job| self.execute_job(executor, job))).await;
+        results.into_iter().collect::<Result<Vec<_>>>()?;
+        Ok(jobs.len())
+    }
+
+    /// Execute one job once a concurrency permit is available and record the result.
+    ///
+    /// A publish running past its platform's timeout is abandoned and recorded
+    /// as a failure with the `timeout` error code, freeing the permit.
+    async fn execute_job(&self, executor: &dyn JobExecutor, job: &PublishJob) -> Result<()> {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .map_err(|e| PubCastError::Configuration(e.to_string()))?;
+
+        if !self.mark_job_running(&job.id).await? {
+            tracing::info!("Job {} was cancelled before it started", job.id);
+            return Ok(());
+        }
+        // The hook and the publish share one deadline so a hung hook can't
+        // hold the permit forever
+        let timeout = self.publish_timeout(&job.platform);
+        let deadline = tokio::time::Instant::now() + timeout;
+        if let Some(hook) = &self.pre_publish {
+            let rejected = match tokio::time::timeout_at(deadline, hook.before_publish(job)).await {
+                Ok(Ok(())) => None,
+                Ok(Err(e)) => Some(e.to_string()),
+                Err(_) => Some(format!(
+                    "Pre-publish hook timed out after {}s",
+                    timeout.as_secs()
+                )),
+            };
+            if let Some(error) = rejected {
+                tracing::warn!("Pre-publish hook rejected job {}: {}", job.id, error);
+                return self
+                    .mark_job_failed(
+                        &job.id,
+                        Some(PRE_PUBLISH_ERROR_CODE.to_string()),
+                        Some(error),
+                    )
+                    .await;
+            }
+        }
+        let started = std::time::Instant::now();
+        let result = match tokio::time::timeout_at(deadline, executor.execute(job)).await {
+            Ok(result) => result,
+            Err(_) => {
+                tracing::warn!("Job {} timed out after {:?}", job.id, timeout);
+                PublishResult {
+                    success: false,
+                    published_url: None,
+                    published_id: None,
+                    error_code: Some(TIMEOUT_ERROR_CODE.to_string()),
+                    error_message: Some(format!("Publish timed out after {}s", timeout.as_secs())),
+                }
+            }
+        };
+
+        let duration_ms = started.elapsed().as_millis() as i64;
+        self.record_attempt(job, &result, duration_ms).await;
+
+        if result.success {
+            self.mark_job_success(&job.id, result.published_url, result.published_id)
+                .await
+        } else {
+            self.mark_job_failed(&job.id, result.error_code, result.error_message)
+                .await
+        }
+    }
+
+    /// Log a publish attempt for the stats; a failure here never fails the job
+    async fn record_attempt(&self, job: &PublishJob, result: &PublishResult, duration_ms: i64) {
+        let status = if result.success {
+            PublishJobStatus::Success
+        } else {
+            PublishJobStatus::Failed
+        };
+        if let Err(e) = self
+            .stats
+            .record_publish_log(
+                job,
+                &status.to_string(),
+                Some(duration_ms),
+                result.error_code.as_deref(),
+                result.error_message.as_deref(),
+            )
+            .await
+        {
+            tracing::warn!("Failed to record publish log for job {}: {}", job.id, e);
+        }
+    }
+
+    /// Update job status to running. Returns `false` if the job is no longer
+    /// pending, e.g. it was cancelled while waiting for a slot.
+    pub async fn mark_job_running(&self, job_id: &str) -> Result<bool> {
         let now = chrono::Utc::now().timestamp();
         let status = PublishJobStatus::Running.to_string();
 
-        sqlx::query!(
-            "UPDATE publish_jobs SET status = ?, started_at = ?, updated_at = ? WHERE id = ?",
+        let result = sqlx::query!(
+            "UPDATE publish_jobs SET status = ?, started_at = ?, updated_at = ? WHERE id = ? AND status = 'pending'",
             status,
             now,
             now,
@@ -280,10 +1048,19 @@ impl SchedulerService {
         .execute(&self.pool)
         .await?;
 
-        Ok(())
+        let started = result.rows_affected() > 0;
+        if started {
+            self.emit_progress(job_id).await;
+        }
+        Ok(started)
     }
 
-    /// Mark job as successful
+    /// Mark job as successful.
+    ///
+    /// Reporting the same `published_id` for a job that already succeeded is
+    /// a no-op, so a re-run publish is never counted twice. Jobs already
+    /// settled as cancelled or failed, e.g. by [`Self::recover_stale_jobs`],
+    /// are left alone.
     pub async fn mark_job_success(
         &self,
         job_id: &str,
@@ -293,22 +1070,32 @@ impl SchedulerService {
         let now = chrono::Utc::now().timestamp();
         let status = PublishJobStatus::Success.to_string();
 
-        sqlx::query!(
-            r#"
+        let result = with_sqlite_retry(|| {
+            sqlx::query!(
+                r#"
             UPDATE publish_jobs 
             SET status = ?, completed_at = ?, published_url = ?, published_id = ?, updated_at = ?
-            WHERE id = ?
+            WHERE id = ? AND status NOT IN ('cancelled', 'failed')
+              AND NOT (status = 'success' AND published_id IS ?)
             "#,
-            status,
-            now,
-            published_url,
-            published_id,
-            now,
-            job_id
-        )
-        .execute(&self.pool)
+                status,
+                now,
+                published_url,
+                published_id,
+                now,
+                job_id,
+                published_id
+            )
+            .execute(&self.pool)
+        })
         .await?;
 
+        // Cancelled or failed while publishing: that already settled the task
+        if result.rows_affected() == 0 {
+            tracing::info!("Ignoring result of settled or already recorded job {}", job_id);
+            return Ok(());
+        }
+
         // Update distribution task counters
         self.update_task_counters_for_job(job_id, true).await?;
 
@@ -323,6 +1110,8 @@ impl SchedulerService {
         error_message: Option<String>,
     ) -> Result<()> {
         let now = chrono::Utc::now().timestamp();
+        let error_category =
+            classify_error(error_code.as_deref(), error_message.as_deref()).to_string();
 
         // Get current retry count
         let job = sqlx::query!(
@@ -334,27 +1123,35 @@ impl SchedulerService {
 
         if job.retry_count < job.max_retries {
             // Schedule retry with exponential backoff
-            let delay = Self::calculate_retry_delay(job.retry_count);
+            let delay =
+                calculate_retry_delay(job.retry_count, self.retry_jitter, &mut rand::thread_rng());
             let retry_at = now + delay as i64;
             let status = PublishJobStatus::Pending.to_string();
 
-            sqlx::query!(
+            let result = sqlx::query!(
                 r#"
                 UPDATE publish_jobs 
                 SET status = ?, retry_count = retry_count + 1, scheduled_at = ?,
-                    error_code = ?, error_message = ?, updated_at = ?
-                WHERE id = ?
+                    error_code = ?, error_message = ?, error_category = ?, updated_at = ?
+                WHERE id = ? AND status != 'cancelled'
                 "#,
                 status,
                 retry_at,
                 error_code,
                 error_message,
+                error_category,
                 now,
                 job_id
             )
             .execute(&self.pool)
             .await?;
 
+            if result.rows_affected() == 0 {
+                tracing::info!("Not retrying cancelled job {}", job_id);
+                return Ok(());
+            }
+            self.emit_progress(job_id).await;
+
             tracing::info!(
                 "Job {} scheduled for retry {} at {}",
                 job_id,
@@ -365,22 +1162,31 @@ impl SchedulerService {
             // Max retries reached, mark as failed
             let status = PublishJobStatus::Failed.to_string();
 
-            sqlx::query!(
-                r#"
+            let result = with_sqlite_retry(|| {
+                sqlx::query!(
+                    r#"
                 UPDATE publish_jobs 
-                SET status = ?, completed_at = ?, error_code = ?, error_message = ?, updated_at = ?
-                WHERE id = ?
+                SET status = ?, completed_at = ?, error_code = ?, error_message = ?,
+                    error_category = ?, updated_at = ?
+                WHERE id = ? AND status != 'cancelled'
                 "#,
-                status,
-                now,
-                error_code,
-                error_message,
-                now,
-                job_id
-            )
-            .execute(&self.pool)
+                    status,
+                    now,
+                    error_code,
+                    error_message,
+                    error_category,
+                    now,
+                    job_id
+                )
+                .execute(&self.pool)
+            })
             .await?;
 
+            if result.rows_affected() == 0 {
+                tracing::info!("Ignoring failure of cancelled job {}", job_id);
+                return Ok(());
+            }
+
             // Update distribution task counters
             self.update_task_counters_for_job(job_id, false).await?;
         }
@@ -388,12 +1194,6 @@ impl SchedulerService {
         Ok(())
     }
 
-    /// Calculate retry delay with exponential backoff
-    fn calculate_retry_delay(retry_count: i64) -> u64 {
-        let delay = RETRY_BASE_DELAY_SECS * 2u64.pow(retry_count as u32);
-        delay.min(MAX_RETRY_DELAY_SECS)
-    }
-
     /// Update distribution task counters after job completion
     async fn update_task_counters_for_job(&self, job_id: &str, success: bool) -> Result<()> {
         let job = sqlx::query!("SELECT distribution_task_id FROM publish_jobs WHERE id = ?", job_id)
@@ -403,41 +1203,98 @@ impl SchedulerService {
         let now = chrono::Utc::now().timestamp();
 
         if success {
-            sqlx::query!(
-                r#"
+            with_sqlite_retry(|| {
+                sqlx::query!(
+                    r#"
                 UPDATE distribution_tasks 
                 SET completed_jobs = completed_jobs + 1, updated_at = ?
                 WHERE id = ?
                 "#,
-                now,
-                job.distribution_task_id
-            )
-            .execute(&self.pool)
+                    now,
+                    job.distribution_task_id
+                )
+                .execute(&self.pool)
+            })
             .await?;
         } else {
-            sqlx::query!(
-                r#"
+            with_sqlite_retry(|| {
+                sqlx::query!(
+                    r#"
                 UPDATE distribution_tasks 
                 SET failed_jobs = failed_jobs + 1, updated_at = ?
                 WHERE id = ?
                 "#,
-                now,
-                job.distribution_task_id
-            )
-            .execute(&self.pool)
+                    now,
+                    job.distribution_task_id
+                )
+                .execute(&self.pool)
+            })
             .await?;
         }
 
-        // Check if task is complete
+        self.emit_progress(job_id).await;
+        self.complete_task_if_finished(&job.distribution_task_id).await
+    }
+
+    /// Send the job's current state and its task's counters to the event
+    /// sender, if any. Failures are logged rather than failing the transition.
+    async fn emit_progress(&self, job_id: &str) {
+        let Some(events) = &self.events else {
+            return;
+        };
+        let progress = async {
+            let last_job = self.get_publish_job(job_id).await?;
+            let task = self.get_distribution_task(&last_job.distribution_task_id).await?;
+            Ok::<_, PubCastError>(TaskProgress {
+                task_id: task.id,
+                completed_jobs: task.completed_jobs,
+                failed_jobs: task.failed_jobs,
+                total_jobs: task.total_jobs,
+                last_job,
+            })
+        };
+        match progress.await {
+            Ok(progress) => {
+                let _ = events.send(TaskEvent::Progress(progress));
+            }
+            Err(e) => tracing::warn!("Failed to read progress for job {}: {}", job_id, e),
+        }
+    }
+
+    /// Settle a task's status once every job has succeeded, failed or been cancelled
+    async fn complete_task_if_finished(&self, task_id: &str) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
         let task = sqlx::query!(
-            "SELECT total_jobs, completed_jobs, failed_jobs FROM distribution_tasks WHERE id = ?",
-            job.distribution_task_id
+            r#"
+            SELECT schedule_type, total_jobs, completed_jobs, failed_jobs,
+                   (SELECT COUNT(*) FROM publish_jobs
+                    WHERE distribution_task_id = distribution_tasks.id AND status = 'cancelled') as "cancelled_jobs!: i64"
+            FROM distribution_tasks WHERE id = ?
+            "#,
+            task_id
         )
         .fetch_one(&self.pool)
         .await?;
 
-        if task.completed_jobs + task.failed_jobs >= task.total_jobs {
-            let status = if task.failed_jobs == 0 {
+        if task.completed_jobs + task.failed_jobs + task.cancelled_jobs < task.total_jobs {
+            return Ok(());
+        }
+
+        if task.schedule_type == ScheduleType::Recurring.to_string() {
+            // Run finished; wait for the next fire time instead of completing
+            let status = DistributionTaskStatus::Pending.to_string();
+            sqlx::query!(
+                "UPDATE distribution_tasks SET status = ?, updated_at = ? WHERE id = ? AND status = 'in_progress'",
+                status,
+                now,
+                task_id
+            )
+            .execute(&self.pool)
+            .await?;
+        } else {
+            let status = if task.completed_jobs == 0 && task.failed_jobs == 0 {
+                DistributionTaskStatus::Cancelled.to_string()
+            } else if task.failed_jobs == 0 {
                 DistributionTaskStatus::Completed.to_string()
             } else if task.completed_jobs == 0 {
                 DistributionTaskStatus::Failed.to_string()
@@ -445,20 +1302,402 @@ impl SchedulerService {
                 DistributionTaskStatus::Completed.to_string() // Partial success still counts as completed
             };
 
-            sqlx::query!(
-                "UPDATE distribution_tasks SET status = ?, completed_at = ?, updated_at = ? WHERE id = ?",
+            let result = sqlx::query!(
+                r#"
+                UPDATE distribution_tasks SET status = ?, completed_at = ?, updated_at = ?
+                WHERE id = ? AND status IN ('pending', 'in_progress')
+                "#,
                 status,
                 now,
                 now,
-                job.distribution_task_id
+                task_id
+            )
+            .execute(&self.pool)
+            .await?;
+
+            let announce = self.events.is_some() || self.notifications.is_some();
+            if result.rows_affected() > 0 && announce {
+                let task = self.get_distribution_task(task_id).await?;
+                if let Some(notifications) = &self.notifications {
+                    // Delivered in the background so slow webhooks never hold up the queue
+                    let notifications = notifications.clone();
+                    let task = task.clone();
+                    tokio::spawn(async move {
+                        notifications.notify_task_finished(&task).await;
+                    });
+                }
+                if let Some(events) = &self.events {
+                    let _ = events.send(TaskEvent::Completed(task));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Settle jobs left `running` by an earlier run of the app, e.g. after a
+    /// crash or restart mid-publish. Run once by the worker on startup with
+    /// `now` as the start time, so only jobs started before it are touched.
+    ///
+    /// Jobs on platforms whose adapter publishes idempotently are re-queued;
+    /// the rest may or may not have been published, so they are failed
+    /// without retry for the user to check.
+    pub async fn recover_stale_jobs(&self, now: i64) -> Result<StaleJobRecovery> {
+        let running = sqlx::query!(
+            "SELECT id, platform FROM publish_jobs WHERE status = 'running' AND (started_at IS NULL OR started_at < ?)",
+            now
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut recovery = StaleJobRecovery::default();
+        for job in running {
+            if self.idempotent_platforms.contains(&job.platform) {
+                let status = PublishJobStatus::Pending.to_string();
+                let result = sqlx::query!(
+                    "UPDATE publish_jobs SET status = ?, started_at = NULL, updated_at = ? WHERE id = ? AND status = 'running'",
+                    status,
+                    now,
+                    job.id
+                )
+                .execute(&self.pool)
+                .await?;
+                if result.rows_affected() > 0 {
+                    tracing::warn!("Re-queued interrupted job {}", job.id);
+                    recovery.requeued += 1;
+                    self.emit_progress(&job.id).await;
+                }
+                continue;
+            }
+
+            let status = PublishJobStatus::Failed.to_string();
+            let error_code = INTERRUPTED_ERROR_CODE;
+            let error_message = "Publish was interrupted; check the platform before retrying";
+            let error_category = classify_error(Some(error_code), Some(error_message)).to_string();
+            let result = sqlx::query!(
+                r#"
+                UPDATE publish_jobs
+                SET status = ?, completed_at = ?, error_code = ?, error_message = ?,
+                    error_category = ?, updated_at = ?
+                WHERE id = ? AND status = 'running'
+                "#,
+                status,
+                now,
+                error_code,
+                error_message,
+                error_category,
+                now,
+                job.id
+            )
+            .execute(&self.pool)
+            .await?;
+            if result.rows_affected() > 0 {
+                tracing::warn!("Failed interrupted job {} for manual review", job.id);
+                recovery.failed += 1;
+                self.update_task_counters_for_job(&job.id, false).await?;
+            }
+        }
+
+        Ok(recovery)
+    }
+
+    /// Stop the background worker started by [`spawn_worker`]
+    pub fn stop_worker(&mut self) {
+        self.shutdown_tx.take();
+    }
+
+    /// Run one pass of background work at `now`
+    pub async fn tick<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Result<()> {
+        let materialized = self.materialize_recurring_jobs(now).await?;
+        if materialized > 0 {
+            tracing::info!("Materialized {} recurring publish jobs", materialized);
+        }
+        self.defer_rate_limited_jobs(now.timestamp()).await?;
+
+        if let Some(executor) = &self.executor {
+            let limit = (self.concurrency() * 2) as i32;
+            let executed = self.dispatch_pending_jobs(executor.as_ref(), limit).await?;
+            if executed > 0 {
+                tracing::info!("Executed {} publish jobs", executed);
+            }
+        }
+        Ok(())
+    }
+
+    /// Push due jobs that would exceed their platform's hourly limit past the
+    /// point where the trailing window frees up. Returns the number deferred.
+    ///
+    /// Successful publishes in the last hour and jobs currently running both
+    /// count against the limit.
+    pub async fn defer_rate_limited_jobs(&self, now: i64) -> Result<usize> {
+        let window_start = now - RATE_LIMIT_WINDOW_SECS;
+        let mut deferred = 0;
+
+        for (platform, limit) in &self.rate_limits {
+            let recent = sqlx::query_scalar!(
+                r#"
+                SELECT COALESCE(completed_at, started_at) as "at!: i64"
+                FROM publish_jobs
+                WHERE platform = ? AND dry_run = 0
+                  AND ((status = 'success' AND completed_at > ?)
+                       OR (status = 'running' AND started_at > ?))
+                ORDER BY 1 ASC
+                "#,
+                platform,
+                window_start,
+                window_start
+            )
+            .fetch_all(&self.pool)
+            .await?;
+
+            // Paused tasks' jobs won't be dispatched, so they take no slots
+            let due = sqlx::query_scalar!(
+                r#"
+                SELECT id FROM publish_jobs
+                WHERE platform = ? AND status = 'pending'
+                  AND (scheduled_at IS NULL OR scheduled_at <= ?)
+                  AND distribution_task_id NOT IN (
+                      SELECT id FROM distribution_tasks WHERE status = 'paused'
+                  )
+                ORDER BY priority DESC, created_at ASC
+                "#,
+                platform,
+                now
+            )
+            .fetch_all(&self.pool)
+            .await?;
+
+            let available = (*limit as usize).saturating_sub(recent.len());
+            if due.len() <= available {
+                continue;
+            }
+
+            // Retry once the oldest publish in the window ages out
+            let retry_at = recent.first().copied().unwrap_or(now) + RATE_LIMIT_WINDOW_SECS;
+            for job_id in &due[available..] {
+                sqlx::query!(
+                    "UPDATE publish_jobs SET scheduled_at = ?, updated_at = ? WHERE id = ?",
+                    retry_at,
+                    now,
+                    job_id
+                )
+                .execute(&self.pool)
+                .await?;
+            }
+
+            tracing::info!(
+                "Rate limit for {} reached ({}/hr); deferred {} jobs until {}",
+                platform,
+                limit,
+                due.len() - available,
+                retry_at
+            );
+            deferred += due.len() - available;
+        }
+
+        Ok(deferred)
+    }
+
+    /// Create the next run's jobs for every recurring task whose fire time has
+    /// arrived and whose previous run has finished. Returns the number of jobs created.
+    ///
+    /// The next fire time is computed from `now`, so windows missed while the
+    /// app was closed collapse into a single run.
+    pub async fn materialize_recurring_jobs<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Result<usize> {
+        let now_ts = now.timestamp();
+        let due = sqlx::query!(
+            r#"
+            SELECT t.id, t.content_id, t.target_accounts, t.cron_expression, t.platform_options
+            FROM distribution_tasks t
+            WHERE t.schedule_type = 'recurring'
+              AND t.status NOT IN ('cancelled', 'paused')
+              AND t.next_run_at <= ?
+              AND NOT EXISTS (
+                  SELECT 1 FROM publish_jobs j
+                  WHERE j.distribution_task_id = t.id AND j.status IN ('pending', 'running')
+              )
+            "#,
+            now_ts
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut created = 0;
+        for task in due {
+            let Some(expr) = task.cron_expression.as_deref() else {
+                continue;
+            };
+            let next_run_at = next_fire_time(&parse_cron(expr)?, now)?;
+            let account_ids: Vec<String> =
+                serde_json::from_str(&task.target_accounts).unwrap_or_default();
+            let platform_options: HashMap<String, serde_json::Value> = task
+                .platform_options
+                .as_deref()
+                .and_then(|options| serde_json::from_str(options).ok())
+                .unwrap_or_default();
+
+            let mut jobs: i64 = 0;
+            for account_id in &account_ids {
+                let account = sqlx::query!(
+                    "SELECT platform FROM accounts WHERE id = ? AND deleted_at IS NULL",
+                    account_id
+                )
+                .fetch_optional(&self.pool)
+                .await?;
+                let Some(account) = account else {
+                    tracing::warn!(
+                        "Recurring task {} skips missing or deleted account {}",
+                        task.id,
+                        account_id
+                    );
+                    continue;
+                };
+                self.create_publish_job(
+                    &task.id,
+                    &task.content_id,
+                    account_id,
+                    &account.platform,
+                    Some(now_ts),
+                    0,
+                    platform_options.get(&account.platform),
+                )
+                .await?;
+                jobs += 1;
+            }
+
+            let status = if jobs > 0 {
+                DistributionTaskStatus::InProgress
+            } else {
+                DistributionTaskStatus::Pending
+            }
+            .to_string();
+            sqlx::query!(
+                r#"
+                UPDATE distribution_tasks
+                SET status = ?, total_jobs = total_jobs + ?, next_run_at = ?, scheduled_at = ?,
+                    started_at = COALESCE(started_at, ?), updated_at = ?
+                WHERE id = ?
+                "#,
+                status,
+                jobs,
+                next_run_at,
+                next_run_at,
+                now_ts,
+                now_ts,
+                task.id
             )
             .execute(&self.pool)
             .await?;
+
+            created += jobs as usize;
+        }
+
+        Ok(created)
+    }
+
+    /// Cancel a single pending or running job, letting the rest of its task proceed.
+    ///
+    /// A running job is not interrupted; its result is discarded when the
+    /// worker tries to record it.
+    pub async fn cancel_job(&self, job_id: &str) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        let status = PublishJobStatus::Cancelled.to_string();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE publish_jobs SET status = ?, completed_at = ?, updated_at = ?
+            WHERE id = ? AND status IN ('pending', 'running')
+            "#,
+            status,
+            now,
+            now,
+            job_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        let job = sqlx::query!(
+            "SELECT distribution_task_id, status FROM publish_jobs WHERE id = ?",
+            job_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| PubCastError::NotFound(format!("Publish job not found: {}", job_id)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(PubCastError::Validation(format!(
+                "Cannot cancel job {} with status {}",
+                job_id, job.status
+            )));
+        }
+
+        tracing::info!("Cancelled publish job {}", job_id);
+        self.emit_progress(job_id).await;
+        self.complete_task_if_finished(&job.distribution_task_id).await
+    }
+
+    /// Hold a pending or in-progress task: its pending jobs stay queued but are
+    /// not dispatched until [`Self::resume_distribution_task`]. Jobs already
+    /// running are allowed to finish.
+    pub async fn pause_distribution_task(&self, task_id: &str) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        let status = DistributionTaskStatus::Paused.to_string();
+
+        let result = sqlx::query!(
+            "UPDATE distribution_tasks SET status = ?, updated_at = ? WHERE id = ? AND status IN ('pending', 'in_progress')",
+            status,
+            now,
+            task_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            let current = self.task_status(task_id).await?;
+            return Err(PubCastError::Validation(format!("Cannot pause a {} task", current)));
+        }
+
+        tracing::info!("Paused distribution task {}", task_id);
+        Ok(())
+    }
+
+    /// Let the worker pick up a paused task's pending jobs again
+    pub async fn resume_distribution_task(&self, task_id: &str) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE distribution_tasks
+            SET status = CASE WHEN started_at IS NULL THEN 'pending' ELSE 'in_progress' END,
+                updated_at = ?
+            WHERE id = ? AND status = 'paused'
+            "#,
+            now,
+            task_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            let current = self.task_status(task_id).await?;
+            return Err(PubCastError::Validation(format!("Cannot resume a {} task", current)));
         }
 
+        // Jobs that were running when the task was paused may have been its last
+        self.complete_task_if_finished(task_id).await?;
+        tracing::info!("Resumed distribution task {}", task_id);
         Ok(())
     }
 
+    async fn task_status(&self, task_id: &str) -> Result<String> {
+        sqlx::query_scalar!("SELECT status FROM distribution_tasks WHERE id = ?", task_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| {
+                PubCastError::NotFound(format!("Distribution task not found: {}", task_id))
+            })
+    }
+
     /// Cancel a distribution task
     pub async fn cancel_distribution_task(&self, task_id: &str) -> Result<()> {
         let now = chrono::Utc::now().timestamp();
@@ -488,4 +1727,1778 @@ impl SchedulerService {
 
         Ok(())
     }
+
+    /// Delete completed, failed and cancelled tasks that finished more than
+    /// `older_than_days` ago, with their jobs and logs
+    pub async fn purge_old_tasks(
+        &self,
+        older_than_days: i64,
+        archive: Option<&Path>,
+    ) -> Result<TaskPurge> {
+        self.purge_tasks_before(purge_cutoff(older_than_days)?, archive)
+            .await
+    }
+
+    /// Delete finished tasks whose last update is before `cutoff`, first
+    /// writing them and their jobs to `archive` as JSON when given.
+    ///
+    /// Tasks still holding pending or running jobs are kept whatever their
+    /// status, as are pending, running and paused tasks.
+    pub async fn purge_tasks_before(
+        &self,
+        cutoff: i64,
+        archive: Option<&Path>,
+    ) -> Result<TaskPurge> {
+        let task_ids = sqlx::query_scalar!(
+            r#"
+            SELECT id FROM distribution_tasks
+            WHERE status IN ('completed', 'failed', 'cancelled')
+              AND COALESCE(completed_at, updated_at) < ?
+              AND NOT EXISTS (
+                  SELECT 1 FROM publish_jobs
+                  WHERE distribution_task_id = distribution_tasks.id
+                    AND status IN ('pending', 'running')
+              )
+            "#,
+            cutoff
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        if task_ids.is_empty() {
+            return Ok(TaskPurge::default());
+        }
+
+        if let Some(archive) = archive {
+            let mut archived = Vec::with_capacity(task_ids.len());
+            for task_id in &task_ids {
+                archived.push(ArchivedTask {
+                    task: self.get_distribution_task(task_id).await?,
+                    jobs: self.list_jobs_for_task(task_id).await?,
+                });
+            }
+            let json = serde_json::to_vec_pretty(&archived)?;
+            tokio::fs::write(archive, json).await.map_err(|e| {
+                PubCastError::Configuration(format!("Failed to write task archive: {}", e))
+            })?;
+        }
+
+        let mut purge = TaskPurge::default();
+        let mut tx = self.pool.begin().await?;
+        for task_id in &task_ids {
+            purge.jobs += sqlx::query!(
+                "DELETE FROM publish_jobs WHERE distribution_task_id = ?",
+                task_id
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            purge.tasks += sqlx::query!("DELETE FROM distribution_tasks WHERE id = ?", task_id)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+        tx.commit().await?;
+
+        tracing::info!(
+            "Purged {} finished tasks and {} jobs",
+            purge.tasks,
+            purge.jobs
+        );
+        Ok(purge)
+    }
+}
+
+/// Timestamp `older_than_days` days before now
+fn purge_cutoff(older_than_days: i64) -> Result<i64> {
+    if older_than_days < 0 {
+        return Err(PubCastError::Validation(
+            "Age of tasks to purge can't be negative".to_string(),
+        ));
+    }
+    older_than_days
+        .checked_mul(86_400)
+        .and_then(|age| chrono::Utc::now().timestamp().checked_sub(age))
+        .ok_or_else(|| {
+            PubCastError::Validation(format!(
+                "Age of tasks to purge is too large: {} days",
+                older_than_days
+            ))
+        })
+}
+
+fn validate_concurrency(concurrency: usize) -> Result<()> {
+    if concurrency == 0 {
+        return Err(PubCastError::Validation(
+            "Concurrency must be at least 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check content against each distinct target platform's adapter.
+/// Platforms without a registered adapter are not checked.
+fn content_issues<'a>(
+    registry: &AdapterRegistry,
+    content: &Content,
+    platforms: impl IntoIterator<Item = &'a str>,
+) -> Vec<ContentIssue> {
+    let mut checked = Vec::new();
+    let mut issues = Vec::new();
+    for platform in platforms {
+        if checked.contains(&platform) {
+            continue;
+        }
+        checked.push(platform);
+        if let Ok(adapter) = registry.get(platform) {
+            issues.extend(adapter.validate_content(content));
+        }
+    }
+    issues
+}
+
+/// Distinct platforms among `platforms` whose adapter can't schedule posts.
+/// Unknown platforms are left to fail at publish time.
+fn unschedulable_platforms<'a>(
+    registry: &AdapterRegistry,
+    platforms: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+    let mut unschedulable: Vec<String> = Vec::new();
+    for platform in platforms {
+        let schedulable = match registry.get(platform) {
+            Ok(adapter) => adapter.capabilities().supports_scheduling,
+            Err(_) => true,
+        };
+        if !schedulable && !unschedulable.iter().any(|p| p == platform) {
+            unschedulable.push(platform.to_string());
+        }
+    }
+    unschedulable
+}
+
+/// Hourly publish limits declared by the registered platform adapters
+fn default_rate_limits(registry: &AdapterRegistry) -> HashMap<String, u32> {
+    registry
+        .all_platforms()
+        .into_iter()
+        .filter_map(|platform| {
+            let limit = registry.get(platform).ok()?.capabilities().rate_limit_per_hour?;
+            Some((platform.to_string(), limit))
+        })
+        .collect()
+}
+
+/// Publish timeouts declared by the registered platform adapters
+fn default_publish_timeouts(registry: &AdapterRegistry) -> HashMap<String, Duration> {
+    registry
+        .all_platforms()
+        .into_iter()
+        .filter_map(|platform| {
+            let secs = registry
+                .get(platform)
+                .ok()?
+                .capabilities()
+                .publish_timeout_secs?;
+            Some((platform.to_string(), Duration::from_secs(secs)))
+        })
+        .collect()
+}
+
+/// Forget permits as running jobs release them until `debt` is paid off.
+///
+/// Permits are taken one at a time so a raise that cancels the debt midway
+/// gets every permit back.
+async fn collect_permit_debt(semaphore: Arc<Semaphore>, debt: Arc<AtomicUsize>) {
+    while debt.load(Ordering::SeqCst) > 0 {
+        let Ok(permit) = semaphore.acquire().await else {
+            return;
+        };
+        let paid = debt
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |d| d.checked_sub(1))
+            .is_ok();
+        if !paid {
+            // Raised while waiting: the permit goes back into circulation
+            return;
+        }
+        permit.forget();
+    }
+}
+
+/// Platforms whose registered adapters publish idempotently
+fn default_idempotent_platforms(registry: &AdapterRegistry) -> HashSet<String> {
+    registry
+        .all_platforms()
+        .into_iter()
+        .filter(|platform| {
+            registry
+                .get(platform)
+                .is_ok_and(|adapter| adapter.capabilities().idempotent_publish)
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Forward scheduler events to the frontend as `task://progress` and
+/// `task://completed` until the scheduler is dropped
+pub fn spawn_event_forwarder(app_handle: tauri::AppHandle, mut events: mpsc::UnboundedReceiver<TaskEvent>) {
+    use tauri::Emitter;
+
+    tokio::spawn(async move {
+        while let Some(event) = events.recv().await {
+            let emitted = match &event {
+                TaskEvent::Progress(progress) => app_handle.emit(TASK_PROGRESS_EVENT, progress),
+                TaskEvent::Completed(task) => app_handle.emit(TASK_COMPLETED_EVENT, task),
+            };
+            if let Err(e) = emitted {
+                tracing::warn!("Failed to emit task event: {}", e);
+            }
+        }
+    });
+}
+
+/// Start the scheduler's background worker
+pub async fn spawn_worker(scheduler: Arc<RwLock<SchedulerService>>) {
+    let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
+    scheduler.write().await.shutdown_tx = Some(shutdown_tx);
+    tokio::spawn(run_worker(scheduler, shutdown_rx));
+}
+
+/// Run the scheduler's background loop until `shutdown` fires or its sender is dropped.
+///
+/// Jobs left running by the previous run are settled first, and the first
+/// tick runs immediately so work that came due while the app was closed is
+/// picked up on startup.
+pub async fn run_worker(scheduler: Arc<RwLock<SchedulerService>>, mut shutdown: mpsc::Receiver<()>) {
+    let started_at = chrono::Utc::now().timestamp();
+    match scheduler.read().await.recover_stale_jobs(started_at).await {
+        Ok(recovery) if recovery != StaleJobRecovery::default() => tracing::info!(
+            "Recovered stale jobs: {} re-queued, {} failed",
+            recovery.requeued,
+            recovery.failed
+        ),
+        Ok(_) => {}
+        Err(e) => tracing::error!("Stale job recovery failed: {}", e),
+    }
+
+    let mut interval = tokio::time::interval(WORKER_TICK_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let now = chrono::Local::now();
+                if let Err(e) = scheduler.read().await.tick(&now).await {
+                    tracing::error!("Scheduler tick failed: {}", e);
+                }
+            }
+            _ = shutdown.recv() => break,
+        }
+    }
+}
+
+/// Parse a standard 5-field cron expression
+pub(crate) fn parse_cron(expr: &str) -> Result<Cron> {
+    Cron::new(expr)
+        .parse()
+        .map_err(|e| PubCastError::Validation(format!("Invalid cron expression '{}': {}", expr, e)))
+}
+
+/// First fire time strictly after `after`, as a UTC timestamp
+pub(crate) fn next_fire_time<Tz: TimeZone>(cron: &Cron, after: &DateTime<Tz>) -> Result<i64> {
+    cron.find_next_occurrence(after, false)
+        .map(|next| next.timestamp())
+        .map_err(|e| PubCastError::Validation(format!("Cron expression never fires: {}", e)))
+}
+
+/// Parse a wall-clock time such as "2024-12-01T09:00" or "2024-12-01 09:00:00"
+fn parse_local_time(value: &str) -> Result<NaiveDateTime> {
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(value, fmt).ok())
+        .ok_or_else(|| PubCastError::Validation(format!("Invalid local time: {}", value)))
+}
+
+/// Convert a wall-clock time in the given IANA timezone to a UTC timestamp.
+///
+/// Accounts without a timezone are treated as UTC. For ambiguous times during a
+/// DST fall-back the earlier instant is used; times skipped by a DST jump are rejected.
+pub(crate) fn local_time_to_utc(local: &NaiveDateTime, timezone: Option<&str>) -> Result<i64> {
+    let tz: chrono_tz::Tz = match timezone {
+        Some(name) => name
+            .parse()
+            .map_err(|_| PubCastError::Validation(format!("Unknown timezone: {}", name)))?,
+        None => chrono_tz::UTC,
+    };
+
+    tz.from_local_datetime(local)
+        .earliest()
+        .map(|dt| dt.timestamp())
+        .ok_or_else(|| {
+            PubCastError::Validation(format!("Local time {} does not exist in {}", local, tz))
+        })
+}
+
+/// Retry delay in seconds: exponential backoff capped at `MAX_RETRY_DELAY_SECS`,
+/// with the top `jitter` share of the window drawn from `rng`
+fn calculate_retry_delay(retry_count: i64, jitter: f64, rng: &mut impl Rng) -> u64 {
+    let delay = 2u64
+        .checked_pow(retry_count.clamp(0, i64::from(u32::MAX)) as u32)
+        .and_then(|factor| factor.checked_mul(RETRY_BASE_DELAY_SECS))
+        .unwrap_or(MAX_RETRY_DELAY_SECS)
+        .min(MAX_RETRY_DELAY_SECS);
+
+    let window = (delay as f64 * jitter).round() as u64;
+    if window == 0 {
+        return delay;
+    }
+    delay - window + rng.gen_range(0..=window)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+    use crate::models::IssueSeverity;
+
+    #[test]
+    fn test_local_time_to_utc_differs_by_zone() {
+        let local = parse_local_time("2030-01-15T09:00").unwrap();
+
+        let shanghai = local_time_to_utc(&local, Some("Asia/Shanghai")).unwrap();
+        let new_york = local_time_to_utc(&local, Some("America/New_York")).unwrap();
+        let utc = local_time_to_utc(&local, None).unwrap();
+
+        assert_eq!(utc - shanghai, 8 * 3600);
+        assert_eq!(new_york - utc, 5 * 3600);
+    }
+
+    #[test]
+    fn test_local_time_to_utc_rejects_invalid_input() {
+        let local = parse_local_time("2030-01-15 09:00:00").unwrap();
+        assert!(local_time_to_utc(&local, Some("Mars/Olympus")).is_err());
+
+        // 02:30 is skipped by the US spring-forward transition
+        let gap = parse_local_time("2030-03-10T02:30").unwrap();
+        assert!(local_time_to_utc(&gap, Some("America/New_York")).is_err());
+
+        assert!(parse_local_time("9am").is_err());
+    }
+
+    #[test]
+    fn test_retry_delay_jitter_stays_within_the_backoff_window() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        for (retry_count, delay) in [(0, 5), (1, 10), (2, 20), (5, 160), (6, 300), (40, 300)] {
+            assert_eq!(calculate_retry_delay(retry_count, 0.0, &mut rng), delay);
+
+            let equal: Vec<u64> = (0..50)
+                .map(|_| calculate_retry_delay(retry_count, 0.5, &mut rng))
+                .collect();
+            let min = delay - (delay as f64 * 0.5).round() as u64;
+            assert!(equal.iter().all(|d| (min..=delay).contains(d)), "{:?}", equal);
+            assert!(equal.iter().any(|d| *d != equal[0]), "{:?}", equal);
+
+            let full: Vec<u64> = (0..50)
+                .map(|_| calculate_retry_delay(retry_count, 1.0, &mut rng))
+                .collect();
+            assert!(full.iter().all(|d| *d <= delay), "{:?}", full);
+            assert!(full.iter().any(|d| *d != full[0]), "{:?}", full);
+        }
+    }
+
+    #[test]
+    fn test_parse_cron() {
+        assert!(parse_cron("0 9 * * 1-5").is_ok());
+        assert!(parse_cron("*/15 * * * *").is_ok());
+        assert!(matches!(parse_cron("every day"), Err(PubCastError::Validation(_))));
+        assert!(matches!(parse_cron("0 25 * * *"), Err(PubCastError::Validation(_))));
+    }
+
+    #[test]
+    fn test_next_fire_time_skips_to_next_weekday() {
+        let weekdays_at_nine = parse_cron("0 9 * * 1-5").unwrap();
+
+        // Friday 2030-01-18 10:00 -> Monday 2030-01-21 09:00
+        let friday = chrono::Utc.with_ymd_and_hms(2030, 1, 18, 10, 0, 0).unwrap();
+        let monday = chrono::Utc.with_ymd_and_hms(2030, 1, 21, 9, 0, 0).unwrap();
+        assert_eq!(next_fire_time(&weekdays_at_nine, &friday).unwrap(), monday.timestamp());
+
+        // Exactly on a fire time moves on to the next one
+        let next = chrono::Utc.with_ymd_and_hms(2030, 1, 22, 9, 0, 0).unwrap();
+        assert_eq!(next_fire_time(&weekdays_at_nine, &monday).unwrap(), next.timestamp());
+
+        // Evaluated in the given zone: 09:00 Shanghai is 01:00 UTC
+        let shanghai = chrono_tz::Asia::Shanghai
+            .with_ymd_and_hms(2030, 1, 21, 8, 0, 0)
+            .unwrap();
+        let expected = chrono::Utc.with_ymd_and_hms(2030, 1, 21, 1, 0, 0).unwrap();
+        assert_eq!(next_fire_time(&weekdays_at_nine, &shanghai).unwrap(), expected.timestamp());
+    }
+
+    #[tokio::test]
+    async fn test_recurring_task_runs_once_after_missed_windows() {
+        let (_dir, pool) = init_test_database().await;
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'Hello')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES ('a1', 'wechat', 'a1', 'active')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let service = SchedulerService::new(pool.clone());
+        let task = service
+            .create_distribution_task(CreateDistributionTaskRequest {
+                content_id: "c1".to_string(),
+                name: None,
+                target_account_ids: vec!["a1".to_string()],
+                schedule_type: ScheduleType::Recurring,
+                scheduled_at: None,
+                local_time: None,
+                cron_expression: Some("0 9 * * *".to_string()),
+                account_priorities: HashMap::new(),
+                target_platforms: None,
+                only_active: false,
+                strict: false,
+                dry_run: false,
+                per_platform_options: HashMap::new(),
+                downgrade_unschedulable: false,
+            })
+            .await
+            .unwrap()
+            .task;
+        assert_eq!(task.total_jobs, 0);
+        assert!(task.next_run_at.is_some());
+
+        // Pretend the app was closed for three days of fire times
+        let now = chrono::Utc.with_ymd_and_hms(2030, 1, 18, 12, 0, 0).unwrap();
+        let three_days_ago = now.timestamp() - 3 * 86_400;
+        sqlx::query("UPDATE distribution_tasks SET next_run_at = ? WHERE id = ?")
+            .bind(three_days_ago)
+            .bind(&task.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(service.materialize_recurring_jobs(&now).await.unwrap(), 1);
+        // Previous run still pending: nothing new
+        assert_eq!(service.materialize_recurring_jobs(&now).await.unwrap(), 0);
+
+        let task = service.get_distribution_task(&task.id).await.unwrap();
+        let tomorrow_nine = chrono::Utc.with_ymd_and_hms(2030, 1, 19, 9, 0, 0).unwrap();
+        assert_eq!(task.next_run_at, Some(tomorrow_nine.timestamp()));
+        assert_eq!(task.status, DistributionTaskStatus::InProgress);
+
+        let job_id: String = sqlx::query_scalar("SELECT id FROM publish_jobs WHERE distribution_task_id = ?")
+            .bind(&task.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        service.mark_job_success(&job_id, None, None).await.unwrap();
+
+        // Run finished but the next fire time hasn't arrived
+        let task = service.get_distribution_task(&task.id).await.unwrap();
+        assert_eq!(task.status, DistributionTaskStatus::Pending);
+        assert_eq!(service.materialize_recurring_jobs(&now).await.unwrap(), 0);
+
+        // Once it arrives, exactly one more run is created
+        assert_eq!(service.materialize_recurring_jobs(&tomorrow_nine).await.unwrap(), 1);
+
+        // A trashed account gets no more runs
+        sqlx::query("UPDATE publish_jobs SET status = 'success'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE accounts SET deleted_at = 1 WHERE id = 'a1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let day_after = chrono::Utc.with_ymd_and_hms(2030, 1, 20, 9, 0, 0).unwrap();
+        assert_eq!(service.materialize_recurring_jobs(&day_after).await.unwrap(), 0);
+    }
+
+    /// Records the highest number of jobs it was executing at once
+    #[derive(Default)]
+    struct CountingExecutor {
+        running: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl JobExecutor for CountingExecutor {
+        async fn execute(&self, _job: &PublishJob) -> PublishResult {
+            use std::sync::atomic::Ordering;
+
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            PublishResult {
+                success: true,
+                published_url: None,
+                published_id: None,
+                error_code: None,
+                error_message: None,
+            }
+        }
+    }
+
+    async fn seed_pending_jobs(pool: &SqlitePool, count: usize) {
+        for sql in [
+            "INSERT INTO contents (id, title) VALUES ('c1', 'Hello')",
+            "INSERT INTO accounts (id, platform, name, status) VALUES ('a1', 'zhihu', 'a1', 'active')",
+            "INSERT INTO distribution_tasks (id, content_id, target_accounts) VALUES ('t1', 'c1', '[]')",
+        ] {
+            sqlx::query(sql).execute(pool).await.unwrap();
+        }
+        for i in 0..count {
+            sqlx::query(
+                "INSERT INTO publish_jobs (id, distribution_task_id, content_id, account_id, platform)
+                 VALUES (?, 't1', 'c1', 'a1', 'zhihu')",
+            )
+            .bind(format!("j{}", i))
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_never_exceeds_concurrency() {
+        let (_dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 6).await;
+
+        let service = SchedulerService::with_concurrency(pool.clone(), 2).unwrap();
+        let executor = CountingExecutor::default();
+        assert_eq!(service.dispatch_pending_jobs(&executor, 10).await.unwrap(), 6);
+        assert_eq!(executor.peak.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        let succeeded: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM publish_jobs WHERE status = 'success'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(succeeded, 6);
+    }
+
+    #[tokio::test]
+    async fn test_set_concurrency_resizes_and_persists() {
+        let (_dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 4).await;
+
+        let service = SchedulerService::load(pool.clone()).await.unwrap();
+        assert_eq!(service.concurrency(), DEFAULT_CONCURRENCY);
+        assert!(matches!(service.set_concurrency(0).await, Err(PubCastError::Validation(_))));
+        assert!(SchedulerService::with_concurrency(pool.clone(), 0).is_err());
+
+        service.set_concurrency(1).await.unwrap();
+        let executor = CountingExecutor::default();
+        service.dispatch_pending_jobs(&executor, 10).await.unwrap();
+        assert_eq!(executor.peak.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Resizing only needs a read lock, so it doesn't wait for the
+        // worker's tick to finish
+        let shared = Arc::new(RwLock::new(service));
+        let _worker_tick = shared.read().await;
+        tokio::time::timeout(Duration::from_secs(1), async {
+            shared.read().await.set_concurrency(5).await.unwrap();
+        })
+        .await
+        .unwrap();
+        assert_eq!(shared.read().await.semaphore.available_permits(), 5);
+
+        // Survives a restart
+        let reloaded = SchedulerService::load(pool.clone()).await.unwrap();
+        assert_eq!(reloaded.concurrency(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_raising_concurrency_cancels_a_pending_shrink() {
+        let (_dir, pool) = init_test_database().await;
+        let service = SchedulerService::with_concurrency(pool, 3).unwrap();
+
+        // Three running jobs hold every permit, so the shrink has to wait
+        let semaphore = service.semaphore.clone();
+        let running = semaphore.acquire_many_owned(3).await.unwrap();
+        service.set_concurrency(1).await.unwrap();
+        assert_eq!(service.permit_debt.load(Ordering::SeqCst), 2);
+
+        // Raised again before any job finished: nothing is owed any more
+        service.set_concurrency(4).await.unwrap();
+        assert_eq!(service.permit_debt.load(Ordering::SeqCst), 0);
+
+        // Once the jobs finish, all four permits are free
+        drop(running);
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while service.semaphore.available_permits() < 4 {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        tokio::task::yield_now().await;
+        assert_eq!(service.semaphore.available_permits(), 4);
+    }
+
+    /// Never finishes `hang_job`; every other job succeeds right away
+    struct HangingExecutor {
+        hang_job: &'static str,
+    }
+
+    #[async_trait]
+    impl JobExecutor for HangingExecutor {
+        async fn execute(&self, job: &PublishJob) -> PublishResult {
+            if job.id == self.hang_job {
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+            }
+            PublishResult {
+                success: true,
+                published_url: None,
+                published_id: None,
+                error_code: None,
+                error_message: None,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hung_publish_times_out_and_frees_its_slot() {
+        let (_dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 3).await;
+        sqlx::query("UPDATE publish_jobs SET max_retries = 0")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let service = SchedulerService::with_concurrency(pool.clone(), 1)
+            .unwrap()
+            .with_default_publish_timeout(Duration::from_secs(60))
+            .with_publish_timeout("zhihu", Some(Duration::from_millis(50)));
+        assert_eq!(service.publish_timeout("zhihu"), Duration::from_millis(50));
+        assert_eq!(service.publish_timeout("weibo"), Duration::from_secs(60));
+        assert_eq!(
+            SchedulerService::new(pool.clone()).publish_timeout("xiaohongshu"),
+            Duration::from_secs(600)
+        );
+
+        let executor = HangingExecutor { hang_job: "j0" };
+        assert_eq!(service.dispatch_pending_jobs(&executor, 10).await.unwrap(), 3);
+
+        let jobs: Vec<(String, String, Option<String>, Option<String>)> = sqlx::query_as(
+            "SELECT id, status, error_code, error_category FROM publish_jobs ORDER BY id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            jobs[0],
+            (
+                "j0".to_string(),
+                "failed".to_string(),
+                Some("timeout".to_string()),
+                Some("network".to_string())
+            )
+        );
+        assert!(jobs[1..].iter().all(|(_, status, _, _)| status == "success"));
+        assert_eq!(service.semaphore.available_permits(), 1);
+    }
+
+    struct HangingHook;
+
+    #[async_trait]
+    impl PrePublishHook for HangingHook {
+        async fn before_publish(&self, _job: &PublishJob) -> Result<()> {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hung_pre_publish_hook_times_out() {
+        let (_dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 1).await;
+        sqlx::query("UPDATE publish_jobs SET max_retries = 0")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let service = SchedulerService::with_concurrency(pool.clone(), 1)
+            .unwrap()
+            .with_publish_timeout("zhihu", Some(Duration::from_millis(50)))
+            .with_pre_publish_hook(Arc::new(HangingHook));
+        let executor = CountingExecutor::default();
+        assert_eq!(service.dispatch_pending_jobs(&executor, 10).await.unwrap(), 1);
+
+        let (status, error_code): (String, Option<String>) =
+            sqlx::query_as("SELECT status, error_code FROM publish_jobs WHERE id = 'j0'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            (status.as_str(), error_code.as_deref()),
+            ("failed", Some(PRE_PUBLISH_ERROR_CODE))
+        );
+        assert_eq!(executor.peak.load(std::sync::atomic::Ordering::SeqCst), 0);
+        assert_eq!(service.semaphore.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_publish_attempts_are_logged_with_their_duration() {
+        let (_dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 1).await;
+
+        let service = SchedulerService::new(pool.clone());
+        let executor = CountingExecutor::default();
+        assert_eq!(service.dispatch_pending_jobs(&executor, 10).await.unwrap(), 1);
+
+        let (job_id, status, duration_ms): (String, String, Option<i64>) =
+            sqlx::query_as("SELECT publish_job_id, status, duration_ms FROM publish_logs")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!((job_id.as_str(), status.as_str()), ("j0", "success"));
+        // The executor sleeps 20ms per job
+        let duration_ms = duration_ms.unwrap();
+        assert!((20..10_000).contains(&duration_ms), "{}", duration_ms);
+
+        let (total, successful): (i64, i64) = sqlx::query_as(
+            "SELECT total_publishes, successful_publishes FROM daily_statistics WHERE platform = 'zhihu'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((total, successful), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_paused_task_is_not_dispatched_until_resumed() {
+        let (_dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 2).await;
+        sqlx::query("UPDATE distribution_tasks SET total_jobs = 2 WHERE id = 't1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let service = SchedulerService::new(pool.clone());
+        assert!(matches!(
+            service.resume_distribution_task("t1").await,
+            Err(PubCastError::Validation(_))
+        ));
+        service.pause_distribution_task("t1").await.unwrap();
+        assert!(matches!(
+            service.pause_distribution_task("t1").await,
+            Err(PubCastError::Validation(_))
+        ));
+        assert!(matches!(
+            service.pause_distribution_task("missing").await,
+            Err(PubCastError::NotFound(_))
+        ));
+
+        let executor = CountingExecutor::default();
+        assert_eq!(service.dispatch_pending_jobs(&executor, 10).await.unwrap(), 0);
+        assert_eq!(task_state(&pool).await, ("paused".to_string(), 0, 0));
+        assert_eq!(
+            service.get_distribution_task("t1").await.unwrap().status,
+            DistributionTaskStatus::Paused
+        );
+
+        service.resume_distribution_task("t1").await.unwrap();
+        assert_eq!(task_state(&pool).await, ("pending".to_string(), 0, 0));
+        assert_eq!(service.dispatch_pending_jobs(&executor, 10).await.unwrap(), 2);
+        assert_eq!(task_state(&pool).await, ("completed".to_string(), 2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_startup_recovers_stale_running_jobs() {
+        let (_dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 3).await;
+        sqlx::query("UPDATE distribution_tasks SET total_jobs = 3 WHERE id = 't1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // j0 and j1 were mid-publish when the app went down an hour ago; j0's
+        // platform publishes idempotently. j2 was started by this run.
+        let now = chrono::Utc::now();
+        let hour_ago = now.timestamp() - 3600;
+        for (id, started_at, platform) in
+            [("j0", hour_ago, "weibo"), ("j1", hour_ago, "zhihu"), ("j2", now.timestamp(), "zhihu")]
+        {
+            sqlx::query("UPDATE publish_jobs SET status = 'running', started_at = ?, platform = ? WHERE id = ?")
+                .bind(started_at)
+                .bind(platform)
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let service = SchedulerService::new(pool.clone()).with_idempotent_publish("weibo", true);
+        assert_eq!(
+            service.recover_stale_jobs(now.timestamp()).await.unwrap(),
+            StaleJobRecovery {
+                requeued: 1,
+                failed: 1
+            }
+        );
+
+        let jobs: Vec<(String, Option<String>)> =
+            sqlx::query_as("SELECT status, error_code FROM publish_jobs ORDER BY id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            jobs,
+            [
+                ("pending".to_string(), None),
+                ("failed".to_string(), Some("interrupted".to_string())),
+                ("running".to_string(), None),
+            ]
+        );
+        assert_eq!(task_state(&pool).await, ("pending".to_string(), 0, 1));
+        assert_eq!(
+            service.recover_stale_jobs(now.timestamp()).await.unwrap(),
+            StaleJobRecovery::default()
+        );
+
+        // The re-queued job reports the same post twice; it only counts once
+        service.mark_job_success("j0", None, Some("post-1".to_string())).await.unwrap();
+        service.mark_job_success("j0", None, Some("post-1".to_string())).await.unwrap();
+        assert_eq!(task_state(&pool).await, ("pending".to_string(), 1, 1));
+
+        // A late success for the failed job doesn't count it twice
+        service.mark_job_success("j1", None, Some("post-2".to_string())).await.unwrap();
+        assert_eq!(task_state(&pool).await, ("pending".to_string(), 1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_finished_task_notifies_webhooks() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(body_partial_json(serde_json::json!({
+                "task_id": "t1",
+                "status": "completed",
+                "completed_jobs": 2,
+                "failed_jobs": 0,
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (_dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 2).await;
+        sqlx::query("UPDATE distribution_tasks SET total_jobs = 2 WHERE id = 't1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let notifications = NotificationService::new(pool.clone());
+        notifications
+            .set_webhooks(&[format!("{}/hook", server.uri())])
+            .await
+            .unwrap();
+
+        let service = SchedulerService::new(pool.clone()).with_notifications(notifications);
+        service.dispatch_pending_jobs(&CountingExecutor::default(), 10).await.unwrap();
+        assert_eq!(task_state(&pool).await, ("completed".to_string(), 2, 0));
+
+        // Delivery happens in the background
+        for _ in 0..50 {
+            if !server.received_requests().await.unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        server.verify().await;
+    }
+
+    async fn task_state(pool: &SqlitePool) -> (String, i64, i64) {
+        sqlx::query_as("SELECT status, completed_jobs, failed_jobs FROM distribution_tasks WHERE id = 't1'")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cancel_job_lets_the_rest_of_the_task_complete() {
+        let (_dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 3).await;
+        sqlx::query("UPDATE distribution_tasks SET total_jobs = 3, status = 'in_progress' WHERE id = 't1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let service = SchedulerService::new(pool.clone());
+        service.cancel_job("j1").await.unwrap();
+        assert!(matches!(service.cancel_job("j1").await, Err(PubCastError::Validation(_))));
+        assert!(matches!(service.cancel_job("missing").await, Err(PubCastError::NotFound(_))));
+
+        let executor = CountingExecutor::default();
+        assert_eq!(service.dispatch_pending_jobs(&executor, 10).await.unwrap(), 2);
+
+        let (status, completed, failed) = task_state(&pool).await;
+        assert_eq!((status.as_str(), completed, failed), ("completed", 2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_running_job_result_is_discarded() {
+        let (_dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 2).await;
+        sqlx::query("UPDATE distribution_tasks SET total_jobs = 2, status = 'in_progress' WHERE id = 't1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let service = SchedulerService::new(pool.clone());
+        assert!(service.mark_job_running("j0").await.unwrap());
+        service.cancel_job("j0").await.unwrap();
+
+        // The worker finishes publishing after the cancellation
+        service.mark_job_success("j0", Some("https://example.com".to_string()), None).await.unwrap();
+        service.mark_job_failed("j0", None, Some("timeout".to_string())).await.unwrap();
+        let status: String = sqlx::query_scalar("SELECT status FROM publish_jobs WHERE id = 'j0'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(status, "cancelled");
+        assert_eq!(task_state(&pool).await, ("in_progress".to_string(), 0, 0));
+
+        // A cancelled job never starts
+        service.cancel_job("j1").await.unwrap();
+        assert!(!service.mark_job_running("j1").await.unwrap());
+        assert_eq!(task_state(&pool).await.0, "cancelled");
+    }
+
+    #[tokio::test]
+    async fn test_job_transitions_emit_progress_matching_the_database() {
+        let (_dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 2).await;
+        sqlx::query("UPDATE distribution_tasks SET total_jobs = 2, status = 'in_progress' WHERE id = 't1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let service = SchedulerService::new(pool.clone()).with_event_sender(tx);
+
+        assert!(service.mark_job_running("j0").await.unwrap());
+        let TaskEvent::Progress(progress) = rx.try_recv().unwrap() else {
+            panic!("expected a progress event");
+        };
+        assert_eq!(progress.last_job.status, PublishJobStatus::Running);
+        assert_eq!(progress.completed_jobs, 0);
+
+        service.mark_job_success("j0", Some("https://example.com/1".to_string()), None).await.unwrap();
+        let TaskEvent::Progress(progress) = rx.try_recv().unwrap() else {
+            panic!("expected a progress event");
+        };
+        let task = service.get_distribution_task("t1").await.unwrap();
+        assert_eq!(progress.task_id, "t1");
+        assert_eq!(
+            (progress.completed_jobs, progress.failed_jobs, progress.total_jobs),
+            (task.completed_jobs, task.failed_jobs, task.total_jobs)
+        );
+        assert_eq!(progress.completed_jobs, 1);
+        assert_eq!(progress.last_job.id, "j0");
+        assert_eq!(progress.last_job.status, PublishJobStatus::Success);
+        assert_eq!(progress.last_job.published_url.as_deref(), Some("https://example.com/1"));
+        assert!(rx.try_recv().is_err());
+
+        // Finishing the last job reports progress, then completion
+        service.cancel_job("j1").await.unwrap();
+        assert!(matches!(rx.try_recv().unwrap(), TaskEvent::Progress(p) if p.last_job.id == "j1"));
+        let TaskEvent::Completed(task) = rx.try_recv().unwrap() else {
+            panic!("expected a completed event");
+        };
+        assert_eq!(task.status, DistributionTaskStatus::Completed);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_default_rate_limits_come_from_adapters() {
+        let limits = default_rate_limits(&AdapterRegistry::new());
+        assert_eq!(limits.get("wechat"), Some(&10));
+        assert_eq!(limits.get("xiaohongshu"), Some(&5));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_defers_jobs_over_the_hourly_limit() {
+        let (_dir, pool) = init_test_database().await;
+        for sql in [
+            "INSERT INTO contents (id, title) VALUES ('c1', 'Hello')",
+            "INSERT INTO accounts (id, platform, name, status) VALUES ('a1', 'wechat', 'a1', 'active')",
+            "INSERT INTO distribution_tasks (id, content_id, target_accounts) VALUES ('t1', 'c1', '[]')",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        for (id, created_at) in [("j1", 1), ("j2", 2), ("j3", 3)] {
+            sqlx::query(
+                "INSERT INTO publish_jobs (id, distribution_task_id, content_id, account_id, platform, created_at)
+                 VALUES (?, 't1', 'c1', 'a1', 'wechat', ?)",
+            )
+            .bind(id)
+            .bind(created_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let service = SchedulerService::new(pool.clone()).with_rate_limit("wechat", Some(2));
+
+        let pending = service.get_pending_jobs(10).await.unwrap();
+        let ids: Vec<_> = pending.iter().map(|j| j.id.as_str()).collect();
+        assert_eq!(ids, ["j1", "j2"]);
+
+        // The third job is deferred, not failed
+        let (status, scheduled_at): (String, Option<i64>) =
+            sqlx::query_as("SELECT status, scheduled_at FROM publish_jobs WHERE id = 'j3'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status, "pending");
+        assert!(scheduled_at.unwrap() > chrono::Utc::now().timestamp());
+
+        // Two publishes in the window: nothing more until the oldest ages out
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query("UPDATE publish_jobs SET status = 'success', completed_at = ? WHERE id IN ('j1', 'j2')")
+            .bind(now - 600)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE publish_jobs SET scheduled_at = NULL WHERE id = 'j3'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(service.defer_rate_limited_jobs(now).await.unwrap(), 1);
+        let scheduled_at: Option<i64> =
+            sqlx::query_scalar("SELECT scheduled_at FROM publish_jobs WHERE id = 'j3'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(scheduled_at, Some(now - 600 + RATE_LIMIT_WINDOW_SECS));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_ignores_jobs_of_paused_tasks() {
+        let (_dir, pool) = init_test_database().await;
+        for sql in [
+            "INSERT INTO contents (id, title) VALUES ('c1', 'Hello')",
+            "INSERT INTO accounts (id, platform, name, status) VALUES ('a1', 'wechat', 'a1', 'active')",
+            "INSERT INTO distribution_tasks (id, content_id, target_accounts, status) VALUES ('paused', 'c1', '[]', 'paused')",
+            "INSERT INTO distribution_tasks (id, content_id, target_accounts) VALUES ('active', 'c1', '[]')",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        for (id, task_id, created_at) in [
+            ("j1", "paused", 1),
+            ("j2", "paused", 2),
+            ("j3", "active", 3),
+        ] {
+            sqlx::query(
+                "INSERT INTO publish_jobs (id, distribution_task_id, content_id, account_id, platform, created_at)
+                 VALUES (?, ?, 'c1', 'a1', 'wechat', ?)",
+            )
+            .bind(id)
+            .bind(task_id)
+            .bind(created_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let service = SchedulerService::new(pool.clone()).with_rate_limit("wechat", Some(1));
+        let now = chrono::Utc::now().timestamp();
+        assert_eq!(service.defer_rate_limited_jobs(now).await.unwrap(), 0);
+
+        let pending = service.get_pending_jobs(10).await.unwrap();
+        let ids: Vec<_> = pending.iter().map(|j| j.id.as_str()).collect();
+        assert_eq!(ids, ["j3"]);
+        let deferred: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM publish_jobs WHERE scheduled_at IS NOT NULL")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(deferred, 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_jobs_for_task_returns_one_job_per_account() {
+        let (_dir, pool) = init_test_database().await;
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'Hello')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, platform) in [("a1", "wechat"), ("a2", "zhihu"), ("a3", "xiaohongshu")] {
+            sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES (?, ?, ?, 'active')")
+                .bind(id)
+                .bind(platform)
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let service = SchedulerService::new(pool.clone());
+        let task = service
+            .create_distribution_task(CreateDistributionTaskRequest {
+                content_id: "c1".to_string(),
+                name: None,
+                target_account_ids: vec!["a1".to_string(), "a2".to_string(), "a3".to_string()],
+                schedule_type: ScheduleType::Immediate,
+                scheduled_at: None,
+                local_time: None,
+                cron_expression: None,
+                account_priorities: HashMap::new(),
+                target_platforms: None,
+                only_active: false,
+                strict: false,
+                dry_run: false,
+                per_platform_options: HashMap::new(),
+                downgrade_unschedulable: false,
+            })
+            .await
+            .unwrap()
+            .task;
+
+        let jobs = service.list_jobs_for_task(&task.id).await.unwrap();
+        assert_eq!(jobs.len(), 3);
+        assert!(jobs.iter().all(|j| j.distribution_task_id == task.id));
+
+        let mut accounts: Vec<_> = jobs.iter().map(|j| j.account_id.as_str()).collect();
+        accounts.sort_unstable();
+        assert_eq!(accounts, ["a1", "a2", "a3"]);
+
+        service
+            .mark_job_failed(&jobs[0].id, Some("AUTH_EXPIRED".to_string()), Some("Cookie expired".to_string()))
+            .await
+            .unwrap();
+        let jobs = service.list_jobs_for_task(&task.id).await.unwrap();
+        assert_eq!(jobs[0].error_code.as_deref(), Some("AUTH_EXPIRED"));
+        assert_eq!(jobs[0].error_message.as_deref(), Some("Cookie expired"));
+        assert!(service.list_jobs_for_task("missing").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_higher_priority_jobs_are_returned_first() {
+        let (_dir, pool) = init_test_database().await;
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'Hello')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for id in ["side", "main", "other"] {
+            sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES (?, 'wechat', ?, 'active')")
+                .bind(id)
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let service = SchedulerService::new(pool.clone());
+        let mut req = CreateDistributionTaskRequest {
+            content_id: "c1".to_string(),
+            name: None,
+            target_account_ids: vec!["side".to_string(), "main".to_string(), "other".to_string()],
+            schedule_type: ScheduleType::Immediate,
+            scheduled_at: None,
+            local_time: None,
+            cron_expression: None,
+            account_priorities: HashMap::from([("main".to_string(), 10), ("other".to_string(), -1)]),
+            target_platforms: None,
+            only_active: false,
+            strict: false,
+            dry_run: false,
+            per_platform_options: HashMap::new(),
+            downgrade_unschedulable: false,
+        };
+        service.create_distribution_task(req.clone()).await.unwrap();
+
+        let pending = service.get_pending_jobs(10).await.unwrap();
+        let accounts: Vec<_> = pending.iter().map(|j| j.account_id.as_str()).collect();
+        assert_eq!(accounts, ["main", "side", "other"]);
+        assert_eq!(pending[0].priority, 10);
+
+        req.account_priorities.insert("stranger".to_string(), 5);
+        assert!(matches!(
+            service.create_distribution_task(req).await,
+            Err(PubCastError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_target_platforms_expand_to_matching_accounts() {
+        let (_dir, pool) = init_test_database().await;
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'Hello')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, platform, status) in [
+            ("wx1", "wechat", "active"),
+            ("wx2", "wechat", "expired"),
+            ("wx3", "wechat", "active"),
+            ("zh1", "zhihu", "active"),
+        ] {
+            sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES (?, ?, ?, ?)")
+                .bind(id)
+                .bind(platform)
+                .bind(id)
+                .bind(status)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let service = SchedulerService::new(pool.clone());
+        let mut req = CreateDistributionTaskRequest {
+            content_id: "c1".to_string(),
+            name: None,
+            target_account_ids: vec!["zh1".to_string(), "wx1".to_string()],
+            schedule_type: ScheduleType::Immediate,
+            scheduled_at: None,
+            local_time: None,
+            cron_expression: None,
+            account_priorities: HashMap::new(),
+            target_platforms: Some(vec!["wechat".to_string()]),
+            only_active: true,
+            strict: false,
+            dry_run: false,
+            per_platform_options: HashMap::new(),
+            downgrade_unschedulable: false,
+        };
+        let task = service.create_distribution_task(req.clone()).await.unwrap().task;
+        assert_eq!(task.total_jobs, 3);
+        let mut accounts: Vec<_> = service
+            .list_jobs_for_task(&task.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|j| j.account_id)
+            .collect();
+        accounts.sort_unstable();
+        assert_eq!(accounts, ["wx1", "wx3", "zh1"]);
+
+        req.only_active = false;
+        assert_eq!(service.create_distribution_task(req.clone()).await.unwrap().task.total_jobs, 4);
+
+        req.target_account_ids.clear();
+        req.target_platforms = Some(vec!["weibo".to_string()]);
+        assert!(matches!(
+            service.create_distribution_task(req).await,
+            Err(PubCastError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_records_prepared_content_without_publishing() {
+        let (_dir, pool) = init_test_database().await;
+        sqlx::query("INSERT INTO contents (id, title, body, tags) VALUES ('c1', ?, 'Body #rust', '[\"pubcast\"]')")
+            .bind("标".repeat(30))
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, platform) in [("wx", "wechat"), ("xhs", "xiaohongshu"), ("dy", "douyin")] {
+            sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES (?, ?, ?, 'active')")
+                .bind(id)
+                .bind(platform)
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let service = SchedulerService::new(pool.clone());
+        let mut req = CreateDistributionTaskRequest {
+            content_id: "c1".to_string(),
+            name: None,
+            target_account_ids: vec!["wx".to_string(), "xhs".to_string(), "dy".to_string()],
+            schedule_type: ScheduleType::Immediate,
+            scheduled_at: None,
+            local_time: None,
+            cron_expression: None,
+            account_priorities: HashMap::new(),
+            target_platforms: None,
+            only_active: false,
+            strict: false,
+            dry_run: true,
+            per_platform_options: HashMap::new(),
+            downgrade_unschedulable: false,
+        };
+        let task = service.create_distribution_task(req.clone()).await.unwrap().task;
+        assert!(task.dry_run);
+        assert_eq!(task.status, DistributionTaskStatus::Completed);
+        assert_eq!((task.completed_jobs, task.failed_jobs), (2, 1));
+
+        let jobs = service.list_jobs_for_task(&task.id).await.unwrap();
+        let xhs = jobs.iter().find(|j| j.account_id == "xhs").unwrap();
+        assert_eq!(xhs.status, PublishJobStatus::Success);
+        let prepared = &xhs.metadata.as_ref().unwrap()["prepared"];
+        assert_eq!(prepared["title"].as_str().unwrap().chars().count(), 20);
+        assert_eq!(prepared["tags"], serde_json::json!(["pubcast", "rust"]));
+        // No adapter to prepare with
+        let dy = jobs.iter().find(|j| j.account_id == "dy").unwrap();
+        assert_eq!(dy.status, PublishJobStatus::Failed);
+
+        // Nothing is left for the worker to publish
+        let executor = CountingExecutor::default();
+        assert_eq!(service.dispatch_pending_jobs(&executor, 10).await.unwrap(), 0);
+        assert_eq!(executor.peak.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        let stats = crate::services::StatsService::new(pool.clone())
+            .get_overall_stats(None, None)
+            .await
+            .unwrap();
+        assert_eq!(stats.total_publishes, 0);
+
+        req.schedule_type = ScheduleType::Recurring;
+        req.cron_expression = Some("0 9 * * *".to_string());
+        assert!(matches!(
+            service.create_distribution_task(req).await,
+            Err(PubCastError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_task_reports_content_over_platform_limits() {
+        let (_dir, pool) = init_test_database().await;
+        sqlx::query("INSERT INTO contents (id, title, body) VALUES ('c1', ?, ?)")
+            .bind("标".repeat(30))
+            .bind("字".repeat(1500))
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO contents (id, title, body) VALUES ('c2', ?, ?)")
+            .bind("t".repeat(70))
+            .bind("b".repeat(20001))
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, platform) in [("wx", "wechat"), ("xhs", "xiaohongshu"), ("zh", "zhihu")] {
+            sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES (?, ?, ?, 'active')")
+                .bind(id)
+                .bind(platform)
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let service = SchedulerService::new(pool.clone());
+        let mut req = CreateDistributionTaskRequest {
+            content_id: "c1".to_string(),
+            name: None,
+            target_account_ids: vec!["wx".to_string(), "xhs".to_string(), "zh".to_string()],
+            schedule_type: ScheduleType::Immediate,
+            scheduled_at: None,
+            local_time: None,
+            cron_expression: None,
+            account_priorities: HashMap::new(),
+            target_platforms: None,
+            only_active: false,
+            strict: false,
+            dry_run: false,
+            per_platform_options: HashMap::new(),
+            downgrade_unschedulable: false,
+        };
+
+        // Within WeChat's limits, over both of Xiaohongshu's
+        let response = service.create_distribution_task(req.clone()).await.unwrap();
+        assert_eq!(response.task.total_jobs, 3);
+        let flagged: Vec<_> = response
+            .issues
+            .iter()
+            .map(|i| (i.platform.as_str(), i.field.as_str(), i.severity))
+            .collect();
+        assert_eq!(
+            flagged,
+            [
+                ("xiaohongshu", "title", IssueSeverity::Warning),
+                ("xiaohongshu", "body", IssueSeverity::Warning),
+            ]
+        );
+
+        req.content_id = "c2".to_string();
+        let response = service.create_distribution_task(req.clone()).await.unwrap();
+        let wechat: Vec<_> = response
+            .issues
+            .iter()
+            .filter(|i| i.platform == "wechat")
+            .map(|i| i.field.as_str())
+            .collect();
+        assert_eq!(wechat, ["title", "body"]);
+
+        req.strict = true;
+        assert!(matches!(
+            service.create_distribution_task(req.clone()).await,
+            Err(PubCastError::Validation(_))
+        ));
+        let tasks: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM distribution_tasks")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(tasks, 2);
+
+        req.target_account_ids = vec!["zh".to_string()];
+        let response = service.create_distribution_task(req).await.unwrap();
+        assert!(response.issues.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_task_schedules_each_account_in_its_own_zone() {
+        let (_dir, pool) = init_test_database().await;
+
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'Hello')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, tz) in [("acc-sh", "Asia/Shanghai"), ("acc-ny", "America/New_York")] {
+            sqlx::query(
+                "INSERT INTO accounts (id, platform, name, status, timezone) VALUES (?, 'wechat', ?, 'active', ?)",
+            )
+            .bind(id)
+            .bind(id)
+            .bind(tz)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let service = SchedulerService::new(pool.clone());
+        let task = service
+            .create_distribution_task(CreateDistributionTaskRequest {
+                content_id: "c1".to_string(),
+                name: None,
+                target_account_ids: vec!["acc-sh".to_string(), "acc-ny".to_string()],
+                schedule_type: ScheduleType::Scheduled,
+                scheduled_at: None,
+                local_time: Some("2030-01-15T09:00".to_string()),
+                cron_expression: None,
+                account_priorities: HashMap::new(),
+                target_platforms: None,
+                only_active: false,
+                strict: false,
+                dry_run: false,
+                per_platform_options: HashMap::new(),
+                downgrade_unschedulable: false,
+            })
+            .await
+            .unwrap()
+            .task;
+
+        let job_time = |account_id: &'static str| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_scalar::<_, Option<i64>>(
+                    "SELECT scheduled_at FROM publish_jobs WHERE account_id = ?",
+                )
+                .bind(account_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+                .unwrap()
+            }
+        };
+
+        let expected_sh = chrono::Utc.with_ymd_and_hms(2030, 1, 15, 1, 0, 0).unwrap().timestamp();
+        let expected_ny = chrono::Utc.with_ymd_and_hms(2030, 1, 15, 14, 0, 0).unwrap().timestamp();
+
+        assert_eq!(job_time("acc-sh").await, expected_sh);
+        assert_eq!(job_time("acc-ny").await, expected_ny);
+        assert_eq!(task.scheduled_at, Some(expected_sh));
+    }
+
+    #[tokio::test]
+    async fn test_platform_options_flow_into_job_metadata() {
+        let (_dir, pool) = init_test_database().await;
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'Hello')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, platform) in [("wx", "wechat"), ("wb", "weibo")] {
+            sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES (?, ?, ?, 'active')")
+                .bind(id)
+                .bind(platform)
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let wechat_options = serde_json::json!({ "original": true });
+        let service = SchedulerService::new(pool.clone());
+        let mut req = CreateDistributionTaskRequest {
+            content_id: "c1".to_string(),
+            name: None,
+            target_account_ids: vec!["wx".to_string(), "wb".to_string()],
+            schedule_type: ScheduleType::Immediate,
+            scheduled_at: None,
+            local_time: None,
+            cron_expression: None,
+            account_priorities: HashMap::new(),
+            target_platforms: None,
+            only_active: false,
+            strict: false,
+            dry_run: false,
+            per_platform_options: HashMap::from([("wechat".to_string(), wechat_options.clone())]),
+            downgrade_unschedulable: false,
+        };
+        let task = service.create_distribution_task(req.clone()).await.unwrap().task;
+
+        let jobs = service.list_jobs_for_task(&task.id).await.unwrap();
+        let wx = jobs.iter().find(|j| j.account_id == "wx").unwrap();
+        assert_eq!(wx.platform_options(), Some(&wechat_options));
+        let wb = jobs.iter().find(|j| j.account_id == "wb").unwrap();
+        assert_eq!(wb.platform_options(), None);
+
+        // Dry runs hand the options to the prepared content
+        req.dry_run = true;
+        let task = service.create_distribution_task(req.clone()).await.unwrap().task;
+        let jobs = service.list_jobs_for_task(&task.id).await.unwrap();
+        let wx = jobs.iter().find(|j| j.account_id == "wx").unwrap();
+        assert_eq!(wx.metadata.as_ref().unwrap()["prepared"]["options"], wechat_options);
+
+        // Recurring tasks keep the options for every run
+        req.dry_run = false;
+        req.schedule_type = ScheduleType::Recurring;
+        req.cron_expression = Some("0 9 * * *".to_string());
+        let task = service.create_distribution_task(req.clone()).await.unwrap().task;
+        let now = chrono::Utc.with_ymd_and_hms(2030, 1, 18, 12, 0, 0).unwrap();
+        sqlx::query("UPDATE distribution_tasks SET next_run_at = ? WHERE id = ?")
+            .bind(now.timestamp())
+            .bind(&task.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(service.materialize_recurring_jobs(&now).await.unwrap(), 2);
+        let jobs = service.list_jobs_for_task(&task.id).await.unwrap();
+        let wx = jobs.iter().find(|j| j.account_id == "wx").unwrap();
+        assert_eq!(wx.platform_options(), Some(&wechat_options));
+
+        req.per_platform_options = HashMap::from([("wechat".to_string(), serde_json::json!(true))]);
+        assert!(matches!(
+            service.create_distribution_task(req).await,
+            Err(PubCastError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_task_checks_platform_scheduling_support() {
+        let (_dir, pool) = init_test_database().await;
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'Hello')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, platform) in [("wx", "wechat"), ("xhs", "xiaohongshu"), ("xhs2", "xiaohongshu")] {
+            sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES (?, ?, ?, 'active')")
+                .bind(id)
+                .bind(platform)
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let service = SchedulerService::new(pool.clone());
+        let scheduled_at = chrono::Utc.with_ymd_and_hms(2030, 1, 15, 9, 0, 0).unwrap().timestamp();
+        let mut req = CreateDistributionTaskRequest {
+            content_id: "c1".to_string(),
+            name: None,
+            target_account_ids: vec!["wx".to_string(), "xhs".to_string(), "xhs2".to_string()],
+            schedule_type: ScheduleType::Scheduled,
+            scheduled_at: Some(scheduled_at),
+            local_time: None,
+            cron_expression: None,
+            account_priorities: HashMap::new(),
+            target_platforms: None,
+            only_active: false,
+            strict: false,
+            dry_run: false,
+            per_platform_options: HashMap::new(),
+            downgrade_unschedulable: false,
+        };
+        match service.create_distribution_task(req.clone()).await {
+            Err(PubCastError::Validation(message)) => {
+                assert!(message.ends_with(": xiaohongshu"), "{}", message);
+            }
+            other => panic!("expected a validation error, got {:?}", other.map(|r| r.task.id)),
+        }
+        let tasks: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM distribution_tasks")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(tasks, 0);
+
+        // With the override only the capable platform keeps its schedule
+        req.downgrade_unschedulable = true;
+        let task = service.create_distribution_task(req.clone()).await.unwrap().task;
+        let jobs = service.list_jobs_for_task(&task.id).await.unwrap();
+        assert_eq!(jobs.len(), 3);
+        for job in &jobs {
+            let expected = if job.platform == "wechat" { Some(scheduled_at) } else { None };
+            assert_eq!(job.scheduled_at, expected, "{}", job.account_id);
+        }
+
+        // Scheduling-capable targets alone need no override
+        req.downgrade_unschedulable = false;
+        req.target_account_ids = vec!["wx".to_string()];
+        service.create_distribution_task(req.clone()).await.unwrap();
+        // Immediate tasks are never checked
+        req.schedule_type = ScheduleType::Immediate;
+        req.scheduled_at = None;
+        req.target_account_ids = vec!["xhs".to_string()];
+        service.create_distribution_task(req).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_purge_removes_only_old_finished_tasks() {
+        let (dir, pool) = init_test_database().await;
+        seed_pending_jobs(&pool, 1).await;
+        let now = chrono::Utc::now().timestamp();
+        let old = now - 90 * 86_400;
+        // (id, status, completed_at, updated_at, job status)
+        let tasks = [
+            ("old_done", "completed", Some(old), old, "success"),
+            ("old_failed", "failed", Some(old), old, "failed"),
+            ("old_cancelled", "cancelled", None, old, "cancelled"),
+            ("old_pending", "pending", None, old, "pending"),
+            ("old_paused", "paused", None, old, "pending"),
+            ("old_cancelled_running", "cancelled", Some(old), old, "running"),
+            ("recent_done", "completed", Some(now), now, "success"),
+        ];
+        for (id, status, completed_at, updated_at, job_status) in tasks {
+            sqlx::query(
+                "INSERT INTO distribution_tasks (id, content_id, target_accounts, status, completed_at, created_at, updated_at)
+                 VALUES (?, 'c1', '[]', ?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(status)
+            .bind(completed_at)
+            .bind(updated_at)
+            .bind(updated_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO publish_jobs (id, distribution_task_id, content_id, account_id, platform, status)
+                 VALUES (?, ?, 'c1', 'a1', 'zhihu', ?)",
+            )
+            .bind(format!("{}_job", id))
+            .bind(id)
+            .bind(job_status)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let service = SchedulerService::new(pool.clone());
+        let archive = dir.path().join("tasks.json");
+        let purge = service
+            .purge_tasks_before(now - 30 * 86_400, Some(&archive))
+            .await
+            .unwrap();
+        assert_eq!(purge, TaskPurge { tasks: 3, jobs: 3 });
+
+        let mut remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM distribution_tasks")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            ["old_cancelled_running", "old_paused", "old_pending", "recent_done", "t1"]
+        );
+        let jobs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM publish_jobs")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(jobs, 5);
+
+        let archived: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&archive).unwrap()).unwrap();
+        let archived = archived.as_array().unwrap();
+        assert_eq!(archived.len(), 3);
+        assert!(archived
+            .iter()
+            .all(|entry| entry["jobs"].as_array().unwrap().len() == 1));
+
+        // Nothing left to purge
+        assert_eq!(
+            service.purge_old_tasks(30, None).await.unwrap(),
+            TaskPurge::default()
+        );
+        assert!(service.purge_old_tasks(-1, None).await.is_err());
+        assert!(matches!(
+            service.purge_old_tasks(i64::MAX, None).await,
+            Err(PubCastError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_distribution_tasks_pages_and_filters() {
+        let (_dir, pool) = init_test_database().await;
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'Hello')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        // 120 completed tasks and 5 failed ones, one second apart
+        for i in 0..125 {
+            let status = if i < 120 { "completed" } else { "failed" };
+            sqlx::query(
+                "INSERT INTO distribution_tasks (id, content_id, target_accounts, status, created_at)
+                 VALUES (?, 'c1', '[]', ?, ?)",
+            )
+            .bind(format!("t{:03}", i))
+            .bind(status)
+            .bind(1_700_000_000 + i as i64)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let service = SchedulerService::new(pool);
+        let list = |page, per_page, status| {
+            let query = DistributionTaskListQuery {
+                page,
+                per_page,
+                status,
+            };
+            let service = &service;
+            async move { service.list_distribution_tasks(&query).await.unwrap() }
+        };
+
+        let first = list(None, Some(100), None).await;
+        assert_eq!(first.total, 125);
+        assert_eq!(first.tasks.len(), 100);
+        assert_eq!(first.tasks[0].id, "t124");
+        assert!(first.has_more);
+
+        let second = list(Some(2), Some(100), None).await;
+        assert_eq!(second.page, 2);
+        assert_eq!(second.tasks.len(), 25);
+        assert_eq!(second.tasks.last().unwrap().id, "t000");
+        assert!(!second.has_more);
+
+        let defaults = list(None, None, None).await;
+        assert_eq!(
+            (defaults.page, defaults.per_page),
+            (1, DEFAULT_TASK_PAGE_SIZE)
+        );
+        let clamped = list(Some(0), Some(1000), None).await;
+        assert_eq!((clamped.page, clamped.per_page), (1, MAX_TASK_PAGE_SIZE));
+
+        let failed = list(None, None, Some(DistributionTaskStatus::Failed)).await;
+        assert_eq!(failed.total, 5);
+        assert!(failed
+            .tasks
+            .iter()
+            .all(|task| task.status == DistributionTaskStatus::Failed));
+        let paused = list(None, None, Some(DistributionTaskStatus::Paused)).await;
+        assert_eq!(paused.total, 0);
+    }
 }
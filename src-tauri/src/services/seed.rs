@@ -0,0 +1,99 @@
+//! Startup data seeding
+//!
+//! Idempotently ensures rows the app expects to exist are present,
+//! independent of which commands the UI happens to call.
+
+use sqlx::SqlitePool;
+
+use crate::error::Result;
+use crate::services::ai::ensure_default_platforms;
+
+/// Default application settings (key, value)
+pub const DEFAULT_SETTINGS: &[(&str, &str)] = &[
+    ("sync_interval_minutes", "30"),
+    ("max_concurrent_publishes", "3"),
+    ("default_retry_count", "3"),
+    ("proxy_health_check_interval_minutes", "30"),
+];
+
+/// Ensure AI platform defaults and default settings exist.
+///
+/// Safe to call on every startup: existing rows are never overwritten.
+pub async fn ensure_schema_seed(pool: &SqlitePool) -> Result<()> {
+    ensure_default_platforms(pool).await?;
+
+    let now = chrono::Utc::now().timestamp();
+    for (key, value) in DEFAULT_SETTINGS {
+        sqlx::query!(
+            r#"
+            INSERT OR IGNORE INTO app_settings (key, value, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+            key,
+            value,
+            now,
+            now
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    tracing::info!("Default data seeded");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+
+    #[tokio::test]
+    async fn test_seed_populates_defaults_without_list_configs() {
+        let (_dir, pool) = init_test_database().await;
+        // Simulate a database whose migration-time seed rows were lost
+        sqlx::query("DELETE FROM app_settings").execute(&pool).await.unwrap();
+
+        ensure_schema_seed(&pool).await.unwrap();
+
+        let platforms: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM ai_configs")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(platforms, 8);
+
+        for (key, value) in DEFAULT_SETTINGS {
+            let stored: String = sqlx::query_scalar("SELECT value FROM app_settings WHERE key = ?")
+                .bind(key)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(&stored, value);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_seed_is_idempotent_and_preserves_user_values() {
+        let (_dir, pool) = init_test_database().await;
+        ensure_schema_seed(&pool).await.unwrap();
+
+        sqlx::query("UPDATE app_settings SET value = '5' WHERE key = 'default_retry_count'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        ensure_schema_seed(&pool).await.unwrap();
+
+        let platforms: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM ai_configs")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(platforms, 8);
+
+        let retry: String =
+            sqlx::query_scalar("SELECT value FROM app_settings WHERE key = 'default_retry_count'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(retry, "5");
+    }
+}
@@ -0,0 +1,183 @@
+//! Persisted application settings
+//!
+//! A typed key-value store over the `app_settings` table. Values are stored
+//! as JSON, so the existing plain numeric rows (e.g. `"30"`) read back as
+//! numbers. Subsystems namespace their keys with a dotted prefix such as
+//! `scheduler.max_concurrency` so they can't collide.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use crate::error::{PubCastError, Result};
+
+/// Maximum length of a setting key
+const MAX_KEY_LEN: usize = 128;
+
+/// Build a namespaced key, e.g. `namespaced_key("scheduler", "max_concurrency")`
+pub fn namespaced_key(namespace: &str, name: &str) -> String {
+    format!("{}.{}", namespace, name)
+}
+
+/// Check a key is one or more `[a-z0-9_]` segments separated by dots
+pub fn validate_key(key: &str) -> Result<()> {
+    let valid_segment = |segment: &str| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    };
+    if key.len() > MAX_KEY_LEN || !key.split('.').all(valid_segment) {
+        return Err(PubCastError::Validation(format!(
+            "Invalid setting key {:?}: use lowercase dotted names like scheduler.max_concurrency",
+            key
+        )));
+    }
+    Ok(())
+}
+
+/// Settings service reading and writing `app_settings`
+pub struct SettingsService {
+    pool: SqlitePool,
+}
+
+impl SettingsService {
+    /// Create a new settings service
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get the raw JSON value of a setting, `None` when unset
+    pub async fn get_value(&self, key: &str) -> Result<Option<serde_json::Value>> {
+        validate_key(key)?;
+        let stored = sqlx::query_scalar!("SELECT value FROM app_settings WHERE key = ?", key)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        stored
+            .map(|value| serde_json::from_str(&value).map_err(PubCastError::from))
+            .transpose()
+    }
+
+    /// Get a typed setting, `None` when unset
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.get_value(key)
+            .await?
+            .map(|value| {
+                serde_json::from_value(value).map_err(|e| {
+                    PubCastError::Validation(format!("Setting {} has an unexpected type: {}", key, e))
+                })
+            })
+            .transpose()
+    }
+
+    /// Get a typed setting, falling back to `default` when unset
+    pub async fn get_or<T: DeserializeOwned>(&self, key: &str, default: T) -> Result<T> {
+        Ok(self.get(key).await?.unwrap_or(default))
+    }
+
+    /// Store a typed setting, replacing any previous value
+    pub async fn set<T: Serialize + ?Sized>(&self, key: &str, value: &T) -> Result<()> {
+        validate_key(key)?;
+        let value = serde_json::to_string(value)?;
+        let now = chrono::Utc::now().timestamp();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO app_settings (key, value, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+            "#,
+            key,
+            value,
+            now,
+            now
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove a setting so readers fall back to their default
+    pub async fn delete(&self, key: &str) -> Result<bool> {
+        validate_key(key)?;
+        let result = sqlx::query!("DELETE FROM app_settings WHERE key = ?", key)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Window {
+        days: u32,
+        enabled: bool,
+    }
+
+    #[tokio::test]
+    async fn test_settings_round_trip_typed_values() {
+        let (_dir, pool) = init_test_database().await;
+        let settings = SettingsService::new(pool);
+
+        let key = namespaced_key("scheduler", "max_concurrency");
+        settings.set(&key, &5usize).await.unwrap();
+        assert_eq!(settings.get::<usize>(&key).await.unwrap(), Some(5));
+
+        settings.set("content.api_base_url", "https://cms.example.com").await.unwrap();
+        assert_eq!(
+            settings.get::<String>("content.api_base_url").await.unwrap().as_deref(),
+            Some("https://cms.example.com")
+        );
+
+        let window = Window { days: 30, enabled: true };
+        settings.set("retention.trash", &window).await.unwrap();
+        assert_eq!(settings.get::<Window>("retention.trash").await.unwrap(), Some(window));
+
+        // Overwrite, then read as the wrong type
+        settings.set(&key, &8usize).await.unwrap();
+        assert_eq!(settings.get_or(&key, 1usize).await.unwrap(), 8);
+        assert!(matches!(
+            settings.get::<Window>(&key).await,
+            Err(PubCastError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_settings_defaults_for_missing_keys() {
+        let (_dir, pool) = init_test_database().await;
+        let settings = SettingsService::new(pool);
+
+        assert_eq!(settings.get::<u32>("sidecar.port").await.unwrap(), None);
+        assert_eq!(settings.get_or("sidecar.port", 3002u32).await.unwrap(), 3002);
+
+        settings.set("sidecar.port", &4000u32).await.unwrap();
+        assert!(settings.delete("sidecar.port").await.unwrap());
+        assert!(!settings.delete("sidecar.port").await.unwrap());
+        assert_eq!(settings.get_or("sidecar.port", 3002u32).await.unwrap(), 3002);
+    }
+
+    #[tokio::test]
+    async fn test_settings_reads_seeded_values() {
+        let (_dir, pool) = init_test_database().await;
+        crate::services::ensure_schema_seed(&pool).await.unwrap();
+        let settings = SettingsService::new(pool);
+
+        assert_eq!(settings.get::<u32>("sync_interval_minutes").await.unwrap(), Some(30));
+    }
+
+    #[test]
+    fn test_validate_key() {
+        assert!(validate_key("scheduler.max_concurrency").is_ok());
+        assert!(validate_key("default_retry_count").is_ok());
+        for key in ["", "scheduler.", ".x", "Scheduler.x", "a b", "a..b"] {
+            assert!(validate_key(key).is_err(), "{:?} should be rejected", key);
+        }
+    }
+}
@@ -1,6 +1,30 @@
+use async_trait::async_trait;
 use reqwest::Client;
+use std::future::Future;
 use std::time::Duration;
 
+use crate::infrastructure::http::HttpClientFactory;
+
+use super::types::DEFAULT_HEALTH_PATH;
+
+/// sidecar `/health` 响应中的服务名
+const SIDECAR_SERVICE_NAME: &str = "playwright-sidecar";
+/// 启动等待的首次重试间隔，之后每次翻倍
+const WAIT_BACKOFF_INITIAL: Duration = Duration::from_millis(200);
+/// 启动等待的最大重试间隔
+const WAIT_BACKOFF_MAX: Duration = Duration::from_secs(3);
+
+/// 健康探测接口（便于在监控循环中替换为测试桩）
+#[async_trait]
+pub trait HealthProbe: Send + Sync {
+    /// 执行一次健康检查
+    async fn check_once(&self) -> bool;
+    /// 检查间隔
+    fn interval(&self) -> Duration;
+    /// 失败阈值
+    fn failure_threshold(&self) -> u32;
+}
+
 /// 健康检查配置
 #[derive(Debug, Clone)]
 pub struct HealthCheckConfig {
@@ -12,6 +36,10 @@ pub struct HealthCheckConfig {
     pub failure_threshold: u32,
     /// 成功阈值（连续成功多少次认为稳定）
     pub success_threshold: u32,
+    /// 健康检查路径（sidecar 挂在前缀下时需要修改）
+    pub path: String,
+    /// 是否要求响应体为 `{ "status": "ok" }`，避免同端口其他服务的 200 被误判为健康
+    pub require_status_ok: bool,
 }
 
 impl Default for HealthCheckConfig {
@@ -21,6 +49,8 @@ impl Default for HealthCheckConfig {
             timeout: Duration::from_secs(5),
             failure_threshold: 3,
             success_threshold: 2,
+            path: DEFAULT_HEALTH_PATH.to_string(),
+            require_status_ok: true,
         }
     }
 }
@@ -38,62 +68,84 @@ pub struct HealthChecker {
 impl HealthChecker {
     /// 创建新的健康检查器
     pub fn new(port: u16, config: HealthCheckConfig) -> Result<Self, String> {
-        let client = Client::builder()
-            .timeout(config.timeout)
-            .build()
+        let client = HttpClientFactory::new()
+            .direct_client(config.timeout)
             .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
+        let path = config.path.trim_start_matches('/');
         Ok(Self {
             client,
-            endpoint: format!("http://localhost:{}/health", port),
+            endpoint: format!("http://localhost:{}/{}", port, path),
             config,
         })
     }
 
     /// 执行一次健康检查
     pub async fn check_once(&self) -> bool {
-        match self.client.get(&self.endpoint).send().await {
-            Ok(resp) => {
-                let is_healthy = resp.status().is_success();
-                if is_healthy {
-                    tracing::debug!("Health check passed");
-                } else {
-                    tracing::debug!("Health check failed with status: {}", resp.status());
-                }
-                is_healthy
-            }
+        let resp = match self.client.get(&self.endpoint).send().await {
+            Ok(resp) => resp,
             Err(e) => {
                 tracing::debug!("Health check failed: {}", e);
+                return false;
+            }
+        };
+        if !resp.status().is_success() {
+            tracing::debug!("Health check failed with status: {}", resp.status());
+            return false;
+        }
+        if !self.config.require_status_ok {
+            tracing::debug!("Health check passed");
+            return true;
+        }
+
+        // 只看状态码会把同端口上其他服务的 200 当成 sidecar 健康
+        match resp.json::<serde_json::Value>().await {
+            Ok(body) if body["status"] == "ok" => {
+                tracing::debug!("Health check passed");
+                true
+            }
+            Ok(body) => {
+                tracing::warn!("Health check got an unexpected response body: {}", body);
+                false
+            }
+            Err(e) => {
+                tracing::warn!("Health check response is not the sidecar's JSON: {}", e);
                 false
             }
         }
     }
 
+    /// 端口上的服务是否是我们的 sidecar（健康检查返回 `service: playwright-sidecar`）
+    pub async fn is_own_sidecar(&self) -> bool {
+        let Ok(resp) = self.client.get(&self.endpoint).send().await else {
+            return false;
+        };
+        if !resp.status().is_success() {
+            return false;
+        }
+
+        resp.json::<serde_json::Value>()
+            .await
+            .map(|body| body["service"] == SIDECAR_SERVICE_NAME)
+            .unwrap_or(false)
+    }
+
     /// 等待服务变得健康（用于启动时）
+    ///
+    /// 重试间隔从 200ms 指数增长到 3s，首次启动安装依赖期间不会频繁探测。
     pub async fn wait_until_healthy(&self, max_duration: Duration) -> Result<(), String> {
-        let start = std::time::Instant::now();
-        let mut attempts = 0;
-
-        loop {
-            if start.elapsed() > max_duration {
-                return Err(format!(
-                    "Health check timeout after {:?} ({} attempts)",
-                    max_duration, attempts
-                ));
-            }
-
-            if self.check_once().await {
-                tracing::info!(
-                    "Health check passed after {} attempts ({:?})",
-                    attempts + 1,
-                    start.elapsed()
-                );
-                return Ok(());
-            }
+        wait_with_backoff(
+            || self.check_once(),
+            max_duration,
+            WAIT_BACKOFF_INITIAL,
+            WAIT_BACKOFF_MAX,
+        )
+        .await
+    }
 
-            attempts += 1;
-            tokio::time::sleep(Duration::from_millis(500)).await;
-        }
+    /// 健康检查端点
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
     }
 
     /// 获取检查间隔
@@ -107,6 +159,62 @@ impl HealthChecker {
     }
 }
 
+/// 按指数退避（`initial` 起翻倍，不超过 `max`）重复 `probe`，直到成功或超过 `max_duration`
+///
+/// 最后一次等待会截断到截止时间，保证截止时刻仍会探测一次。
+async fn wait_with_backoff<F, Fut>(
+    mut probe: F,
+    max_duration: Duration,
+    initial: Duration,
+    max: Duration,
+) -> Result<(), String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let start = tokio::time::Instant::now();
+    let mut attempts = 0;
+    let mut delay = initial;
+
+    loop {
+        attempts += 1;
+        if probe().await {
+            tracing::info!(
+                "Health check passed after {} attempts ({:?})",
+                attempts,
+                start.elapsed()
+            );
+            return Ok(());
+        }
+
+        let remaining = max_duration.saturating_sub(start.elapsed());
+        if remaining.is_zero() {
+            return Err(format!(
+                "Health check timeout after {:?} ({} attempts)",
+                max_duration, attempts
+            ));
+        }
+
+        tokio::time::sleep(delay.min(remaining)).await;
+        delay = (delay * 2).min(max);
+    }
+}
+
+#[async_trait]
+impl HealthProbe for HealthChecker {
+    async fn check_once(&self) -> bool {
+        HealthChecker::check_once(self).await
+    }
+
+    fn interval(&self) -> Duration {
+        HealthChecker::interval(self)
+    }
+
+    fn failure_threshold(&self) -> u32 {
+        HealthChecker::failure_threshold(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,6 +238,61 @@ mod tests {
         assert_eq!(checker.endpoint, "http://localhost:8857/health");
     }
 
+    #[tokio::test]
+    async fn test_check_once_uses_custom_path() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/sidecar/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "status": "ok", "service": "playwright-sidecar" }),
+            ))
+            .mount(&server)
+            .await;
+
+        let config = HealthCheckConfig {
+            path: "/sidecar/health".to_string(),
+            ..Default::default()
+        };
+        let checker = HealthChecker::new(server.address().port(), config).unwrap();
+        assert!(checker.endpoint().ends_with("/sidecar/health"));
+        assert!(checker.check_once().await);
+        assert!(checker.is_own_sidecar().await);
+
+        // 默认路径在该服务上不存在
+        let checker =
+            HealthChecker::new(server.address().port(), HealthCheckConfig::default()).unwrap();
+        assert!(!checker.check_once().await);
+    }
+
+    #[tokio::test]
+    async fn test_check_once_rejects_foreign_200() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // 同端口上的其他服务：200 但不是 sidecar 的响应
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html>It works!</html>"))
+            .mount(&server)
+            .await;
+        let port = server.address().port();
+
+        let checker = HealthChecker::new(port, HealthCheckConfig::default()).unwrap();
+        assert!(!checker.check_once().await);
+
+        // 关闭响应体校验后只看状态码
+        let config = HealthCheckConfig {
+            require_status_ok: false,
+            ..Default::default()
+        };
+        let checker = HealthChecker::new(port, config).unwrap();
+        assert!(checker.check_once().await);
+    }
+
     #[tokio::test]
     async fn test_check_once_failure() {
         // 使用一个不存在的端口
@@ -143,4 +306,59 @@ mod tests {
         let result = checker.check_once().await;
         assert!(!result);
     }
+
+    #[tokio::test]
+    async fn test_wait_with_backoff_grows_intervals_until_deadline() {
+        let initial = Duration::from_millis(10);
+        let max = Duration::from_millis(80);
+        let deadline = Duration::from_millis(300);
+
+        let start = std::time::Instant::now();
+        let mut probes = Vec::new();
+        let result = wait_with_backoff(
+            || {
+                probes.push(start.elapsed());
+                async { false }
+            },
+            deadline,
+            initial,
+            max,
+        )
+        .await;
+
+        // 超时错误中带有探测次数
+        let err = result.unwrap_err();
+        assert!(err.contains(&format!("({} attempts)", probes.len())), "{}", err);
+
+        // 间隔依次为 10, 20, 40, 80, 80...ms（sleep 只会更长）
+        let gaps: Vec<Duration> = probes.windows(2).map(|w| w[1] - w[0]).collect();
+        let mut expected = initial;
+        for gap in &gaps[..gaps.len() - 1] {
+            assert!(*gap >= expected, "gap {:?} < {:?}", gap, expected);
+            expected = (expected * 2).min(max);
+        }
+
+        // 最后一次探测在截止时间（截断后的等待）而不是之后一个完整间隔
+        let last = *probes.last().unwrap();
+        assert!(last >= deadline);
+        assert!(last < deadline + max, "last probe at {:?}", last);
+    }
+
+    #[tokio::test]
+    async fn test_wait_with_backoff_returns_once_healthy() {
+        let mut calls = 0;
+        let result = wait_with_backoff(
+            || {
+                calls += 1;
+                let healthy = calls == 3;
+                async move { healthy }
+            },
+            Duration::from_secs(5),
+            Duration::from_millis(1),
+            Duration::from_millis(5),
+        )
+        .await;
+        assert!(result.is_ok());
+        assert_eq!(calls, 3);
+    }
 }
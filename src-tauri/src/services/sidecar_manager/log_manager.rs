@@ -1,6 +1,7 @@
+use std::collections::VecDeque;
 use std::fs::{File, OpenOptions};
-use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -16,6 +17,57 @@ pub struct LogManager {
     stdout_writer: Arc<Mutex<BufWriter<File>>>,
     /// stderr 日志写入器
     stderr_writer: Arc<Mutex<BufWriter<File>>>,
+    /// stdout 当前文件最近的行
+    stdout_recent: std::sync::Mutex<RecentLines>,
+    /// stderr 当前文件最近的行
+    stderr_recent: std::sync::Mutex<RecentLines>,
+}
+
+/// 内存中保留的最近日志行数
+const RECENT_LINES_CAPACITY: usize = 1000;
+
+/// 当前日志文件末尾若干行的环形缓冲
+struct RecentLines {
+    lines: VecDeque<String>,
+    capacity: usize,
+}
+
+impl RecentLines {
+    fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// 从已有日志文件读取末尾的行
+    fn load(path: &Path, capacity: usize) -> std::io::Result<Self> {
+        let mut recent = Self::new(capacity);
+        for line in BufReader::new(File::open(path)?).lines().map_while(Result::ok) {
+            recent.push(line);
+        }
+        Ok(recent)
+    }
+
+    fn push(&mut self, line: String) {
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// 最后 N 行；缓冲已满且不足 N 行时返回 None，需要读文件
+    fn tail(&self, n: usize) -> Option<Vec<String>> {
+        if n > self.lines.len() && self.lines.len() == self.capacity {
+            return None;
+        }
+        let start = self.lines.len().saturating_sub(n);
+        Some(self.lines.range(start..).cloned().collect())
+    }
 }
 
 impl LogManager {
@@ -44,86 +96,80 @@ impl LogManager {
             max_log_files: 5,
             stdout_writer: Arc::new(Mutex::new(BufWriter::new(stdout_file))),
             stderr_writer: Arc::new(Mutex::new(BufWriter::new(stderr_file))),
+            stdout_recent: std::sync::Mutex::new(RecentLines::load(&stdout_path, RECENT_LINES_CAPACITY)?),
+            stderr_recent: std::sync::Mutex::new(RecentLines::load(&stderr_path, RECENT_LINES_CAPACITY)?),
         })
     }
 
     /// 写入 stdout 日志
     pub async fn write_stdout(&self, line: String) -> std::io::Result<()> {
-        let mut writer = self.stdout_writer.lock().await;
-        writeln!(writer, "{}", line)?;
-        writer.flush()?;
-
-        // 检查是否需要轮转
-        if let Ok(metadata) = writer.get_ref().metadata() {
-            if metadata.len() > self.max_log_size {
-                drop(writer); // 释放锁
-                self.rotate_stdout().await?;
-            }
-        }
-
-        Ok(())
+        self.write_line(&self.stdout_writer, "stdout", &line).await
     }
 
     /// 写入 stderr 日志
     pub async fn write_stderr(&self, line: String) -> std::io::Result<()> {
-        let mut writer = self.stderr_writer.lock().await;
+        self.write_line(&self.stderr_writer, "stderr", &line).await
+    }
+
+    /// 写入一行，超过大小时在持有写入器锁的情况下轮转，
+    /// 保证并发写入不会落到已归档的文件或旧的句柄上
+    async fn write_line(
+        &self,
+        writer: &Mutex<BufWriter<File>>,
+        log_type: &str,
+        line: &str,
+    ) -> std::io::Result<()> {
+        let mut writer = writer.lock().await;
         writeln!(writer, "{}", line)?;
         writer.flush()?;
+        if let Some(recent) = self.recent_lines(log_type) {
+            recent.lock().unwrap().push(line.to_string());
+        }
 
         // 检查是否需要轮转
         if let Ok(metadata) = writer.get_ref().metadata() {
             if metadata.len() > self.max_log_size {
-                drop(writer); // 释放锁
-                self.rotate_stderr().await?;
+                self.rotate_log(log_type, &mut writer)?;
             }
         }
 
         Ok(())
     }
 
-    /// 轮转 stdout 日志
-    async fn rotate_stdout(&self) -> std::io::Result<()> {
-        self.rotate_log("stdout").await
-    }
-
-    /// 轮转 stderr 日志
-    async fn rotate_stderr(&self) -> std::io::Result<()> {
-        self.rotate_log("stderr").await
-    }
-
-    /// 轮转日志文件
-    async fn rotate_log(&self, log_type: &str) -> std::io::Result<()> {
+    /// 轮转日志文件，调用方需持有该日志的写入器锁
+    fn rotate_log(&self, log_type: &str, writer: &mut BufWriter<File>) -> std::io::Result<()> {
         let base_name = format!("sidecar-{}", log_type);
         let current_file = self.log_dir.join(format!("{}.log", base_name));
 
-        // 生成归档文件名
-        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
-        let archived_name = format!("{}-{}.log", base_name, timestamp);
+        // 生成归档文件名，同一毫秒内多次轮转时追加序号
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S%3f");
+        let mut archived_name = format!("{}-{}.log", base_name, timestamp);
+        let mut sequence = 1;
+        while self.log_dir.join(&archived_name).exists() {
+            archived_name = format!("{}-{}-{}.log", base_name, timestamp, sequence);
+            sequence += 1;
+        }
         let archived_path = self.log_dir.join(&archived_name);
 
         // 重命名当前文件
+        writer.flush()?;
         if current_file.exists() {
             std::fs::rename(&current_file, &archived_path)?;
         }
 
-        // 清理旧文件
-        self.cleanup_old_logs(&base_name)?;
-
-        // 重新打开文件
+        // 重新打开文件并替换写入器
         let new_file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&current_file)?;
-
-        // 更新写入器
-        if log_type == "stdout" {
-            let mut writer = self.stdout_writer.lock().await;
-            *writer = BufWriter::new(new_file);
-        } else {
-            let mut writer = self.stderr_writer.lock().await;
-            *writer = BufWriter::new(new_file);
+        *writer = BufWriter::new(new_file);
+        if let Some(recent) = self.recent_lines(log_type) {
+            recent.lock().unwrap().clear();
         }
 
+        // 清理旧文件
+        self.cleanup_old_logs(&base_name)?;
+
         tracing::info!("Rotated {} log to {}", log_type, archived_name);
         Ok(())
     }
@@ -155,21 +201,36 @@ impl LogManager {
         Ok(())
     }
 
-    /// 获取最近的 N 行日志
+    /// 某类日志的最近行缓冲
+    fn recent_lines(&self, log_type: &str) -> Option<&std::sync::Mutex<RecentLines>> {
+        match log_type {
+            "stdout" => Some(&self.stdout_recent),
+            "stderr" => Some(&self.stderr_recent),
+            _ => None,
+        }
+    }
+
+    /// 获取最近的 N 行日志，缓冲足够时不读文件
     pub fn get_recent_logs(&self, log_type: &str, n: usize) -> std::io::Result<Vec<String>> {
+        if let Some(lines) = self
+            .recent_lines(log_type)
+            .and_then(|recent| recent.lock().unwrap().tail(n))
+        {
+            return Ok(lines);
+        }
+
         let log_path = self.log_dir.join(format!("sidecar-{}.log", log_type));
 
         if !log_path.exists() {
             return Ok(Vec::new());
         }
 
-        use std::io::{BufRead, BufReader};
         let file = File::open(log_path)?;
         let reader = BufReader::new(file);
 
         let lines: Vec<String> = reader
             .lines()
-            .filter_map(|l| l.ok())
+            .map_while(Result::ok)
             .collect();
 
         // 返回最后 N 行
@@ -182,6 +243,119 @@ impl LogManager {
         Ok(lines[start..].to_vec())
     }
 
+    /// 获取最近 N 条结构化日志，可按最低级别和起始时间（Unix 毫秒）过滤
+    ///
+    /// 没有时间戳的行（如堆栈）沿用上方最近一行的时间戳，上方没有时间戳时保留
+    pub fn get_recent_entries(
+        &self,
+        log_type: &str,
+        n: usize,
+        level: Option<LogLevel>,
+        since: Option<i64>,
+    ) -> std::io::Result<Vec<LogEntry>> {
+        let mut last_timestamp = None;
+        let entries: Vec<LogEntry> = self
+            .get_recent_logs(log_type, usize::MAX)?
+            .iter()
+            .map(|line| LogEntry::parse(line))
+            .filter(|entry| {
+                last_timestamp = entry.timestamp.or(last_timestamp);
+                let recent = match (since, last_timestamp) {
+                    (Some(since), Some(timestamp)) => timestamp >= since,
+                    _ => true,
+                };
+                recent && level.is_none_or(|level| entry.level >= level)
+            })
+            .collect();
+
+        let start = entries.len().saturating_sub(n);
+        Ok(entries[start..].to_vec())
+    }
+
+    /// 在当前和归档日志中搜索（不区分大小写），最多返回 `max_results` 条
+    pub fn search_logs(
+        &self,
+        query: &str,
+        log_type: &str,
+        max_results: usize,
+    ) -> std::io::Result<Vec<LogSearchMatch>> {
+        self.search_logs_with_case(query, log_type, max_results, false)
+    }
+
+    /// 在当前和归档日志中搜索
+    ///
+    /// 文件按从新到旧扫描，文件内按行顺序逐行读取，不会整体载入内存
+    pub fn search_logs_with_case(
+        &self,
+        query: &str,
+        log_type: &str,
+        max_results: usize,
+        case_sensitive: bool,
+    ) -> std::io::Result<Vec<LogSearchMatch>> {
+        let needle = if case_sensitive {
+            query.to_string()
+        } else {
+            query.to_lowercase()
+        };
+        let mut matches = Vec::new();
+        if needle.is_empty() || max_results == 0 {
+            return Ok(matches);
+        }
+
+        for path in self.log_files_newest_first(log_type)? {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let reader = BufReader::new(File::open(&path)?);
+
+            for (index, bytes) in reader.split(b'\n').enumerate() {
+                let bytes = bytes?;
+                let line = String::from_utf8_lossy(&bytes);
+                let line = line.trim_end_matches('\r');
+                let found = if case_sensitive {
+                    line.contains(&needle)
+                } else {
+                    line.to_lowercase().contains(&needle)
+                };
+                if found {
+                    matches.push(LogSearchMatch {
+                        file: file_name.clone(),
+                        line_number: index + 1,
+                        line: line.to_string(),
+                    });
+                    if matches.len() >= max_results {
+                        return Ok(matches);
+                    }
+                }
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// 当前日志文件在前，归档文件按时间戳从新到旧
+    fn log_files_newest_first(&self, log_type: &str) -> std::io::Result<Vec<PathBuf>> {
+        let current_name = format!("sidecar-{}.log", log_type);
+        let archive_prefix = format!("sidecar-{}-", log_type);
+
+        let mut archived: Vec<String> = std::fs::read_dir(&self.log_dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with(&archive_prefix) && name.ends_with(".log"))
+            .collect();
+        // 归档文件名中的时间戳按字典序即时间顺序
+        archived.sort_unstable_by(|a, b| b.cmp(a));
+
+        let current = self.log_dir.join(&current_name);
+        Ok(current
+            .exists()
+            .then_some(current)
+            .into_iter()
+            .chain(archived.into_iter().map(|name| self.log_dir.join(name)))
+            .collect())
+    }
+
     /// 获取所有日志文件列表
     pub fn list_log_files(&self) -> std::io::Result<Vec<LogFileInfo>> {
         let mut files = Vec::new();
@@ -259,6 +433,8 @@ impl LogManager {
 
         *self.stdout_writer.lock().await = BufWriter::new(stdout_file);
         *self.stderr_writer.lock().await = BufWriter::new(stderr_file);
+        self.stdout_recent.lock().unwrap().clear();
+        self.stderr_recent.lock().unwrap().clear();
 
         tracing::info!("Cleared all sidecar logs");
         Ok(())
@@ -278,6 +454,111 @@ pub struct LogFileInfo {
     pub modified: Option<u64>,
 }
 
+/// 日志搜索命中的一行
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LogSearchMatch {
+    /// 所在文件名
+    pub file: String,
+    /// 行号（从 1 开始）
+    pub line_number: usize,
+    /// 整行内容
+    pub line: String,
+}
+
+/// 日志级别，`Unknown` 表示行首没有可识别的级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Unknown,
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// 解析级别标记，如 `INFO`、`[warn]`、`error:`
+    fn from_token(token: &str) -> Option<Self> {
+        let token = token.trim_start_matches('[').trim_end_matches([']', ':']);
+        match token.to_ascii_lowercase().as_str() {
+            "trace" => Some(Self::Trace),
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" | "err" | "fatal" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+/// 解析后的一行日志
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LogEntry {
+    /// 行首时间戳（Unix 毫秒）
+    pub timestamp: Option<i64>,
+    /// 日志级别
+    pub level: LogLevel,
+    /// 去掉时间戳和级别后的内容
+    pub message: String,
+}
+
+impl LogEntry {
+    /// 解析 `[时间戳] [级别] 内容` 格式的行，两者都可省略；不匹配时整行作为内容
+    pub fn parse(line: &str) -> Self {
+        let (timestamp, rest) = match parse_timestamp(line.trim_start()) {
+            Some((timestamp, rest)) => (Some(timestamp), rest.trim_start()),
+            None => (None, line.trim_start()),
+        };
+
+        let (token, after_token) = split_token(rest);
+        let (level, message) = match LogLevel::from_token(token) {
+            Some(level) => (level, after_token.trim_start()),
+            None => (LogLevel::Unknown, rest),
+        };
+
+        Self {
+            timestamp,
+            level,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// 拆出第一个标记：方括号内的整段，或到下一个空白为止
+fn split_token(s: &str) -> (&str, &str) {
+    if s.starts_with('[') {
+        if let Some(end) = s.find(']') {
+            return (&s[..=end], &s[end + 1..]);
+        }
+    }
+    s.split_once(char::is_whitespace).unwrap_or((s, ""))
+}
+
+/// 解析行首的 RFC 3339 或 `YYYY-MM-DD HH:MM:SS` 本地时间戳
+fn parse_timestamp(s: &str) -> Option<(i64, &str)> {
+    let (token, rest) = split_token(s);
+    let token = token.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(token) {
+        return Some((timestamp.timestamp_millis(), rest));
+    }
+    if let Some(timestamp) = parse_local_datetime(token) {
+        return Some((timestamp, rest));
+    }
+
+    // 日期和时间被空白分成两个标记
+    let (time, rest) = split_token(rest.trim_start());
+    parse_local_datetime(&format!("{} {}", token, time)).map(|timestamp| (timestamp, rest))
+}
+
+fn parse_local_datetime(s: &str) -> Option<i64> {
+    let naive = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").ok()?;
+    naive
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(|timestamp| timestamp.timestamp_millis())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -355,4 +636,207 @@ mod tests {
         let logs_after = log_manager.get_recent_logs("stdout", 10).unwrap();
         assert_eq!(logs_after.len(), 0);
     }
+
+    #[test]
+    fn test_parse_log_entries() {
+        let entry = LogEntry::parse("2024-05-01T12:00:00.500Z [ERROR] Launch failed");
+        assert_eq!(entry.timestamp, Some(1714564800500));
+        assert_eq!(entry.level, LogLevel::Error);
+        assert_eq!(entry.message, "Launch failed");
+
+        let entry = LogEntry::parse("[2024-05-01T12:00:00+08:00] warn: proxy slow");
+        assert_eq!(entry.timestamp, Some(1714536000000));
+        assert_eq!(entry.level, LogLevel::Warn);
+        assert_eq!(entry.message, "proxy slow");
+
+        let entry = LogEntry::parse("INFO Server listening");
+        assert_eq!(entry.timestamp, None);
+        assert_eq!(entry.level, LogLevel::Info);
+        assert_eq!(entry.message, "Server listening");
+
+        let entry = LogEntry::parse("2024-05-01 12:00:00 DEBUG tick");
+        assert!(entry.timestamp.is_some());
+        assert_eq!(entry.level, LogLevel::Debug);
+        assert_eq!(entry.message, "tick");
+
+        // 不符合格式的行整行保留
+        let entry = LogEntry::parse("[BrowserManager] 🚀 Launching browser");
+        assert_eq!(entry.timestamp, None);
+        assert_eq!(entry.level, LogLevel::Unknown);
+        assert_eq!(entry.message, "[BrowserManager] 🚀 Launching browser");
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_entries_filters_by_level_and_time() {
+        let temp_dir = tempdir().unwrap();
+        let log_manager = LogManager::new(temp_dir.path().to_path_buf()).unwrap();
+
+        for line in [
+            "plain startup banner",
+            "2024-05-01T12:00:00Z INFO old info",
+            "2024-05-01T12:00:00Z ERROR old error",
+            "2024-05-01T13:00:00Z WARN new warning",
+            "2024-05-01T13:00:01Z ERROR new error",
+            "    at launch (browser-manager.js:230)",
+            "2024-05-01T13:00:02Z INFO new info",
+        ] {
+            log_manager.write_stdout(line.to_string()).await.unwrap();
+        }
+
+        let all = log_manager.get_recent_entries("stdout", 100, None, None).unwrap();
+        assert_eq!(all.len(), 7);
+        assert_eq!(all[0].level, LogLevel::Unknown);
+
+        let warnings = log_manager
+            .get_recent_entries("stdout", 100, Some(LogLevel::Warn), None)
+            .unwrap();
+        let messages: Vec<_> = warnings.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["old error", "new warning", "new error"]);
+
+        // 12:30 之后：堆栈行沿用上一行的时间，无时间的开头行保留
+        let since = 1714566600000;
+        let recent = log_manager.get_recent_entries("stdout", 100, None, Some(since)).unwrap();
+        let messages: Vec<_> = recent.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "plain startup banner",
+                "new warning",
+                "new error",
+                "at launch (browser-manager.js:230)",
+                "new info",
+            ]
+        );
+
+        let last = log_manager
+            .get_recent_entries("stdout", 1, Some(LogLevel::Error), Some(since))
+            .unwrap();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].message, "new error");
+    }
+
+    #[tokio::test]
+    async fn test_search_logs_spans_active_and_rotated_files() {
+        let temp_dir = tempdir().unwrap();
+        let log_manager = LogManager::new(temp_dir.path().to_path_buf()).unwrap();
+
+        std::fs::write(
+            temp_dir.path().join("sidecar-stdout-20240101-000000.log"),
+            "old line\nLaunching browser for account A1\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("sidecar-stdout-20240102-000000.log"),
+            "account a1 logged in\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("sidecar-stderr-20240103-000000.log"),
+            "account a1 crashed\n",
+        )
+        .unwrap();
+        log_manager.write_stdout("unrelated".to_string()).await.unwrap();
+        log_manager.write_stdout("Closing browser for account a1".to_string()).await.unwrap();
+
+        // 当前文件在前，归档从新到旧，不区分大小写
+        let matches = log_manager.search_logs("account a1", "stdout", 10).unwrap();
+        let found: Vec<_> = matches
+            .iter()
+            .map(|m| (m.file.as_str(), m.line_number))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("sidecar-stdout.log", 2),
+                ("sidecar-stdout-20240102-000000.log", 1),
+                ("sidecar-stdout-20240101-000000.log", 2),
+            ]
+        );
+        assert_eq!(matches[2].line, "Launching browser for account A1");
+
+        let capped = log_manager.search_logs("account a1", "stdout", 2).unwrap();
+        assert_eq!(capped.len(), 2);
+
+        let exact = log_manager
+            .search_logs_with_case("account A1", "stdout", 10, true)
+            .unwrap();
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].file, "sidecar-stdout-20240101-000000.log");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_rotation_under_concurrent_writes_keeps_every_line() {
+        const TASKS: usize = 16;
+        const LINES_PER_TASK: usize = 200;
+
+        let temp_dir = tempdir().unwrap();
+        let mut log_manager = LogManager::new(temp_dir.path().to_path_buf()).unwrap();
+        log_manager.max_log_size = 4 * 1024;
+        log_manager.max_log_files = usize::MAX;
+        let log_manager = Arc::new(log_manager);
+
+        let writers: Vec<_> = (0..TASKS)
+            .map(|task| {
+                let log_manager = log_manager.clone();
+                tokio::spawn(async move {
+                    for i in 0..LINES_PER_TASK {
+                        log_manager
+                            .write_stdout(format!("task {} line {}", task, i))
+                            .await
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.await.unwrap();
+        }
+
+        let mut lines = Vec::new();
+        let mut files = 0;
+        for entry in std::fs::read_dir(temp_dir.path()).unwrap() {
+            let name = entry.unwrap().file_name().to_string_lossy().to_string();
+            if name.starts_with("sidecar-stdout") {
+                files += 1;
+                let content = std::fs::read_to_string(temp_dir.path().join(name)).unwrap();
+                lines.extend(content.lines().map(str::to_string));
+            }
+        }
+
+        assert!(files > 2, "expected several rotations, got {} files", files);
+        assert_eq!(lines.len(), TASKS * LINES_PER_TASK);
+        lines.sort();
+        lines.dedup();
+        assert_eq!(lines.len(), TASKS * LINES_PER_TASK);
+    }
+
+    #[tokio::test]
+    async fn test_recent_logs_served_from_ring_buffer() {
+        let temp_dir = tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("sidecar-stdout.log"), "Earlier 0\nEarlier 1\n").unwrap();
+        let mut log_manager = LogManager::new(temp_dir.path().to_path_buf()).unwrap();
+
+        // 启动时从已有文件加载
+        assert_eq!(log_manager.get_recent_logs("stdout", 1).unwrap(), ["Earlier 1"]);
+
+        log_manager.stdout_recent = std::sync::Mutex::new(RecentLines::new(10));
+        for i in 0..25 {
+            log_manager.write_stdout(format!("Line {}", i)).await.unwrap();
+        }
+        // 绕过管理器追加的行只有读文件时可见
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(temp_dir.path().join("sidecar-stdout.log"))
+            .unwrap();
+        writeln!(file, "External line").unwrap();
+
+        let tail = log_manager.get_recent_logs("stdout", 5).unwrap();
+        assert_eq!(tail, ["Line 20", "Line 21", "Line 22", "Line 23", "Line 24"]);
+        assert_eq!(log_manager.get_recent_logs("stdout", 10).unwrap()[0], "Line 15");
+
+        let oversized = log_manager.get_recent_logs("stdout", 20).unwrap();
+        assert_eq!(oversized.len(), 20);
+        assert_eq!(oversized[0], "Line 6");
+        assert_eq!(oversized[19], "External line");
+    }
 }
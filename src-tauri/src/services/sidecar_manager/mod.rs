@@ -1,19 +1,47 @@
 mod health_checker;
 mod log_manager;
+mod node_runtime;
 mod types;
 
-pub use health_checker::{HealthCheckConfig, HealthChecker};
-pub use log_manager::{LogFileInfo, LogManager};
+pub use health_checker::{HealthCheckConfig, HealthChecker, HealthProbe};
+pub use log_manager::{LogEntry, LogFileInfo, LogLevel, LogManager, LogSearchMatch};
+pub use node_runtime::NodeRuntime;
 pub use types::*;
 
+use async_trait::async_trait;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
-use tokio::sync::{RwLock, Notify};
+use tokio::sync::{Mutex, RwLock, Notify};
+
+/// 自动重启退避的基础时长（1s, 2s, 4s, 8s, 16s）
+const RESTART_BACKOFF_BASE: Duration = Duration::from_secs(1);
+
+/// Windows 下 `POST /shutdown` 请求的超时
+#[cfg(windows)]
+const SHUTDOWN_REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 状态变更事件名，payload 为 [`SidecarStatusInfo`]
+pub const SIDECAR_STATUS_EVENT: &str = "sidecar://status";
+
+/// 向前端广播当前状态
+fn emit_status(app_handle: &tauri::AppHandle, state: &SidecarState) {
+    let info = SidecarStatusInfo::from(state);
+    if let Err(e) = app_handle.emit(SIDECAR_STATUS_EVENT, &info) {
+        tracing::warn!("Failed to emit sidecar status event: {}", e);
+    }
+}
+
+/// 重启成功后执行的回调，用于清理随旧进程丢失的状态（如浏览器会话）
+#[async_trait]
+pub trait RestartHook: Send + Sync {
+    async fn after_restart(&self);
+}
 
 /// Sidecar 进程管理器
+#[derive(Clone)]
 pub struct SidecarManager {
     /// 当前状态
     state: Arc<RwLock<SidecarState>>,
@@ -27,17 +55,27 @@ pub struct SidecarManager {
     health_checker: Arc<HealthChecker>,
     /// 停止监控信号
     stop_monitoring: Arc<Notify>,
+    /// 用于发送状态事件
+    app_handle: tauri::AppHandle,
+    /// 防止手动重启与自动重启重叠
+    restart_lock: Arc<Mutex<()>>,
+    /// 重启成功后的回调
+    restart_hooks: Vec<Arc<dyn RestartHook>>,
 }
 
 impl SidecarManager {
-    /// 创建新的 SidecarManager
-    pub fn new(app_handle: &tauri::AppHandle) -> Result<Self, SidecarError> {
-        // 获取 sidecar 目录
-        let sidecar_dir = app_handle
+    /// 创建新的 SidecarManager，sidecar 监听在 `port`
+    pub fn new(app_handle: &tauri::AppHandle, port: u16) -> Result<Self, SidecarError> {
+        // 获取 sidecar 目录与内置 Node 运行时目录
+        let resource_dir = app_handle
             .path()
             .resource_dir()
-            .map_err(|e| SidecarError::Other(format!("Failed to get resource dir: {}", e)))?
-            .join("playwright-sidecar");
+            .map_err(|e| SidecarError::Other(format!("Failed to get resource dir: {}", e)))?;
+        let sidecar_dir = resource_dir.join("playwright-sidecar");
+        let bundled_node_dir = resource_dir.join("node");
+        let node_path = std::env::var_os(NODE_PATH_ENV).map(PathBuf::from);
+        let health_path = std::env::var(SIDECAR_HEALTH_PATH_ENV).ok();
+        let require_status_ok = std::env::var(SIDECAR_HEALTH_REQUIRE_STATUS_OK_ENV).ok();
 
         // 获取日志目录（使用 app_data_dir）
         let log_dir = app_handle
@@ -50,8 +88,13 @@ impl SidecarManager {
         std::fs::create_dir_all(&log_dir)?;
 
         let config = SidecarConfig {
+            port,
             sidecar_dir,
             log_dir,
+            node_path,
+            bundled_node_dir,
+            health_check_path: parse_health_path(health_path.as_deref()),
+            health_check_require_status_ok: parse_require_status_ok(require_status_ok.as_deref()),
             ..Default::default()
         };
 
@@ -62,15 +105,8 @@ impl SidecarManager {
         );
 
         // 初始化健康检查器
-        let health_check_config = HealthCheckConfig {
-            interval: config.health_check_interval,
-            timeout: config.health_check_timeout,
-            failure_threshold: 3,
-            success_threshold: 2,
-        };
-
         let health_checker = Arc::new(
-            HealthChecker::new(config.port, health_check_config)
+            HealthChecker::new(config.port, config.health_check_config())
                 .map_err(|e| SidecarError::Other(format!("Failed to create HealthChecker: {}", e)))?
         );
 
@@ -88,9 +124,25 @@ impl SidecarManager {
             log_manager,
             health_checker,
             stop_monitoring: Arc::new(Notify::new()),
+            app_handle: app_handle.clone(),
+            restart_lock: Arc::new(Mutex::new(())),
+            restart_hooks: Vec::new(),
         })
     }
 
+    /// 每次重启（手动或自动）成功后调用 `hook`
+    pub fn with_restart_hook(mut self, hook: Arc<dyn RestartHook>) -> Self {
+        self.restart_hooks.push(hook);
+        self
+    }
+
+    /// 重启成功后依次执行回调
+    async fn run_restart_hooks(&self) {
+        for hook in &self.restart_hooks {
+            hook.after_restart().await;
+        }
+    }
+
     /// 获取当前状态
     pub async fn get_state(&self) -> SidecarState {
         self.state.read().await.clone()
@@ -102,6 +154,13 @@ impl SidecarManager {
         SidecarStatusInfo::from(&*state)
     }
 
+    /// 更新状态并通知前端
+    async fn set_state(&self, new_state: SidecarState) {
+        let mut state = self.state.write().await;
+        *state = new_state;
+        emit_status(&self.app_handle, &state);
+    }
+
     /// 启动 sidecar
     pub async fn start(&self) -> Result<(), SidecarError> {
         // 检查当前状态
@@ -112,6 +171,45 @@ impl SidecarManager {
             }
         }
 
+        let result = self.start_inner().await;
+
+        // 启动失败时进入 Failed，避免前端停留在 starting
+        if let Err(e) = &result {
+            self.set_state(SidecarState::Failed {
+                error: e.to_string(),
+                last_attempt: Instant::now(),
+            })
+            .await;
+        }
+
+        result
+    }
+
+    /// 启动流程
+    async fn start_inner(&self) -> Result<(), SidecarError> {
+        let pid = self.launch().await?;
+
+        // 5. 更新为运行状态
+        self.update_progress(StartStage::Ready, "Sidecar 已就绪")
+            .await;
+
+        self.set_state(SidecarState::Running {
+            pid,
+            started_at: Instant::now(),
+            restart_count: 0,
+        })
+        .await;
+
+        tracing::info!("Sidecar started successfully on port {}", self.config.port);
+
+        // 6. 启动后台健康监控
+        self.start_health_monitoring();
+
+        Ok(())
+    }
+
+    /// 检查依赖、生成进程并等待健康检查通过，返回 PID
+    async fn launch(&self) -> Result<u32, SidecarError> {
         tracing::info!("Starting sidecar...");
 
         // 1. 检查 sidecar 目录
@@ -121,12 +219,29 @@ impl SidecarManager {
         )
         .await;
 
+        // 端口被占用时尽早报错；若占用者正是我们的 sidecar，则直接复用
+        let port_status = probe_port(self.config.port, &self.health_checker).await?;
+        if port_status == PortStatus::OwnSidecar {
+            tracing::warn!(
+                "A PubCast sidecar is already listening on port {}, reusing it",
+                self.config.port
+            );
+            return Ok(EXTERNAL_SIDECAR_PID);
+        }
+
         if !self.config.sidecar_dir.exists() {
             return Err(SidecarError::DirectoryNotFound(
                 self.config.sidecar_dir.clone(),
             ));
         }
 
+        let runtime = NodeRuntime::resolve(
+            self.config.node_path.as_deref(),
+            &self.config.bundled_node_dir,
+            std::env::var_os("PATH").as_deref(),
+        )?;
+        tracing::info!("Using Node runtime: {}", runtime);
+
         // 2. 检查并安装依赖
         let node_modules = self.config.sidecar_dir.join("node_modules");
         if !node_modules.exists() {
@@ -136,14 +251,14 @@ impl SidecarManager {
             )
             .await;
 
-            self.install_dependencies().await?;
+            self.install_dependencies(&runtime).await?;
         }
 
         // 3. 启动进程
         self.update_progress(StartStage::SpawningProcess, "启动 Sidecar 进程...")
             .await;
 
-        let mut child = self.spawn_process().await?;
+        let mut child = self.spawn_process(&runtime).await?;
         let pid = child.id().ok_or_else(|| {
             SidecarError::ProcessSpawn("Failed to get process ID".to_string())
         })?;
@@ -188,22 +303,7 @@ impl SidecarManager {
 
         self.wait_for_health().await?;
 
-        // 5. 更新为运行状态
-        *self.state.write().await = SidecarState::Running {
-            pid,
-            started_at: Instant::now(),
-            restart_count: 0,
-        };
-
-        self.update_progress(StartStage::Ready, "Sidecar 已就绪")
-            .await;
-
-        tracing::info!("Sidecar started successfully on port {}", self.config.port);
-
-        // 6. 启动后台健康监控
-        self.start_health_monitoring();
-
-        Ok(())
+        Ok(pid)
     }
 
     /// 停止 sidecar
@@ -218,7 +318,7 @@ impl SidecarManager {
                 tracing::info!("Stopping sidecar (PID: {})...", pid);
 
                 // 更新状态为 Stopping
-                *self.state.write().await = SidecarState::Stopping;
+                self.set_state(SidecarState::Stopping).await;
 
                 // 停止健康监控
                 self.stop_monitoring.notify_waiters();
@@ -230,7 +330,7 @@ impl SidecarManager {
                 }
 
                 // 更新状态为 Stopped
-                *self.state.write().await = SidecarState::Stopped;
+                self.set_state(SidecarState::Stopped).await;
 
                 Ok(())
             }
@@ -242,98 +342,63 @@ impl SidecarManager {
         }
     }
 
-    /// 优雅关闭进程
+    /// 优雅关闭进程：先请求退出（Unix: SIGTERM，Windows: `POST /shutdown`），超时后强制终止
     async fn graceful_shutdown(&self, child: &mut tokio::process::Child, pid: u32) -> Result<(), SidecarError> {
-        #[cfg(unix)]
-        {
-            use nix::sys::signal::{kill, Signal};
-            use nix::unistd::Pid;
+        // 1. 发送优雅关闭请求
+        if let Err(e) = request_shutdown(pid, self.config.port).await {
+            tracing::warn!("Failed to request graceful shutdown: {}", e);
+            // 信号发送失败，直接强制终止
+            return self.force_kill(child, pid).await;
+        }
 
-            // 1. 发送 SIGTERM（优雅关闭信号）
-            tracing::info!("Sending SIGTERM to sidecar (PID: {})...", pid);
+        // 2. 等待进程退出（带超时）
+        tracing::debug!("Waiting for graceful shutdown (timeout: {:?})...", self.config.shutdown_timeout);
 
-            let nix_pid = Pid::from_raw(pid as i32);
-            if let Err(e) = kill(nix_pid, Signal::SIGTERM) {
-                tracing::warn!("Failed to send SIGTERM: {}", e);
-                // SIGTERM 失败，直接 SIGKILL
-                return self.force_kill(child, pid).await;
+        let shutdown_result = tokio::time::timeout(
+            self.config.shutdown_timeout,
+            child.wait()
+        ).await;
+
+        match shutdown_result {
+            Ok(Ok(status)) => {
+                tracing::info!("Sidecar exited gracefully with status: {:?}", status);
+                Ok(())
+            }
+            Ok(Err(e)) => {
+                tracing::warn!("Error waiting for sidecar exit: {}", e);
+                Err(SidecarError::Other(format!("Wait error: {}", e)))
+            }
+            Err(_) => {
+                // 3. 超时，强制终止
+                tracing::warn!("Graceful shutdown timeout, force killing...");
+                self.force_kill(child, pid).await
             }
+        }
+    }
 
-            // 2. 等待进程退出（带超时）
-            tracing::debug!("Waiting for graceful shutdown (timeout: {:?})...", self.config.shutdown_timeout);
+    /// 强制终止进程（Unix: SIGKILL，Windows: TerminateProcess）
+    async fn force_kill(&self, child: &mut tokio::process::Child, pid: u32) -> Result<(), SidecarError> {
+        tracing::info!("Force killing sidecar (PID: {})...", pid);
 
-            let shutdown_result = tokio::time::timeout(
-                self.config.shutdown_timeout,
-                child.wait()
-            ).await;
+        #[cfg(unix)]
+        {
+            use nix::sys::signal::{kill, Signal};
+            use nix::unistd::Pid;
 
-            match shutdown_result {
-                Ok(Ok(status)) => {
-                    tracing::info!("Sidecar exited gracefully with status: {:?}", status);
-                    Ok(())
-                }
-                Ok(Err(e)) => {
-                    tracing::warn!("Error waiting for sidecar exit: {}", e);
-                    Err(SidecarError::Other(format!("Wait error: {}", e)))
-                }
-                Err(_) => {
-                    // 3. 超时，发送 SIGKILL（强制终止）
-                    tracing::warn!("Graceful shutdown timeout, sending SIGKILL...");
-                    self.force_kill(child, pid).await
-                }
+            let nix_pid = Pid::from_raw(pid as i32);
+            if let Err(e) = kill(nix_pid, Signal::SIGKILL) {
+                tracing::error!("Failed to send SIGKILL: {}", e);
+                return Err(SidecarError::Other(format!("SIGKILL failed: {}", e)));
             }
         }
 
         #[cfg(not(unix))]
         {
-            // Windows: 直接使用 kill (相当于 TerminateProcess)
-            tracing::info!("Terminating sidecar (PID: {})...", pid);
-
-            match child.kill().await {
-                Ok(_) => {
-                    tracing::info!("Sent termination signal to sidecar");
-                    // 等待进程退出（带超时）
-                    let shutdown_result = tokio::time::timeout(
-                        self.config.shutdown_timeout,
-                        child.wait()
-                    ).await;
-
-                    match shutdown_result {
-                        Ok(Ok(status)) => {
-                            tracing::info!("Sidecar exited with status: {:?}", status);
-                            Ok(())
-                        }
-                        Ok(Err(e)) => {
-                            tracing::warn!("Error waiting for sidecar exit: {}", e);
-                            Err(SidecarError::Other(format!("Wait error: {}", e)))
-                        }
-                        Err(_) => {
-                            tracing::warn!("Shutdown timeout reached");
-                            Ok(()) // 已发送终止信号，认为成功
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to terminate sidecar: {}", e);
-                    Err(SidecarError::Other(format!("Termination failed: {}", e)))
-                }
+            if let Err(e) = child.start_kill() {
+                tracing::error!("Failed to terminate sidecar: {}", e);
+                return Err(SidecarError::Other(format!("Termination failed: {}", e)));
             }
         }
-    }
-
-    /// 强制终止进程（SIGKILL）
-    #[cfg(unix)]
-    async fn force_kill(&self, child: &mut tokio::process::Child, pid: u32) -> Result<(), SidecarError> {
-        use nix::sys::signal::{kill, Signal};
-        use nix::unistd::Pid;
-
-        tracing::info!("Force killing sidecar (PID: {})...", pid);
-
-        let nix_pid = Pid::from_raw(pid as i32);
-        if let Err(e) = kill(nix_pid, Signal::SIGKILL) {
-            tracing::error!("Failed to send SIGKILL: {}", e);
-            return Err(SidecarError::Other(format!("SIGKILL failed: {}", e)));
-        }
 
         // 等待进程退出
         match child.wait().await {
@@ -342,8 +407,8 @@ impl SidecarManager {
                 Ok(())
             }
             Err(e) => {
-                tracing::warn!("Error waiting after SIGKILL: {}", e);
-                // SIGKILL 通常不会失败，即使 wait 失败也认为成功
+                tracing::warn!("Error waiting after force kill: {}", e);
+                // 强制终止通常不会失败，即使 wait 失败也认为成功
                 Ok(())
             }
         }
@@ -351,6 +416,8 @@ impl SidecarManager {
 
     /// 重启 sidecar
     pub async fn restart(&self) -> Result<(), SidecarError> {
+        let _guard = self.restart_lock.lock().await;
+
         tracing::info!("Restarting sidecar...");
 
         // 先停止
@@ -362,32 +429,31 @@ impl SidecarManager {
         // 再启动
         self.start().await?;
 
+        // 旧进程中的状态已丢失，交给回调清理
+        self.run_restart_hooks().await;
+
         Ok(())
     }
 
     /// 更新启动进度
     async fn update_progress(&self, stage: StartStage, message: &str) {
-        let mut state = self.state.write().await;
-        *state = SidecarState::Starting {
+        self.set_state(SidecarState::Starting {
             progress: StartProgress {
                 stage,
                 message: message.to_string(),
                 timestamp: Instant::now(),
             },
-        };
+        })
+        .await;
         tracing::info!("[{:?}] {}", stage, message);
     }
 
     /// 安装 npm 依赖
-    async fn install_dependencies(&self) -> Result<(), SidecarError> {
+    async fn install_dependencies(&self, runtime: &NodeRuntime) -> Result<(), SidecarError> {
         tracing::info!("Installing npm dependencies...");
 
-        #[cfg(target_os = "windows")]
-        const NPM_CMD: &str = "npm.cmd";
-        #[cfg(not(target_os = "windows"))]
-        const NPM_CMD: &str = "npm";
-
-        let output = Command::new(NPM_CMD)
+        let output = runtime
+            .npm_command()
             .arg("install")
             .current_dir(&self.config.sidecar_dir)
             .output()
@@ -407,15 +473,15 @@ impl SidecarManager {
     }
 
     /// 生成 sidecar 进程
-    async fn spawn_process(&self) -> Result<tokio::process::Child, SidecarError> {
-        #[cfg(target_os = "windows")]
-        const NPM_CMD: &str = "npm.cmd";
-        #[cfg(not(target_os = "windows"))]
-        const NPM_CMD: &str = "npm";
-
-        let child = Command::new(NPM_CMD)
+    async fn spawn_process(
+        &self,
+        runtime: &NodeRuntime,
+    ) -> Result<tokio::process::Child, SidecarError> {
+        let child = runtime
+            .npm_command()
             .arg("start")
             .current_dir(&self.config.sidecar_dir)
+            .env("PORT", self.config.port.to_string())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .kill_on_drop(true)
@@ -432,6 +498,32 @@ impl SidecarManager {
             .map_err(|e| SidecarError::Other(format!("Failed to get logs: {}", e)))
     }
 
+    /// 获取最近的结构化日志，可按最低级别和起始时间（Unix 毫秒）过滤
+    pub fn get_log_entries(
+        &self,
+        log_type: &str,
+        lines: usize,
+        level: Option<LogLevel>,
+        since: Option<i64>,
+    ) -> Result<Vec<LogEntry>, SidecarError> {
+        self.log_manager
+            .get_recent_entries(log_type, lines, level, since)
+            .map_err(|e| SidecarError::Other(format!("Failed to get logs: {}", e)))
+    }
+
+    /// 在当前和归档日志中搜索
+    pub fn search_logs(
+        &self,
+        query: &str,
+        log_type: &str,
+        max_results: usize,
+        case_sensitive: bool,
+    ) -> Result<Vec<LogSearchMatch>, SidecarError> {
+        self.log_manager
+            .search_logs_with_case(query, log_type, max_results, case_sensitive)
+            .map_err(|e| SidecarError::Other(format!("Failed to search logs: {}", e)))
+    }
+
     /// 获取所有日志文件列表
     pub fn list_log_files(&self) -> Result<Vec<LogFileInfo>, SidecarError> {
         self.log_manager
@@ -457,121 +549,290 @@ impl SidecarManager {
 
     /// 启动后台健康监控
     fn start_health_monitoring(&self) {
-        let state = self.state.clone();
-        let health_checker = self.health_checker.clone();
+        let probe: Arc<dyn HealthProbe> = self.health_checker.clone();
+        let target: Arc<dyn MonitorTarget> = Arc::new(self.clone());
         let stop_signal = self.stop_monitoring.clone();
-        let config = self.config.clone();
+        let max_restart_count = self.config.max_restart_count;
 
         tokio::spawn(async move {
-            let mut consecutive_failures = 0;
-            let mut restart_count = 0;
+            run_health_monitor(
+                probe,
+                target,
+                max_restart_count,
+                RESTART_BACKOFF_BASE,
+                stop_signal,
+            )
+            .await;
+        });
+    }
 
-            tracing::info!("Health monitoring started");
+    /// 健康检查连续失败后的自动重启：结束旧进程并重新拉起
+    async fn auto_restart(&self, restart_count: u32) -> Result<(), SidecarError> {
+        let Ok(_guard) = self.restart_lock.try_lock() else {
+            return Err(SidecarError::Other("Restart already in progress".to_string()));
+        };
 
-            loop {
-                // 等待检查间隔或停止信号
-                tokio::select! {
-                    _ = stop_signal.notified() => {
-                        tracing::info!("Health monitoring stopped");
-                        break;
-                    }
-                    _ = tokio::time::sleep(health_checker.interval()) => {
-                        // 执行健康检查
-                    }
+        let old_child = self.child.write().await.take();
+        if let Some(mut child) = old_child {
+            if let Some(pid) = child.id() {
+                if let Err(e) = self.graceful_shutdown(&mut child, pid).await {
+                    tracing::warn!("Failed to stop unhealthy sidecar: {}", e);
                 }
+            }
+        }
 
-                // 检查当前状态
-                let current_state = state.read().await.clone();
-                if !matches!(current_state, SidecarState::Running { .. }) {
-                    tracing::debug!("Sidecar not running, stopping health monitoring");
-                    break;
-                }
+        let pid = self.launch().await?;
+
+        self.set_state(SidecarState::Running {
+            pid,
+            started_at: Instant::now(),
+            restart_count,
+        })
+        .await;
+
+        tracing::info!(
+            "Sidecar restarted on port {} (restart {})",
+            self.config.port,
+            restart_count
+        );
+
+        self.run_restart_hooks().await;
+
+        Ok(())
+    }
+}
 
+/// 复用已在运行的 sidecar 时使用的 PID（该进程不由本管理器持有）
+const EXTERNAL_SIDECAR_PID: u32 = 0;
+
+/// 启动前的端口状态
+#[derive(Debug, PartialEq, Eq)]
+enum PortStatus {
+    /// 端口空闲
+    Free,
+    /// 端口上已有我们自己的 sidecar 在运行
+    OwnSidecar,
+}
+
+/// 检查端口：空闲、已是我们的 sidecar，或被其他程序占用（`PortInUse`）
+async fn probe_port(
+    port: u16,
+    health_checker: &HealthChecker,
+) -> Result<PortStatus, SidecarError> {
+    match ensure_port_available(port) {
+        Ok(()) => Ok(PortStatus::Free),
+        Err(SidecarError::PortInUse(port)) => {
+            if health_checker.is_own_sidecar().await {
+                Ok(PortStatus::OwnSidecar)
+            } else {
+                Err(SidecarError::PortInUse(port))
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// 确认端口未被其他程序占用
+fn ensure_port_available(port: u16) -> Result<(), SidecarError> {
+    match std::net::TcpListener::bind(("127.0.0.1", port)) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => Err(SidecarError::PortInUse(port)),
+        Err(e) => Err(SidecarError::Io(e)),
+    }
+}
+
+/// 请求进程优雅退出：发送 SIGTERM
+#[cfg(unix)]
+async fn request_shutdown(pid: u32, _port: u16) -> std::io::Result<()> {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+
+    tracing::info!("Sending SIGTERM to sidecar (PID: {})...", pid);
+    kill(Pid::from_raw(pid as i32), Signal::SIGTERM)?;
+    Ok(())
+}
+
+/// 请求进程优雅退出：调用 sidecar 的 `POST /shutdown`
+///
+/// Release 构建没有控制台（`windows_subsystem = "windows"`），
+/// 控制台信号无法送达 sidecar，因此改用 HTTP 请求。
+#[cfg(windows)]
+async fn request_shutdown(_pid: u32, port: u16) -> std::io::Result<()> {
+    tracing::info!("Requesting sidecar shutdown via HTTP (port: {})...", port);
+    let client = crate::infrastructure::http::HttpClientFactory::new()
+        .direct_client(SHUTDOWN_REQUEST_TIMEOUT)
+        .map_err(std::io::Error::other)?;
+    client
+        .post(format!("{}/shutdown", sidecar_base_url(port)))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(std::io::Error::other)?;
+    Ok(())
+}
+
+/// 健康监控所操作的对象（便于测试时替换）
+#[async_trait]
+trait MonitorTarget: Send + Sync {
+    /// 是否处于 Running 状态
+    async fn is_running(&self) -> bool;
+    /// 执行第 `restart_count` 次自动重启
+    async fn restart(&self, restart_count: u32) -> Result<(), SidecarError>;
+    /// 重启次数耗尽，标记为失败
+    async fn mark_failed(&self, error: String);
+}
+
+#[async_trait]
+impl MonitorTarget for SidecarManager {
+    async fn is_running(&self) -> bool {
+        matches!(*self.state.read().await, SidecarState::Running { .. })
+    }
+
+    async fn restart(&self, restart_count: u32) -> Result<(), SidecarError> {
+        self.auto_restart(restart_count).await
+    }
+
+    async fn mark_failed(&self, error: String) {
+        self.set_state(SidecarState::Failed {
+            error,
+            last_attempt: Instant::now(),
+        })
+        .await;
+    }
+}
+
+/// 后台健康监控循环
+///
+/// 连续失败达到阈值后按指数退避（`backoff_base` × 1, 2, 4, 8, 16）自动重启，
+/// 重启次数用尽才进入 Failed。
+async fn run_health_monitor(
+    probe: Arc<dyn HealthProbe>,
+    target: Arc<dyn MonitorTarget>,
+    max_restart_count: u32,
+    backoff_base: Duration,
+    stop_signal: Arc<Notify>,
+) {
+    let mut consecutive_failures = 0;
+    let mut restart_count = 0;
+
+    tracing::info!("Health monitoring started");
+
+    loop {
+        // 等待检查间隔或停止信号
+        tokio::select! {
+            _ = stop_signal.notified() => {
+                tracing::info!("Health monitoring stopped");
+                break;
+            }
+            _ = tokio::time::sleep(probe.interval()) => {
                 // 执行健康检查
-                let is_healthy = health_checker.check_once().await;
+            }
+        }
 
-                if is_healthy {
-                    // 重置失败计数
-                    if consecutive_failures > 0 {
-                        tracing::info!("Health check recovered after {} failures", consecutive_failures);
-                        consecutive_failures = 0;
-                    }
-                } else {
-                    consecutive_failures += 1;
-                    tracing::warn!(
-                        "Health check failed ({}/{})",
-                        consecutive_failures,
-                        health_checker.failure_threshold()
-                    );
-
-                    // 检查是否达到失败阈值
-                    if consecutive_failures >= health_checker.failure_threshold() {
-                        tracing::error!("Health check failed {} times, attempting restart", consecutive_failures);
-
-                        // 检查是否超过最大重启次数
-                        if restart_count >= config.max_restart_count {
-                            tracing::error!(
-                                "Max restart count ({}) reached, marking as failed",
-                                config.max_restart_count
-                            );
-
-                            *state.write().await = SidecarState::Failed {
-                                error: format!(
-                                    "Health check failed after {} consecutive failures. Max restart count ({}) reached.",
-                                    consecutive_failures, config.max_restart_count
-                                ),
-                                last_attempt: Instant::now(),
-                            };
-                            break;
-                        }
-
-                        // 计算退避时间（指数退避：1s, 2s, 4s, 8s, 16s）
-                        let backoff_secs = 2_u64.pow(restart_count.min(4));
-                        let backoff_duration = Duration::from_secs(backoff_secs);
-
-                        tracing::info!(
-                            "Waiting {:?} before restart (attempt {}/{})",
-                            backoff_duration,
-                            restart_count + 1,
-                            config.max_restart_count
-                        );
-
-                        tokio::time::sleep(backoff_duration).await;
-
-                        // 尝试重启（这里只是标记为失败，实际重启需要外部触发）
-                        // 在实际应用中，可以通过事件总线或回调通知外部进行重启
-                        restart_count += 1;
-
-                        tracing::warn!(
-                            "Sidecar health check failed, needs restart (attempt {}/{})",
-                            restart_count,
-                            config.max_restart_count
-                        );
-
-                        // 更新状态为 Failed，需要外部重启
-                        *state.write().await = SidecarState::Failed {
-                            error: format!(
-                                "Health check failed after {} consecutive failures",
-                                health_checker.failure_threshold()
-                            ),
-                            last_attempt: Instant::now(),
-                        };
-
-                        // 停止监控，等待外部重启
-                        break;
-                    }
+        // 检查当前状态
+        if !target.is_running().await {
+            tracing::debug!("Sidecar not running, stopping health monitoring");
+            break;
+        }
+
+        if probe.check_once().await {
+            // 重置失败计数
+            if consecutive_failures > 0 {
+                tracing::info!("Health check recovered after {} failures", consecutive_failures);
+                consecutive_failures = 0;
+            }
+            continue;
+        }
+
+        consecutive_failures += 1;
+        tracing::warn!(
+            "Health check failed ({}/{})",
+            consecutive_failures,
+            probe.failure_threshold()
+        );
+
+        if consecutive_failures < probe.failure_threshold() {
+            continue;
+        }
+
+        tracing::error!("Health check failed {} times, attempting restart", consecutive_failures);
+
+        // 重启直到成功或次数用尽
+        loop {
+            if restart_count >= max_restart_count {
+                tracing::error!(
+                    "Max restart count ({}) reached, marking as failed",
+                    max_restart_count
+                );
+                target
+                    .mark_failed(format!(
+                        "Health check failed after {} consecutive failures. Max restart count ({}) reached.",
+                        consecutive_failures, max_restart_count
+                    ))
+                    .await;
+                tracing::info!("Health monitoring task exited");
+                return;
+            }
+
+            // 计算退避时间（指数退避：1s, 2s, 4s, 8s, 16s）
+            let backoff_duration = backoff_base * 2_u32.pow(restart_count.min(4));
+
+            tracing::info!(
+                "Waiting {:?} before restart (attempt {}/{})",
+                backoff_duration,
+                restart_count + 1,
+                max_restart_count
+            );
+
+            tokio::select! {
+                _ = stop_signal.notified() => {
+                    tracing::info!("Health monitoring stopped during restart backoff");
+                    return;
                 }
+                _ = tokio::time::sleep(backoff_duration) => {}
             }
 
-            tracing::info!("Health monitoring task exited");
-        });
+            restart_count += 1;
+
+            match target.restart(restart_count).await {
+                Ok(()) => {
+                    consecutive_failures = 0;
+                    break;
+                }
+                Err(e) => tracing::warn!(
+                    "Sidecar restart attempt {}/{} failed: {}",
+                    restart_count,
+                    max_restart_count,
+                    e
+                ),
+            }
+        }
     }
+
+    tracing::info!("Health monitoring task exited");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn test_request_shutdown_posts_to_sidecar() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/shutdown"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        request_shutdown(0, server.address().port()).await.unwrap();
+    }
+
     #[test]
     fn test_sidecar_config_default() {
         let config = SidecarConfig::default();
@@ -580,6 +841,99 @@ mod tests {
         assert_eq!(config.max_restart_count, 5);
     }
 
+    #[test]
+    fn test_parse_port() {
+        assert_eq!(parse_port(None).unwrap(), DEFAULT_SIDECAR_PORT);
+        assert_eq!(parse_port(Some(" ")).unwrap(), DEFAULT_SIDECAR_PORT);
+        assert_eq!(parse_port(Some("9123")).unwrap(), 9123);
+        assert!(matches!(parse_port(Some("0")), Err(SidecarError::InvalidPort(_))));
+        assert!(matches!(parse_port(Some("http")), Err(SidecarError::InvalidPort(_))));
+    }
+
+    #[test]
+    fn test_custom_port_propagates_to_health_endpoint() {
+        let config = SidecarConfig {
+            port: 9123,
+            ..Default::default()
+        };
+        let checker = HealthChecker::new(config.port, HealthCheckConfig::default()).unwrap();
+        assert_eq!(checker.endpoint(), "http://localhost:9123/health");
+        assert_eq!(sidecar_base_url(config.port), "http://localhost:9123");
+    }
+
+    #[test]
+    fn test_health_check_settings_propagate_from_config() {
+        assert_eq!(parse_health_path(None), DEFAULT_HEALTH_PATH);
+        assert_eq!(parse_health_path(Some(" ")), DEFAULT_HEALTH_PATH);
+        assert!(parse_require_status_ok(None));
+        assert!(parse_require_status_ok(Some("1")));
+        assert!(!parse_require_status_ok(Some("false")));
+        assert!(!parse_require_status_ok(Some(" OFF ")));
+
+        let config = SidecarConfig {
+            port: 9123,
+            health_check_path: parse_health_path(Some("/pw/health")),
+            health_check_require_status_ok: false,
+            ..Default::default()
+        };
+        let health_check_config = config.health_check_config();
+        assert!(!health_check_config.require_status_ok);
+        let checker = HealthChecker::new(config.port, health_check_config).unwrap();
+        assert_eq!(checker.endpoint(), "http://localhost:9123/pw/health");
+
+        assert!(SidecarConfig::default().health_check_config().require_status_ok);
+    }
+
+    #[test]
+    fn test_port_in_use_is_reported() {
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        assert!(matches!(
+            ensure_port_available(port),
+            Err(SidecarError::PortInUse(p)) if p == port
+        ));
+
+        drop(listener);
+        assert!(ensure_port_available(port).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_probe_port_reports_foreign_occupant() {
+        let listener = std::net::TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let config = HealthCheckConfig {
+            timeout: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let checker = HealthChecker::new(port, config).unwrap();
+
+        assert!(matches!(
+            probe_port(port, &checker).await,
+            Err(SidecarError::PortInUse(p)) if p == port
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_probe_port_reuses_own_sidecar() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "ok",
+                "service": "playwright-sidecar",
+            })))
+            .mount(&server)
+            .await;
+        let port = server.address().port();
+        let checker = HealthChecker::new(port, HealthCheckConfig::default()).unwrap();
+
+        assert_eq!(probe_port(port, &checker).await.unwrap(), PortStatus::OwnSidecar);
+    }
+
     #[test]
     fn test_sidecar_status_info_from_stopped() {
         let state = SidecarState::Stopped;
@@ -603,6 +957,150 @@ mod tests {
         assert_eq!(info.message, Some("启动中".to_string()));
     }
 
+    #[test]
+    fn test_sidecar_status_event_payload() {
+        let state = SidecarState::Starting {
+            progress: StartProgress {
+                stage: StartStage::InstallingDependencies,
+                message: "安装 npm 依赖...".to_string(),
+                timestamp: Instant::now(),
+            },
+        };
+        let payload = serde_json::to_value(SidecarStatusInfo::from(&state)).unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "state": "starting",
+                "message": "安装 npm 依赖...",
+                "stage": "installing_dependencies",
+                "uptime": null,
+                "restart_count": null,
+                "pid": null,
+            })
+        );
+
+        let state = SidecarState::Failed {
+            error: "boom".to_string(),
+            last_attempt: Instant::now(),
+        };
+        let payload = serde_json::to_value(SidecarStatusInfo::from(&state)).unwrap();
+        assert_eq!(payload["state"], "failed");
+        assert_eq!(payload["message"], "boom");
+        assert!(payload["stage"].is_null());
+    }
+
+    /// 前 `failures` 次检查失败，之后恢复健康
+    struct FlakyProbe {
+        failures: u32,
+        checks: std::sync::atomic::AtomicU32,
+    }
+
+    #[async_trait]
+    impl HealthProbe for FlakyProbe {
+        async fn check_once(&self) -> bool {
+            let n = self.checks.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            n >= self.failures
+        }
+
+        fn interval(&self) -> Duration {
+            Duration::from_millis(1)
+        }
+
+        fn failure_threshold(&self) -> u32 {
+            2
+        }
+    }
+
+    /// 记录重启与失败；第 `fail_restarts` 次之前的重启都失败
+    #[derive(Default)]
+    struct RecordingTarget {
+        fail_restarts: u32,
+        restarts: std::sync::Mutex<Vec<u32>>,
+        failed: std::sync::Mutex<Option<String>>,
+    }
+
+    #[async_trait]
+    impl MonitorTarget for RecordingTarget {
+        async fn is_running(&self) -> bool {
+            self.failed.lock().unwrap().is_none()
+        }
+
+        async fn restart(&self, restart_count: u32) -> Result<(), SidecarError> {
+            self.restarts.lock().unwrap().push(restart_count);
+            if restart_count <= self.fail_restarts {
+                Err(SidecarError::ProcessSpawn("stub".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+
+        async fn mark_failed(&self, error: String) {
+            *self.failed.lock().unwrap() = Some(error);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_monitor_restarts_before_giving_up() {
+        // 一直失败：先重启 3 次，然后才标记 Failed
+        let probe = Arc::new(FlakyProbe {
+            failures: u32::MAX,
+            checks: Default::default(),
+        });
+        let target = Arc::new(RecordingTarget::default());
+
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            run_health_monitor(
+                probe,
+                target.clone(),
+                3,
+                Duration::from_millis(1),
+                Arc::new(Notify::new()),
+            ),
+        )
+        .await
+        .expect("monitor should give up");
+
+        assert_eq!(*target.restarts.lock().unwrap(), vec![1, 2, 3]);
+        let error = target.failed.lock().unwrap().clone().unwrap();
+        assert!(error.contains("Max restart count (3)"));
+    }
+
+    #[tokio::test]
+    async fn test_health_monitor_recovers_after_restart() {
+        // 第一次重启失败、第二次成功，之后健康检查恢复
+        let probe = Arc::new(FlakyProbe {
+            failures: 2,
+            checks: Default::default(),
+        });
+        let target = Arc::new(RecordingTarget {
+            fail_restarts: 1,
+            ..Default::default()
+        });
+        let stop = Arc::new(Notify::new());
+
+        let monitor = tokio::spawn(run_health_monitor(
+            probe.clone(),
+            target.clone(),
+            5,
+            Duration::from_millis(1),
+            stop.clone(),
+        ));
+
+        // 等待若干次健康检查后停止监控
+        while probe.checks.load(std::sync::atomic::Ordering::SeqCst) < 10 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        stop.notify_one();
+        tokio::time::timeout(Duration::from_secs(5), monitor)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(*target.restarts.lock().unwrap(), vec![1, 2]);
+        assert!(target.failed.lock().unwrap().is_none());
+    }
+
     #[test]
     fn test_sidecar_error_user_message() {
         let error = SidecarError::AlreadyRunning;
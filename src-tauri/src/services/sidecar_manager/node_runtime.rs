@@ -0,0 +1,196 @@
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use super::types::SidecarError;
+
+#[cfg(target_os = "windows")]
+const NPM_CMD: &str = "npm.cmd";
+#[cfg(not(target_os = "windows"))]
+const NPM_CMD: &str = "npm";
+
+#[cfg(target_os = "windows")]
+const NODE_BIN: &str = "node.exe";
+#[cfg(not(target_os = "windows"))]
+const NODE_BIN: &str = "node";
+
+/// 运行 npm 所用的 Node 运行时
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeRuntime {
+    /// 指定或内置的 node，通过其附带的 npm-cli.js 运行 npm
+    Managed { node: PathBuf, npm_cli: PathBuf },
+    /// PATH 上的 npm
+    System { npm: PathBuf },
+}
+
+impl NodeRuntime {
+    /// 按优先级解析运行时：显式配置的 `node_path` > 内置运行时目录 > PATH
+    pub fn resolve(
+        node_path: Option<&Path>,
+        bundled_dir: &Path,
+        path_var: Option<&OsStr>,
+    ) -> Result<Self, SidecarError> {
+        if let Some(node) = node_path {
+            // 显式配置的路径无效时直接报错，不悄悄回退
+            return Self::managed(node).ok_or_else(|| {
+                SidecarError::NodeNotFound(format!(
+                    "configured node_path {} is not a Node.js install with npm",
+                    node.display()
+                ))
+            });
+        }
+
+        if let Some(runtime) = bundled_node(bundled_dir).and_then(|node| Self::managed(&node)) {
+            return Ok(runtime);
+        }
+
+        path_var
+            .and_then(|paths| {
+                std::env::split_paths(paths)
+                    .map(|dir| dir.join(NPM_CMD))
+                    .find(|npm| npm.is_file())
+            })
+            .map(|npm| Self::System { npm })
+            .ok_or_else(|| {
+                SidecarError::NodeNotFound(format!(
+                    "no bundled runtime in {} and no {} on PATH",
+                    bundled_dir.display(),
+                    NPM_CMD
+                ))
+            })
+    }
+
+    /// 使用 node 可执行文件构造运行时，需能找到配套的 npm-cli.js
+    fn managed(node: &Path) -> Option<Self> {
+        if !node.is_file() {
+            return None;
+        }
+        npm_cli_for(node).map(|npm_cli| Self::Managed {
+            node: node.to_path_buf(),
+            npm_cli,
+        })
+    }
+
+    /// 构造 `npm <args>` 命令
+    pub fn npm_command(&self) -> Command {
+        match self {
+            Self::Managed { node, npm_cli } => {
+                let mut cmd = Command::new(node);
+                cmd.arg(npm_cli);
+                // 让 npm scripts 中的 `node` 也指向同一运行时
+                if let Some(path) = prepend_path(node.parent(), std::env::var_os("PATH")) {
+                    cmd.env("PATH", path);
+                }
+                cmd
+            }
+            Self::System { npm } => Command::new(npm),
+        }
+    }
+}
+
+impl std::fmt::Display for NodeRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Managed { node, .. } => write!(f, "managed node at {}", node.display()),
+            Self::System { npm } => write!(f, "system npm at {}", npm.display()),
+        }
+    }
+}
+
+/// 内置运行时中的 node 可执行文件（Windows 包在根目录，其他平台在 bin/ 下）
+fn bundled_node(dir: &Path) -> Option<PathBuf> {
+    [dir.join(NODE_BIN), dir.join("bin").join(NODE_BIN)]
+        .into_iter()
+        .find(|p| p.is_file())
+}
+
+/// 查找与 node 同一发行包中的 npm-cli.js
+fn npm_cli_for(node: &Path) -> Option<PathBuf> {
+    let node_dir = node.parent()?;
+    let candidates = [
+        // Windows 发行包布局
+        node_dir.join("node_modules/npm/bin/npm-cli.js"),
+        // Unix 发行包布局（node 位于 bin/ 下）
+        node_dir.join("../lib/node_modules/npm/bin/npm-cli.js"),
+    ];
+    candidates.into_iter().find(|p| p.is_file())
+}
+
+fn prepend_path(dir: Option<&Path>, current: Option<OsString>) -> Option<OsString> {
+    let dir = dir?;
+    let rest = current
+        .as_deref()
+        .map(|paths| std::env::split_paths(paths).collect::<Vec<_>>())
+        .unwrap_or_default();
+    std::env::join_paths(std::iter::once(dir.to_path_buf()).chain(rest)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 在 `root` 下按 Unix 布局创建一个假的 Node 发行包，返回 node 路径
+    fn fake_node_dist(root: &Path) -> PathBuf {
+        let bin = root.join("bin");
+        let npm_bin = root.join("lib/node_modules/npm/bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::create_dir_all(&npm_bin).unwrap();
+        std::fs::write(bin.join(NODE_BIN), "").unwrap();
+        std::fs::write(npm_bin.join("npm-cli.js"), "").unwrap();
+        bin.join(NODE_BIN)
+    }
+
+    #[test]
+    fn test_configured_node_path_is_preferred() {
+        let dir = tempfile::tempdir().unwrap();
+        let custom = fake_node_dist(&dir.path().join("custom"));
+        let bundled_dir = dir.path().join("bundled");
+        fake_node_dist(&bundled_dir);
+
+        let runtime = NodeRuntime::resolve(Some(&custom), &bundled_dir, None).unwrap();
+        assert!(matches!(runtime, NodeRuntime::Managed { ref node, .. } if *node == custom));
+
+        // 没有配置时使用内置运行时
+        let runtime = NodeRuntime::resolve(None, &bundled_dir, None).unwrap();
+        assert!(
+            matches!(runtime, NodeRuntime::Managed { ref node, .. } if node.starts_with(&bundled_dir))
+        );
+    }
+
+    #[test]
+    fn test_invalid_configured_node_path_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("nope/node");
+
+        assert!(matches!(
+            NodeRuntime::resolve(Some(&missing), dir.path(), None),
+            Err(SidecarError::NodeNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_falls_back_to_npm_on_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path_dir = dir.path().join("path-bin");
+        std::fs::create_dir_all(&path_dir).unwrap();
+        std::fs::write(path_dir.join(NPM_CMD), "").unwrap();
+        let path_var = std::env::join_paths([&path_dir]).unwrap();
+
+        let runtime =
+            NodeRuntime::resolve(None, &dir.path().join("bundled"), Some(&path_var)).unwrap();
+        assert_eq!(
+            runtime,
+            NodeRuntime::System {
+                npm: path_dir.join(NPM_CMD)
+            }
+        );
+
+        let empty = dir.path().join("empty");
+        std::fs::create_dir_all(&empty).unwrap();
+        let path_var = std::env::join_paths([&empty]).unwrap();
+        assert!(matches!(
+            NodeRuntime::resolve(None, &dir.path().join("bundled"), Some(&path_var)),
+            Err(SidecarError::NodeNotFound(_))
+        ));
+    }
+}
@@ -2,6 +2,68 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
+use super::health_checker::HealthCheckConfig;
+
+/// Sidecar 默认端口
+pub const DEFAULT_SIDECAR_PORT: u16 = 8857;
+
+/// 覆盖 Sidecar 端口的环境变量
+pub const SIDECAR_PORT_ENV: &str = "PUBCAST_SIDECAR_PORT";
+
+/// 读取配置的 Sidecar 端口（环境变量优先，否则使用默认端口）
+pub fn configured_port() -> Result<u16, SidecarError> {
+    parse_port(std::env::var(SIDECAR_PORT_ENV).ok().as_deref())
+}
+
+/// 解析端口配置，空值视为未设置
+pub(crate) fn parse_port(value: Option<&str>) -> Result<u16, SidecarError> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        None => Ok(DEFAULT_SIDECAR_PORT),
+        Some(v) => v
+            .parse::<u16>()
+            .ok()
+            .filter(|port| *port != 0)
+            .ok_or_else(|| SidecarError::InvalidPort(v.to_string())),
+    }
+}
+
+/// Sidecar HTTP 服务的基础 URL
+pub fn sidecar_base_url(port: u16) -> String {
+    format!("http://localhost:{}", port)
+}
+
+/// 指定 Node 可执行文件路径的环境变量
+pub const NODE_PATH_ENV: &str = "PUBCAST_NODE_PATH";
+
+/// 默认健康检查路径
+pub const DEFAULT_HEALTH_PATH: &str = "/health";
+
+/// 覆盖健康检查路径的环境变量（sidecar 挂在前缀下时使用）
+pub const SIDECAR_HEALTH_PATH_ENV: &str = "PUBCAST_SIDECAR_HEALTH_PATH";
+
+/// 设为 0/false/no/off 时健康检查只看状态码，不要求 `{ "status": "ok" }`
+pub const SIDECAR_HEALTH_REQUIRE_STATUS_OK_ENV: &str = "PUBCAST_SIDECAR_HEALTH_REQUIRE_STATUS_OK";
+
+/// 解析健康检查路径配置，空值视为未设置
+pub(crate) fn parse_health_path(value: Option<&str>) -> String {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .unwrap_or(DEFAULT_HEALTH_PATH)
+        .to_string()
+}
+
+/// 解析是否校验健康检查响应体，空值视为未设置（默认校验）
+pub(crate) fn parse_require_status_ok(value: Option<&str>) -> bool {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        None => true,
+        Some(v) => !matches!(
+            v.to_ascii_lowercase().as_str(),
+            "0" | "false" | "no" | "off"
+        ),
+    }
+}
+
 /// Sidecar 管理器的配置
 #[derive(Debug, Clone)]
 pub struct SidecarConfig {
@@ -11,10 +73,18 @@ pub struct SidecarConfig {
     pub sidecar_dir: PathBuf,
     /// 日志目录路径
     pub log_dir: PathBuf,
+    /// 指定的 Node 可执行文件（优先于内置运行时和 PATH）
+    pub node_path: Option<PathBuf>,
+    /// 内置 Node 运行时目录
+    pub bundled_node_dir: PathBuf,
     /// 健康检查间隔
     pub health_check_interval: Duration,
     /// 健康检查超时
     pub health_check_timeout: Duration,
+    /// 健康检查路径
+    pub health_check_path: String,
+    /// 健康检查是否要求响应体为 `{ "status": "ok" }`
+    pub health_check_require_status_ok: bool,
     /// 启动超时
     pub startup_timeout: Duration,
     /// 关闭超时
@@ -28,11 +98,15 @@ pub struct SidecarConfig {
 impl Default for SidecarConfig {
     fn default() -> Self {
         Self {
-            port: 8857,
+            port: DEFAULT_SIDECAR_PORT,
             sidecar_dir: PathBuf::new(),
             log_dir: PathBuf::new(),
+            node_path: None,
+            bundled_node_dir: PathBuf::new(),
             health_check_interval: Duration::from_secs(30),
             health_check_timeout: Duration::from_secs(5),
+            health_check_path: DEFAULT_HEALTH_PATH.to_string(),
+            health_check_require_status_ok: true,
             startup_timeout: Duration::from_secs(30),
             shutdown_timeout: Duration::from_secs(5),
             max_restart_count: 5,
@@ -41,6 +115,20 @@ impl Default for SidecarConfig {
     }
 }
 
+impl SidecarConfig {
+    /// 按本配置生成健康检查器的配置
+    pub fn health_check_config(&self) -> HealthCheckConfig {
+        HealthCheckConfig {
+            interval: self.health_check_interval,
+            timeout: self.health_check_timeout,
+            failure_threshold: 3,
+            success_threshold: 2,
+            path: self.health_check_path.clone(),
+            require_status_ok: self.health_check_require_status_ok,
+        }
+    }
+}
+
 /// Sidecar 进程状态
 #[derive(Debug, Clone)]
 pub enum SidecarState {
@@ -105,6 +193,8 @@ pub struct SidecarStatusInfo {
     pub state: String,
     /// 消息
     pub message: Option<String>,
+    /// 启动阶段（仅 starting 状态）
+    pub stage: Option<StartStage>,
     /// 运行时间（秒）
     pub uptime: Option<u64>,
     /// 重启次数
@@ -119,6 +209,7 @@ impl From<&SidecarState> for SidecarStatusInfo {
             SidecarState::Stopped => Self {
                 state: "stopped".to_string(),
                 message: None,
+                stage: None,
                 uptime: None,
                 restart_count: None,
                 pid: None,
@@ -126,6 +217,7 @@ impl From<&SidecarState> for SidecarStatusInfo {
             SidecarState::Starting { progress } => Self {
                 state: "starting".to_string(),
                 message: Some(progress.message.clone()),
+                stage: Some(progress.stage),
                 uptime: None,
                 restart_count: None,
                 pid: None,
@@ -137,6 +229,7 @@ impl From<&SidecarState> for SidecarStatusInfo {
             } => Self {
                 state: "running".to_string(),
                 message: None,
+                stage: None,
                 uptime: Some(started_at.elapsed().as_secs()),
                 restart_count: Some(*restart_count),
                 pid: Some(*pid),
@@ -144,6 +237,7 @@ impl From<&SidecarState> for SidecarStatusInfo {
             SidecarState::Stopping => Self {
                 state: "stopping".to_string(),
                 message: None,
+                stage: None,
                 uptime: None,
                 restart_count: None,
                 pid: None,
@@ -151,6 +245,7 @@ impl From<&SidecarState> for SidecarStatusInfo {
             SidecarState::Failed { error, .. } => Self {
                 state: "failed".to_string(),
                 message: Some(error.clone()),
+                stage: None,
                 uptime: None,
                 restart_count: None,
                 pid: None,
@@ -180,6 +275,15 @@ pub enum SidecarError {
     #[error("Sidecar is already running")]
     AlreadyRunning,
 
+    #[error("Port {0} is already in use")]
+    PortInUse(u16),
+
+    #[error("Invalid sidecar port: {0}")]
+    InvalidPort(String),
+
+    #[error("Node.js not found: {0}")]
+    NodeNotFound(String),
+
     #[error("Restart limit exceeded: {0}/{1}")]
     RestartLimitExceeded(u32, u32),
 
@@ -206,6 +310,17 @@ impl SidecarError {
             Self::HealthCheckFailed(msg) => format!("健康检查失败: {}", msg),
             Self::NotRunning => "Sidecar 未运行".to_string(),
             Self::AlreadyRunning => "Sidecar 已在运行".to_string(),
+            Self::PortInUse(port) => {
+                format!(
+                    "端口 {} 已被其他程序占用，请关闭占用该端口的程序，或通过环境变量 {} 指定其他端口",
+                    port, SIDECAR_PORT_ENV
+                )
+            }
+            Self::InvalidPort(value) => format!("无效的 Sidecar 端口: {}", value),
+            Self::NodeNotFound(detail) => format!(
+                "未找到 Node.js（{}），请安装 Node.js 或通过环境变量 {} 指定 node 路径",
+                detail, NODE_PATH_ENV
+            ),
             Self::RestartLimitExceeded(current, max) => {
                 format!("重启次数超限 ({}/{})", current, max)
             }
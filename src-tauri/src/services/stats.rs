@@ -1,10 +1,11 @@
 //! Statistics service
 //!
-//! Provides publishing statistics and reporting.
+//! Provides publishing statistics and reporting. Dry-run jobs are never counted.
 
 use sqlx::SqlitePool;
 
-use crate::error::Result;
+use crate::error::{PubCastError, Result};
+use crate::models::{classify_error, ErrorCategory, PublishJob};
 
 /// Statistics for a time period
 #[derive(Debug, serde::Serialize)]
@@ -34,6 +35,45 @@ pub struct DailyStats {
     pub failed_publishes: i64,
 }
 
+/// Account statistics
+#[derive(Debug, serde::Serialize)]
+pub struct AccountStats {
+    pub account_id: String,
+    pub account_name: Option<String>,
+    pub platform: String,
+    pub total_publishes: i64,
+    pub successful_publishes: i64,
+    pub failed_publishes: i64,
+}
+
+/// Which statistics to export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsKind {
+    Overall,
+    Platform,
+    Account,
+    Daily,
+}
+
+/// Failure count for one error category
+#[derive(Debug, serde::Serialize)]
+pub struct ErrorBreakdown {
+    pub category: ErrorCategory,
+    pub count: i64,
+    /// Share of all failed publishes, 0-100
+    pub percentage: f64,
+}
+
+/// Rows removed by [`StatsService::reset_stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct StatsReset {
+    pub publish_logs: u64,
+    pub daily_statistics: u64,
+    /// Finished publish jobs, only cleared when asked to
+    pub publish_jobs: u64,
+}
+
 /// Statistics service
 pub struct StatsService {
     pool: SqlitePool,
@@ -45,19 +85,44 @@ impl StatsService {
         Self { pool }
     }
 
-    /// Get overall statistics
-    pub async fn get_overall_stats(&self) -> Result<PublishStats> {
+    /// Get overall statistics, optionally limited to jobs completed within
+    /// `[from, to]` (Unix timestamps, inclusive)
+    pub async fn get_overall_stats(&self, from: Option<i64>, to: Option<i64>) -> Result<PublishStats> {
+        validate_range(from, to)?;
+
         let row = sqlx::query!(
             r#"
             SELECT 
                 COUNT(*) as total,
                 SUM(CASE WHEN status = 'success' THEN 1 ELSE 0 END) as success,
-                SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END) as failed,
-                AVG(CASE WHEN completed_at IS NOT NULL AND started_at IS NOT NULL 
-                    THEN (completed_at - started_at) * 1000 ELSE NULL END) as avg_duration
+                SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END) as failed
             FROM publish_jobs
-            WHERE status IN ('success', 'failed')
-            "#
+            WHERE status IN ('success', 'failed') AND dry_run = 0
+              AND (? IS NULL OR completed_at >= ?)
+              AND (? IS NULL OR completed_at <= ?)
+            "#,
+            from,
+            from,
+            to,
+            to
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        // Job timestamps are whole seconds; the attempt logs time each
+        // publish in milliseconds
+        let avg_duration = sqlx::query_scalar!(
+            r#"
+            SELECT AVG(duration_ms) AS "avg_duration: f64"
+            FROM publish_logs
+            WHERE duration_ms IS NOT NULL
+              AND (? IS NULL OR created_at >= ?)
+              AND (? IS NULL OR created_at <= ?)
+            "#,
+            from,
+            from,
+            to,
+            to
         )
         .fetch_one(&self.pool)
         .await?;
@@ -76,12 +141,19 @@ impl StatsService {
             successful_publishes: success,
             failed_publishes: failed,
             success_rate,
-            avg_duration_ms: row.avg_duration.map(|d| d as i64),
+            avg_duration_ms: avg_duration.map(|d| d.round() as i64),
         })
     }
 
-    /// Get statistics by platform
-    pub async fn get_platform_stats(&self) -> Result<Vec<PlatformStats>> {
+    /// Get statistics by platform, optionally limited to jobs completed
+    /// within `[from, to]` (Unix timestamps, inclusive)
+    pub async fn get_platform_stats(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Vec<PlatformStats>> {
+        validate_range(from, to)?;
+
         let rows = sqlx::query!(
             r#"
             SELECT 
@@ -90,10 +162,16 @@ impl StatsService {
                 SUM(CASE WHEN status = 'success' THEN 1 ELSE 0 END) as success,
                 SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END) as failed
             FROM publish_jobs
-            WHERE status IN ('success', 'failed')
+            WHERE status IN ('success', 'failed') AND dry_run = 0
+              AND (? IS NULL OR completed_at >= ?)
+              AND (? IS NULL OR completed_at <= ?)
             GROUP BY platform
             ORDER BY total DESC
-            "#
+            "#,
+            from,
+            from,
+            to,
+            to
         )
         .fetch_all(&self.pool)
         .await?;
@@ -122,7 +200,7 @@ impl StatsService {
                 SUM(CASE WHEN status = 'success' THEN 1 ELSE 0 END) as success,
                 SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END) as failed
             FROM publish_jobs
-            WHERE status IN ('success', 'failed')
+            WHERE status IN ('success', 'failed') AND dry_run = 0
               AND created_at >= strftime('%s', 'now', ? || ' days')
             GROUP BY stat_date
             ORDER BY stat_date DESC
@@ -147,31 +225,260 @@ impl StatsService {
         Ok(stats)
     }
 
-    /// Record a publish log entry
+    /// Get statistics by account, optionally limited to jobs completed
+    /// within `[from, to]` (Unix timestamps, inclusive)
+    pub async fn get_account_stats(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Vec<AccountStats>> {
+        validate_range(from, to)?;
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT 
+                j.account_id,
+                a.name as "account_name?",
+                j.platform,
+                COUNT(*) as total,
+                SUM(CASE WHEN j.status = 'success' THEN 1 ELSE 0 END) as "success!: i64",
+                SUM(CASE WHEN j.status = 'failed' THEN 1 ELSE 0 END) as "failed!: i64"
+            FROM publish_jobs j
+            LEFT JOIN accounts a ON a.id = j.account_id
+            WHERE j.status IN ('success', 'failed') AND j.dry_run = 0
+              AND (? IS NULL OR j.completed_at >= ?)
+              AND (? IS NULL OR j.completed_at <= ?)
+            GROUP BY j.account_id, j.platform
+            ORDER BY total DESC
+            "#,
+            from,
+            from,
+            to,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let stats = rows
+            .into_iter()
+            .map(|row| AccountStats {
+                account_id: row.account_id,
+                account_name: row.account_name,
+                platform: row.platform,
+                total_publishes: row.total,
+                successful_publishes: row.success,
+                failed_publishes: row.failed,
+            })
+            .collect();
+
+        Ok(stats)
+    }
+
+    /// Get per-day statistics for jobs completed within `[from, to]`
+    async fn get_daily_stats_between(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Vec<DailyStats>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT 
+                date(datetime(completed_at, 'unixepoch')) as "stat_date!: String",
+                COUNT(*) as total,
+                SUM(CASE WHEN status = 'success' THEN 1 ELSE 0 END) as "success!: i64",
+                SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END) as "failed!: i64"
+            FROM publish_jobs
+            WHERE status IN ('success', 'failed') AND dry_run = 0
+              AND completed_at IS NOT NULL
+              AND (? IS NULL OR completed_at >= ?)
+              AND (? IS NULL OR completed_at <= ?)
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+            from,
+            from,
+            to,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let stats = rows
+            .into_iter()
+            .map(|row| DailyStats {
+                date: row.stat_date,
+                total_publishes: row.total,
+                successful_publishes: row.success,
+                failed_publishes: row.failed,
+            })
+            .collect();
+
+        Ok(stats)
+    }
+
+    /// Export statistics as CSV text with a header row
+    pub async fn export_csv(
+        &self,
+        kind: StatsKind,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<String> {
+        validate_range(from, to)?;
+
+        match kind {
+            StatsKind::Overall => to_csv(
+                &[
+                    "total_publishes",
+                    "successful_publishes",
+                    "failed_publishes",
+                    "success_rate",
+                    "avg_duration_ms",
+                ],
+                &[self.get_overall_stats(from, to).await?],
+            ),
+            StatsKind::Platform => to_csv(
+                &["platform", "total_publishes", "successful_publishes", "failed_publishes"],
+                &self.get_platform_stats(from, to).await?,
+            ),
+            StatsKind::Account => to_csv(
+                &[
+                    "account_id",
+                    "account_name",
+                    "platform",
+                    "total_publishes",
+                    "successful_publishes",
+                    "failed_publishes",
+                ],
+                &self.get_account_stats(from, to).await?,
+            ),
+            StatsKind::Daily => to_csv(
+                &["date", "total_publishes", "successful_publishes", "failed_publishes"],
+                &self.get_daily_stats_between(from, to).await?,
+            ),
+        }
+    }
+
+    /// Get failed publish counts per error category, largest first
+    pub async fn get_error_breakdown(&self) -> Result<Vec<ErrorBreakdown>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                COALESCE(error_category, 'unknown') as "category!: String",
+                COUNT(*) as count
+            FROM publish_jobs
+            WHERE status = 'failed' AND dry_run = 0
+            GROUP BY 1
+            ORDER BY count DESC
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let total: i64 = rows.iter().map(|row| row.count).sum();
+        let breakdown = rows
+            .into_iter()
+            .map(|row| ErrorBreakdown {
+                category: row.category.parse().unwrap_or(ErrorCategory::Unknown),
+                count: row.count,
+                percentage: (row.count as f64 / total as f64) * 100.0,
+            })
+            .collect();
+
+        Ok(breakdown)
+    }
+
+    /// Clear the publish history behind the statistics.
+    ///
+    /// Publish logs and daily totals are always cleared. Most statistics are
+    /// computed from publish jobs, so `include_jobs` also deletes successful,
+    /// failed and cancelled jobs and takes them out of their tasks' job
+    /// counters; queued and running ones are kept. Accounts, proxies,
+    /// contents and tasks are left alone.
+    pub async fn reset_stats(&self, include_jobs: bool) -> Result<StatsReset> {
+        let mut tx = self.pool.begin().await?;
+        let mut reset = StatsReset {
+            publish_logs: sqlx::query!("DELETE FROM publish_logs")
+                .execute(&mut *tx)
+                .await?
+                .rows_affected(),
+            daily_statistics: sqlx::query!("DELETE FROM daily_statistics")
+                .execute(&mut *tx)
+                .await?
+                .rows_affected(),
+            publish_jobs: 0,
+        };
+        if include_jobs {
+            // Take the deleted jobs out of their tasks' counters so tasks
+            // with jobs still queued can complete
+            sqlx::query!(
+                r#"
+                UPDATE distribution_tasks
+                SET total_jobs = MAX(total_jobs - (
+                        SELECT COUNT(*) FROM publish_jobs
+                        WHERE distribution_task_id = distribution_tasks.id
+                          AND status IN ('success', 'failed', 'cancelled')
+                    ), 0),
+                    completed_jobs = 0,
+                    failed_jobs = 0
+                WHERE EXISTS (
+                    SELECT 1 FROM publish_jobs
+                    WHERE distribution_task_id = distribution_tasks.id
+                      AND status IN ('success', 'failed', 'cancelled')
+                )
+                "#
+            )
+            .execute(&mut *tx)
+            .await?;
+            reset.publish_jobs = sqlx::query!(
+                "DELETE FROM publish_jobs WHERE status IN ('success', 'failed', 'cancelled')"
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+        tx.commit().await?;
+
+        tracing::info!(
+            "Reset statistics: {} logs, {} daily rows, {} jobs",
+            reset.publish_logs,
+            reset.daily_statistics,
+            reset.publish_jobs
+        );
+        Ok(reset)
+    }
+
+    /// Record one publish attempt of `job` and add it to the daily statistics
     pub async fn record_publish_log(
         &self,
-        job_id: &str,
-        account_id: &str,
-        platform: &str,
+        job: &PublishJob,
         status: &str,
         duration_ms: Option<i64>,
+        error_code: Option<&str>,
         error_message: Option<&str>,
     ) -> Result<()> {
         let now = chrono::Utc::now().timestamp();
         let id = uuid::Uuid::new_v4().to_string();
+        let error_category = (status == "failed")
+            .then(|| classify_error(error_code, error_message).to_string());
 
         sqlx::query!(
             r#"
-            INSERT INTO publish_logs (id, publish_job_id, account_id, platform, status, duration_ms, error_message, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO publish_logs (
+                id, publish_job_id, account_id, platform, content_id, status, duration_ms,
+                error_code, error_message, error_category, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             id,
-            job_id,
-            account_id,
-            platform,
+            job.id,
+            job.account_id,
+            job.platform,
+            job.content_id,
             status,
             duration_ms,
+            error_code,
             error_message,
+            error_category,
             now
         )
         .execute(&self.pool)
@@ -192,8 +499,8 @@ impl StatsService {
                 failed_publishes = failed_publishes + excluded.failed_publishes
             "#,
             today,
-            platform,
-            account_id,
+            job.platform,
+            job.account_id,
             success_count,
             failed_count
         )
@@ -203,3 +510,320 @@ impl StatsService {
         Ok(())
     }
 }
+
+/// Write `rows` as CSV; the header is written explicitly so empty exports
+/// still have one
+fn to_csv<T: serde::Serialize>(headers: &[&str], rows: &[T]) -> Result<String> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    writer.write_record(headers)?;
+    for row in rows {
+        writer.serialize(row)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| csv::Error::from(e.into_error()))?;
+    // The writer only ever receives valid UTF-8
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Reject ranges whose start is after their end
+fn validate_range(from: Option<i64>, to: Option<i64>) -> Result<()> {
+    match (from, to) {
+        (Some(from), Some(to)) if from > to => Err(PubCastError::Validation(format!(
+            "Invalid date range: from ({}) is after to ({})",
+            from, to
+        ))),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::init_test_database;
+    use crate::services::scheduler::SchedulerService;
+
+    const DAY: i64 = 86_400;
+
+    /// Seed the content, account and task that publish jobs hang off
+    async fn seed_task(pool: &SqlitePool) {
+        sqlx::query("INSERT INTO contents (id, title) VALUES ('c1', 'Hello')")
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO accounts (id, platform, name, status) VALUES ('a1', 'wechat', 'a1', 'active')")
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO distribution_tasks (id, content_id, target_accounts) VALUES ('t1', 'c1', '[]')")
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    async fn insert_completed_job(pool: &SqlitePool, id: &str, platform: &str, status: &str, completed_at: i64) {
+        sqlx::query(
+            "INSERT INTO publish_jobs (id, distribution_task_id, content_id, account_id, platform, status, started_at, completed_at)
+             VALUES (?, 't1', 'c1', 'a1', ?, ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(platform)
+        .bind(status)
+        .bind(completed_at - 1)
+        .bind(completed_at)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stats_date_range_excludes_jobs_outside_window() {
+        let (_dir, pool) = init_test_database().await;
+        seed_task(&pool).await;
+
+        let base = 1_700_000_000;
+        insert_completed_job(&pool, "j1", "wechat", "success", base).await;
+        insert_completed_job(&pool, "j2", "wechat", "failed", base + DAY).await;
+        insert_completed_job(&pool, "j3", "zhihu", "success", base + 2 * DAY).await;
+        insert_completed_job(&pool, "j4", "zhihu", "success", base + 5 * DAY).await;
+
+        let service = StatsService::new(pool);
+
+        let all_time = service.get_overall_stats(None, None).await.unwrap();
+        assert_eq!(all_time.total_publishes, 4);
+
+        let window = service
+            .get_overall_stats(Some(base + DAY), Some(base + 2 * DAY))
+            .await
+            .unwrap();
+        assert_eq!(window.total_publishes, 2);
+        assert_eq!(window.successful_publishes, 1);
+        assert_eq!(window.failed_publishes, 1);
+
+        let since = service.get_overall_stats(Some(base + 2 * DAY), None).await.unwrap();
+        assert_eq!(since.total_publishes, 2);
+
+        let platforms = service.get_platform_stats(None, Some(base + DAY)).await.unwrap();
+        assert_eq!(platforms.len(), 1);
+        assert_eq!(platforms[0].platform, "wechat");
+        assert_eq!(platforms[0].total_publishes, 2);
+    }
+
+    fn parse_csv(text: &str) -> Vec<csv::StringRecord> {
+        csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(text.as_bytes())
+            .records()
+            .map(|r| r.unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_export_csv_quotes_names_and_keeps_field_counts() {
+        let (_dir, pool) = init_test_database().await;
+        seed_task(&pool).await;
+        sqlx::query("UPDATE accounts SET name = 'Acme, \"Inc\"' WHERE id = 'a1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let base = 1_700_000_000;
+        insert_completed_job(&pool, "j1", "wechat", "success", base).await;
+        insert_completed_job(&pool, "j2", "wechat", "failed", base + DAY).await;
+        insert_completed_job(&pool, "j3", "zhihu, cn", "success", base + DAY).await;
+
+        let service = StatsService::new(pool);
+        let expected = [
+            (StatsKind::Overall, 5, 2),
+            (StatsKind::Platform, 4, 3),
+            (StatsKind::Account, 6, 3),
+            (StatsKind::Daily, 4, 3),
+        ];
+        for (kind, fields, records) in expected {
+            let text = service.export_csv(kind, None, None).await.unwrap();
+            let rows = parse_csv(&text);
+            assert_eq!(rows.len(), records, "{:?}", kind);
+            assert!(rows.iter().all(|r| r.len() == fields), "{:?}: {}", kind, text);
+        }
+
+        let accounts = parse_csv(&service.export_csv(StatsKind::Account, None, None).await.unwrap());
+        assert_eq!(&accounts[0][0], "account_id");
+        assert_eq!(&accounts[1][1], "Acme, \"Inc\"");
+
+        let platforms = parse_csv(&service.export_csv(StatsKind::Platform, None, None).await.unwrap());
+        assert!(platforms.iter().any(|r| &r[0] == "zhihu, cn"));
+
+        // A range with no jobs still yields the header row
+        let empty = service.export_csv(StatsKind::Daily, Some(0), Some(1)).await.unwrap();
+        assert_eq!(parse_csv(&empty).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stats_rejects_inverted_range() {
+        let (_dir, pool) = init_test_database().await;
+        let service = StatsService::new(pool);
+
+        assert!(matches!(
+            service.get_overall_stats(Some(200), Some(100)).await,
+            Err(PubCastError::Validation(_))
+        ));
+        assert!(matches!(
+            service.get_platform_stats(Some(200), Some(100)).await,
+            Err(PubCastError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_error_breakdown_groups_failed_jobs_by_category() {
+        let (_dir, pool) = init_test_database().await;
+        seed_task(&pool).await;
+
+        let failures = [
+            ("j1", "Too many requests"),
+            ("j2", "rate limit exceeded"),
+            ("j3", "rate limited by platform"),
+            ("j4", "login expired"),
+            ("j5", "???"),
+        ];
+        let scheduler = SchedulerService::new(pool.clone());
+        for (id, message) in failures {
+            sqlx::query(
+                "INSERT INTO publish_jobs (id, distribution_task_id, content_id, account_id, platform, max_retries)
+                 VALUES (?, 't1', 'c1', 'a1', 'wechat', 0)",
+            )
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+            scheduler
+                .mark_job_failed(id, None, Some(message.to_string()))
+                .await
+                .unwrap();
+        }
+
+        let breakdown = StatsService::new(pool).get_error_breakdown().await.unwrap();
+
+        assert_eq!(breakdown.len(), 3);
+        assert_eq!(breakdown[0].category, ErrorCategory::RateLimit);
+        assert_eq!(breakdown[0].count, 3);
+        assert!((breakdown[0].percentage - 60.0).abs() < f64::EPSILON);
+        let total: i64 = breakdown.iter().map(|b| b.count).sum();
+        assert_eq!(total, 5);
+    }
+
+    #[tokio::test]
+    async fn test_reset_stats_clears_history_only() {
+        let (_dir, pool) = init_test_database().await;
+        seed_task(&pool).await;
+
+        let base = chrono::Utc::now().timestamp();
+        insert_completed_job(&pool, "j1", "wechat", "success", base).await;
+        insert_completed_job(&pool, "j2", "wechat", "failed", base).await;
+        sqlx::query(
+            "INSERT INTO publish_jobs (id, distribution_task_id, content_id, account_id, platform)
+             VALUES ('queued', 't1', 'c1', 'a1', 'wechat')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let service = StatsService::new(pool.clone());
+        let scheduler = SchedulerService::new(pool.clone());
+        for (id, status) in [("j1", "success"), ("j2", "failed")] {
+            let job = scheduler.get_publish_job(id).await.unwrap();
+            service
+                .record_publish_log(&job, status, Some(1000), None, None)
+                .await
+                .unwrap();
+        }
+        sqlx::query(
+            "UPDATE distribution_tasks SET total_jobs = 3, completed_jobs = 1, failed_jobs = 1 WHERE id = 't1'",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let before = service.get_overall_stats(None, None).await.unwrap();
+        assert_eq!(before.total_publishes, 2);
+
+        let reset = service.reset_stats(true).await.unwrap();
+        assert_eq!(
+            reset,
+            StatsReset {
+                publish_logs: 2,
+                daily_statistics: 1,
+                publish_jobs: 2,
+            }
+        );
+
+        let overall = service.get_overall_stats(None, None).await.unwrap();
+        assert_eq!(overall.total_publishes, 0);
+        assert_eq!(overall.success_rate, 0.0);
+        let platforms = service.get_platform_stats(None, None).await.unwrap();
+        assert!(platforms.is_empty());
+        assert!(service.get_daily_stats(30).await.unwrap().is_empty());
+        assert!(service.get_error_breakdown().await.unwrap().is_empty());
+
+        // The queued job and everything it hangs off are kept
+        let remaining: Vec<String> = sqlx::query_scalar(
+            "SELECT id FROM publish_jobs
+             UNION ALL SELECT id FROM distribution_tasks
+             UNION ALL SELECT id FROM accounts
+             UNION ALL SELECT id FROM contents",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(remaining, ["queued", "t1", "a1", "c1"]);
+
+        // Only the queued job is left on the task
+        let task = scheduler.get_distribution_task("t1").await.unwrap();
+        assert_eq!(task.total_jobs, 1);
+        assert_eq!(task.completed_jobs, 0);
+        assert_eq!(task.failed_jobs, 0);
+    }
+
+    #[tokio::test]
+    async fn test_average_duration_comes_from_attempt_logs() {
+        let (_dir, pool) = init_test_database().await;
+        seed_task(&pool).await;
+
+        let base = 1_700_000_000;
+        // Both jobs took under a second by their whole-second timestamps
+        insert_completed_job(&pool, "j1", "wechat", "success", base).await;
+        insert_completed_job(&pool, "j2", "wechat", "failed", base).await;
+        for (id, job, duration_ms, created_at) in [
+            ("l1", "j1", 250, base),
+            ("l2", "j2", 750, base),
+            ("l3", "j2", 9000, base - 10 * DAY),
+        ] {
+            sqlx::query(
+                "INSERT INTO publish_logs (id, publish_job_id, account_id, platform, content_id, status, duration_ms, created_at)
+                 VALUES (?, ?, 'a1', 'wechat', 'c1', 'success', ?, ?)",
+            )
+            .bind(id)
+            .bind(job)
+            .bind(duration_ms)
+            .bind(created_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let service = StatsService::new(pool);
+        let window = service
+            .get_overall_stats(Some(base - DAY), Some(base + DAY))
+            .await
+            .unwrap();
+        assert_eq!(window.avg_duration_ms, Some(500));
+        let all_time = service.get_overall_stats(None, None).await.unwrap();
+        assert_eq!(all_time.avg_duration_ms, Some(3333));
+        let empty = service
+            .get_overall_stats(Some(base + DAY), None)
+            .await
+            .unwrap();
+        assert_eq!(empty.avg_duration_ms, None);
+    }
+}
@@ -33,8 +33,8 @@ export function AICheck() {
 
   async function loadLogs() {
     try {
-      const data = await listAILogs(100);
-      setLogs(data);
+      const data = await listAILogs({ limit: 100 });
+      setLogs(data.logs);
     } catch (e) {
       console.error("Failed to load logs:", e);
     }
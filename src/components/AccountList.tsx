@@ -43,6 +43,7 @@ const PLATFORMS: Platform[] = [
   { id: "zhihu", name: "知乎", color: "bg-blue-400", loginUrl: "https://www.zhihu.com/signin", homeUrl: "https://www.zhihu.com/" },
   { id: "wechat", name: "微信公众号", color: "bg-green-600", loginUrl: "https://mp.weixin.qq.com/", homeUrl: "https://mp.weixin.qq.com/cgi-bin/home" },
   { id: "xiaohongshu", name: "小红书", color: "bg-red-500", loginUrl: "https://creator.xiaohongshu.com/", homeUrl: "https://creator.xiaohongshu.com/creator/home" },
+  { id: "weibo", name: "微博", color: "bg-orange-500", loginUrl: "https://weibo.com/login.php", homeUrl: "https://weibo.com/" },
   { id: "douyin", name: "抖音", color: "bg-black", loginUrl: "https://creator.douyin.com/", homeUrl: "https://creator.douyin.com/creator-micro/home" },
   { id: "bilibili", name: "B站", color: "bg-pink-400", loginUrl: "https://member.bilibili.com/", homeUrl: "https://member.bilibili.com/platform/home" },
   { id: "csdn", name: "CSDN", color: "bg-red-600", loginUrl: "https://mp.csdn.net/", homeUrl: "https://mp.csdn.net/mp_blog/manage/article" },
@@ -18,7 +18,7 @@ export function ContentList() {
     try {
       setLoading(true);
       const data = await listContents();
-      setContents(data);
+      setContents(data.contents);
       setError(null);
     } catch (e) {
       setError(e instanceof Error ? e.message : String(e));
@@ -25,6 +25,7 @@ const platformNames: Record<string, string> = {
   douyin: "抖音",
   bilibili: "B站",
   zhihu: "知乎",
+  toutiao: "今日头条",
 };
 
 export function StatsPanel() {
@@ -18,6 +18,7 @@ export function StatusBadge({ status, showDot = true }: StatusBadgeProps) {
     pending: "等待中",
     running: "运行中",
     in_progress: "进行中",
+    paused: "已暂停",
     success: "成功",
     failed: "失败",
     cancelled: "已取消",
@@ -49,7 +49,7 @@ export function TaskList() {
     try {
       const [accounts, contents, overallStats] = await Promise.all([
         listAccounts(),
-        listContents(),
+        listContents({ per_page: 1 }),
         getOverallStats().catch(() => ({ total_publishes: 0, successful_publishes: 0 }))
       ]);
 
@@ -57,7 +57,7 @@ export function TaskList() {
         authorizedAccounts: accounts.length,
         todayPublished: 0, // TODO: Need backend API for daily stats
         totalPublished: overallStats.total_publishes || 0,
-        totalArticles: contents.length,
+        totalArticles: contents.total,
       });
       
       setLastUpdateTime(new Date().toLocaleString());
@@ -3,18 +3,45 @@
  */
 
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type {
   Account,
+  AccountImportRecord,
+  AccountStatus,
+  ImportResult,
+  ImportSummary,
   Proxy,
+  ProxyHealthRecord,
   ProxyHealthResult,
+  ProxyUsageRecord,
   AIConfig,
-  AICheckLog,
+  AICheckLogList,
+  AICheckLogQuery,
+  ConflictResolution,
   Content,
+  DuplicateContentGroup,
+  ConnectionInfo,
+  ContentApiSettings,
+  ContentListQuery,
+  ContentListResponse,
+  CreateContentRequest,
+  UpdateContentRequest,
   DistributionTask,
+  DistributionTaskListQuery,
+  DistributionTaskListResponse,
   CreateDistributionTaskRequest,
+  CreateDistributionTaskResponse,
+  PublishJob,
   PublishStats,
   PlatformStats,
+  ErrorBreakdown,
+  StatsKind,
   Statistics,
+  SidecarStatusInfo,
+  SystemHealth,
+  TaskProgress,
+  VacuumReport,
+  DbVersion,
 } from "./types";
 
 export type { Account };
@@ -39,6 +66,18 @@ export async function addProxy(
   return invoke("add_proxy", { protocol, host, port, username, password });
 }
 
+/** Pass an empty `username` to clear it */
+export async function updateProxy(
+  id: string,
+  protocol?: string,
+  host?: string,
+  port?: number,
+  username?: string,
+  password?: string
+): Promise<Proxy> {
+  return invoke("update_proxy", { id, protocol, host, port, username, password });
+}
+
 export async function deleteProxy(id: string): Promise<void> {
   return invoke("delete_proxy", { id });
 }
@@ -47,7 +86,45 @@ export async function checkProxy(id: string): Promise<ProxyHealthResult> {
   return invoke("check_proxy", { id });
 }
 
-export async function importProxies(text: string): Promise<Proxy[]> {
+export async function getProxyHistory(
+  id: string,
+  limit?: number
+): Promise<ProxyHealthRecord[]> {
+  return invoke("get_proxy_history", { id, limit });
+}
+
+export async function getProxyUsage(
+  id: string,
+  limit?: number
+): Promise<ProxyUsageRecord[]> {
+  return invoke("get_proxy_usage", { id, limit });
+}
+
+export async function checkAllProxies(concurrency?: number): Promise<ProxyHealthResult[]> {
+  return invoke("check_all_proxies", { concurrency });
+}
+
+export interface ProxyHealthCheckStatus {
+  running: boolean;
+  interval_minutes: number;
+  /** When every proxy was last checked, by the background task or by hand */
+  last_full_check_at: number | null;
+}
+
+/** Check every proxy periodically; stays on across restarts until stopped */
+export async function startProxyHealthChecks(intervalMinutes?: number): Promise<ProxyHealthCheckStatus> {
+  return invoke("start_proxy_health_checks", { intervalMinutes });
+}
+
+export async function stopProxyHealthChecks(): Promise<ProxyHealthCheckStatus> {
+  return invoke("stop_proxy_health_checks");
+}
+
+export async function getProxyHealthCheckStatus(): Promise<ProxyHealthCheckStatus> {
+  return invoke("get_proxy_health_check_status");
+}
+
+export async function importProxies(text: string): Promise<ImportResult> {
   return invoke("import_proxies", { text });
 }
 
@@ -70,22 +147,57 @@ export async function getAccount(id: string): Promise<Account> {
 export async function addAccount(
   platform: string,
   name: string,
-  username?: string
+  username?: string,
+  timezone?: string
 ): Promise<Account> {
-  return invoke("add_account", { platform, name, username });
+  return invoke("add_account", { platform, name, username, timezone });
 }
 
+/** Create accounts from records parsed out of a CSV or JSON file */
+export async function importAccounts(records: AccountImportRecord[]): Promise<ImportSummary> {
+  return invoke("import_accounts", { records });
+}
+
+/** Account list without credentials or cookies, as JSON ready to save */
+export async function exportAccounts(): Promise<string> {
+  return invoke("export_accounts");
+}
+
+/** Recreate accounts from `exportAccounts` JSON; each needs to log in again */
+export async function importAccountsMetadata(json: string): Promise<ImportSummary> {
+  return invoke("import_accounts_metadata", { json });
+}
+
+/** Pass an empty `timezone` to clear it */
 export async function updateAccount(
   id: string,
   name?: string,
   username?: string,
-  status?: string
+  status?: string,
+  timezone?: string
 ): Promise<Account> {
-  return invoke("update_account", { id, name, username, status });
+  return invoke("update_account", { id, name, username, status, timezone });
+}
+
+/** Moves the account to trash unless `hard` is set */
+export async function deleteAccount(id: string, hard?: boolean): Promise<void> {
+  return invoke("delete_account", { id, hard });
+}
+
+export async function listTrashedAccounts(): Promise<Account[]> {
+  return invoke("list_trashed_accounts");
 }
 
-export async function deleteAccount(id: string): Promise<void> {
-  return invoke("delete_account", { id });
+export async function trashAccount(id: string): Promise<void> {
+  return invoke("trash_account", { id });
+}
+
+export async function restoreAccount(id: string): Promise<Account> {
+  return invoke("restore_account", { id });
+}
+
+export async function purgeAccount(id: string): Promise<void> {
+  return invoke("purge_account", { id });
 }
 
 export async function updateAccountStatus(
@@ -100,28 +212,147 @@ export async function updateAccountStatus(
   });
 }
 
+export async function checkAccountAuth(accountId: string): Promise<boolean> {
+  return invoke("check_account_auth", { accountId });
+}
+
+export interface AccountRefreshSummary {
+  total: number;
+  active: number;
+  expired: number;
+  error: number;
+}
+
+export interface AccountRefreshProgress {
+  account_id: string;
+  status: AccountStatus;
+  completed: number;
+  total: number;
+}
+
+export async function refreshAllAccounts(concurrency?: number): Promise<AccountRefreshSummary> {
+  return invoke("refresh_all_accounts", { concurrency });
+}
+
+/** Fired after each account is checked by `refreshAllAccounts` */
+export function onAccountRefreshProgress(handler: (progress: AccountRefreshProgress) => void): Promise<UnlistenFn> {
+  return listen<AccountRefreshProgress>("accounts://refresh-progress", (event) => handler(event.payload));
+}
+
 // ============ Content Commands ============
 
-export async function listContents(): Promise<Content[]> {
-  return invoke("list_contents");
+export async function listContents(query: ContentListQuery = {}): Promise<ContentListResponse> {
+  return invoke("list_contents", {
+    page: query.page,
+    perPage: query.per_page,
+    status: query.status,
+    search: query.search,
+  });
 }
 
 export async function getContent(id: string): Promise<Content> {
   return invoke("get_content", { id });
 }
 
+/** Content adapted for a platform, as returned by its adapter */
+export interface PreparedContent {
+  title: string;
+  body: string;
+  cover_image: string | null;
+  tags: string[];
+  extra: unknown;
+}
+
+/** Render a content the way a platform would publish it, without creating a task */
+export async function previewContent(contentId: string, platform: string): Promise<PreparedContent> {
+  return invoke("preview_content", { contentId, platform });
+}
+
+export async function createContent(req: CreateContentRequest): Promise<Content> {
+  return invoke("create_content", { req });
+}
+
+export async function updateContent(id: string, req: UpdateContentRequest): Promise<Content> {
+  return invoke("update_content", { id, req });
+}
+
+export async function deleteContent(id: string, hard?: boolean): Promise<void> {
+  return invoke("delete_content", { id, hard });
+}
+
+/** Every tag in use as `[tag, contentCount]`, most used first */
+export async function listContentTags(): Promise<[string, number][]> {
+  return invoke("list_content_tags");
+}
+
+/** Rename a tag across all contents, resolving to how many changed */
+export async function renameContentTag(from: string, to: string): Promise<number> {
+  return invoke("rename_content_tag", { from, to });
+}
+
+/** Merge tags into one across all contents, resolving to how many changed */
+export async function mergeContentTags(from: string[], into: string): Promise<number> {
+  return invoke("merge_content_tags", { from, into });
+}
+
 export interface SyncResult {
   synced: number;
   failed: number;
+  conflicts: number;
+  /** Synced contents that look like duplicates of another content */
+  duplicates: number;
+  /** Proxy the CMS requests went through, if any */
+  proxy_id: string | null;
+  /** The proxy failed and the sync went direct instead */
+  fell_back_to_direct: boolean;
 }
 
 export async function syncContents(): Promise<SyncResult> {
   return invoke("sync_contents");
 }
 
+/** Pull one content from the CMS by its remote ID without a full sync */
+export async function syncContent(remoteId: string): Promise<Content> {
+  return invoke("sync_content", { remoteId });
+}
+
+export async function getContentApiConfig(): Promise<ContentApiSettings> {
+  return invoke("get_content_api_config");
+}
+
+/** Omit `apiKey` to keep the stored key; pass "" to remove it */
+export async function setContentApiConfig(
+  baseUrl: string,
+  apiKey?: string
+): Promise<ContentApiSettings> {
+  return invoke("set_content_api_config", { baseUrl, apiKey });
+}
+
+/** Check the CMS API address and key before syncing */
+export async function testCmsConnection(): Promise<ConnectionInfo> {
+  return invoke("test_cms_connection");
+}
+
+export async function getContentConflicts(): Promise<Content[]> {
+  return invoke("get_content_conflicts");
+}
+
+export async function resolveContentConflict(
+  id: string,
+  keep: ConflictResolution
+): Promise<Content> {
+  return invoke("resolve_content_conflict", { id, keep });
+}
+
+export async function findDuplicateContents(): Promise<DuplicateContentGroup[]> {
+  return invoke("find_duplicate_contents");
+}
+
 // ============ Scheduler Commands ============
 
-export async function createDistributionTask(req: CreateDistributionTaskRequest): Promise<DistributionTask> {
+export async function createDistributionTask(
+  req: CreateDistributionTaskRequest
+): Promise<CreateDistributionTaskResponse> {
   return invoke("create_distribution_task", { req });
 }
 
@@ -129,28 +360,116 @@ export async function getDistributionTask(id: string): Promise<DistributionTask>
   return invoke("get_distribution_task", { id });
 }
 
-export async function listDistributionTasks(): Promise<DistributionTask[]> {
-  return invoke("list_distribution_tasks");
+export async function listDistributionTasks(
+  query: DistributionTaskListQuery = {}
+): Promise<DistributionTaskListResponse> {
+  return invoke("list_distribution_tasks", {
+    page: query.page,
+    perPage: query.per_page,
+    status: query.status,
+  });
+}
+
+export async function listTaskJobs(taskId: string): Promise<PublishJob[]> {
+  return invoke("list_task_jobs", { taskId });
+}
+
+export interface TaskPurge {
+  tasks: number;
+  jobs: number;
+}
+
+/** Delete finished tasks older than `olderThanDays`, optionally archiving them to a JSON file first */
+export async function purgeOldTasks(
+  olderThanDays: number,
+  archivePath?: string
+): Promise<TaskPurge> {
+  return invoke("purge_old_tasks", { olderThanDays, archivePath });
 }
 
 export async function cancelDistributionTask(id: string): Promise<void> {
   return invoke("cancel_distribution_task", { id });
 }
 
+/** Stop dispatching a task's pending jobs until it is resumed */
+export async function pauseDistributionTask(id: string): Promise<void> {
+  return invoke("pause_distribution_task", { id });
+}
+
+export async function resumeDistributionTask(id: string): Promise<void> {
+  return invoke("resume_distribution_task", { id });
+}
+
+export async function cancelPublishJob(id: string): Promise<void> {
+  return invoke("cancel_publish_job", { id });
+}
+
+/** Subscribe to job transitions of any distribution task */
+export function onTaskProgress(handler: (progress: TaskProgress) => void): Promise<UnlistenFn> {
+  return listen<TaskProgress>("task://progress", (event) => handler(event.payload));
+}
+
+/** Subscribe to distribution tasks reaching a final status */
+export function onTaskCompleted(handler: (task: DistributionTask) => void): Promise<UnlistenFn> {
+  return listen<DistributionTask>("task://completed", (event) => handler(event.payload));
+}
+
+/** Webhooks POSTed a summary when a distribution task finishes */
+export async function getNotificationWebhooks(): Promise<string[]> {
+  return invoke("get_notification_webhooks");
+}
+
+/** Replace the webhook list; returns the URLs that were stored */
+export async function setNotificationWebhook(urls: string[]): Promise<string[]> {
+  return invoke("set_notification_webhook", { urls });
+}
+
+/** Send a sample notification to check a webhook works */
+export async function testNotificationWebhook(url: string): Promise<void> {
+  return invoke("test_notification_webhook", { url });
+}
+
+export async function getPublishConcurrency(): Promise<number> {
+  return invoke("get_publish_concurrency");
+}
+
+export async function setPublishConcurrency(concurrency: number): Promise<void> {
+  return invoke("set_publish_concurrency", { concurrency });
+}
+
 // ============ Stats Commands ============
 
-export async function getOverallStats(): Promise<PublishStats> {
-  return invoke("get_overall_stats");
+export async function getOverallStats(from?: number, to?: number): Promise<PublishStats> {
+  return invoke("get_overall_stats", { from, to });
 }
 
-export async function getPlatformStats(): Promise<PlatformStats[]> {
-  return invoke("get_platform_stats");
+export async function getPlatformStats(from?: number, to?: number): Promise<PlatformStats[]> {
+  return invoke("get_platform_stats", { from, to });
 }
 
 export async function getDailyStats(days?: number): Promise<Statistics[]> {
   return invoke("get_daily_stats", { days });
 }
 
+export async function getErrorBreakdown(): Promise<ErrorBreakdown[]> {
+  return invoke("get_error_breakdown");
+}
+
+export async function exportStatsCsv(kind: StatsKind, from?: number, to?: number): Promise<string> {
+  return invoke("export_stats_csv", { kind, from, to });
+}
+
+export interface StatsReset {
+  publish_logs: number;
+  daily_statistics: number;
+  publish_jobs: number;
+}
+
+/** Clear publish history; `confirm` must be true. `includeJobs` also deletes finished publish jobs */
+export async function resetStats(confirm: boolean, includeJobs = false): Promise<StatsReset> {
+  return invoke("reset_stats", { confirm, includeJobs });
+}
+
 // ============ AI Commands ============
 
 export async function listAIConfigs(): Promise<AIConfig[]> {
@@ -161,12 +480,23 @@ export async function toggleAIAuth(platform: string): Promise<AIConfig> {
   return invoke("toggle_ai_auth", { platform });
 }
 
+export async function setAICredentials(
+  platform: string,
+  credentials: Record<string, unknown>
+): Promise<AIConfig> {
+  return invoke("set_ai_credentials", { platform, credentials });
+}
+
+export async function clearAICredentials(platform: string): Promise<AIConfig> {
+  return invoke("clear_ai_credentials", { platform });
+}
+
 export async function runAICheck(): Promise<void> {
   return invoke("run_ai_check");
 }
 
-export async function listAILogs(limit?: number): Promise<AICheckLog[]> {
-  return invoke("list_ai_logs", { limit });
+export async function listAILogs(query: AICheckLogQuery = {}): Promise<AICheckLogList> {
+  return invoke("list_ai_logs", { ...query });
 }
 
 export async function clearAILogs(): Promise<void> {
@@ -230,10 +560,27 @@ export async function browserClose(accountId: string): Promise<BrowserResponse>
   return invoke("browser_close", { accountId });
 }
 
+/** Base64-encoded PNG of the account's browser viewport */
+export async function browserScreenshot(accountId: string): Promise<string> {
+  return invoke("browser_screenshot", { accountId });
+}
+
 export async function browserGetSessions(): Promise<BrowserSession[]> {
   return invoke("browser_get_sessions");
 }
 
+/** An active browser session with its account resolved */
+export interface BrowserSessionDetail extends BrowserSession {
+  platform: string | null;
+  accountName: string | null;
+  /** The session's account was deleted or moved to the trash */
+  orphaned: boolean;
+}
+
+export async function browserListSessionDetails(): Promise<BrowserSessionDetail[]> {
+  return invoke("browser_list_session_details");
+}
+
 export async function browserCloseAll(): Promise<BrowserResponse> {
   return invoke("browser_close_all");
 }
@@ -248,15 +595,45 @@ export interface LoginStateResponse {
   isLoggedIn: boolean;
   loginDetectedAt?: number;
   error?: string;
+  /** Set on a login watch's final event when it gave up waiting for the login */
+  timedOut: boolean;
 }
 
 export async function browserGetLoginState(accountId: string): Promise<LoginStateResponse> {
   return invoke("browser_get_login_state", { accountId });
 }
 
+/** Poll the account's login in the background until it logs in, then back up its auth */
+export async function startLoginWatch(accountId: string): Promise<void> {
+  return invoke("start_login_watch", { accountId });
+}
+
+/** Stop a login watch, resolving to whether one was running */
+export async function stopLoginWatch(accountId: string): Promise<boolean> {
+  return invoke("stop_login_watch", { accountId });
+}
+
+/** Subscribe to login state changes seen by login watches */
+export function onLoginState(handler: (state: LoginStateResponse) => void): Promise<UnlistenFn> {
+  return listen<LoginStateResponse>("browser://login-state", (event) => handler(event.payload));
+}
+
+export interface SessionsReset {
+  /** Accounts whose browser was lost when the sidecar restarted */
+  staleAccountIds: string[];
+  /** Accounts with a browser still open in the sidecar */
+  activeAccountIds: string[];
+}
+
+/** Fired after a sidecar restart once open browsers are reconciled */
+export function onSessionsReset(handler: (reset: SessionsReset) => void): Promise<UnlistenFn> {
+  return listen<SessionsReset>("browser://sessions-reset", (event) => handler(event.payload));
+}
+
 // ============ Auth Commands (for cross-device migration) ============
 
 export interface AuthBackup {
+  account_id: string;
   platform: string;
   profile_id: string;
   cookies: unknown;
@@ -264,6 +641,13 @@ export interface AuthBackup {
   exported_at: number;
 }
 
+export interface BackupVerification {
+  account_id: string;
+  platform: string;
+  ok: boolean;
+  error?: string | null;
+}
+
 export interface AuthResponse {
   success: boolean;
   error?: string;
@@ -277,24 +661,130 @@ export async function updateAuthStatus(platform: string, authStatus: string): Pr
   return invoke("update_auth_status", { platform, authStatus });
 }
 
-export async function getAuthStatus(platform: string): Promise<string> {
-  return invoke("get_auth_status", { platform });
+export async function getAuthStatus(accountId: string): Promise<string> {
+  return invoke("get_auth_status", { accountId });
 }
 
 export async function exportAuthBackups(): Promise<AuthBackup[]> {
   return invoke("export_auth_backups");
 }
 
+/** Checks every stored auth backup still decrypts with the current key */
+export async function verifyAuthBackups(): Promise<BackupVerification[]> {
+  return invoke("verify_auth_backups");
+}
+
 export async function importAuthBackup(backup: AuthBackup): Promise<AuthResponse> {
   return invoke("import_auth_backup", { backup });
 }
 
+/** Writes all auth backups to `path`, encrypted with `passphrase` */
+export async function exportAuthFile(path: string, passphrase: string): Promise<void> {
+  return invoke("export_auth_file", { path, passphrase });
+}
+
+export async function importAuthFile(path: string, passphrase: string): Promise<number> {
+  return invoke("import_auth_file", { path, passphrase });
+}
+
 export async function clearAuth(platform: string): Promise<AuthResponse> {
   return invoke("clear_auth", { platform });
 }
 
-export async function restoreAuthToBrowser(platform: string): Promise<AuthResponse> {
-  return invoke("restore_auth_to_browser", { platform });
+/** Restores an account's auth backup into its own browser profile */
+export async function restoreAuthToBrowser(accountId: string): Promise<AuthResponse> {
+  return invoke("restore_auth_to_browser", { accountId });
+}
+
+/** Returns the verified auth status: "authorized", "expired" or "none" */
+export async function verifyAuth(accountId: string): Promise<string> {
+  return invoke("verify_auth", { accountId });
+}
+
+// ============ Sidecar Commands ============
+
+export async function getSidecarStatus(): Promise<SidecarStatusInfo> {
+  return invoke("get_sidecar_status");
+}
+
+export async function restartSidecar(): Promise<void> {
+  return invoke("restart_sidecar");
+}
+
+/** Subscribe to sidecar state changes and startup progress */
+export function onSidecarStatus(
+  handler: (status: SidecarStatusInfo) => void
+): Promise<UnlistenFn> {
+  return listen<SidecarStatusInfo>("sidecar://status", (event) => handler(event.payload));
+}
+
+// ============ Health Commands ============
+
+/** Check sidecar, browser service, database, CMS API, proxies and accounts at once */
+export async function systemHealth(): Promise<SystemHealth> {
+  return invoke("system_health");
+}
+
+// ============ Settings Commands ============
+
+/** Keys are dotted and namespaced, e.g. "scheduler.max_concurrency"; null when unset */
+export async function getSetting<T = unknown>(key: string): Promise<T | null> {
+  return invoke("get_setting", { key });
+}
+
+export async function setSetting<T>(key: string, value: T): Promise<void> {
+  return invoke("set_setting", { key, value });
+}
+
+/** Whether outbound HTTP requests are logged (secrets redacted) */
+export async function getHttpLogging(): Promise<boolean> {
+  return invoke("get_http_logging");
+}
+
+export async function setHttpLogging(enabled: boolean): Promise<void> {
+  return invoke("set_http_logging", { enabled });
+}
+
+// ============ Platform Commands ============
+
+/** Enabled platform IDs; disabled platforms keep their accounts and data */
+export async function listPlatforms(): Promise<string[]> {
+  return invoke("list_platforms");
+}
+
+export async function isPlatformEnabled(platform: string): Promise<boolean> {
+  return invoke("is_platform_enabled", { platform });
+}
+
+export async function setPlatformEnabled(platform: string, enabled: boolean): Promise<void> {
+  return invoke("set_platform_enabled", { platform, enabled });
+}
+
+// ============ Security Commands ============
+
+/** Returns the rows that could not be decrypted and were left unchanged */
+export async function rotateMasterKey(): Promise<string[]> {
+  return invoke("rotate_master_key");
+}
+
+// ============ Database Commands ============
+
+export async function backupDatabase(dest: string): Promise<void> {
+  return invoke("backup_database", { dest });
+}
+
+/** Stages the backup and restarts the app to load it */
+export async function restoreDatabase(src: string): Promise<void> {
+  return invoke("restore_database", { src });
+}
+
+export async function vacuumDatabase(): Promise<VacuumReport> {
+  return invoke("vacuum_database");
+}
+
+/** Applied migration version and app version, for support diagnostics */
+export async function getDbVersion(): Promise<DbVersion> {
+  return invoke("get_db_version");
 }
 
 // ============ Utility Commands ============
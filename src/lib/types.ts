@@ -9,7 +9,7 @@ export interface AIConfig {
   id: string;
   platform: string;
   status: "active" | "inactive" | "expired";
-  auth_data?: string;
+  has_credentials: boolean;
   created_at: number;
   updated_at: number;
 }
@@ -17,12 +17,24 @@ export interface AIConfig {
 export interface AICheckLog {
   id: string;
   platform: string;
-  status: "success" | "failed" | "running";
+  status: "success" | "failed" | "running" | "not_implemented";
   message?: string;
   duration_ms?: number;
   created_at: number;
 }
 
+export interface AICheckLogQuery {
+  platform?: string;
+  status?: AICheckLog["status"];
+  limit?: number;
+  offset?: number;
+}
+
+export interface AICheckLogList {
+  logs: AICheckLog[];
+  total: number;
+}
+
 export type AuthStatus = "authorized" | "expired" | "none";
 
 export interface Account {
@@ -41,12 +53,33 @@ export interface Account {
   auth_status: AuthStatus;
   profile_id: string | null;
   last_auth_sync_at: number | null;
+  timezone: string | null;
+  /** When the account was moved to trash */
+  deleted_at: number | null;
+}
+
+export interface AccountImportRecord {
+  platform: string;
+  name: string;
+  username?: string | null;
+  credentials?: Record<string, unknown> | null;
+  timezone?: string | null;
+}
+
+export interface ImportSummary {
+  imported: Account[];
+  skipped: number;
 }
 
 // Proxy types
-export type ProxyProtocol = "http" | "https" | "socks5";
+export type ProxyProtocol = "http" | "https" | "socks4" | "socks5" | "socks5h";
 export type ProxyStatus = "healthy" | "unhealthy" | "unknown";
-export type ProxyStrategy = "fixed" | "round_robin" | "random";
+export type ProxyStrategy =
+  | "fixed"
+  | "round_robin"
+  | "random"
+  | "lowest_latency"
+  | "least_failures";
 
 export interface Proxy {
   id: string;
@@ -59,10 +92,37 @@ export interface Proxy {
   last_check_ip: string | null;
   last_check_location: string | null;
   fail_count: number;
+  last_latency_ms: number | null;
+  /** When the proxy was last handed out or used for a browser launch */
+  last_used_at: number | null;
   created_at: number;
   updated_at: number;
 }
 
+export interface ImportResult {
+  imported: Proxy[];
+  skipped: number;
+  invalid: number;
+}
+
+export interface ProxyHealthRecord {
+  id: number;
+  proxy_id: string;
+  checked_at: number;
+  is_healthy: boolean;
+  exit_ip: string | null;
+  latency_ms: number | null;
+  error: string | null;
+}
+
+export interface ProxyUsageRecord {
+  id: number;
+  proxy_id: string;
+  account_id: string;
+  account_name: string | null;
+  used_at: number;
+}
+
 export interface ProxyHealthResult {
   proxy_id: string;
   is_healthy: boolean;
@@ -91,19 +151,67 @@ export interface Content {
   remote_updated_at: number | null;
   local_updated_at: number;
   metadata: Record<string, unknown> | null;
+  sync_conflict: boolean;
   created_at: number;
   updated_at: number;
 }
 
+export type ConflictResolution = "keep_local" | "keep_remote";
+
+/** Contents sharing the same normalized title and body, oldest first */
+export interface DuplicateContentGroup {
+  content_hash: string;
+  contents: Content[];
+}
+
+export interface ContentListQuery {
+  page?: number;
+  per_page?: number;
+  status?: ContentStatus;
+  search?: string;
+}
+
+export interface ContentListResponse {
+  contents: Content[];
+  total: number;
+  page: number;
+  per_page: number;
+  has_more: boolean;
+}
+
+export interface CreateContentRequest {
+  title: string;
+  body?: string | null;
+  cover_image_url?: string | null;
+  tags?: string[] | null;
+  category?: string | null;
+  author?: string | null;
+  source_url?: string | null;
+  metadata?: Record<string, unknown> | null;
+}
+
+export interface UpdateContentRequest {
+  title?: string | null;
+  body?: string | null;
+  cover_image_url?: string | null;
+  tags?: string[] | null;
+  category?: string | null;
+  author?: string | null;
+  source_url?: string | null;
+  status?: ContentStatus | null;
+  metadata?: Record<string, unknown> | null;
+}
+
 // Distribution task types
 export type DistributionTaskStatus =
   | "pending"
   | "in_progress"
+  | "paused"
   | "completed"
   | "failed"
   | "cancelled";
 
-export type ScheduleType = "immediate" | "scheduled";
+export type ScheduleType = "immediate" | "scheduled" | "recurring";
 
 export interface DistributionTask {
   id: string;
@@ -113,22 +221,107 @@ export interface DistributionTask {
   target_accounts: string[];
   schedule_type: ScheduleType;
   scheduled_at: number | null;
+  cron_expression: string | null;
+  next_run_at: number | null;
   started_at: number | null;
   completed_at: number | null;
   total_jobs: number;
   completed_jobs: number;
   failed_jobs: number;
   error_message: string | null;
+  /** Jobs were prepared but not published */
+  dry_run: boolean;
   created_at: number;
   updated_at: number;
 }
 
+export interface DistributionTaskListQuery {
+  page?: number;
+  per_page?: number;
+  status?: DistributionTaskStatus;
+}
+
+export interface DistributionTaskListResponse {
+  tasks: DistributionTask[];
+  total: number;
+  page: number;
+  per_page: number;
+  has_more: boolean;
+}
+
 export interface CreateDistributionTaskRequest {
   content_id: string;
   name: string | null;
   target_account_ids: string[];
+  /** Also target every account on these platforms, resolved at creation */
+  target_platforms?: string[] | null;
+  /** Limit target_platforms to active accounts */
+  only_active?: boolean;
   schedule_type: ScheduleType;
   scheduled_at: number | null;
+  local_time?: string | null;
+  /** Standard 5-field cron expression; required when schedule_type is "recurring" */
+  cron_expression?: string | null;
+  /** Job priority by account ID; higher publishes first, unlisted accounts get 0 */
+  account_priorities?: Record<string, number>;
+  /** Refuse to create the task if the content has any issue on a target platform */
+  strict?: boolean;
+  /** Prepare content per platform and record it on the jobs instead of publishing */
+  dry_run?: boolean;
+  /** Publish options by platform ID, copied into that platform's jobs */
+  per_platform_options?: Record<string, Record<string, unknown>>;
+  /** Publish now on target platforms that can't schedule, instead of refusing the task */
+  downgrade_unschedulable?: boolean;
+}
+
+export type IssueSeverity = "warning" | "error";
+
+export interface ContentIssue {
+  platform: string;
+  severity: IssueSeverity;
+  field: string;
+  message: string;
+}
+
+export interface CreateDistributionTaskResponse {
+  task: DistributionTask;
+  /** Issues found when checking the content against each target platform */
+  issues: ContentIssue[];
+}
+
+/** Payload of the task://progress event */
+export interface TaskProgress {
+  task_id: string;
+  completed_jobs: number;
+  failed_jobs: number;
+  total_jobs: number;
+  /** The job whose transition triggered this update */
+  last_job: PublishJob;
+}
+
+// CMS API settings types
+export interface ContentApiSettings {
+  base_url: string;
+  /** Whether a key is stored; the key itself is never returned */
+  has_api_key: boolean;
+}
+
+// CMS connection test types
+export type ConnectionStatus =
+  | "connected"
+  | "unauthorized"
+  | "http_error"
+  | "dns_error"
+  | "unreachable";
+
+export interface ConnectionInfo {
+  status: ConnectionStatus;
+  base_url: string;
+  http_status: number | null;
+  /** Total content count reported by the server */
+  total_contents: number | null;
+  latency_ms: number;
+  message: string | null;
 }
 
 // Publish job types
@@ -200,3 +393,67 @@ export interface PlatformStats {
   successful_publishes: number;
   failed_publishes: number;
 }
+
+export type StatsKind = "overall" | "platform" | "account" | "daily";
+
+export type ErrorCategory = "auth" | "rate_limit" | "content" | "network" | "unknown";
+
+export interface ErrorBreakdown {
+  category: ErrorCategory;
+  count: number;
+  percentage: number;
+}
+
+// Sidecar types
+export type SidecarStartStage =
+  | "checking_dependencies"
+  | "installing_dependencies"
+  | "spawning_process"
+  | "waiting_for_health"
+  | "ready";
+
+export interface SidecarStatusInfo {
+  state: "stopped" | "starting" | "running" | "stopping" | "failed";
+  message: string | null;
+  stage: SidecarStartStage | null;
+  uptime: number | null;
+  restart_count: number | null;
+  pid: number | null;
+}
+
+// System health types
+export type HealthStatus = "ok" | "degraded" | "down";
+
+export interface SubsystemHealth {
+  status: HealthStatus;
+  message: string;
+}
+
+export interface SystemHealth {
+  /** Worst status across all subsystems */
+  status: HealthStatus;
+  sidecar: SubsystemHealth;
+  browser: SubsystemHealth;
+  database: SubsystemHealth;
+  content_api: SubsystemHealth;
+  proxies: SubsystemHealth;
+  accounts: SubsystemHealth;
+  healthy_proxies: number;
+  authorized_accounts: number;
+}
+
+// Database maintenance types
+export interface VacuumReport {
+  size_before: number;
+  size_after: number;
+  reclaimed_bytes: number;
+}
+
+export interface DbVersion {
+  /** Latest applied migration, 0 if none */
+  version: number;
+  /** Latest migration this build ships */
+  latest_version: number;
+  pending: boolean;
+  app_version: string;
+}
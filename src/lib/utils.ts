@@ -104,6 +104,7 @@ export function getPlatformName(platform: string): string {
     douyin: "抖音",
     bilibili: "B站",
     zhihu: "知乎",
+    toutiao: "今日头条",
   };
   return names[platform] || platform;
 }